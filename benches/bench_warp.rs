@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 
 use kornia_rs::image::{Image, ImageSize};
-use kornia_rs::interpolation::InterpolationMode;
+use kornia_rs::interpolation::{BorderMode, InterpolationMode};
 use kornia_rs::warp::{get_rotation_matrix2d, warp_affine};
 
 fn bench_warp_affine(c: &mut Criterion) {
@@ -15,7 +15,15 @@ fn bench_warp_affine(c: &mut Criterion) {
         let image_f32 = image.clone().cast::<f32>().unwrap();
         let m = get_rotation_matrix2d((width as f32 / 2.0, height as f32 / 2.0), 45.0, 1.0);
         group.bench_with_input(BenchmarkId::new("native", &id), &image_f32, |b, i| {
-            b.iter(|| warp_affine(black_box(i), m, image_size, InterpolationMode::Bilinear))
+            b.iter(|| {
+                warp_affine(
+                    black_box(i),
+                    m,
+                    image_size,
+                    InterpolationMode::Bilinear,
+                    BorderMode::Constant(0.0),
+                )
+            })
         });
     }
     group.finish();
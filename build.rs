@@ -0,0 +1,11 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // the protobuf schema is only compiled for the gRPC helpers
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/kornia.proto");
+        tonic_build::compile_protos("proto/kornia.proto")
+            .expect("Failed to compile the protobuf schema");
+    }
+}
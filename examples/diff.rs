@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use kornia_rs::image::Image;
+use kornia_rs::io::functional as F;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// The first image to compare
+    image1: PathBuf,
+
+    /// The second image to compare
+    image2: PathBuf,
+
+    /// The path to save the difference heatmap
+    #[arg(short, long, default_value = "diff.png")]
+    output: PathBuf,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    // read the images
+    let image1: Image<u8, 3> = F::read_image_any(&args.image1)?;
+    let image2: Image<u8, 3> = F::read_image_any(&args.image2)?;
+
+    // compute the heatmap of the differences
+    let (heatmap, stats) = kornia_rs::metrics::diff_image(&image1, &image2)?;
+
+    println!("Max difference: {}", stats.max);
+    println!("Mean difference: {}", stats.mean);
+    println!(
+        "Different pixels: {} / {}",
+        stats.num_different,
+        heatmap.width() * heatmap.height()
+    );
+
+    // save the heatmap
+    let buffer = image::RgbImage::from_raw(
+        heatmap.width() as u32,
+        heatmap.height() as u32,
+        heatmap.data.into_raw_vec(),
+    )
+    .ok_or("Failed to create the heatmap buffer")?;
+    buffer.save(&args.output)?;
+
+    println!("Heatmap saved to {}", args.output.display());
+
+    // exit with an error code if the images differ
+    if stats.num_different > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
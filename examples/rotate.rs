@@ -27,6 +27,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             rotation_matrix,
             image.size(),
             kornia_rs::interpolation::InterpolationMode::Bilinear,
+            kornia_rs::interpolation::BorderMode::Replicate,
         )?;
 
         let output = kornia_rs::normalize::normalize_min_max(&output, 0.0, 255.0)?;
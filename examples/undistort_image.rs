@@ -64,6 +64,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         &map_x,
         &map_y,
         kornia_rs::interpolation::InterpolationMode::Bilinear,
+        kornia_rs::interpolation::BorderMode::Constant(0.0),
     )?;
 
     // create a Rerun recording stream
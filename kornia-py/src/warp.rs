@@ -35,8 +35,14 @@ pub fn warp_affine(
         .cast::<f32>()
         .map_err(|e| PyErr::new::<pyo3::exceptions::PyException, _>(format!("{}", e)))?;
 
-    let image = kornia_rs::warp::warp_affine(&image, m, new_size, interpolation)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyException, _>(format!("{}", e)))?;
+    let image = kornia_rs::warp::warp_affine(
+        &image,
+        m,
+        new_size,
+        interpolation,
+        kornia_rs::interpolation::BorderMode::Constant(0.0),
+    )
+    .map_err(|e| PyErr::new::<pyo3::exceptions::PyException, _>(format!("{}", e)))?;
 
     // NOTE: for bicubic interpolation (not implemented yet), f32 may overshoot 255
     let image = image
@@ -0,0 +1,157 @@
+use crate::image::Image;
+use crate::interpolation::{remap, BorderMode, InterpolationMode};
+use anyhow::Result;
+
+/// Find the undistorted radius of a distorted radius with the Newton method.
+///
+/// Returns None if the distortion is not invertible at that radius, i.e. the model
+/// folds onto itself.
+fn undistort_radius(r_d: f32, k1: f32, k2: f32) -> Option<f32> {
+    let mut r = r_d;
+    for _ in 0..20 {
+        let r2 = r * r;
+        let f = r * (1.0 + k1 * r2 + k2 * r2 * r2) - r_d;
+        let df = 1.0 + 3.0 * k1 * r2 + 5.0 * k2 * r2 * r2;
+        if df <= 0.0 {
+            return None;
+        }
+        let step = f / df;
+        r -= step;
+        if step.abs() < 1e-6 {
+            return (r >= 0.0).then_some(r);
+        }
+    }
+    None
+}
+
+/// Applies a radial lens distortion to an image.
+///
+/// The image is warped as if taken with a lens following the radial model
+/// `r_d = r * (1 + k1 * r^2 + k2 * r^4)`, where the radius is normalized by half of the
+/// largest side of the image around its center. Negative coefficients simulate a barrel
+/// distortion and positive ones a pincushion distortion.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `k1` - The second order radial distortion coefficient.
+/// * `k2` - The fourth order radial distortion coefficient.
+/// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the pixels mapped outside of the input image.
+///
+/// # Returns
+///
+/// The distorted image with the same shape as the input.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::augment::lens_distort;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::interpolation::{BorderMode, InterpolationMode};
+///
+/// let image = Image::<f32, 3>::from_size_val(
+///     ImageSize {
+///         width: 16,
+///         height: 12,
+///     },
+///     1.0,
+/// )
+/// .unwrap();
+///
+/// let barrel = lens_distort(
+///     &image,
+///     -0.2,
+///     0.0,
+///     InterpolationMode::Bilinear,
+///     BorderMode::Constant(0.0),
+/// )
+/// .unwrap();
+///
+/// // the corners are pulled from outside of the image
+/// assert_eq!(barrel.data[[0, 0, 0]], 0.0);
+/// assert!((barrel.data[[6, 8, 0]] - 1.0).abs() < 1e-6);
+/// ```
+pub fn lens_distort<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    k1: f32,
+    k2: f32,
+    interpolation: InterpolationMode,
+    border: BorderMode,
+) -> Result<Image<f32, CHANNELS>> {
+    let (width, height) = (src.width(), src.height());
+    let (cx, cy) = ((width as f32 - 1.0) / 2.0, (height as f32 - 1.0) / 2.0);
+    let focal = (width.max(height) as f32 / 2.0).max(1.0);
+
+    // sample each distorted pixel at its undistorted position
+    let mut map_x = Image::<f32, 1>::from_size_val(src.size(), 0.0)?;
+    let mut map_y = Image::<f32, 1>::from_size_val(src.size(), 0.0)?;
+    ndarray::Zip::indexed(map_x.data.rows_mut())
+        .and(map_y.data.rows_mut())
+        .par_for_each(|(y, x), mut u, mut v| {
+            let (dx, dy) = ((x as f32 - cx) / focal, (y as f32 - cy) / focal);
+            let r_d = dx.hypot(dy);
+            if r_d <= f32::EPSILON {
+                u[0] = x as f32;
+                v[0] = y as f32;
+                return;
+            }
+            let Some(r_u) = undistort_radius(r_d, k1, k2) else {
+                // out of the domain of the model, sample outside of the image
+                u[0] = -1.0;
+                v[0] = -1.0;
+                return;
+            };
+            let scale = r_u / r_d;
+            u[0] = cx + dx * scale * focal;
+            v[0] = cy + dy * scale * focal;
+        });
+
+    remap(src, &map_x, &map_y, interpolation, border)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use crate::interpolation::{BorderMode, InterpolationMode};
+    use anyhow::Result;
+
+    #[test]
+    fn lens_distort() -> Result<()> {
+        // a horizontal ramp, so that each pixel holds its source x coordinate
+        let size = ImageSize {
+            width: 21,
+            height: 11,
+        };
+        let ramp = Image::<f32, 1>::new(size, (0..21 * 11).map(|i| (i % 21) as f32).collect())?;
+
+        let same = super::lens_distort(
+            &ramp,
+            0.0,
+            0.0,
+            InterpolationMode::Bilinear,
+            BorderMode::Replicate,
+        )?;
+        for (a, b) in same.data.iter().zip(ramp.data.iter()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+
+        // the sampled positions are distorted back onto the output pixels
+        let (k1, k2) = (0.1, 0.05);
+        let pincushion = super::lens_distort(
+            &ramp,
+            k1,
+            k2,
+            InterpolationMode::Bilinear,
+            BorderMode::Replicate,
+        )?;
+        let (cx, focal) = (10.0, 10.5);
+        for x in 0..21 {
+            let r = (pincushion.data[[5, x, 0]] - cx) / focal;
+            let r_d = r * (1.0 + k1 * r * r + k2 * r.powi(4));
+            assert!((r_d * focal + cx - x as f32).abs() < 1e-3);
+        }
+        assert!(pincushion.data[[5, 20, 0]] < 20.0);
+        Ok(())
+    }
+}
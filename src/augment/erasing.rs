@@ -0,0 +1,206 @@
+use super::Rng;
+use crate::geometry::Rect;
+use crate::image::{Image, ImageDtype};
+use anyhow::Result;
+
+/// The number of attempts to draw a region that fits in the image.
+const MAX_ATTEMPTS: usize = 10;
+
+/// Parameters of [`random_erasing`].
+#[derive(Debug, Clone)]
+pub struct RandomErasingParams<T> {
+    /// The probability to erase a region of the image.
+    pub probability: f32,
+    /// The range of the erased area relative to the image area.
+    pub area_range: (f32, f32),
+    /// The range of the aspect ratio, height over width, of the erased region.
+    pub aspect_range: (f32, f32),
+    /// The value to fill the erased region with.
+    pub fill: T,
+}
+
+impl<T: Default> Default for RandomErasingParams<T> {
+    fn default() -> Self {
+        Self {
+            probability: 0.5,
+            area_range: (0.02, 0.33),
+            aspect_range: (0.3, 3.3),
+            fill: T::default(),
+        }
+    }
+}
+
+/// Erases a random rectangular region of an image in place.
+///
+/// Implements the Random Erasing data augmentation from Zhong et al. (2020), also known
+/// as cutout. The area and the aspect ratio of the region are drawn from their ranges,
+/// the aspect ratio uniformly in log scale.
+///
+/// # Arguments
+///
+/// * `image` - The image to erase with shape (H, W, C).
+/// * `mask` - An optional mask with shape (H, W, 1), zeroed in the erased region.
+/// * `params` - The parameters of the augmentation.
+/// * `rng` - The random number generator.
+///
+/// # Returns
+///
+/// The erased region, or None if the image was kept.
+///
+/// # Errors
+///
+/// Returns an error if the parameters are invalid or the mask size does not match.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::augment::{random_erasing, RandomErasingParams, Rng};
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let mut image = Image::<u8, 3>::from_size_val(
+///     ImageSize {
+///         width: 32,
+///         height: 32,
+///     },
+///     255,
+/// )
+/// .unwrap();
+///
+/// let params = RandomErasingParams {
+///     probability: 1.0,
+///     ..Default::default()
+/// };
+/// let region = random_erasing(&mut image, None, &params, &mut Rng::new(0))
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(image.data[[region.y, region.x, 0]], 0);
+/// ```
+pub fn random_erasing<T: ImageDtype, const CHANNELS: usize>(
+    image: &mut Image<T, CHANNELS>,
+    mask: Option<&mut Image<u8, 1>>,
+    params: &RandomErasingParams<T>,
+    rng: &mut Rng,
+) -> Result<Option<Rect>> {
+    let (area_min, area_max) = params.area_range;
+    let (aspect_min, aspect_max) = params.aspect_range;
+    let valid = (0.0..=1.0).contains(&params.probability)
+        && area_min > 0.0
+        && area_min <= area_max
+        && area_max <= 1.0
+        && aspect_min > 0.0
+        && aspect_min <= aspect_max;
+    if !valid {
+        return Err(anyhow::anyhow!(
+            "Invalid random erasing parameters: probability {}, area range {:?}, aspect range {:?}",
+            params.probability,
+            params.area_range,
+            params.aspect_range
+        ));
+    }
+
+    if let Some(mask) = &mask {
+        if mask.size() != image.size() {
+            return Err(anyhow::anyhow!(
+                "The size of the mask {} does not match the size of the image {}.",
+                mask.size(),
+                image.size()
+            ));
+        }
+    }
+
+    if rng.uniform(0.0, 1.0) >= params.probability {
+        return Ok(None);
+    }
+
+    let (width, height) = (image.width(), image.height());
+    let area = (width * height) as f32;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let target_area = area * rng.uniform(area_min, area_max);
+        let aspect = rng.uniform(aspect_min.ln(), aspect_max.ln()).exp();
+
+        let h = (target_area * aspect).sqrt().round() as usize;
+        let w = (target_area / aspect).sqrt().round() as usize;
+        if h == 0 || w == 0 || h > height || w > width {
+            continue;
+        }
+
+        let region = Rect {
+            x: rng.index(width - w + 1),
+            y: rng.index(height - h + 1),
+            width: w,
+            height: h,
+        };
+
+        let (ys, xs) = (region.y..region.y + h, region.x..region.x + w);
+        image
+            .data
+            .slice_mut(ndarray::s![ys.clone(), xs.clone(), ..])
+            .fill(params.fill);
+        if let Some(mask) = mask {
+            mask.data.slice_mut(ndarray::s![ys, xs, ..]).fill(0);
+        }
+
+        return Ok(Some(region));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{random_erasing, RandomErasingParams};
+    use crate::augment::Rng;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn random_erasing_region() -> Result<()> {
+        let size = ImageSize {
+            width: 40,
+            height: 30,
+        };
+        let params = RandomErasingParams {
+            probability: 1.0,
+            area_range: (0.1, 0.2),
+            aspect_range: (0.5, 2.0),
+            fill: -1.0,
+        };
+
+        let mut rng = Rng::new(7);
+        for _ in 0..20 {
+            let mut image = Image::<f32, 2>::from_size_val(size, 1.0)?;
+            let mut mask = Image::<u8, 1>::from_size_val(size, 255)?;
+            let region = random_erasing(&mut image, Some(&mut mask), &params, &mut rng)?.unwrap();
+
+            // the region has the requested area and shape, and is erased in both images
+            let area = region.area() as f32 / (40.0 * 30.0);
+            assert!((0.08..=0.22).contains(&area));
+            let aspect = region.height as f32 / region.width as f32;
+            assert!((0.4..=2.5).contains(&aspect));
+
+            for y in 0..30 {
+                for x in 0..40 {
+                    let inside = region.contains(x, y);
+                    assert_eq!(image.data[[y, x, 1]] == -1.0, inside);
+                    assert_eq!(mask.data[[y, x, 0]] == 0, inside);
+                }
+            }
+        }
+
+        // the probability gates the augmentation
+        let mut image = Image::<f32, 2>::from_size_val(size, 1.0)?;
+        let never = RandomErasingParams {
+            probability: 0.0,
+            ..params.clone()
+        };
+        assert!(random_erasing(&mut image, None, &never, &mut rng)?.is_none());
+
+        let invalid = RandomErasingParams {
+            area_range: (0.5, 0.1),
+            ..params
+        };
+        assert!(random_erasing(&mut image, None, &invalid, &mut rng).is_err());
+        Ok(())
+    }
+}
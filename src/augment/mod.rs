@@ -0,0 +1,9 @@
+mod distort;
+mod erasing;
+mod rng;
+mod vignette;
+
+pub use distort::lens_distort;
+pub use erasing::{random_erasing, RandomErasingParams};
+pub use rng::Rng;
+pub use vignette::add_vignette;
@@ -0,0 +1,33 @@
+use crate::geometry::SplitMix64;
+
+/// A seeded random number generator for the augmentations.
+///
+/// The same seed always produces the same sequence of augmentations, so that the
+/// transformations of a dataset can be reproduced.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::augment::Rng;
+///
+/// let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+/// assert_eq!(a.uniform(0.0, 1.0), b.uniform(0.0, 1.0));
+/// ```
+pub struct Rng(SplitMix64);
+
+impl Rng {
+    /// Create a new generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self(SplitMix64::new(seed))
+    }
+
+    /// Draw a uniform value in `[low, high)`.
+    pub fn uniform(&mut self, low: f32, high: f32) -> f32 {
+        low + (high - low) * self.0.next_f32()
+    }
+
+    /// Draw a uniform index in `[0, n)`.
+    pub(crate) fn index(&mut self, n: usize) -> usize {
+        (self.0.next_u64() % n.max(1) as u64) as usize
+    }
+}
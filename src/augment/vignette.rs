@@ -0,0 +1,92 @@
+use crate::enhance::{apply_radial_gain, VignetteModel};
+use crate::image::Image;
+use anyhow::Result;
+
+/// Darkens the borders of an image to simulate the vignetting of a lens.
+///
+/// The brightness decreases quadratically with the distance to the center, from 1 at
+/// the center to `1 - strength` at the corners.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `strength` - The attenuation at the corners, in the range [0, 1].
+///
+/// # Returns
+///
+/// The vignetted image with the same shape as the input.
+///
+/// # Errors
+///
+/// Returns an error if the strength is out of range.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::augment::add_vignette;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 3,
+///         height: 3,
+///     },
+///     1.0,
+/// )
+/// .unwrap();
+///
+/// let vignetted = add_vignette(&image, 0.5).unwrap();
+/// assert_eq!(vignetted.data[[1, 1, 0]], 1.0);
+/// assert_eq!(vignetted.data[[0, 0, 0]], 0.5);
+/// ```
+pub fn add_vignette<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    strength: f32,
+) -> Result<Image<f32, CHANNELS>> {
+    if !(0.0..=1.0).contains(&strength) {
+        return Err(anyhow::anyhow!(
+            "The vignetting strength {} must be in the range [0, 1].",
+            strength
+        ));
+    }
+
+    let model = VignetteModel {
+        k1: -strength,
+        ..Default::default()
+    };
+    apply_radial_gain(src, |r| model.gain(r))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enhance::{devignette, VignetteModel};
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn add_vignette_roundtrip() -> Result<()> {
+        let image = Image::<f32, 3>::new(
+            ImageSize {
+                width: 6,
+                height: 4,
+            },
+            (0..6 * 4 * 3).map(|x| x as f32 / 72.0).collect(),
+        )?;
+
+        let vignetted = super::add_vignette(&image, 0.4)?;
+        assert!(vignetted.data[[0, 0, 0]] <= image.data[[0, 0, 0]]);
+
+        // the matching model removes the vignetting
+        let model = VignetteModel {
+            k1: -0.4,
+            ..Default::default()
+        };
+        let restored = devignette(&vignetted, &model)?;
+        for (a, b) in restored.data.iter().zip(image.data.iter()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+
+        assert!(super::add_vignette(&image, 1.5).is_err());
+        Ok(())
+    }
+}
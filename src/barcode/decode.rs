@@ -0,0 +1,478 @@
+use super::reed_solomon;
+
+/// The largest supported version of QR code, with 57x57 modules.
+pub(crate) const MAX_VERSION: usize = 10;
+
+/// The error correction levels of a QR code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcLevel {
+    /// Recovers about 7% of the codewords.
+    L,
+    /// Recovers about 15% of the codewords.
+    M,
+    /// Recovers about 25% of the codewords.
+    Q,
+    /// Recovers about 30% of the codewords.
+    H,
+}
+
+impl EcLevel {
+    /// Get the level from its two bits in the format information.
+    fn from_bits(bits: u16) -> Self {
+        match bits {
+            1 => EcLevel::L,
+            0 => EcLevel::M,
+            3 => EcLevel::Q,
+            _ => EcLevel::H,
+        }
+    }
+}
+
+/// The error correction blocks of a version and level: the number of error correction
+/// codewords per block, the number of blocks and data codewords per block of the first
+/// group, and the number of blocks of the second group with one more data codeword.
+type EcBlocks = (usize, usize, usize, usize);
+
+/// The error correction blocks of each version for the levels L, M, Q and H.
+const EC_BLOCKS: [[EcBlocks; 4]; MAX_VERSION] = [
+    [(7, 1, 19, 0), (10, 1, 16, 0), (13, 1, 13, 0), (17, 1, 9, 0)],
+    [
+        (10, 1, 34, 0),
+        (16, 1, 28, 0),
+        (22, 1, 22, 0),
+        (28, 1, 16, 0),
+    ],
+    [
+        (15, 1, 55, 0),
+        (26, 1, 44, 0),
+        (18, 2, 17, 0),
+        (22, 2, 13, 0),
+    ],
+    [
+        (20, 1, 80, 0),
+        (18, 2, 32, 0),
+        (26, 2, 24, 0),
+        (16, 4, 9, 0),
+    ],
+    [
+        (26, 1, 108, 0),
+        (24, 2, 43, 0),
+        (18, 2, 15, 2),
+        (22, 2, 11, 2),
+    ],
+    [
+        (18, 2, 68, 0),
+        (16, 4, 27, 0),
+        (24, 4, 19, 0),
+        (28, 4, 15, 0),
+    ],
+    [
+        (20, 2, 78, 0),
+        (18, 4, 31, 0),
+        (18, 2, 14, 4),
+        (26, 4, 13, 1),
+    ],
+    [
+        (24, 2, 97, 0),
+        (22, 2, 38, 2),
+        (22, 4, 18, 2),
+        (26, 4, 14, 2),
+    ],
+    [
+        (30, 2, 116, 0),
+        (22, 3, 36, 2),
+        (20, 4, 16, 4),
+        (24, 4, 12, 4),
+    ],
+    [
+        (18, 2, 68, 2),
+        (26, 4, 43, 1),
+        (24, 6, 19, 2),
+        (28, 6, 15, 2),
+    ],
+];
+
+/// The row and column coordinates of the alignment patterns of each version.
+const ALIGNMENT_POSITIONS: [&[usize]; MAX_VERSION] = [
+    &[],
+    &[6, 18],
+    &[6, 22],
+    &[6, 26],
+    &[6, 30],
+    &[6, 34],
+    &[6, 22, 38],
+    &[6, 24, 42],
+    &[6, 26, 46],
+    &[6, 28, 50],
+];
+
+/// The characters of the alphanumeric mode.
+const ALPHANUMERIC: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Get the number of modules on a side of a version.
+pub(crate) fn dimension(version: usize) -> usize {
+    4 * version + 17
+}
+
+/// Append the BCH error correction bits of a value.
+fn bch_code(value: u32, num_bits: u32, generator: u32) -> u32 {
+    let degree = 31 - generator.leading_zeros();
+    let mut remainder = value << degree;
+    for bit in (degree..degree + num_bits).rev() {
+        if remainder & (1 << bit) != 0 {
+            remainder ^= generator << (bit - degree);
+        }
+    }
+    (value << degree) | remainder
+}
+
+/// Find the value whose BCH code is the closest to some bits, up to 3 bit errors.
+fn decode_bch(
+    bits: &[u32],
+    values: impl Iterator<Item = u32>,
+    code: impl Fn(u32) -> u32,
+) -> Option<u32> {
+    values
+        .map(|v| {
+            let c = code(v);
+            let distance = bits.iter().map(|b| (b ^ c).count_ones()).min();
+            (distance.unwrap_or(u32::MAX), v)
+        })
+        .min()
+        .filter(|(distance, _)| *distance <= 3)
+        .map(|(_, v)| v)
+}
+
+/// The modules of a QR code, dark modules being true.
+pub(crate) struct Grid {
+    pub(crate) dimension: usize,
+    pub(crate) modules: Vec<bool>,
+}
+
+impl Grid {
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.dimension + x]
+    }
+
+    /// Read the bits of the modules at some positions, the first one being the most significant.
+    fn read_bits(&self, positions: impl Iterator<Item = (usize, usize)>) -> u32 {
+        positions.fold(0, |bits, (x, y)| (bits << 1) | self.get(x, y) as u32)
+    }
+
+    /// Read the error correction level and the mask from the two copies of the format.
+    fn read_format(&self) -> Option<(EcLevel, usize)> {
+        let d = self.dimension;
+        let around_top_left = (0..6)
+            .map(|x| (x, 8))
+            .chain([(7, 8), (8, 8), (8, 7)])
+            .chain((0..6).rev().map(|y| (8, y)));
+        let split = (d - 7..d)
+            .rev()
+            .map(|y| (8, y))
+            .chain((d - 8..d).map(|x| (x, 8)));
+        let bits = [self.read_bits(around_top_left), self.read_bits(split)];
+
+        let format = decode_bch(&bits, 0..32, |v| bch_code(v, 5, 0x537) ^ 0x5412)?;
+        Some((
+            EcLevel::from_bits((format >> 3) as u16),
+            (format & 7) as usize,
+        ))
+    }
+
+    /// Read the version from its two copies, only present from the version 7.
+    pub(crate) fn read_version(&self) -> Option<usize> {
+        let d = self.dimension;
+        let top_right = (0..6)
+            .rev()
+            .flat_map(|y| (d - 11..d - 8).rev().map(move |x| (x, y)));
+        let bottom_left = (0..6)
+            .rev()
+            .flat_map(|x| (d - 11..d - 8).rev().map(move |y| (x, y)));
+        let bits = [self.read_bits(top_right), self.read_bits(bottom_left)];
+
+        decode_bch(&bits, 7..41, |v| bch_code(v, 6, 0x1f25)).map(|v| v as usize)
+    }
+}
+
+/// Get the modules of the function patterns of a version, which hold no data.
+fn function_patterns(version: usize) -> Vec<bool> {
+    let d = dimension(version);
+    let mut function = vec![false; d * d];
+    let mut set_region = |x0: usize, y0: usize, width: usize, height: usize| {
+        for y in y0..y0 + height {
+            for x in x0..x0 + width {
+                function[y * d + x] = true;
+            }
+        }
+    };
+
+    // the finder patterns with their separators and the format information
+    set_region(0, 0, 9, 9);
+    set_region(d - 8, 0, 8, 9);
+    set_region(0, d - 8, 9, 8);
+
+    let positions = ALIGNMENT_POSITIONS[version - 1];
+    for &y in positions {
+        for &x in positions {
+            let last = positions[positions.len() - 1];
+            if (x == 6 && (y == 6 || y == last)) || (x == last && y == 6) {
+                continue;
+            }
+            set_region(x - 2, y - 2, 5, 5);
+        }
+    }
+
+    // the timing patterns
+    set_region(6, 9, 1, d - 17);
+    set_region(9, 6, d - 17, 1);
+
+    if version >= 7 {
+        set_region(d - 11, 0, 3, 6);
+        set_region(0, d - 11, 6, 3);
+    }
+
+    function
+}
+
+/// Check if a module is flipped by a data mask.
+fn is_masked(mask: usize, x: usize, y: usize) -> bool {
+    let (i, j) = (y, x);
+    match mask {
+        0 => (i + j) % 2 == 0,
+        1 => i % 2 == 0,
+        2 => j % 3 == 0,
+        3 => (i + j) % 3 == 0,
+        4 => (i / 2 + j / 3) % 2 == 0,
+        5 => (i * j) % 2 + (i * j) % 3 == 0,
+        6 => ((i * j) % 2 + (i * j) % 3) % 2 == 0,
+        _ => ((i + j) % 2 + (i * j) % 3) % 2 == 0,
+    }
+}
+
+/// Read the codewords of the data modules, in the zigzag order from the bottom-right corner.
+fn read_codewords(grid: &Grid, version: usize, mask: usize, num_codewords: usize) -> Vec<u8> {
+    let d = grid.dimension;
+    let function = function_patterns(version);
+
+    let mut codewords = Vec::with_capacity(num_codewords);
+    let (mut byte, mut num_bits) = (0u8, 0);
+    let mut upwards = true;
+    let mut right = d - 1;
+    while right > 0 && codewords.len() < num_codewords {
+        // the vertical timing pattern is skipped
+        if right == 6 {
+            right -= 1;
+        }
+        for count in 0..d {
+            let y = if upwards { d - 1 - count } else { count };
+            for x in [right, right - 1] {
+                if function[y * d + x] {
+                    continue;
+                }
+                byte = (byte << 1) | (grid.get(x, y) ^ is_masked(mask, x, y)) as u8;
+                num_bits += 1;
+                if num_bits == 8 {
+                    codewords.push(byte);
+                    (byte, num_bits) = (0, 0);
+                }
+            }
+        }
+        upwards = !upwards;
+        right = right.saturating_sub(2);
+    }
+
+    codewords.truncate(num_codewords);
+    codewords
+}
+
+/// A reader of the bits of a byte stream, from the most significant.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn available(&self) -> usize {
+        8 * self.data.len() - self.position
+    }
+
+    fn read(&mut self, num_bits: usize) -> Option<u32> {
+        if num_bits > self.available() {
+            return None;
+        }
+        let mut value = 0u32;
+        for _ in 0..num_bits {
+            let bit = (self.data[self.position / 8] >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u32;
+            self.position += 1;
+        }
+        Some(value)
+    }
+}
+
+/// Decode the segments of the data codewords into the payload bytes.
+fn decode_segments(data: &[u8], version: usize) -> Option<Vec<u8>> {
+    let mut reader = BitReader { data, position: 0 };
+    let mut payload = Vec::new();
+    // the lengths of the character counts of the numeric, alphanumeric, byte and kanji modes
+    let count_bits = if version < 10 {
+        [10, 9, 8, 8]
+    } else {
+        [12, 11, 16, 10]
+    };
+
+    while reader.available() >= 4 {
+        match reader.read(4)? {
+            0 => break,
+            // numeric
+            1 => {
+                let mut count = reader.read(count_bits[0])? as usize;
+                while count > 0 {
+                    let digits = count.min(3);
+                    let value = reader.read([4, 7, 10][digits - 1])?;
+                    if value >= 10u32.pow(digits as u32) {
+                        return None;
+                    }
+                    payload.extend(format!("{:0width$}", value, width = digits).bytes());
+                    count -= digits;
+                }
+            }
+            // alphanumeric
+            2 => {
+                let mut count = reader.read(count_bits[1])? as usize;
+                while count > 0 {
+                    if count >= 2 {
+                        let value = reader.read(11)? as usize;
+                        if value >= 45 * 45 {
+                            return None;
+                        }
+                        payload.extend([ALPHANUMERIC[value / 45], ALPHANUMERIC[value % 45]]);
+                        count -= 2;
+                    } else {
+                        payload.push(*ALPHANUMERIC.get(reader.read(6)? as usize)?);
+                        count -= 1;
+                    }
+                }
+            }
+            // byte
+            4 => {
+                let count = reader.read(count_bits[2])?;
+                for _ in 0..count {
+                    payload.push(reader.read(8)? as u8);
+                }
+            }
+            // kanji, as Shift JIS bytes
+            8 => {
+                let count = reader.read(count_bits[3])?;
+                for _ in 0..count {
+                    let value = reader.read(13)?;
+                    let assembled = ((value / 0xc0) << 8) | (value % 0xc0);
+                    let sjis = assembled + if assembled < 0x1f00 { 0x8140 } else { 0xc140 };
+                    payload.extend([(sjis >> 8) as u8, sjis as u8]);
+                }
+            }
+            // extended channel interpretation, the designator is skipped
+            7 => {
+                let first = reader.read(8)?;
+                if first & 0x80 == 0x80 {
+                    reader.read(if first & 0xc0 == 0x80 { 8 } else { 16 })?;
+                }
+            }
+            // structured append, the sequence and the parity are skipped
+            3 => {
+                reader.read(16)?;
+            }
+            // FNC1 in the first or second position
+            5 => {}
+            9 => {
+                reader.read(8)?;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(payload)
+}
+
+/// Decode the payload of the modules of a QR code.
+///
+/// Returns the error correction level and the payload, or None if the modules are not
+/// a valid QR code of the version.
+pub(crate) fn decode_grid(grid: &Grid, version: usize) -> Option<(EcLevel, Vec<u8>)> {
+    if !(1..=MAX_VERSION).contains(&version) || grid.dimension != dimension(version) {
+        return None;
+    }
+    let (ec_level, mask) = grid.read_format()?;
+
+    let (num_ec, blocks1, data1, blocks2) = EC_BLOCKS[version - 1][ec_level as usize];
+    let num_blocks = blocks1 + blocks2;
+    let num_codewords = num_blocks * num_ec + blocks1 * data1 + blocks2 * (data1 + 1);
+    let codewords = read_codewords(grid, version, mask, num_codewords);
+    if codewords.len() != num_codewords {
+        return None;
+    }
+
+    // deinterleave the blocks, the data codewords first then the error correction ones
+    let data_len = |b: usize| if b < blocks1 { data1 } else { data1 + 1 };
+    let mut blocks = (0..num_blocks)
+        .map(|b| Vec::with_capacity(data_len(b) + num_ec))
+        .collect::<Vec<_>>();
+    let mut codewords = codewords.into_iter();
+    for i in 0..data1 + 1 {
+        for (b, block) in blocks.iter_mut().enumerate() {
+            if i < data_len(b) {
+                block.push(codewords.next()?);
+            }
+        }
+    }
+    for _ in 0..num_ec {
+        for block in blocks.iter_mut() {
+            block.push(codewords.next()?);
+        }
+    }
+
+    let mut data = Vec::new();
+    for (b, block) in blocks.iter_mut().enumerate() {
+        reed_solomon::correct(block, num_ec)?;
+        data.extend_from_slice(&block[..data_len(b)]);
+    }
+
+    Some((ec_level, decode_segments(&data, version)?))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn decode_segments() {
+        // "01234567" in numeric mode, "AC-42" in alphanumeric mode and the terminator
+        let bits = concat!(
+            "0001",
+            "0000001000",
+            "0000001100",
+            "0101011001",
+            "1000011",
+            "0010",
+            "000000101",
+            "00111001110",
+            "11100111001",
+            "000010",
+            "0000",
+        );
+        let mut data = bits
+            .as_bytes()
+            .chunks(8)
+            .map(|c| c.iter().fold(0u8, |b, &bit| (b << 1) | (bit - b'0')) << (8 - c.len()))
+            .collect::<Vec<_>>();
+        data.extend([0xec, 0x11]);
+
+        let payload = super::decode_segments(&data, 1).unwrap();
+        assert_eq!(payload, b"01234567AC-42");
+
+        // the version info is encoded with the BCH code of the standard
+        assert_eq!(super::bch_code(7, 6, 0x1f25), 0b000111110010010100);
+        assert_eq!(
+            super::bch_code(0b01100, 5, 0x537) ^ 0x5412,
+            0b110011000101111
+        );
+    }
+}
@@ -0,0 +1,638 @@
+use super::decode::{decode_grid, dimension, EcLevel, Grid, MAX_VERSION};
+use crate::geometry::Point2;
+use crate::image::Image;
+use crate::warp::{get_perspective_transform, PerspectiveMatrix};
+use anyhow::Result;
+
+/// The maximum number of finder patterns combined into QR codes.
+const MAX_FINDER_PATTERNS: usize = 20;
+
+/// A QR code decoded in an image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QrCode {
+    /// The outer corners of the code in the image: top-left, top-right, bottom-right and
+    /// bottom-left in the orientation of the code.
+    pub corners: [Point2; 4],
+    /// The version of the code, from 1 to 10.
+    pub version: usize,
+    /// The error correction level of the code.
+    pub ec_level: EcLevel,
+    /// The decoded payload.
+    pub payload: Vec<u8>,
+}
+
+impl QrCode {
+    /// Get the payload as text, if it is valid UTF-8.
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.payload).ok()
+    }
+}
+
+/// A binarized image, dark pixels being true.
+struct BinaryImage {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+}
+
+impl BinaryImage {
+    /// Binarize a grayscale image with the Otsu threshold.
+    fn from_image(image: &Image<u8, 1>) -> Self {
+        let data = image.as_contiguous_slice();
+        let mut histogram = [0usize; 256];
+        data.iter().for_each(|&v| histogram[v as usize] += 1);
+
+        // the threshold maximizing the variance between the dark and light pixels
+        let total = data.len() as f64;
+        let sum = histogram
+            .iter()
+            .enumerate()
+            .map(|(v, &n)| (v * n) as f64)
+            .sum::<f64>();
+        let (mut count0, mut sum0) = (0f64, 0f64);
+        let (mut threshold, mut best) = (0, -1.0);
+        for (v, &n) in histogram.iter().enumerate() {
+            count0 += n as f64;
+            sum0 += (v * n) as f64;
+            let count1 = total - count0;
+            if count0 == 0.0 || count1 == 0.0 {
+                continue;
+            }
+            let diff = sum0 / count0 - (sum - sum0) / count1;
+            let variance = count0 * count1 * diff * diff;
+            if variance > best {
+                (threshold, best) = (v, variance);
+            }
+        }
+
+        Self {
+            width: image.width(),
+            height: image.height(),
+            dark: data.iter().map(|&v| v as usize <= threshold).collect(),
+        }
+    }
+
+    /// Get a pixel, or None outside of the image.
+    fn get(&self, x: isize, y: isize) -> Option<bool> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(self.dark[y as usize * self.width + x as usize])
+    }
+}
+
+/// A finder pattern, one of the three squares at the corners of a QR code.
+#[derive(Debug, Clone, Copy)]
+struct FinderPattern {
+    center: Point2,
+    module_size: f32,
+    count: usize,
+}
+
+/// Check if the runs of a line follow the 1:1:3:1:1 ratios of a finder pattern.
+fn is_finder_runs(runs: &[usize; 5]) -> bool {
+    let total = runs.iter().sum::<usize>();
+    if total < 7 || runs.contains(&0) {
+        return false;
+    }
+    let module = total as f32 / 7.0;
+    let tolerance = module / 2.0;
+    runs.iter()
+        .zip([1.0, 1.0, 3.0, 1.0, 1.0])
+        .all(|(&run, ratio)| (run as f32 - ratio * module).abs() < ratio * tolerance)
+}
+
+/// Check the finder pattern runs along a line crossing its center.
+///
+/// Returns the center of the pattern on the line and the total length of its runs.
+fn cross_check_finder(
+    at: impl Fn(isize) -> Option<bool>,
+    max_run: usize,
+    total: usize,
+) -> Option<(f32, usize)> {
+    let mut runs = [0usize; 5];
+    // the runs before the center, from the center outwards
+    let mut t = 0;
+    while at(t) == Some(true) {
+        runs[2] += 1;
+        t -= 1;
+    }
+    for (run, dark) in [(1, false), (0, true)] {
+        while at(t) == Some(dark) && runs[run] <= max_run {
+            runs[run] += 1;
+            t -= 1;
+        }
+        if runs[run] > max_run {
+            return None;
+        }
+    }
+
+    // the runs after the center
+    t = 1;
+    while at(t) == Some(true) {
+        runs[2] += 1;
+        t += 1;
+    }
+    for (run, dark) in [(3, false), (4, true)] {
+        while at(t) == Some(dark) && runs[run] <= max_run {
+            runs[run] += 1;
+            t += 1;
+        }
+        if runs[run] > max_run {
+            return None;
+        }
+    }
+
+    let length = runs.iter().sum::<usize>();
+    if 5 * length.abs_diff(total) >= 2 * total || !is_finder_runs(&runs) {
+        return None;
+    }
+    let center = t as f32 - runs[4] as f32 - runs[3] as f32 - runs[2] as f32 / 2.0;
+    Some((center, length))
+}
+
+/// Find the finder patterns of an image.
+fn find_finder_patterns(image: &BinaryImage) -> Vec<FinderPattern> {
+    let mut patterns: Vec<FinderPattern> = Vec::new();
+
+    let mut check = |runs: &[usize; 5], x: usize, y: usize| -> bool {
+        let total = runs.iter().sum::<usize>();
+        let cx = x as f32 - runs[4] as f32 - runs[3] as f32 - runs[2] as f32 / 2.0;
+
+        // confirm vertically, then horizontally again at the center
+        let column = cx as isize;
+        let Some((dy, _)) =
+            cross_check_finder(|t| image.get(column, y as isize + t), runs[2], total)
+        else {
+            return false;
+        };
+        let cy = y as f32 + dy;
+        let row = cy as isize;
+        let Some((dx, length)) = cross_check_finder(|t| image.get(column + t, row), runs[2], total)
+        else {
+            return false;
+        };
+
+        let center = Point2::new(column as f32 + dx, cy);
+        let module_size = length as f32 / 7.0;
+        match patterns.iter_mut().find(|p| {
+            (p.center.x - center.x).abs() <= p.module_size
+                && (p.center.y - center.y).abs() <= p.module_size
+                && (p.module_size - module_size).abs() <= p.module_size.max(1.0)
+        }) {
+            Some(p) => {
+                // the running average of the detections
+                let n = p.count as f32;
+                p.center = Point2::new(
+                    (p.center.x * n + center.x) / (n + 1.0),
+                    (p.center.y * n + center.y) / (n + 1.0),
+                );
+                p.module_size = (p.module_size * n + module_size) / (n + 1.0);
+                p.count += 1;
+            }
+            None => patterns.push(FinderPattern {
+                center,
+                module_size,
+                count: 1,
+            }),
+        }
+        true
+    };
+
+    for y in 0..image.height {
+        let mut runs = [0usize; 5];
+        let mut state = 0;
+        for x in 0..image.width {
+            let dark = image.dark[y * image.width + x];
+            if dark {
+                // a dark pixel after a light run starts the next dark run
+                if state % 2 == 1 {
+                    state += 1;
+                }
+                runs[state] += 1;
+            } else if state % 2 == 1 {
+                runs[state] += 1;
+            } else if state == 4 {
+                if is_finder_runs(&runs) && check(&runs, x, y) {
+                    runs = [0; 5];
+                    state = 0;
+                } else {
+                    // shift the runs to look for a pattern starting at the next dark run
+                    runs = [runs[2], runs[3], runs[4], 1, 0];
+                    state = 3;
+                }
+            } else {
+                state += 1;
+                runs[state] += 1;
+            }
+        }
+    }
+
+    // the patterns seen on the most rows first
+    patterns.sort_by_key(|p| std::cmp::Reverse(p.count));
+    patterns.truncate(MAX_FINDER_PATTERNS);
+    patterns
+}
+
+/// Order three finder patterns as the top-left, top-right and bottom-left ones.
+///
+/// Returns None if they do not form the right isosceles triangle of a QR code, along
+/// with a score of their regularity otherwise, lower being better.
+fn order_finder_patterns(patterns: [FinderPattern; 3]) -> Option<([FinderPattern; 3], f32)> {
+    let [a, b, c] = patterns;
+    let sizes = patterns.map(|p| p.module_size);
+    let (min_size, max_size) = (
+        sizes.iter().cloned().fold(f32::MAX, f32::min),
+        sizes.iter().cloned().fold(0.0, f32::max),
+    );
+    if max_size > 1.5 * min_size {
+        return None;
+    }
+
+    // the top-left pattern is at the right angle, opposite to the longest side
+    let (ab, ac, bc) = (
+        a.center.distance(&b.center),
+        a.center.distance(&c.center),
+        b.center.distance(&c.center),
+    );
+    let (top_left, mut top_right, mut bottom_left) = if bc >= ab && bc >= ac {
+        (a, b, c)
+    } else if ac >= ab {
+        (b, a, c)
+    } else {
+        (c, a, b)
+    };
+
+    let (u, v) = (
+        (
+            top_right.center.x - top_left.center.x,
+            top_right.center.y - top_left.center.y,
+        ),
+        (
+            bottom_left.center.x - top_left.center.x,
+            bottom_left.center.y - top_left.center.y,
+        ),
+    );
+    if u.0 * v.1 - u.1 * v.0 < 0.0 {
+        std::mem::swap(&mut top_right, &mut bottom_left);
+    }
+
+    let module_size = sizes.iter().sum::<f32>() / 3.0;
+    let (leg1, leg2) = (
+        (u.0 * u.0 + u.1 * u.1).sqrt(),
+        (v.0 * v.0 + v.1 * v.1).sqrt(),
+    );
+    let hypotenuse2 = ab.max(ac).max(bc).powi(2);
+    let legs2 = leg1 * leg1 + leg2 * leg2;
+    let max_span = (dimension(MAX_VERSION) - 7) as f32 * 1.5 * module_size;
+    if leg1.min(leg2) < 10.0 * module_size || leg1.max(leg2) > max_span {
+        return None;
+    }
+
+    let score = (1.0 - leg1.min(leg2) / leg1.max(leg2)) + (1.0 - hypotenuse2 / legs2).abs();
+    (score < 0.5).then_some(([top_left, top_right, bottom_left], score))
+}
+
+/// Check the alignment pattern runs along a line crossing its center.
+///
+/// Returns the center of the pattern on the line.
+fn cross_check_alignment(at: impl Fn(isize) -> Option<bool>, module_size: f32) -> Option<f32> {
+    let close = |run: isize| (run as f32 - module_size).abs() <= module_size / 2.0 + 1.0;
+    let max_run = (2.0 * module_size).ceil() as isize + 1;
+
+    // the dark center, then the light ring and the dark ring on each side
+    let mut ends = [0isize; 2];
+    for (end, step) in ends.iter_mut().zip([-1, 1]) {
+        let mut t = 0;
+        while at(t + step) == Some(true) && t.abs() < max_run {
+            t += step;
+        }
+        *end = t;
+        let start = t;
+        t += step;
+        while at(t) == Some(false) && (t - start).abs() <= max_run {
+            t += step;
+        }
+        if at(t) != Some(true) || !close((t - start).abs() - 1) {
+            return None;
+        }
+    }
+
+    (at(0) == Some(true) && close(ends[1] - ends[0] + 1))
+        .then_some((ends[0] + ends[1]) as f32 / 2.0 + 0.5)
+}
+
+/// The maximum number of alignment pattern candidates tried for a QR code.
+const MAX_ALIGNMENT_CANDIDATES: usize = 3;
+
+/// Find the candidate alignment patterns of a QR code around its predicted position.
+///
+/// The runs of the pattern along the image axes are longer than the module size for a
+/// rotated code, by the factor `run_scale`. The candidates are sorted by their distance
+/// to the prediction.
+fn find_alignment_patterns(
+    image: &BinaryImage,
+    predicted: Point2,
+    module_size: f32,
+    run_scale: f32,
+) -> Vec<Point2> {
+    let radius = (4.0 * module_size).ceil() as isize;
+    let (px, py) = (predicted.x as isize, predicted.y as isize);
+    let run_size = module_size * run_scale;
+
+    let mut candidates: Vec<(f32, Point2)> = Vec::new();
+    for y in py - radius..=py + radius {
+        for x in px - radius..=px + radius {
+            if image.get(x, y) != Some(true) {
+                continue;
+            }
+            let Some(dx) = cross_check_alignment(|t| image.get(x + t, y), run_size) else {
+                continue;
+            };
+            let column = x + dx as isize;
+            let Some(dy) = cross_check_alignment(|t| image.get(column, y + t), run_size) else {
+                continue;
+            };
+            let center = Point2::new(x as f32 + dx, y as f32 + dy);
+            // the pixels of a same pattern give a single candidate
+            if candidates
+                .iter()
+                .all(|(_, c)| c.distance(&center) > module_size)
+            {
+                candidates.push((center.distance(&predicted), center));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    candidates
+        .into_iter()
+        .take(MAX_ALIGNMENT_CANDIDATES)
+        .map(|(_, c)| c)
+        .collect()
+}
+
+/// Apply a perspective transform to a point.
+fn project(m: &PerspectiveMatrix, x: f32, y: f32) -> Point2 {
+    let w = m[6] * x + m[7] * y + m[8];
+    Point2::new(
+        (m[0] * x + m[1] * y + m[2]) / w,
+        (m[3] * x + m[4] * y + m[5]) / w,
+    )
+}
+
+/// Sample the modules of a QR code at their centers.
+fn sample_grid(image: &BinaryImage, m: &PerspectiveMatrix, dimension: usize) -> Grid {
+    let mut modules = Vec::with_capacity(dimension * dimension);
+    for j in 0..dimension {
+        for i in 0..dimension {
+            let p = project(m, i as f32 + 0.5, j as f32 + 0.5);
+            let dark = image.get(p.x.floor() as isize, p.y.floor() as isize);
+            modules.push(dark.unwrap_or(false));
+        }
+    }
+    Grid { dimension, modules }
+}
+
+/// Sample and decode the modules of a QR code of a version, given the image positions
+/// of the centers of its finder patterns and of a fourth point.
+fn decode_version(
+    image: &BinaryImage,
+    version: usize,
+    points: [Point2; 4],
+    fourth_module: Point2,
+) -> Option<QrCode> {
+    let d = dimension(version) as f32;
+    let modules = [
+        Point2::new(3.5, 3.5),
+        Point2::new(d - 3.5, 3.5),
+        Point2::new(3.5, d - 3.5),
+        fourth_module,
+    ];
+    let m = get_perspective_transform(&modules, &points).ok()?;
+
+    let grid = sample_grid(image, &m, dimension(version));
+    if version >= 7 && grid.read_version().is_some_and(|v| v != version) {
+        return None;
+    }
+    let (ec_level, payload) = decode_grid(&grid, version)?;
+
+    Some(QrCode {
+        corners: [(0.0, 0.0), (d, 0.0), (d, d), (0.0, d)].map(|(x, y)| project(&m, x, y)),
+        version,
+        ec_level,
+        payload,
+    })
+}
+
+/// Decode the QR code located by three finder patterns.
+fn decode_finder_patterns(image: &BinaryImage, patterns: [FinderPattern; 3]) -> Option<QrCode> {
+    let [top_left, top_right, bottom_left] = patterns.map(|p| p.center);
+
+    // the runs along the image axes are longer than the modules of a rotated code
+    let angle = (top_right.y - top_left.y).atan2(top_right.x - top_left.x);
+    let run_scale = 1.0 / angle.cos().abs().max(angle.sin().abs());
+    let [size_tl, size_tr, size_bl] = patterns.map(|p| p.module_size / run_scale);
+    let module_size = (size_tl + size_tr + size_bl) / 3.0;
+
+    // the number of modules between the centers of the finder patterns gives the version,
+    // the module size varying along the sides under perspective
+    let span = (top_left.distance(&top_right) * 2.0 / (size_tl + size_tr)
+        + top_left.distance(&bottom_left) * 2.0 / (size_tl + size_bl))
+        / 2.0;
+    let estimate = ((span + 7.0 - 17.0) / 4.0).round() as isize;
+
+    for version in [estimate, estimate - 1, estimate + 1] {
+        if !(1..=MAX_VERSION as isize).contains(&version) {
+            continue;
+        }
+        let version = version as usize;
+        let d = dimension(version) as f32;
+
+        if version == 1 {
+            // without alignment pattern, the missing corner of the parallelogram
+            let corner = Point2::new(
+                top_right.x + bottom_left.x - top_left.x,
+                top_right.y + bottom_left.y - top_left.y,
+            );
+            let points = [top_left, top_right, bottom_left, corner];
+            if let Some(code) = decode_version(image, version, points, Point2::new(17.5, 17.5)) {
+                return Some(code);
+            }
+            continue;
+        }
+
+        // the alignment pattern at the bottom-right, or its prediction as a fallback
+        let k = (d - 10.0) / (d - 7.0);
+        let predicted = Point2::new(
+            top_left.x + (top_right.x + bottom_left.x - 2.0 * top_left.x) * k,
+            top_left.y + (top_right.y + bottom_left.y - 2.0 * top_left.y) * k,
+        );
+        let mut alignments = find_alignment_patterns(image, predicted, module_size, run_scale);
+        alignments.push(predicted);
+
+        for alignment in alignments {
+            let points = [top_left, top_right, bottom_left, alignment];
+            let module = Point2::new(d - 6.5, d - 6.5);
+            if let Some(code) = decode_version(image, version, points, module) {
+                return Some(code);
+            }
+        }
+    }
+
+    None
+}
+
+/// Detects and decodes the QR codes of an image.
+///
+/// The image is binarized with the Otsu threshold, and the finder patterns at three of
+/// the corners of the codes are found from the 1:1:3:1:1 ratios of their dark and light
+/// runs. The modules of each code are sampled through the perspective transform given by
+/// its finder and alignment patterns, and its payload is decoded after the correction of
+/// the errors.
+///
+/// The versions 1 to 10 are supported, i.e. up to 57x57 modules.
+///
+/// # Arguments
+///
+/// * `image` - The input grayscale image with shape (H, W, 1).
+///
+/// # Returns
+///
+/// The decoded QR codes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::barcode::detect_qr_codes;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 64,
+///         height: 48,
+///     },
+///     255,
+/// )
+/// .unwrap();
+///
+/// assert!(detect_qr_codes(&image).unwrap().is_empty());
+/// ```
+pub fn detect_qr_codes(image: &Image<u8, 1>) -> Result<Vec<QrCode>> {
+    let binary = BinaryImage::from_image(image);
+    let patterns = find_finder_patterns(&binary);
+
+    // the candidate triplets of finder patterns, the most regular first
+    let mut triplets = Vec::new();
+    for i in 0..patterns.len() {
+        for j in i + 1..patterns.len() {
+            for k in j + 1..patterns.len() {
+                let triplet = [patterns[i], patterns[j], patterns[k]];
+                if let Some((ordered, score)) = order_finder_patterns(triplet) {
+                    triplets.push(([i, j, k], ordered, score));
+                }
+            }
+        }
+    }
+    triplets.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let mut used = vec![false; patterns.len()];
+    let mut codes = Vec::new();
+    for (indices, ordered, _) in triplets {
+        if indices.iter().any(|&i| used[i]) {
+            continue;
+        }
+        if let Some(code) = decode_finder_patterns(&binary, ordered) {
+            indices.iter().for_each(|&i| used[i] = true);
+            codes.push(code);
+        }
+    }
+
+    Ok(codes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EcLevel;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    /// A version 2 QR code encoding "https://kornia.org".
+    const CODE: [&str; 25] = [
+        "#######.#..##...#.#######",
+        "#.....#.#....#....#.....#",
+        "#.###.#...#.#####.#.###.#",
+        "#.###.#.#.###..##.#.###.#",
+        "#.###.#....#.####.#.###.#",
+        "#.....#...###.#...#.....#",
+        "#######.#.#.#.#.#.#######",
+        "........##..#####........",
+        "#.##.###.#.######.#..#.##",
+        "..####.#.#.#....#..#...#.",
+        "..######..##..#...##.....",
+        "..###.......####.######..",
+        "..#.#####........##.#.###",
+        ".#.###.##..#.#.#..###...#",
+        ".#....##..#.#.#.....#.##.",
+        "#.##....###.##.#...##...#",
+        "..##..#...##....#########",
+        "........###.##..#...#.#.#",
+        "#######.#..##...#.#.#.###",
+        "#.....#.##..#..##...#..##",
+        "#.###.#...####..######...",
+        "#.###.#.#.#.###..##.#####",
+        "#.###.#.#..###..###.#.##.",
+        "#.....#..#.......##.#.#..",
+        "#######.#.#.#.#....######",
+    ];
+
+    /// Draw the code with 4 pixels per module, rotated clockwise by quarter turns.
+    fn draw(image: &mut Image<u8, 1>, x0: usize, y0: usize, quarter_turns: usize) {
+        let d = CODE.len();
+        for (y, row) in CODE.iter().enumerate() {
+            for (x, module) in row.bytes().enumerate() {
+                let (mut u, mut v) = (x, y);
+                for _ in 0..quarter_turns {
+                    (u, v) = (d - 1 - v, u);
+                }
+                let value = if module == b'#' { 20 } else { 230 };
+                for dy in 0..4 {
+                    for dx in 0..4 {
+                        image.data[[y0 + 4 * v + dy, x0 + 4 * u + dx, 0]] = value;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn detect_qr_codes() -> Result<()> {
+        let mut image = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 270,
+                height: 132,
+            },
+            230,
+        )?;
+        draw(&mut image, 16, 16, 0);
+        draw(&mut image, 154, 16, 1);
+
+        let mut codes = super::detect_qr_codes(&image)?;
+        codes.sort_by(|a, b| a.corners[2].x.total_cmp(&b.corners[2].x));
+        assert_eq!(codes.len(), 2);
+        for code in codes.iter() {
+            assert_eq!(code.text(), Some("https://kornia.org"));
+            assert_eq!((code.version, code.ec_level), (2, EcLevel::M));
+        }
+
+        // the corners follow the orientation of the codes
+        let close = |p: &crate::geometry::Point2, x: f32, y: f32| {
+            (p.x - x).abs() < 1.5 && (p.y - y).abs() < 1.5
+        };
+        assert!(close(&codes[0].corners[0], 16.0, 16.0));
+        assert!(close(&codes[0].corners[2], 116.0, 116.0));
+        assert!(close(&codes[1].corners[0], 254.0, 16.0));
+        assert!(close(&codes[1].corners[2], 154.0, 116.0));
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+mod decode;
+mod detect;
+mod reed_solomon;
+pub use decode::EcLevel;
+pub use detect::{detect_qr_codes, QrCode};
@@ -0,0 +1,207 @@
+/// Build the exponential table of GF(256) with the QR code polynomial x^8 + x^4 + x^3 + x^2 + 1.
+///
+/// The table is doubled to multiply without a modulo on the logarithms.
+const fn build_exp() -> [u8; 512] {
+    let mut exp = [0u8; 512];
+    let mut x = 1u16;
+    let mut i = 0;
+    while i < 512 {
+        exp[i] = x as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11d;
+        }
+        i += 1;
+    }
+    exp
+}
+
+/// Build the logarithm table of GF(256) from the exponential table.
+const fn build_log(exp: &[u8; 512]) -> [u8; 256] {
+    let mut log = [0u8; 256];
+    let mut i = 0;
+    while i < 255 {
+        log[exp[i] as usize] = i as u8;
+        i += 1;
+    }
+    log
+}
+
+const EXP: [u8; 512] = build_exp();
+const LOG: [u8; 256] = build_log(&EXP);
+
+fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + LOG[b as usize] as usize]
+    }
+}
+
+fn div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + 255 - LOG[b as usize] as usize]
+    }
+}
+
+/// Get the power of the primitive element alpha, for any exponent.
+fn alpha_pow(e: isize) -> u8 {
+    EXP[e.rem_euclid(255) as usize]
+}
+
+/// Evaluate a polynomial with the coefficients from the lowest degree.
+fn eval(poly: &[u8], x: u8) -> u8 {
+    poly.iter().rev().fold(0, |acc, &c| mul(acc, x) ^ c)
+}
+
+/// Compute the syndromes of a codeword, whose first byte is the highest degree coefficient.
+fn syndromes(codeword: &[u8], num_ec: usize) -> Vec<u8> {
+    (0..num_ec)
+        .map(|i| {
+            let x = alpha_pow(i as isize);
+            codeword.iter().fold(0, |acc, &c| mul(acc, x) ^ c)
+        })
+        .collect()
+}
+
+/// Correct the errors of a Reed-Solomon codeword in place.
+///
+/// The codeword holds the data followed by `num_ec` error correction bytes, and up to
+/// `num_ec / 2` erroneous bytes can be corrected.
+///
+/// Returns the number of corrected bytes, or None if the codeword is not correctable.
+pub(crate) fn correct(codeword: &mut [u8], num_ec: usize) -> Option<usize> {
+    let n = codeword.len();
+    let s = syndromes(codeword, num_ec);
+    if s.iter().all(|&v| v == 0) {
+        return Some(0);
+    }
+
+    // the error locator polynomial with the Berlekamp-Massey algorithm
+    let (mut c, mut b) = (vec![1u8], vec![1u8]);
+    let (mut l, mut m, mut last) = (0usize, 1usize, 1u8);
+    for k in 0..num_ec {
+        let mut d = s[k];
+        for (i, &ci) in c.iter().enumerate().take(l + 1).skip(1) {
+            d ^= mul(ci, s[k - i]);
+        }
+        if d == 0 {
+            m += 1;
+            continue;
+        }
+
+        let coef = div(d, last);
+        let mut next = c.clone();
+        next.resize(next.len().max(b.len() + m), 0);
+        for (i, &bi) in b.iter().enumerate() {
+            next[i + m] ^= mul(coef, bi);
+        }
+
+        if 2 * l <= k {
+            b = std::mem::replace(&mut c, next);
+            l = k + 1 - l;
+            last = d;
+            m = 1;
+        } else {
+            c = next;
+            m += 1;
+        }
+    }
+    c.truncate(l + 1);
+    if 2 * l > num_ec || c.len() != l + 1 {
+        return None;
+    }
+
+    // the error positions with a Chien search, the byte k being at the power n - 1 - k
+    let positions = (0..n)
+        .filter(|&k| eval(&c, alpha_pow(-((n - 1 - k) as isize))) == 0)
+        .collect::<Vec<_>>();
+    if positions.len() != l {
+        return None;
+    }
+
+    // the error magnitudes with the Forney algorithm
+    let mut omega = vec![0u8; num_ec];
+    for (i, &si) in s.iter().enumerate() {
+        for (j, &cj) in c.iter().enumerate() {
+            if i + j < num_ec {
+                omega[i + j] ^= mul(si, cj);
+            }
+        }
+    }
+    let derivative = c
+        .iter()
+        .enumerate()
+        .map(|(i, &ci)| if i % 2 == 1 { ci } else { 0 })
+        .skip(1)
+        .collect::<Vec<_>>();
+
+    for &k in positions.iter() {
+        let x = alpha_pow((n - 1 - k) as isize);
+        let x_inv = alpha_pow(-((n - 1 - k) as isize));
+        let denominator = eval(&derivative, x_inv);
+        if denominator == 0 {
+            return None;
+        }
+        codeword[k] ^= mul(x, div(eval(&omega, x_inv), denominator));
+    }
+
+    syndromes(codeword, num_ec)
+        .iter()
+        .all(|&v| v == 0)
+        .then_some(l)
+}
+
+#[cfg(test)]
+mod tests {
+    /// Append the error correction bytes to some data.
+    fn encode(data: &[u8], num_ec: usize) -> Vec<u8> {
+        // the generator polynomial, from the highest degree coefficient
+        let mut generator = vec![1u8];
+        for i in 0..num_ec {
+            let root = super::alpha_pow(i as isize);
+            let mut next = vec![0u8; generator.len() + 1];
+            for (j, &g) in generator.iter().enumerate() {
+                next[j] ^= g;
+                next[j + 1] ^= super::mul(g, root);
+            }
+            generator = next;
+        }
+
+        let mut remainder = data.to_vec();
+        remainder.resize(data.len() + num_ec, 0);
+        for i in 0..data.len() {
+            let factor = remainder[i];
+            for (j, &g) in generator.iter().enumerate() {
+                remainder[i + j] ^= super::mul(g, factor);
+            }
+        }
+
+        let mut codeword = data.to_vec();
+        codeword.extend_from_slice(&remainder[data.len()..]);
+        codeword
+    }
+
+    #[test]
+    fn correct_errors() {
+        let data = b"kornia reed solomon".to_vec();
+        let codeword = encode(&data, 10);
+
+        let mut received = codeword.clone();
+        assert_eq!(super::correct(&mut received, 10), Some(0));
+
+        // up to half of the error correction bytes can be corrected
+        for (i, position) in [0, 7, 12, 20, 28].into_iter().enumerate() {
+            received[position] ^= 0x5a + i as u8;
+        }
+        assert_eq!(super::correct(&mut received, 10), Some(5));
+        assert_eq!(received, codeword);
+
+        for position in [1, 3, 5, 9, 11, 13] {
+            received[position] ^= 0xff;
+        }
+        assert_eq!(super::correct(&mut received, 10), None);
+    }
+}
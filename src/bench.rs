@@ -0,0 +1,281 @@
+use crate::geometry::SplitMix64;
+use crate::image::{Image, ImageSize};
+use anyhow::Result;
+use std::time::{Duration, Instant};
+
+/// The parameters of [`profile_op_with_params`].
+#[derive(Debug, Clone)]
+pub struct ProfileParams {
+    /// The number of runs discarded before the measurements.
+    pub warmup_iterations: usize,
+    /// The minimum number of measured runs per image size.
+    pub min_iterations: usize,
+    /// The minimum measured time per image size, to stabilize the statistics of fast ops.
+    pub min_duration: Duration,
+    /// The seed of the generated images.
+    pub seed: u64,
+}
+
+impl Default for ProfileParams {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: 3,
+            min_iterations: 10,
+            min_duration: Duration::from_millis(500),
+            seed: 0,
+        }
+    }
+}
+
+/// The measurements of an op for an image size.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ProfileResult {
+    /// The width of the input images.
+    pub width: usize,
+    /// The height of the input images.
+    pub height: usize,
+    /// The number of measured runs.
+    pub iterations: usize,
+    /// The mean latency in microseconds.
+    pub mean_us: f64,
+    /// The median latency in microseconds.
+    pub median_us: f64,
+    /// The minimum latency in microseconds.
+    pub min_us: f64,
+    /// The 95th percentile of the latency in microseconds.
+    pub p95_us: f64,
+    /// The throughput in megapixels per second, from the median latency.
+    pub megapixels_per_second: f64,
+}
+
+/// The measurements of an op over a set of image sizes.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ProfileReport {
+    /// The measurements of each image size.
+    pub results: Vec<ProfileResult>,
+}
+
+impl ProfileReport {
+    /// Format the report as a table with a row per image size.
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:>11} {:>8} {:>12} {:>12} {:>12} {:>12} {:>10}\n",
+            "size", "iters", "mean (us)", "median (us)", "min (us)", "p95 (us)", "MP/s"
+        );
+        for r in &self.results {
+            table += &format!(
+                "{:>11} {:>8} {:>12.1} {:>12.1} {:>12.1} {:>12.1} {:>10.1}\n",
+                format!("{}x{}", r.width, r.height),
+                r.iterations,
+                r.mean_us,
+                r.median_us,
+                r.min_us,
+                r.p95_us,
+                r.megapixels_per_second
+            );
+        }
+        table
+    }
+
+    /// Format a table comparing the median latencies of the report with a baseline.
+    ///
+    /// # Arguments
+    ///
+    /// * `baseline` - The report of the reference op, over the same image sizes.
+    ///
+    /// # Returns
+    ///
+    /// A table with the speedup of the report over the baseline for each image size.
+    pub fn compare(&self, baseline: &ProfileReport) -> String {
+        let mut table = format!(
+            "{:>11} {:>16} {:>12} {:>8}\n",
+            "size", "baseline (us)", "this (us)", "speedup"
+        );
+        for r in &self.results {
+            let reference = baseline
+                .results
+                .iter()
+                .find(|b| (b.width, b.height) == (r.width, r.height));
+            let size = format!("{}x{}", r.width, r.height);
+            match reference {
+                Some(b) => {
+                    table += &format!(
+                        "{:>11} {:>16.1} {:>12.1} {:>7.2}x\n",
+                        size,
+                        b.median_us,
+                        r.median_us,
+                        b.median_us.max(1e-3) / r.median_us.max(1e-3)
+                    )
+                }
+                None => {
+                    table += &format!(
+                        "{:>11} {:>16} {:>12.1} {:>8}\n",
+                        size, "-", r.median_us, "-"
+                    )
+                }
+            }
+        }
+        table
+    }
+
+    /// Serialize the report to JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Measures the latency and the throughput of an op with the default parameters.
+///
+/// See [`profile_op_with_params`].
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::bench::profile_op;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let sizes = [ImageSize {
+///     width: 64,
+///     height: 48,
+/// }];
+/// let report = profile_op(
+///     |image: &Image<f32, 3>| kornia_rs::color::gray_from_rgb(image),
+///     &sizes,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(report.results[0].width, 64);
+/// println!("{}", report.to_table());
+/// ```
+pub fn profile_op<T, const CHANNELS: usize, R, F>(
+    op: F,
+    sizes: &[ImageSize],
+) -> Result<ProfileReport>
+where
+    T: Clone + Default + num_traits::NumCast + std::fmt::Debug,
+    F: FnMut(&Image<T, CHANNELS>) -> R,
+{
+    profile_op_with_params(op, sizes, &ProfileParams::default())
+}
+
+/// Measures the latency and the throughput of an op over generated images.
+///
+/// For each size, an image of uniform random values in [0, 255] is generated from the
+/// seed and the op is run on it until both the minimum number of runs and the minimum
+/// duration are reached. The results of the op are passed through a
+/// [`black_box`](std::hint::black_box) so that the compiler cannot skip its work.
+///
+/// # Arguments
+///
+/// * `op` - The op to measure.
+/// * `sizes` - The sizes of the input images.
+/// * `params` - The parameters of the measurements.
+///
+/// # Returns
+///
+/// The measurements for each image size.
+pub fn profile_op_with_params<T, const CHANNELS: usize, R, F>(
+    mut op: F,
+    sizes: &[ImageSize],
+    params: &ProfileParams,
+) -> Result<ProfileReport>
+where
+    T: Clone + Default + num_traits::NumCast + std::fmt::Debug,
+    F: FnMut(&Image<T, CHANNELS>) -> R,
+{
+    let mut rng = SplitMix64::new(params.seed);
+    let mut results = Vec::with_capacity(sizes.len());
+
+    for &size in sizes {
+        let data = (0..size.width * size.height * CHANNELS)
+            .map(|_| (rng.next_u64() >> 56) as u8)
+            .collect();
+        let image = Image::<u8, CHANNELS>::new(size, data)?.cast::<T>()?;
+
+        for _ in 0..params.warmup_iterations {
+            std::hint::black_box(op(std::hint::black_box(&image)));
+        }
+
+        let mut latencies = Vec::new();
+        let start = Instant::now();
+        while latencies.len() < params.min_iterations.max(1)
+            || start.elapsed() < params.min_duration
+        {
+            let t = Instant::now();
+            std::hint::black_box(op(std::hint::black_box(&image)));
+            latencies.push(t.elapsed().as_secs_f64() * 1e6);
+        }
+        latencies.sort_by(f64::total_cmp);
+
+        let n = latencies.len();
+        let median_us = if n % 2 == 0 {
+            (latencies[n / 2 - 1] + latencies[n / 2]) / 2.0
+        } else {
+            latencies[n / 2]
+        };
+        let p95 = latencies[((n as f64 * 0.95).ceil() as usize).clamp(1, n) - 1];
+
+        results.push(ProfileResult {
+            width: size.width,
+            height: size.height,
+            iterations: n,
+            mean_us: latencies.iter().sum::<f64>() / n as f64,
+            median_us,
+            min_us: latencies[0],
+            p95_us: p95,
+            megapixels_per_second: (size.width * size.height) as f64 / median_us.max(1e-3),
+        });
+    }
+
+    Ok(ProfileReport { results })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{profile_op_with_params, ProfileParams, ProfileReport};
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+    use std::time::Duration;
+
+    #[test]
+    fn profile_op() -> Result<()> {
+        let sizes = [
+            ImageSize {
+                width: 8,
+                height: 4,
+            },
+            ImageSize {
+                width: 16,
+                height: 8,
+            },
+        ];
+        let params = ProfileParams {
+            min_iterations: 5,
+            min_duration: Duration::ZERO,
+            ..Default::default()
+        };
+
+        let mut runs = 0;
+        let report = profile_op_with_params(
+            |image: &Image<u8, 1>| {
+                runs += 1;
+                image.data.iter().map(|&v| v as u32).sum::<u32>()
+            },
+            &sizes,
+            &params,
+        )?;
+        assert_eq!(runs, 2 * (5 + 3));
+        assert_eq!(report.results.len(), 2);
+        assert_eq!(report.results[1].width, 16);
+        assert!(report.results[0].min_us <= report.results[0].median_us);
+
+        assert_eq!(report.to_table().lines().count(), 3);
+        assert!(report.compare(&report).contains("1.00x"));
+        let json = report.to_json()?;
+        assert!(json.contains("\"megapixels_per_second\""));
+
+        let empty = ProfileReport { results: vec![] };
+        assert_eq!(report.compare(&empty).lines().count(), 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+use crate::image::{Image, ImageSize};
+use anyhow::Result;
+
+/// A level of a pyramid stored as an interleaved row-major buffer.
+#[derive(Clone)]
+struct Level {
+    data: Vec<f32>,
+    width: usize,
+    height: usize,
+    channels: usize,
+}
+
+impl Level {
+    fn at(&self, x: usize, y: usize, c: usize) -> f32 {
+        self.data[(y * self.width + x) * self.channels + c]
+    }
+
+    /// Blur with the 5-tap binomial kernel and drop the odd rows and columns.
+    fn pyr_down(&self) -> Self {
+        const KERNEL: [f32; 5] = [1.0 / 16.0, 4.0 / 16.0, 6.0 / 16.0, 4.0 / 16.0, 1.0 / 16.0];
+        let (w, h, ch) = (self.width, self.height, self.channels);
+        let (width, height) = (w.div_ceil(2), h.div_ceil(2));
+
+        // horizontal pass evaluated at the even columns only
+        let mut rows = vec![0f32; width * h * ch];
+        for y in 0..h {
+            for x in 0..width {
+                for c in 0..ch {
+                    rows[(y * width + x) * ch + c] = KERNEL
+                        .iter()
+                        .enumerate()
+                        .map(|(k, &wk)| {
+                            let sx = (2 * x + k).saturating_sub(2).min(w - 1);
+                            wk * self.at(sx, y, c)
+                        })
+                        .sum();
+                }
+            }
+        }
+
+        // vertical pass evaluated at the even rows only
+        let mut data = vec![0f32; width * height * ch];
+        for y in 0..height {
+            for x in 0..width {
+                for c in 0..ch {
+                    data[(y * width + x) * ch + c] = KERNEL
+                        .iter()
+                        .enumerate()
+                        .map(|(k, &wk)| {
+                            let sy = (2 * y + k).saturating_sub(2).min(h - 1);
+                            wk * rows[(sy * width + x) * ch + c]
+                        })
+                        .sum();
+                }
+            }
+        }
+
+        Self {
+            data,
+            width,
+            height,
+            channels: ch,
+        }
+    }
+
+    /// Upsample to the given size, interpolating with the 5-tap binomial kernel.
+    fn pyr_up(&self, width: usize, height: usize) -> Self {
+        let (w, h, ch) = (self.width, self.height, self.channels);
+
+        // the even samples take 1/8, 6/8, 1/8 of their neighbours and the odd ones 1/2, 1/2
+        let taps = |i: usize, len: usize| -> [(usize, f32); 3] {
+            let j = i / 2;
+            if i % 2 == 1 {
+                [(j.min(len - 1), 0.5), ((j + 1).min(len - 1), 0.5), (0, 0.0)]
+            } else {
+                [
+                    (j.saturating_sub(1), 0.125),
+                    (j.min(len - 1), 0.75),
+                    ((j + 1).min(len - 1), 0.125),
+                ]
+            }
+        };
+
+        let mut rows = vec![0f32; width * h * ch];
+        for y in 0..h {
+            for x in 0..width {
+                for c in 0..ch {
+                    rows[(y * width + x) * ch + c] = taps(x, w)
+                        .iter()
+                        .map(|&(sx, wk)| wk * self.at(sx, y, c))
+                        .sum();
+                }
+            }
+        }
+
+        let mut data = vec![0f32; width * height * ch];
+        for y in 0..height {
+            for x in 0..width {
+                for c in 0..ch {
+                    data[(y * width + x) * ch + c] = taps(y, h)
+                        .iter()
+                        .map(|&(sy, wk)| wk * rows[(sy * width + x) * ch + c])
+                        .sum();
+                }
+            }
+        }
+
+        Self {
+            data,
+            width,
+            height,
+            channels: ch,
+        }
+    }
+}
+
+/// Build the gaussian pyramid of a level, from the finest to the coarsest.
+fn gaussian_pyramid(base: Level, levels: usize) -> Vec<Level> {
+    let mut pyramid = vec![base];
+    for _ in 1..levels {
+        let next = pyramid.last().unwrap().pyr_down();
+        pyramid.push(next);
+    }
+    pyramid
+}
+
+/// Build the laplacian pyramid from a gaussian pyramid, keeping the coarsest level as is.
+fn laplacian_pyramid(gaussian: &[Level]) -> Vec<Level> {
+    let mut pyramid = Vec::with_capacity(gaussian.len());
+    for pair in gaussian.windows(2) {
+        let (fine, coarse) = (&pair[0], &pair[1]);
+        let up = coarse.pyr_up(fine.width, fine.height);
+        let mut level = fine.clone();
+        level
+            .data
+            .iter_mut()
+            .zip(up.data.iter())
+            .for_each(|(v, u)| *v -= u);
+        pyramid.push(level);
+    }
+    if let Some(last) = gaussian.last() {
+        pyramid.push(last.clone());
+    }
+    pyramid
+}
+
+/// Blend two images with a multi-band (laplacian pyramid) blending.
+///
+/// Each frequency band of the images is blended separately with a mask smoothed at the
+/// same scale, as proposed by Burt and Adelson (1983). The low frequencies are mixed over
+/// large transitions while the details are kept sharp, hiding the seam between the images.
+///
+/// # Arguments
+///
+/// * `a` - The first image with shape (H, W, C).
+/// * `b` - The second image with shape (H, W, C).
+/// * `mask` - The weight of the first image in the range [0, 1] with shape (H, W, 1).
+/// * `levels` - The number of pyramid levels, with 1 for a plain weighted average.
+///
+/// # Returns
+///
+/// The blended image with shape (H, W, C).
+///
+/// # Errors
+///
+/// Returns an error if the sizes of the images and the mask differ, the number of levels
+/// is zero or the images are too small for the number of levels.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::blend::multiband_blend;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let size = ImageSize {
+///     width: 16,
+///     height: 16,
+/// };
+/// let a = Image::<f32, 3>::from_size_val(size, 1.0).unwrap();
+/// let b = Image::<f32, 3>::from_size_val(size, 0.0).unwrap();
+/// let mask = Image::<f32, 1>::new(
+///     size,
+///     (0..16 * 16).map(|i| if i % 16 < 8 { 1.0 } else { 0.0 }).collect(),
+/// )
+/// .unwrap();
+///
+/// let blended = multiband_blend(&a, &b, &mask, 3).unwrap();
+/// assert_eq!(blended.size(), size);
+/// assert!(blended.get_pixel(0, 8, 0).unwrap() > 0.9);
+/// assert!(blended.get_pixel(15, 8, 0).unwrap() < 0.1);
+/// ```
+pub fn multiband_blend<const CHANNELS: usize>(
+    a: &Image<f32, CHANNELS>,
+    b: &Image<f32, CHANNELS>,
+    mask: &Image<f32, 1>,
+    levels: usize,
+) -> Result<Image<f32, CHANNELS>> {
+    if a.size() != b.size() || a.size() != mask.size() {
+        return Err(anyhow::anyhow!(
+            "The sizes of the images {}, {} and the mask {} must be equal.",
+            a.size(),
+            b.size(),
+            mask.size()
+        ));
+    }
+
+    if levels == 0 {
+        return Err(anyhow::anyhow!("The number of levels must be positive."));
+    }
+
+    let (width, height) = (a.width(), a.height());
+    if (width.min(height) >> (levels - 1)) == 0 {
+        return Err(anyhow::anyhow!(
+            "The image size {} is too small for {} pyramid levels.",
+            a.size(),
+            levels
+        ));
+    }
+
+    let to_level = |data: Vec<f32>, channels: usize| Level {
+        data,
+        width,
+        height,
+        channels,
+    };
+
+    let lap_a = laplacian_pyramid(&gaussian_pyramid(
+        to_level(a.data.iter().cloned().collect(), CHANNELS),
+        levels,
+    ));
+    let lap_b = laplacian_pyramid(&gaussian_pyramid(
+        to_level(b.data.iter().cloned().collect(), CHANNELS),
+        levels,
+    ));
+    let masks = gaussian_pyramid(to_level(mask.data.iter().cloned().collect(), 1), levels);
+
+    // blend each band and collapse the pyramid from the coarsest level
+    let mut result: Option<Level> = None;
+    for ((la, lb), m) in lap_a.iter().zip(lap_b.iter()).zip(masks.iter()).rev() {
+        let mut band = la.clone();
+        for (i, v) in band.data.iter_mut().enumerate() {
+            let w = m.data[i / CHANNELS];
+            *v = w * *v + (1.0 - w) * lb.data[i];
+        }
+
+        if let Some(coarse) = result {
+            let up = coarse.pyr_up(band.width, band.height);
+            band.data
+                .iter_mut()
+                .zip(up.data.iter())
+                .for_each(|(v, u)| *v += u);
+        }
+        result = Some(band);
+    }
+
+    let result = result.ok_or_else(|| anyhow::anyhow!("The pyramid is empty."))?;
+    Image::new(ImageSize { width, height }, result.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn laplacian_pyramid_reconstruction() {
+        // collapsing the laplacian pyramid recovers the original image
+        let base = super::Level {
+            data: (0..9 * 7).map(|i| ((i * 37) % 11) as f32).collect(),
+            width: 9,
+            height: 7,
+            channels: 1,
+        };
+        let laplacian = super::laplacian_pyramid(&super::gaussian_pyramid(base.clone(), 3));
+        assert_eq!(laplacian[1].width, 5);
+        assert_eq!(laplacian[2].height, 2);
+
+        let mut result = laplacian[2].clone();
+        for band in laplacian[..2].iter().rev() {
+            let up = result.pyr_up(band.width, band.height);
+            result = band.clone();
+            result
+                .data
+                .iter_mut()
+                .zip(up.data.iter())
+                .for_each(|(v, u)| *v += u);
+        }
+
+        for (r, b) in result.data.iter().zip(base.data.iter()) {
+            assert!((r - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn multiband_blend_seam() -> Result<()> {
+        let size = ImageSize {
+            width: 32,
+            height: 8,
+        };
+        let a = Image::<f32, 1>::from_size_val(size, 1.0)?;
+        let b = Image::<f32, 1>::from_size_val(size, 0.0)?;
+        let mask = Image::<f32, 1>::new(
+            size,
+            (0..32 * 8)
+                .map(|i| if i % 32 < 16 { 1.0 } else { 0.0 })
+                .collect(),
+        )?;
+
+        // a single level is a hard composite
+        let hard = super::multiband_blend(&a, &b, &mask, 1)?;
+        assert_eq!(hard.data, mask.data);
+
+        // more levels smooth the transition around the seam
+        let soft = super::multiband_blend(&a, &b, &mask, 3)?;
+        let row = (0..32)
+            .map(|x| soft.get_pixel(x, 4, 0))
+            .collect::<Result<Vec<_>>>()?;
+        assert!(row[0] > 0.95 && row[31] < 0.05);
+        assert!(row[15] > 0.5 && row[15] < 0.9);
+        assert!(row[16] > 0.1 && row[16] < 0.5);
+        assert!(row.windows(2).all(|w| w[0] >= w[1] - 1e-5));
+
+        assert!(super::multiband_blend(&a, &b, &mask, 0).is_err());
+        assert!(super::multiband_blend(&a, &b, &mask, 5).is_err());
+        Ok(())
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
 /// * `k6` - The sixth radial distortion coefficient
 /// * `p1` - The first tangential distortion coefficient
 /// * `p2` - The second tangential distortion coefficient
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PolynomialDistortion {
     pub k1: f64,
     pub k2: f64,
@@ -0,0 +1,350 @@
+use anyhow::Result;
+use std::fmt::Write;
+use std::path::Path;
+
+use super::distortion::PolynomialDistortion;
+use super::{CameraIntrinsic, PinholeCamera};
+use crate::image::ImageSize;
+
+/// The layout of the JSON camera files.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CameraFile {
+    image_width: usize,
+    image_height: usize,
+    intrinsic: CameraIntrinsic,
+    distortion: PolynomialDistortion,
+}
+
+/// Find the value of a top level key in an OpenCV YAML document.
+///
+/// # Returns
+///
+/// The text following the key, up to the end of the document.
+fn find_key<'a>(text: &'a str, key: &str) -> Result<&'a str> {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if let Some(rest) = line.strip_prefix(key) {
+            if let Some(rest) = rest.strip_prefix(':') {
+                let start = offset + line.len() - rest.len();
+                return Ok(&text[start..]);
+            }
+        }
+        offset += line.len();
+    }
+
+    Err(anyhow::anyhow!(
+        "The key {} is missing from the YAML file.",
+        key
+    ))
+}
+
+/// Parse the value on the first line of the given text.
+fn parse_value<T: std::str::FromStr>(text: &str, name: &str) -> Result<T> {
+    let value = text.lines().next().unwrap_or("").trim();
+    value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid value {} for {}.", value, name))
+}
+
+/// Parse an `opencv-matrix` node of an OpenCV YAML document.
+///
+/// # Returns
+///
+/// The number of rows, the number of columns and the row-major data of the matrix.
+fn parse_matrix(text: &str, key: &str) -> Result<(usize, usize, Vec<f64>)> {
+    let node = find_key(text, key)?;
+    let field = |name: &str| -> Result<&str> {
+        node.find(name)
+            .map(|pos| &node[pos + name.len()..])
+            .ok_or_else(|| anyhow::anyhow!("The matrix {} has no {} field.", key, name))
+    };
+
+    let rows = parse_value(field("rows:")?, "rows")?;
+    let cols = parse_value(field("cols:")?, "cols")?;
+
+    // the data may span several lines
+    let data = field("data:")?;
+    let (start, end) = match (data.find('['), data.find(']')) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return Err(anyhow::anyhow!("Invalid data of the matrix {}.", key)),
+    };
+    let values = data[start + 1..end]
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse::<f64>()
+                .map_err(|_| anyhow::anyhow!("Invalid value {} in the matrix {}.", v, key))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if values.len() != rows * cols {
+        return Err(anyhow::anyhow!(
+            "The matrix {} has {} values but is {}x{}.",
+            key,
+            values.len(),
+            rows,
+            cols
+        ));
+    }
+
+    Ok((rows, cols, values))
+}
+
+/// Parse a camera from an OpenCV YAML document.
+fn parse_camera_yaml(text: &str) -> Result<PinholeCamera> {
+    let size = ImageSize {
+        width: parse_value(find_key(text, "image_width")?, "image_width")?,
+        height: parse_value(find_key(text, "image_height")?, "image_height")?,
+    };
+
+    let (rows, cols, k) = parse_matrix(text, "camera_matrix")?;
+    if (rows, cols) != (3, 3) {
+        return Err(anyhow::anyhow!(
+            "The camera matrix must be 3x3, got {}x{}.",
+            rows,
+            cols
+        ));
+    }
+
+    // the coefficients are ordered as k1, k2, p1, p2[, k3[, k4, k5, k6]]
+    let (_, _, d) = parse_matrix(text, "distortion_coefficients")?;
+    if ![4, 5, 8].contains(&d.len()) {
+        return Err(anyhow::anyhow!(
+            "Expected 4, 5 or 8 distortion coefficients, got {}.",
+            d.len()
+        ));
+    }
+    let coeff = |i: usize| d.get(i).copied().unwrap_or(0.0);
+
+    Ok(PinholeCamera {
+        size,
+        intrinsic: CameraIntrinsic {
+            fx: k[0],
+            fy: k[4],
+            cx: k[2],
+            cy: k[5],
+        },
+        distortion: PolynomialDistortion {
+            k1: coeff(0),
+            k2: coeff(1),
+            p1: coeff(2),
+            p2: coeff(3),
+            k3: coeff(4),
+            k4: coeff(5),
+            k5: coeff(6),
+            k6: coeff(7),
+        },
+    })
+}
+
+/// Format a camera as an OpenCV YAML document.
+fn format_camera_yaml(camera: &PinholeCamera) -> Result<String> {
+    let (k, d) = (&camera.intrinsic, &camera.distortion);
+    let matrix = |rows: usize, cols: usize, data: &[f64]| {
+        let data = data.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>();
+        format!(
+            "!!opencv-matrix\n   rows: {}\n   cols: {}\n   dt: d\n   data: [ {} ]",
+            rows,
+            cols,
+            data.join(", ")
+        )
+    };
+
+    let mut text = String::from("%YAML:1.0\n---\n");
+    writeln!(text, "image_width: {}", camera.size.width)?;
+    writeln!(text, "image_height: {}", camera.size.height)?;
+    writeln!(
+        text,
+        "camera_matrix: {}",
+        matrix(3, 3, &[k.fx, 0.0, k.cx, 0.0, k.fy, k.cy, 0.0, 0.0, 1.0])
+    )?;
+    writeln!(
+        text,
+        "distortion_coefficients: {}",
+        matrix(1, 8, &[d.k1, d.k2, d.p1, d.p2, d.k3, d.k4, d.k5, d.k6])
+    )?;
+
+    Ok(text)
+}
+
+/// Reads the parameters of a camera from an OpenCV YAML file.
+///
+/// The file must contain the `image_width`, `image_height`, `camera_matrix` and
+/// `distortion_coefficients` keys, as written by the OpenCV calibration tools.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the YAML file.
+///
+/// # Returns
+///
+/// The camera with its intrinsic and distortion parameters.
+///
+/// # Errors
+///
+/// Returns an error if a key is missing or the matrices have an unexpected size.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::calibration::io::read_camera_yaml;
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("camera.yaml");
+/// std::fs::write(
+///     &file_path,
+///     "%YAML:1.0\n---\nimage_width: 640\nimage_height: 480\n\
+///      camera_matrix: !!opencv-matrix\n   rows: 3\n   cols: 3\n   dt: d\n\
+///      \u{20}  data: [ 500., 0., 320., 0., 510., 240., 0., 0., 1. ]\n\
+///      distortion_coefficients: !!opencv-matrix\n   rows: 1\n   cols: 5\n   dt: d\n\
+///      \u{20}  data: [ -0.1, 0.01, 0., 0., 0.001 ]\n",
+/// )
+/// .unwrap();
+///
+/// let camera = read_camera_yaml(&file_path).unwrap();
+/// assert_eq!(camera.size.width, 640);
+/// assert_eq!(camera.intrinsic.fy, 510.0);
+/// assert_eq!(camera.distortion.k3, 0.001);
+/// ```
+pub fn read_camera_yaml(file_path: &Path) -> Result<PinholeCamera> {
+    parse_camera_yaml(&std::fs::read_to_string(file_path)?)
+}
+
+/// Writes the parameters of a camera to an OpenCV YAML file.
+///
+/// The file can be read back with `cv::FileStorage`, with the 8 distortion coefficients
+/// of the rational model.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the YAML file.
+/// * `camera` - The camera to write.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_camera_yaml(file_path: &Path, camera: &PinholeCamera) -> Result<()> {
+    std::fs::write(file_path, format_camera_yaml(camera)?)?;
+    Ok(())
+}
+
+/// Reads the parameters of a camera from a JSON file.
+///
+/// The file contains the `image_width` and `image_height` of the camera, an
+/// `intrinsic` object with `fx`, `fy`, `cx` and `cy`, and a `distortion` object with
+/// the coefficients `k1` to `k6`, `p1` and `p2`.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JSON file.
+///
+/// # Returns
+///
+/// The camera with its intrinsic and distortion parameters.
+///
+/// # Errors
+///
+/// Returns an error if the file does not follow the schema.
+pub fn read_camera_json(file_path: &Path) -> Result<PinholeCamera> {
+    let file: CameraFile = serde_json::from_str(&std::fs::read_to_string(file_path)?)?;
+    Ok(PinholeCamera {
+        size: ImageSize {
+            width: file.image_width,
+            height: file.image_height,
+        },
+        intrinsic: file.intrinsic,
+        distortion: file.distortion,
+    })
+}
+
+/// Writes the parameters of a camera to a JSON file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JSON file.
+/// * `camera` - The camera to write.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_camera_json(file_path: &Path, camera: &PinholeCamera) -> Result<()> {
+    let file = CameraFile {
+        image_width: camera.size.width,
+        image_height: camera.size.height,
+        intrinsic: camera.intrinsic.clone(),
+        distortion: camera.distortion.clone(),
+    };
+    std::fs::write(file_path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calibration::distortion::PolynomialDistortion;
+    use crate::calibration::{CameraIntrinsic, PinholeCamera};
+    use crate::image::ImageSize;
+    use anyhow::Result;
+
+    fn camera() -> PinholeCamera {
+        PinholeCamera {
+            size: ImageSize {
+                width: 1280,
+                height: 720,
+            },
+            intrinsic: CameraIntrinsic {
+                fx: 911.4215,
+                fy: 910.9871,
+                cx: 642.1234,
+                cy: 361.9876,
+            },
+            distortion: PolynomialDistortion {
+                k1: -0.3185,
+                k2: 0.1321,
+                k3: -0.0241,
+                k4: 0.0,
+                k5: 0.0,
+                k6: 0.0,
+                p1: 0.00012,
+                p2: -0.00034,
+            },
+        }
+    }
+
+    #[test]
+    fn camera_yaml() -> Result<()> {
+        let camera = camera();
+        let text = super::format_camera_yaml(&camera)?;
+        assert!(text.starts_with("%YAML:1.0\n---\n"));
+        assert_eq!(super::parse_camera_yaml(&text)?, camera);
+
+        // a file written by OpenCV with the matrix data over several lines
+        let text = "%YAML:1.0\n---\nimage_width: 1280\nimage_height: 720\n\
+            camera_matrix: !!opencv-matrix\n   rows: 3\n   cols: 3\n   dt: d\n\
+            \u{20}  data: [ 9.114215e+02, 0., 6.421234e+02, 0.,\n\
+            \u{20}      9.109871e+02, 3.619876e+02, 0., 0., 1. ]\n\
+            distortion_coefficients: !!opencv-matrix\n   rows: 1\n   cols: 5\n   dt: d\n\
+            \u{20}  data: [ -3.185e-01, 1.321e-01,\n\
+            \u{20}      1.2e-04, -3.4e-04, -2.41e-02 ]\n";
+        assert_eq!(super::parse_camera_yaml(text)?, camera);
+
+        let truncated = text.replace("cols: 5", "cols: 6");
+        assert!(super::parse_camera_yaml(&truncated).is_err());
+        assert!(super::parse_camera_yaml("%YAML:1.0\n---\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn camera_json() -> Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let file_path = tmp_dir.path().join("camera.json");
+
+        let camera = camera();
+        super::write_camera_json(&file_path, &camera)?;
+        assert_eq!(super::read_camera_json(&file_path)?, camera);
+
+        let file_path = tmp_dir.path().join("camera.yaml");
+        super::write_camera_yaml(&file_path, &camera)?;
+        assert_eq!(super::read_camera_yaml(&file_path)?, camera);
+        Ok(())
+    }
+}
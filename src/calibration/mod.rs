@@ -1,4 +1,8 @@
 pub mod distortion;
+pub mod io;
+
+use crate::image::ImageSize;
+use distortion::PolynomialDistortion;
 
 /// Represents the instrinsic parameters of a pinhole camera
 ///
@@ -8,6 +12,7 @@ pub mod distortion;
 /// * `fy` - The focal length in the y direction
 /// * `cx` - The x coordinate of the principal point
 /// * `cy` - The y coordinate of the principal point
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct CameraIntrinsic {
     pub fx: f64,
     pub fy: f64,
@@ -21,7 +26,22 @@ pub struct CameraIntrinsic {
 ///
 /// * `rotation` - The rotation matrix of the camera 3x3
 /// * `translation` - The translation vector of the camera 3x1
+#[derive(Clone, Debug, PartialEq)]
 pub struct CameraExtrinsic {
     pub rotation: [[f64; 3]; 3],
     pub translation: [f64; 3],
 }
+
+/// Represents a calibrated pinhole camera
+///
+/// # Fields
+///
+/// * `size` - The size of the images the camera was calibrated with
+/// * `intrinsic` - The intrinsic parameters of the camera
+/// * `distortion` - The distortion parameters of the camera
+#[derive(Clone, Debug, PartialEq)]
+pub struct PinholeCamera {
+    pub size: ImageSize,
+    pub intrinsic: CameraIntrinsic,
+    pub distortion: PolynomialDistortion,
+}
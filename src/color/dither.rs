@@ -0,0 +1,197 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Algorithm used to distribute the quantization error of a dithered image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DitherMethod {
+    /// Error diffusion to the neighbouring pixels with the Floyd-Steinberg weights.
+    FloydSteinberg,
+    /// Ordered dithering with a 4x4 Bayer threshold matrix.
+    Bayer,
+}
+
+/// The 4x4 Bayer threshold matrix.
+const BAYER_4X4: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+/// Get the index of the palette color closest to a color.
+fn nearest_color<const CHANNELS: usize>(palette: &[[u8; CHANNELS]], color: &[f32]) -> usize {
+    let dist = |p: &[u8; CHANNELS]| {
+        p.iter()
+            .zip(color.iter())
+            .map(|(&a, &b)| (a as f32 - b).powi(2))
+            .sum::<f32>()
+    };
+    palette
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| dist(a).total_cmp(&dist(b)))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Map an image to a fixed palette with dithering.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `palette` - The colors of the palette, with at most 256 colors.
+/// * `method` - The dithering algorithm.
+///
+/// # Returns
+///
+/// The indexed image with shape (H, W, 1), where each pixel holds the index of its
+/// color in the palette.
+///
+/// # Errors
+///
+/// Returns an error if the palette is empty or has more than 256 colors.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::color::{dither, DitherMethod};
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 4,
+///         height: 4,
+///     },
+///     128,
+/// )
+/// .unwrap();
+///
+/// // a mid gray becomes a checkerboard of black and white pixels
+/// let indices = dither(&image, &[[0], [255]], DitherMethod::Bayer).unwrap();
+/// let white = indices.data.iter().filter(|&&i| i == 1).count();
+/// assert_eq!(white, 8);
+/// ```
+pub fn dither<const CHANNELS: usize>(
+    src: &Image<u8, CHANNELS>,
+    palette: &[[u8; CHANNELS]],
+    method: DitherMethod,
+) -> Result<Image<u8, 1>> {
+    if palette.is_empty() || palette.len() > 256 {
+        return Err(anyhow::anyhow!(
+            "The palette must have between 1 and 256 colors, got {}.",
+            palette.len()
+        ));
+    }
+
+    let (width, height) = (src.width(), src.height());
+    let mut values = src.data.iter().map(|&v| v as f32).collect::<Vec<_>>();
+    let mut indices = vec![0u8; width * height];
+
+    match method {
+        DitherMethod::FloydSteinberg => {
+            // (dx, dy, weight) of the neighbours receiving the error
+            let weights = [
+                (1, 0, 7.0 / 16.0),
+                (-1, 1, 3.0 / 16.0),
+                (0, 1, 5.0 / 16.0),
+                (1, 1, 1.0 / 16.0),
+            ];
+
+            for y in 0..height {
+                for x in 0..width {
+                    let idx = y * width + x;
+                    let color = &values[idx * CHANNELS..(idx + 1) * CHANNELS];
+                    let k = nearest_color(palette, color);
+                    indices[idx] = k as u8;
+
+                    let mut error = [0f32; CHANNELS];
+                    for (c, e) in error.iter_mut().enumerate() {
+                        *e = values[idx * CHANNELS + c] - palette[k][c] as f32;
+                    }
+
+                    for &(dx, dy, w) in weights.iter() {
+                        let nx = x as isize + dx;
+                        let ny = y + dy;
+                        if nx < 0 || nx >= width as isize || ny >= height {
+                            continue;
+                        }
+                        let n = ny * width + nx as usize;
+                        for (c, e) in error.iter().enumerate() {
+                            values[n * CHANNELS + c] += e * w;
+                        }
+                    }
+                }
+            }
+        }
+        DitherMethod::Bayer => {
+            // amplitude of the threshold offsets: the spacing between palette levels
+            let spread = 255.0 / (palette.len() as f32 - 1.0).max(1.0);
+
+            for y in 0..height {
+                for x in 0..width {
+                    let idx = y * width + x;
+                    let offset = (BAYER_4X4[y % 4][x % 4] + 0.5) / 16.0 - 0.5;
+                    let color = values[idx * CHANNELS..(idx + 1) * CHANNELS]
+                        .iter_mut()
+                        .map(|v| {
+                            *v += offset * spread;
+                            *v
+                        })
+                        .collect::<Vec<_>>();
+                    indices[idx] = nearest_color(palette, &color) as u8;
+                }
+            }
+        }
+    }
+
+    Image::new(src.size(), indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn dither_preserves_mean() -> Result<()> {
+        let size = ImageSize {
+            width: 8,
+            height: 8,
+        };
+        let image = Image::<u8, 1>::from_size_val(size, 64)?;
+        let palette = [[0], [255]];
+
+        for method in [
+            super::DitherMethod::FloydSteinberg,
+            super::DitherMethod::Bayer,
+        ] {
+            let indices = super::dither(&image, &palette, method)?;
+            assert_eq!(indices.size(), size);
+
+            // a quarter of the pixels must be white to keep the mean intensity
+            let white = indices.data.iter().filter(|&&i| i == 1).count();
+            assert!((14..=18).contains(&white), "{method:?}: {white}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn dither_exact_colors() -> Result<()> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 2,
+                height: 1,
+            },
+            vec![255, 0, 0, 0, 0, 255],
+        )?;
+        let palette = [[0, 0, 255], [255, 0, 0]];
+
+        let indices = super::dither(&image, &palette, super::DitherMethod::FloydSteinberg)?;
+        assert_eq!(indices.data.as_slice().unwrap(), &[1, 0]);
+
+        assert!(super::dither(&image, &[], super::DitherMethod::Bayer).is_err());
+
+        Ok(())
+    }
+}
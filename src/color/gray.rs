@@ -79,4 +79,18 @@ mod tests {
         assert_eq!(gray.size().height, 195);
         Ok(())
     }
+
+    #[test]
+    fn gray_from_rgb_planar() -> Result<()> {
+        use crate::image::{Image, ImageSize};
+        let size = ImageSize {
+            width: 2,
+            height: 1,
+        };
+        let planar = Image::<f32, 3>::from_planar(size, vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0])?;
+        let interleaved = Image::<f32, 3>::new(size, vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0])?;
+        let gray = super::gray_from_rgb(&planar)?;
+        assert_eq!(gray.data, super::gray_from_rgb(&interleaved)?.data);
+        Ok(())
+    }
 }
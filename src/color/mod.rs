@@ -1,5 +1,9 @@
+mod dither;
 mod gray;
 mod hsv;
+mod quantize;
 
+pub use dither::{dither, DitherMethod};
 pub use gray::gray_from_rgb;
 pub use hsv::hsv_from_rgb;
+pub use quantize::{quantize, QuantizeMethod};
@@ -0,0 +1,252 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Algorithm used to build the palette of a quantized image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuantizeMethod {
+    /// Recursively split the color box with the widest range at its median.
+    MedianCut,
+    /// Refine the median cut palette with k-means clustering.
+    KMeans,
+}
+
+/// Maximum number of k-means refinement iterations.
+const KMEANS_MAX_ITERATIONS: usize = 10;
+
+/// Build a palette by recursively splitting the colors at the median of the widest channel.
+fn median_cut(colors: &[[u8; 3]], n_colors: usize) -> Vec<[f32; 3]> {
+    let mut boxes: Vec<Vec<[u8; 3]>> = vec![colors.to_vec()];
+
+    // range of the widest channel of a box
+    let widest_channel = |colors: &[[u8; 3]]| -> (usize, u8) {
+        (0..3)
+            .map(|c| {
+                let min = colors.iter().map(|p| p[c]).min().unwrap_or(0);
+                let max = colors.iter().map(|p| p[c]).max().unwrap_or(0);
+                (c, max - min)
+            })
+            .max_by_key(|&(_, range)| range)
+            .unwrap_or((0, 0))
+    };
+
+    while boxes.len() < n_colors {
+        // split the box with the widest range
+        let (idx, channel, range) = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let (c, r) = widest_channel(b);
+                (i, c, r)
+            })
+            .max_by_key(|&(_, _, range)| range)
+            .unwrap_or((0, 0, 0));
+
+        if range == 0 {
+            break;
+        }
+
+        let mut colors = boxes.swap_remove(idx);
+        colors.sort_unstable_by_key(|p| p[channel]);
+        let upper = colors.split_off(colors.len() / 2);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    boxes
+        .iter()
+        .map(|b| {
+            let mut sum = [0f32; 3];
+            for p in b.iter() {
+                for (s, &v) in sum.iter_mut().zip(p.iter()) {
+                    *s += v as f32;
+                }
+            }
+            sum.map(|s| s / b.len() as f32)
+        })
+        .collect()
+}
+
+/// Get the index of the palette color closest to a color.
+fn nearest_color(palette: &[[f32; 3]], color: &[u8; 3]) -> usize {
+    let dist = |p: &[f32; 3]| {
+        (0..3)
+            .map(|c| (p[c] - color[c] as f32).powi(2))
+            .sum::<f32>()
+    };
+    palette
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| dist(a).total_cmp(&dist(b)))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Refine a palette with Lloyd's k-means iterations.
+fn kmeans(colors: &[[u8; 3]], mut palette: Vec<[f32; 3]>) -> Vec<[f32; 3]> {
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        let mut sums = vec![[0f32; 3]; palette.len()];
+        let mut counts = vec![0usize; palette.len()];
+
+        for color in colors.iter() {
+            let k = nearest_color(&palette, color);
+            for (s, &v) in sums[k].iter_mut().zip(color.iter()) {
+                *s += v as f32;
+            }
+            counts[k] += 1;
+        }
+
+        let mut changed = false;
+        for (k, center) in palette.iter_mut().enumerate() {
+            if counts[k] == 0 {
+                continue;
+            }
+            let new_center = sums[k].map(|s| s / counts[k] as f32);
+            if new_center != *center {
+                *center = new_center;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    palette
+}
+
+/// Reduce the number of colors of an RGB image.
+///
+/// # Arguments
+///
+/// * `src` - The input RGB image with shape (H, W, 3).
+/// * `n_colors` - The maximum number of colors of the palette in the range [1, 256].
+/// * `method` - The algorithm used to build the palette.
+///
+/// # Returns
+///
+/// The palette and the indexed image with shape (H, W, 1), where each pixel holds
+/// the index of its color in the palette. The palette may have less colors than
+/// requested if the image has less distinct colors.
+///
+/// # Errors
+///
+/// Returns an error if the number of colors is not in the range [1, 256].
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::color::{quantize, QuantizeMethod};
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 3>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 2,
+///     },
+///     vec![255, 0, 0, 250, 0, 0, 0, 0, 255, 0, 0, 250],
+/// )
+/// .unwrap();
+///
+/// let (palette, indices) = quantize(&image, 2, QuantizeMethod::MedianCut).unwrap();
+/// assert_eq!(palette.len(), 2);
+/// assert_eq!(indices.get_pixel(0, 0, 0).unwrap(), indices.get_pixel(1, 0, 0).unwrap());
+/// assert_ne!(indices.get_pixel(0, 0, 0).unwrap(), indices.get_pixel(0, 1, 0).unwrap());
+/// ```
+pub fn quantize(
+    src: &Image<u8, 3>,
+    n_colors: usize,
+    method: QuantizeMethod,
+) -> Result<(Vec<[u8; 3]>, Image<u8, 1>)> {
+    if !(1..=256).contains(&n_colors) {
+        return Err(anyhow::anyhow!(
+            "The number of colors must be in the range [1, 256], got {}.",
+            n_colors
+        ));
+    }
+
+    let colors = src
+        .data
+        .rows()
+        .into_iter()
+        .map(|p| [p[0], p[1], p[2]])
+        .collect::<Vec<_>>();
+
+    let palette = match method {
+        QuantizeMethod::MedianCut => median_cut(&colors, n_colors),
+        QuantizeMethod::KMeans => kmeans(&colors, median_cut(&colors, n_colors)),
+    };
+
+    let indices = colors
+        .iter()
+        .map(|color| nearest_color(&palette, color) as u8)
+        .collect();
+
+    let palette = palette
+        .iter()
+        .map(|p| p.map(|v| v.round().clamp(0.0, 255.0) as u8))
+        .collect();
+
+    Ok((palette, Image::new(src.size(), indices)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn quantize_two_clusters() -> Result<()> {
+        let size = ImageSize {
+            width: 4,
+            height: 2,
+        };
+        // dark pixels on the top row and bright pixels on the bottom row
+        #[rustfmt::skip]
+        let data = vec![
+            10, 10, 10, 12, 12, 12, 8, 8, 8, 10, 12, 8,
+            240, 240, 240, 250, 250, 250, 245, 245, 245, 235, 240, 250,
+        ];
+        let image = Image::<u8, 3>::new(size, data)?;
+
+        for method in [
+            super::QuantizeMethod::MedianCut,
+            super::QuantizeMethod::KMeans,
+        ] {
+            let (palette, indices) = super::quantize(&image, 2, method)?;
+            assert_eq!(palette.len(), 2);
+
+            let dark = indices.get_pixel(0, 0, 0)? as usize;
+            let bright = indices.get_pixel(0, 1, 0)? as usize;
+            assert_ne!(dark, bright);
+            assert!(palette[dark].iter().all(|&v| v < 20));
+            assert!(palette[bright].iter().all(|&v| v > 230));
+
+            for x in 0..size.width {
+                assert_eq!(indices.get_pixel(x, 0, 0)? as usize, dark);
+                assert_eq!(indices.get_pixel(x, 1, 0)? as usize, bright);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn quantize_invalid_colors() -> Result<()> {
+        let image = Image::<u8, 3>::from_size_val(
+            ImageSize {
+                width: 2,
+                height: 2,
+            },
+            0,
+        )?;
+        assert!(super::quantize(&image, 0, super::QuantizeMethod::MedianCut).is_err());
+        assert!(super::quantize(&image, 257, super::QuantizeMethod::KMeans).is_err());
+
+        // a constant image has a single color
+        let (palette, _) = super::quantize(&image, 4, super::QuantizeMethod::MedianCut)?;
+        assert_eq!(palette.len(), 1);
+
+        Ok(())
+    }
+}
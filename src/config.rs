@@ -0,0 +1,99 @@
+use anyhow::Result;
+
+/// Set the number of threads used by the parallel operations of the crate.
+///
+/// The operations run on the global rayon thread pool, which can only be configured
+/// once and before any parallel operation is executed. Use [`with_num_threads`] to run
+/// some operations with a different number of threads.
+///
+/// # Arguments
+///
+/// * `num_threads` - The number of threads, or 0 to use the available parallelism.
+///
+/// # Errors
+///
+/// Returns an error if the global thread pool was already initialized.
+///
+/// # Example
+///
+/// ```
+/// kornia_rs::config::set_num_threads(2).unwrap();
+/// assert_eq!(kornia_rs::config::num_threads(), 2);
+/// ```
+pub fn set_num_threads(num_threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build_global()
+        .map_err(|e| anyhow::anyhow!("Failed to configure the global thread pool: {}", e))
+}
+
+/// Get the number of threads used by the parallel operations in the current context.
+pub fn num_threads() -> usize {
+    rayon::current_num_threads()
+}
+
+/// Run a function with a dedicated pool of threads for the parallel operations.
+///
+/// The parallel operations called inside the function run on the new pool instead of
+/// the global one, which is useful when the crate is embedded in an application
+/// managing its own threads.
+///
+/// # Arguments
+///
+/// * `num_threads` - The number of threads, or 0 to use the available parallelism.
+/// * `f` - The function to run.
+///
+/// # Returns
+///
+/// The result of the function.
+///
+/// # Errors
+///
+/// Returns an error if the thread pool cannot be created.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::config::{num_threads, with_num_threads};
+///
+/// let n = with_num_threads(3, num_threads).unwrap();
+/// assert_eq!(n, 3);
+/// ```
+pub fn with_num_threads<R, F>(num_threads: usize, f: F) -> Result<R>
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create the thread pool: {}", e))?;
+    Ok(pool.install(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn with_num_threads() -> Result<()> {
+        let image = Image::<u8, 3>::from_size_val(
+            ImageSize {
+                width: 8,
+                height: 8,
+            },
+            10,
+        )?;
+
+        // the parallel operations run on the scoped pool
+        let (n, converted) = super::with_num_threads(1, || {
+            let n = super::num_threads();
+            (n, crate::convert::u8_to_f32_scaled(&image, 0.5))
+        })?;
+        assert_eq!(n, 1);
+        assert_eq!(converted?.get_pixel(3, 3, 0)?, 5.0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Apply a conversion to each value of an image, in parallel over the rows.
+///
+/// The rows of contiguous images are processed as plain slices so that the compiler
+/// vectorizes the conversion.
+fn convert_values<S, D, const CHANNELS: usize>(
+    src: &Image<S, CHANNELS>,
+    dst: &mut Image<D, CHANNELS>,
+    f: impl Fn(S) -> D + Sync,
+) where
+    S: Copy + Send + Sync,
+    D: Copy + Send + Sync,
+{
+    ndarray::Zip::from(dst.data.outer_iter_mut())
+        .and(src.data.outer_iter())
+        .par_for_each(
+            |mut dst_row, src_row| match (dst_row.as_slice_mut(), src_row.as_slice()) {
+                (Some(dst_row), Some(src_row)) => {
+                    for (d, &s) in dst_row.iter_mut().zip(src_row) {
+                        *d = f(s);
+                    }
+                }
+                _ => dst_row.zip_mut_with(&src_row, |d, &s| *d = f(s)),
+            },
+        );
+}
+
+/// Convert an image from u8 to f32, multiplying the values by a scale.
+///
+/// This is a fast path for the common `cast_and_scale::<f32>` used to preprocess images,
+/// without the per-value generic cast.
+///
+/// # Arguments
+///
+/// * `image` - The input image with values in the range [0, 255].
+/// * `scale` - The factor applied to each value, e.g. `1.0 / 255.0`.
+///
+/// # Returns
+///
+/// The converted image.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::convert::u8_to_f32_scaled;
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![0, 51, 255],
+/// )
+/// .unwrap();
+///
+/// let image_f32 = u8_to_f32_scaled(&image, 0.5).unwrap();
+/// assert_eq!(image_f32.data.as_slice().unwrap(), &[0.0, 25.5, 127.5]);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(size = %image.size())))]
+pub fn u8_to_f32_scaled<const CHANNELS: usize>(
+    image: &Image<u8, CHANNELS>,
+    scale: f32,
+) -> Result<Image<f32, CHANNELS>> {
+    let mut output = Image::<f32, CHANNELS>::from_size_val(image.size(), 0.0)?;
+    convert_values(image, &mut output, |x| x as f32 * scale);
+    Ok(output)
+}
+
+/// Convert an image from f32 to u8, multiplying the values by a scale.
+///
+/// The scaled values are rounded to the nearest integer and saturated to [0, 255].
+///
+/// # Arguments
+///
+/// * `image` - The input image.
+/// * `scale` - The factor applied to each value, e.g. `255.0` for values in [0, 1].
+///
+/// # Returns
+///
+/// The converted image.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::convert::f32_to_u8_scaled;
+///
+/// let image = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 4,
+///         height: 1,
+///     },
+///     vec![-0.5, 0.2, 0.5, 1.5],
+/// )
+/// .unwrap();
+///
+/// let image_u8 = f32_to_u8_scaled(&image, 255.0).unwrap();
+/// assert_eq!(image_u8.data.as_slice().unwrap(), &[0, 51, 128, 255]);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(size = %image.size())))]
+pub fn f32_to_u8_scaled<const CHANNELS: usize>(
+    image: &Image<f32, CHANNELS>,
+    scale: f32,
+) -> Result<Image<u8, CHANNELS>> {
+    let mut output = Image::<u8, CHANNELS>::from_size_val(image.size(), 0)?;
+    // the float to integer cast saturates, so only non-negative values need rounding
+    convert_values(image, &mut output, |x| (x * scale + 0.5) as u8);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn u8_to_f32_scaled() -> Result<()> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 5,
+                height: 4,
+            },
+            (0..60).map(|i| (i * 4) as u8).collect(),
+        )?;
+
+        let expected = image.clone().cast_and_scale::<f32>(1.0 / 255.0)?;
+        let converted = super::u8_to_f32_scaled(&image, 1.0 / 255.0)?;
+        assert_eq!(converted.data, expected.data);
+
+        // a strided view of the image
+        let flipped = Image::<u8, 3> {
+            data: image.data.slice(ndarray::s![.., ..;-1, ..]).to_owned(),
+        };
+        let strided = Image::<u8, 3> {
+            data: image.data.clone().slice_move(ndarray::s![.., ..;-1, ..]),
+        };
+        assert!(!strided.is_contiguous());
+        assert_eq!(
+            super::u8_to_f32_scaled(&strided, 2.0)?.data,
+            super::u8_to_f32_scaled(&flipped, 2.0)?.data
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn f32_to_u8_scaled() -> Result<()> {
+        let image = Image::<u8, 1>::new(
+            ImageSize {
+                width: 256,
+                height: 1,
+            },
+            (0..=255).collect(),
+        )?;
+
+        // the round trip is exact
+        let image_f32 = super::u8_to_f32_scaled(&image, 1.0 / 255.0)?;
+        let image_back = super::f32_to_u8_scaled(&image_f32, 255.0)?;
+        assert_eq!(image_back.data, image.data);
+
+        let image = Image::<f32, 1>::new(
+            ImageSize {
+                width: 4,
+                height: 1,
+            },
+            vec![-3.0, f32::NAN, 2.49, 300.0],
+        )?;
+        let image_u8 = super::f32_to_u8_scaled(&image, 1.0)?;
+        assert_eq!(image_u8.data.as_slice().unwrap(), &[0, 0, 2, 255]);
+
+        Ok(())
+    }
+}
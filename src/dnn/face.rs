@@ -0,0 +1,363 @@
+use crate::geometry::{nms_rotated, umeyama, Point2, RotatedBox};
+use crate::image::{Image, ImageSize};
+use crate::interpolation::{BorderMode, InterpolationMode};
+use crate::resize::{resize_keep_aspect, Fit};
+use crate::tensor::{CpuAllocator, Tensor};
+use crate::warp::warp_affine;
+use anyhow::Result;
+use std::path::Path;
+
+/// The five landmarks of the ArcFace template in a 112x112 face crop: the eyes, the nose
+/// and the corners of the mouth.
+const ARCFACE_TEMPLATE: [(f32, f32); 5] = [
+    (38.2946, 51.6963),
+    (73.5318, 51.5014),
+    (56.0252, 71.7366),
+    (41.5493, 92.3655),
+    (70.7299, 92.2041),
+];
+
+/// A face detected in an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FaceDetection {
+    /// The bounding box of the face as (x_min, y_min, x_max, y_max).
+    pub bbox: [f32; 4],
+    /// The confidence of the detection.
+    pub score: f32,
+    /// The left eye, the right eye, the nose, and the left and right corners of the mouth.
+    pub landmarks: [Point2; 5],
+}
+
+impl FaceDetection {
+    /// Map the coordinates of the detection with a function.
+    fn map(&self, f: impl Fn(f32, f32) -> (f32, f32)) -> Self {
+        let (x_min, y_min) = f(self.bbox[0], self.bbox[1]);
+        let (x_max, y_max) = f(self.bbox[2], self.bbox[3]);
+        Self {
+            bbox: [x_min, y_min, x_max, y_max],
+            score: self.score,
+            landmarks: self.landmarks.map(|p| {
+                let (x, y) = f(p.x, p.y);
+                Point2::new(x, y)
+            }),
+        }
+    }
+}
+
+/// A face detection model, to plug a network into the [`FaceDetector`] pipeline.
+pub trait FaceModel {
+    /// Get the size of the input images of the model.
+    fn input_size(&self) -> ImageSize;
+
+    /// Run the model on an RGB image of its input size, with values in the range [0, 255].
+    ///
+    /// Returns the faces in the coordinates of the input image.
+    fn infer(&mut self, input: &Image<f32, 3>) -> Result<Vec<FaceDetection>>;
+}
+
+/// A face detection model in the ONNX format.
+///
+/// The model takes a normalized NCHW image and returns the faces as a tensor with shape
+/// (N, 15) or (1, N, 15), each row holding the bounding box, the score and the (x, y)
+/// coordinates of the five landmarks in input pixels.
+pub struct OnnxFaceModel {
+    session: ort::Session,
+    input_size: ImageSize,
+    mean: [f32; 3],
+    std: [f32; 3],
+}
+
+impl OnnxFaceModel {
+    /// Load a face detection model from an ONNX file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the ONNX model.
+    /// * `input_size` - The size of the input images of the model.
+    pub fn from_file(path: impl AsRef<Path>, input_size: ImageSize) -> Result<Self> {
+        let session = ort::Session::builder()?.commit_from_file(path)?;
+        Ok(Self {
+            session,
+            input_size,
+            mean: [127.5; 3],
+            std: [128.0; 3],
+        })
+    }
+
+    /// Set the mean and the standard deviation to normalize the input channels.
+    pub fn with_normalization(mut self, mean: [f32; 3], std: [f32; 3]) -> Self {
+        self.mean = mean;
+        self.std = std;
+        self
+    }
+}
+
+impl FaceModel for OnnxFaceModel {
+    fn input_size(&self) -> ImageSize {
+        self.input_size
+    }
+
+    fn infer(&mut self, input: &Image<f32, 3>) -> Result<Vec<FaceDetection>> {
+        let (width, height) = (input.width(), input.height());
+        let mut tensor = ndarray::Array4::<f32>::zeros((1, 3, height, width));
+        for ((y, x, c), &value) in input.data.indexed_iter() {
+            tensor[[0, c, y, x]] = (value - self.mean[c]) / self.std[c];
+        }
+
+        let outputs = self.session.run(ort::inputs![tensor.view()]?)?;
+        let output = outputs[0].try_extract_tensor::<f32>()?;
+        let values = output.iter().copied().collect::<Vec<_>>();
+        if values.len() % 15 != 0 {
+            return Err(anyhow::anyhow!(
+                "The face model output with shape {:?} is not a list of 15 values per face",
+                output.shape()
+            ));
+        }
+
+        Ok(values
+            .chunks_exact(15)
+            .map(|row| FaceDetection {
+                bbox: [row[0], row[1], row[2], row[3]],
+                score: row[4],
+                landmarks: std::array::from_fn(|i| Point2::new(row[5 + 2 * i], row[6 + 2 * i])),
+            })
+            .collect())
+    }
+}
+
+/// A face detection pipeline around a model.
+///
+/// The image is letterboxed to the input size of the model, and the detections are
+/// filtered by score and non-maximum suppression before being mapped back to the image.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::dnn::face::{align_face, FaceDetector, OnnxFaceModel};
+/// use kornia_rs::image::ImageSize;
+///
+/// let size = ImageSize {
+///     width: 640,
+///     height: 640,
+/// };
+/// let model = OnnxFaceModel::from_file("face.onnx", size).unwrap();
+/// let mut detector = FaceDetector::new(model).with_score_threshold(0.6);
+///
+/// let path = std::path::Path::new("people.jpg");
+/// let image = kornia_rs::io::functional::read_image_any(path).unwrap();
+/// for face in detector.detect(&image).unwrap() {
+///     let crop = align_face(
+///         &image.clone().cast::<f32>().unwrap(),
+///         &face.landmarks,
+///         ImageSize {
+///             width: 112,
+///             height: 112,
+///         },
+///     )
+///     .unwrap();
+///     println!("face at {:?} with shape {:?}", face.bbox, crop.size());
+/// }
+/// ```
+pub struct FaceDetector<M: FaceModel> {
+    model: M,
+    score_threshold: f32,
+    iou_threshold: f32,
+}
+
+impl<M: FaceModel> FaceDetector<M> {
+    /// Create a new face detector.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The face detection model.
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            score_threshold: 0.5,
+            iou_threshold: 0.4,
+        }
+    }
+
+    /// Set the minimum score of the detections. Defaults to 0.5.
+    pub fn with_score_threshold(mut self, score_threshold: f32) -> Self {
+        self.score_threshold = score_threshold;
+        self
+    }
+
+    /// Set the maximum intersection over union of the kept detections. Defaults to 0.4.
+    pub fn with_iou_threshold(mut self, iou_threshold: f32) -> Self {
+        self.iou_threshold = iou_threshold;
+        self
+    }
+
+    /// Detect the faces of an image.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The input RGB image with shape (H, W, 3).
+    ///
+    /// # Returns
+    ///
+    /// The faces in the coordinates of the image, from the most confident.
+    pub fn detect(&mut self, image: &Image<u8, 3>) -> Result<Vec<FaceDetection>> {
+        let (letterbox, transform) = resize_keep_aspect(
+            image,
+            self.model.input_size(),
+            Fit::Pad(0),
+            InterpolationMode::Bilinear,
+        )?;
+
+        let faces = self
+            .model
+            .infer(&letterbox.cast::<f32>()?)?
+            .into_iter()
+            .filter(|face| face.score >= self.score_threshold)
+            .collect::<Vec<_>>();
+
+        let boxes = faces
+            .iter()
+            .map(|face| {
+                let [x_min, y_min, x_max, y_max] = face.bbox;
+                RotatedBox {
+                    cx: (x_min + x_max) / 2.0,
+                    cy: (y_min + y_max) / 2.0,
+                    width: x_max - x_min,
+                    height: y_max - y_min,
+                    angle: 0.0,
+                }
+            })
+            .collect::<Vec<_>>();
+        let scores = faces.iter().map(|face| face.score).collect::<Vec<_>>();
+        let keep = nms_rotated(&boxes, &scores, self.iou_threshold)?;
+
+        Ok(keep
+            .into_iter()
+            .map(|i| faces[i].map(|x, y| transform.inverse(x, y)))
+            .collect())
+    }
+}
+
+/// Crops and aligns a face on the ArcFace template from its five landmarks.
+///
+/// The similarity transform mapping the landmarks onto the template is estimated with
+/// the Umeyama method, so that the eyes, nose and mouth of all the crops are aligned,
+/// e.g. for face recognition.
+///
+/// # Arguments
+///
+/// * `image` - The input RGB image with shape (H, W, 3).
+/// * `landmarks` - The five landmarks of the face, as in [`FaceDetection`].
+/// * `size` - The size of the crop, the template being scaled from 112x112.
+///
+/// # Returns
+///
+/// The aligned face crop.
+///
+/// # Errors
+///
+/// Returns an error if the landmarks are degenerate.
+pub fn align_face(
+    image: &Image<f32, 3>,
+    landmarks: &[Point2; 5],
+    size: ImageSize,
+) -> Result<Image<f32, 3>> {
+    let (sx, sy) = (size.width as f32 / 112.0, size.height as f32 / 112.0);
+    let src = landmarks.iter().flat_map(|p| [p.x, p.y]).collect();
+    let dst = ARCFACE_TEMPLATE
+        .iter()
+        .flat_map(|&(x, y)| [x * sx, y * sy])
+        .collect();
+
+    let src = Tensor::<f32, 2>::from_shape_vec([5, 2], src, CpuAllocator)?;
+    let dst = Tensor::<f32, 2>::from_shape_vec([5, 2], dst, CpuAllocator)?;
+    let m = umeyama(&src, &dst, true)?;
+    let m = m.as_slice();
+
+    warp_affine(
+        image,
+        (m[0], m[1], m[2], m[3], m[4], m[5]),
+        size,
+        InterpolationMode::Bilinear,
+        BorderMode::Constant(0.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FaceDetection, FaceDetector, FaceModel};
+    use crate::geometry::Point2;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    /// A model returning fixed detections in its input coordinates.
+    struct FixedModel(Vec<FaceDetection>);
+
+    impl FaceModel for FixedModel {
+        fn input_size(&self) -> ImageSize {
+            ImageSize {
+                width: 100,
+                height: 100,
+            }
+        }
+
+        fn infer(&mut self, input: &Image<f32, 3>) -> Result<Vec<FaceDetection>> {
+            assert_eq!(input.size(), self.input_size());
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn face_detector() -> Result<()> {
+        let face = |x: f32, score: f32| FaceDetection {
+            bbox: [x, 30.0, x + 20.0, 50.0],
+            score,
+            landmarks: [Point2::new(x + 10.0, 40.0); 5],
+        };
+        let model = FixedModel(vec![face(10.0, 0.9), face(11.0, 0.8), face(60.0, 0.3)]);
+        let mut detector = FaceDetector::new(model).with_score_threshold(0.5);
+
+        // the image is letterboxed with a scale of 0.5 and an offset of 25 pixels in y
+        let image = Image::<u8, 3>::from_size_val(
+            ImageSize {
+                width: 200,
+                height: 100,
+            },
+            128,
+        )?;
+        let faces = detector.detect(&image)?;
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].bbox, [20.0, 10.0, 60.0, 50.0]);
+        assert_eq!(faces[0].landmarks[0], Point2::new(40.0, 30.0));
+        Ok(())
+    }
+
+    #[test]
+    fn align_face() -> Result<()> {
+        // the landmarks of a face scaled by 2 and shifted from the template
+        let landmarks = super::ARCFACE_TEMPLATE.map(|(x, y)| Point2::new(2.0 * x + 30.0, 2.0 * y));
+        let mut image = Image::<f32, 3>::from_size_val(
+            ImageSize {
+                width: 300,
+                height: 260,
+            },
+            0.0,
+        )?;
+        // mark a block around the tip of the nose
+        let (nx, ny) = (landmarks[2].x as usize, landmarks[2].y as usize);
+        for y in ny - 2..=ny + 2 {
+            for x in nx - 2..=nx + 2 {
+                image.data[[y, x, 1]] = 1.0;
+            }
+        }
+
+        let size = ImageSize {
+            width: 112,
+            height: 112,
+        };
+        let crop = super::align_face(&image, &landmarks, size)?;
+        assert_eq!(crop.size(), size);
+        // the tip of the nose lands on the template
+        assert!(crop.data[[72, 56, 1]] > 0.99);
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+#[cfg(feature = "onnx")]
+pub mod face;
+pub mod postprocess;
@@ -0,0 +1,403 @@
+use crate::image::{Image, ImageSize};
+use crate::tensor::{CpuAllocator, Tensor};
+use anyhow::Result;
+
+/// Converts the logits of a segmentation model into label maps.
+///
+/// # Arguments
+///
+/// * `logits` - The scores of the classes with shape (N, C, H, W), with at most 256 classes.
+///
+/// # Returns
+///
+/// A label map with shape (H, W, 1) per image of the batch, holding the index of the
+/// class with the highest score at each pixel.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::dnn::postprocess::argmax_to_labels;
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// // two classes over a 1x2 image
+/// let logits =
+///     Tensor::<f32, 4>::from_shape_vec([1, 2, 1, 2], vec![0.9, 0.2, 0.1, 0.8], CpuAllocator)
+///         .unwrap();
+///
+/// let labels = argmax_to_labels(&logits).unwrap();
+/// assert_eq!(labels.len(), 1);
+/// assert_eq!(labels[0].data.as_slice().unwrap(), &[0, 1]);
+/// ```
+pub fn argmax_to_labels(logits: &Tensor<f32, 4>) -> Result<Vec<Image<u8, 1>>> {
+    let [n, c, h, w] = logits.shape;
+    if c == 0 || c > 256 {
+        return Err(anyhow::anyhow!(
+            "The number of classes should be between 1 and 256, got {}",
+            c
+        ));
+    }
+
+    let data = logits.as_slice();
+    (0..n)
+        .map(|b| {
+            let batch = &data[b * c * h * w..(b + 1) * c * h * w];
+            let labels = (0..h * w)
+                .map(|i| {
+                    let mut best = 0;
+                    for k in 1..c {
+                        if batch[k * h * w + i] > batch[best * h * w + i] {
+                            best = k;
+                        }
+                    }
+                    best as u8
+                })
+                .collect();
+            Image::new(
+                ImageSize {
+                    width: w,
+                    height: h,
+                },
+                labels,
+            )
+        })
+        .collect()
+}
+
+/// Colorizes a label map with a palette.
+///
+/// The result can be blended over the input image, e.g. with
+/// [`add_weighted`](crate::enhance::add_weighted), to overlay the segmentation.
+///
+/// # Arguments
+///
+/// * `labels` - The label map with shape (H, W, 1).
+/// * `palette` - The RGB color of each label.
+///
+/// # Returns
+///
+/// The RGB image of the labels with shape (H, W, 3).
+///
+/// # Errors
+///
+/// Returns an error if a label has no color in the palette.
+pub fn colorize_labels(labels: &Image<u8, 1>, palette: &[[u8; 3]]) -> Result<Image<u8, 3>> {
+    let mut data = Vec::with_capacity(labels.width() * labels.height() * 3);
+    for &label in labels.data.iter() {
+        let color = palette.get(label as usize).ok_or_else(|| {
+            anyhow::anyhow!(
+                "The label {} is out of the palette of {} colors",
+                label,
+                palette.len()
+            )
+        })?;
+        data.extend_from_slice(color);
+    }
+
+    Image::new(labels.size(), data)
+}
+
+/// Resizes the logits of a segmentation model with bilinear interpolation.
+///
+/// The logits are usually predicted at a fraction of the input resolution, and are
+/// upsampled before [`argmax_to_labels`] to get smooth label boundaries. The pixel
+/// centers are aligned as in the `align_corners=False` mode of PyTorch.
+///
+/// # Arguments
+///
+/// * `logits` - The scores of the classes with shape (N, C, H, W).
+/// * `size` - The size of the resized logits.
+///
+/// # Returns
+///
+/// The resized logits with shape (N, C, size.height, size.width).
+pub fn resize_logits(logits: &Tensor<f32, 4>, size: ImageSize) -> Result<Tensor<f32, 4>> {
+    let [n, c, h, w] = logits.shape;
+    if h == 0 || w == 0 || size.width == 0 || size.height == 0 {
+        return Err(anyhow::anyhow!(
+            "Cannot resize logits of size {}x{} to {}",
+            w,
+            h,
+            size
+        ));
+    }
+
+    // the source coordinate and the interpolation weight along an axis
+    let sample = |i: usize, src_len: usize, dst_len: usize| {
+        let x = ((i as f32 + 0.5) * src_len as f32 / dst_len as f32 - 0.5).max(0.0);
+        let x0 = (x as usize).min(src_len - 1);
+        let x1 = (x0 + 1).min(src_len - 1);
+        (x0, x1, x - x0 as f32)
+    };
+    let xs = (0..size.width)
+        .map(|x| sample(x, w, size.width))
+        .collect::<Vec<_>>();
+    let ys = (0..size.height)
+        .map(|y| sample(y, h, size.height))
+        .collect::<Vec<_>>();
+
+    let data = logits.as_slice();
+    let resized = Tensor::from_shape_fn(
+        [n, c, size.height, size.width],
+        |[b, k, y, x]| {
+            let plane = &data[(b * c + k) * h * w..(b * c + k + 1) * h * w];
+            let (y0, y1, fy) = ys[y];
+            let (x0, x1, fx) = xs[x];
+            let top = plane[y0 * w + x0] * (1.0 - fx) + plane[y0 * w + x1] * fx;
+            let bottom = plane[y1 * w + x0] * (1.0 - fx) + plane[y1 * w + x1] * fx;
+            top * (1.0 - fy) + bottom * fy
+        },
+        CpuAllocator,
+    );
+
+    Ok(resized)
+}
+
+/// An object detected in an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    /// The bounding box of the object as (x_min, y_min, x_max, y_max).
+    pub bbox: [f32; 4],
+    /// The confidence of the detection.
+    pub score: f32,
+    /// The index of the class of the object.
+    pub class_id: usize,
+}
+
+impl Detection {
+    /// Computes the intersection over union of the bounding boxes of two detections.
+    pub fn iou(&self, other: &Detection) -> f32 {
+        let [ax0, ay0, ax1, ay1] = self.bbox;
+        let [bx0, by0, bx1, by1] = other.bbox;
+        let iw = (ax1.min(bx1) - ax0.max(bx0)).max(0.0);
+        let ih = (ay1.min(by1) - ay0.max(by0)).max(0.0);
+        let intersection = iw * ih;
+        let union = (ax1 - ax0) * (ay1 - ay0) + (bx1 - bx0) * (by1 - by0) - intersection;
+        if union <= 0.0 {
+            0.0
+        } else {
+            intersection / union
+        }
+    }
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Decodes the outputs of an anchor-based YOLO detection head.
+///
+/// Each output is the raw prediction of a scale with shape (1, A * (5 + C), H, W), for
+/// A anchors and C classes. The predictions of an anchor are the box offsets
+/// (tx, ty, tw, th), the objectness and the class scores, all decoded with a sigmoid as
+/// in YOLOv5:
+///
+/// * `cx = (2 * sigmoid(tx) - 0.5 + x) * stride`
+/// * `w = (2 * sigmoid(tw))^2 * anchor_w`
+///
+/// where the stride of a scale is the ratio between the input and the output widths.
+///
+/// # Arguments
+///
+/// * `outputs` - The raw outputs of the scales of the head.
+/// * `anchors` - The (width, height) of the anchors of each scale in input pixels.
+/// * `input_size` - The size of the input image of the model.
+/// * `conf_thresh` - The minimum score of the detections, the product of the objectness
+///   and the class score.
+///
+/// # Returns
+///
+/// The detections in the coordinates of the input image, before non-maximum suppression.
+///
+/// # Errors
+///
+/// Returns an error if the shape of an output does not match its anchors.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::dnn::postprocess::{decode_yolo, non_max_suppression};
+/// use kornia_rs::image::ImageSize;
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// // a single 2x2 scale with one anchor and one class, confident at the top left cell
+/// let mut data = vec![0.0; 6 * 4];
+/// data[4 * 4] = 10.0;
+/// data[5 * 4] = 10.0;
+/// let output = Tensor::<f32, 4>::from_shape_vec([1, 6, 2, 2], data, CpuAllocator).unwrap();
+///
+/// let input_size = ImageSize {
+///     width: 64,
+///     height: 64,
+/// };
+/// let detections = decode_yolo(&[output], &[vec![[16.0, 16.0]]], input_size, 0.5).unwrap();
+/// let detections = non_max_suppression(detections, 0.45);
+///
+/// assert_eq!(detections.len(), 1);
+/// assert_eq!(detections[0].bbox, [8.0, 8.0, 24.0, 24.0]);
+/// ```
+pub fn decode_yolo(
+    outputs: &[Tensor<f32, 4>],
+    anchors: &[Vec<[f32; 2]>],
+    input_size: ImageSize,
+    conf_thresh: f32,
+) -> Result<Vec<Detection>> {
+    if outputs.len() != anchors.len() {
+        return Err(anyhow::anyhow!(
+            "Got {} outputs for {} scales of anchors",
+            outputs.len(),
+            anchors.len()
+        ));
+    }
+
+    let mut detections = Vec::new();
+    for (output, anchors) in outputs.iter().zip(anchors) {
+        let [n, channels, h, w] = output.shape;
+        let num_anchors = anchors.len();
+        if n != 1 || num_anchors == 0 || channels % num_anchors != 0 || channels / num_anchors < 6 {
+            return Err(anyhow::anyhow!(
+                "The output with shape {:?} does not match {} anchors",
+                output.shape,
+                num_anchors
+            ));
+        }
+
+        let num_classes = channels / num_anchors - 5;
+        let stride_x = input_size.width as f32 / w as f32;
+        let stride_y = input_size.height as f32 / h as f32;
+        let data = output.as_slice();
+
+        for (a, &[anchor_w, anchor_h]) in anchors.iter().enumerate() {
+            let offset = a * (5 + num_classes) * h * w;
+            let value = |k: usize, i: usize| data[offset + k * h * w + i];
+
+            for i in 0..h * w {
+                let objectness = sigmoid(value(4, i));
+                if objectness < conf_thresh {
+                    continue;
+                }
+
+                let (class_id, class_score) = (0..num_classes)
+                    .map(|k| (k, value(5 + k, i)))
+                    .fold((0, f32::MIN), |best, c| if c.1 > best.1 { c } else { best });
+                let score = objectness * sigmoid(class_score);
+                if score < conf_thresh {
+                    continue;
+                }
+
+                let (x, y) = ((i % w) as f32, (i / w) as f32);
+                let cx = (2.0 * sigmoid(value(0, i)) - 0.5 + x) * stride_x;
+                let cy = (2.0 * sigmoid(value(1, i)) - 0.5 + y) * stride_y;
+                let bw = (2.0 * sigmoid(value(2, i))).powi(2) * anchor_w;
+                let bh = (2.0 * sigmoid(value(3, i))).powi(2) * anchor_h;
+
+                detections.push(Detection {
+                    bbox: [cx - bw / 2.0, cy - bh / 2.0, cx + bw / 2.0, cy + bh / 2.0],
+                    score,
+                    class_id,
+                });
+            }
+        }
+    }
+
+    Ok(detections)
+}
+
+/// Filters overlapping detections of the same class, keeping the most confident.
+///
+/// # Arguments
+///
+/// * `detections` - The detections to filter.
+/// * `iou_thresh` - The maximum intersection over union between two kept detections
+///   of the same class.
+///
+/// # Returns
+///
+/// The kept detections sorted by decreasing score.
+pub fn non_max_suppression(mut detections: Vec<Detection>, iou_thresh: f32) -> Vec<Detection> {
+    detections.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut kept: Vec<Detection> = Vec::new();
+    for detection in detections {
+        let suppressed = kept.iter().any(|other| {
+            other.class_id == detection.class_id && other.iou(&detection) > iou_thresh
+        });
+        if !suppressed {
+            kept.push(detection);
+        }
+    }
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{argmax_to_labels, colorize_labels, non_max_suppression, resize_logits, Detection};
+    use crate::image::ImageSize;
+    use crate::tensor::{CpuAllocator, Tensor};
+    use anyhow::Result;
+
+    #[test]
+    fn segmentation_postprocess() -> Result<()> {
+        // the score of the second class increases from left to right
+        #[rustfmt::skip]
+        let logits = Tensor::<f32, 4>::from_shape_vec(
+            [1, 2, 1, 2],
+            vec![
+                1.0, 0.0,
+                0.0, 1.0,
+            ],
+            CpuAllocator,
+        )?;
+
+        let resized = resize_logits(
+            &logits,
+            ImageSize {
+                width: 4,
+                height: 2,
+            },
+        )?;
+        assert_eq!(resized.shape, [1, 2, 2, 4]);
+        assert_eq!(&resized.as_slice()[..4], &[1.0, 0.75, 0.25, 0.0]);
+
+        let labels = argmax_to_labels(&resized)?;
+        assert_eq!(
+            labels[0].data.as_slice().unwrap(),
+            &[0, 0, 1, 1, 0, 0, 1, 1]
+        );
+
+        let palette = [[0, 0, 0], [255, 0, 0]];
+        let colors = colorize_labels(&labels[0], &palette)?;
+        assert_eq!(
+            colors.data.as_slice().unwrap()[..9],
+            [0, 0, 0, 0, 0, 0, 255, 0, 0]
+        );
+
+        assert!(colorize_labels(&labels[0], &palette[..1]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn class_aware_nms() {
+        let detection = |x: f32, score: f32, class_id: usize| Detection {
+            bbox: [x, 0.0, x + 10.0, 10.0],
+            score,
+            class_id,
+        };
+        let detections = vec![
+            detection(1.0, 0.8, 0),
+            detection(0.0, 0.9, 0),
+            detection(0.0, 0.7, 1),
+            detection(20.0, 0.6, 0),
+        ];
+
+        let kept = non_max_suppression(detections, 0.5);
+        assert_eq!(
+            kept,
+            vec![
+                detection(0.0, 0.9, 0),
+                detection(0.0, 0.7, 1),
+                detection(20.0, 0.6, 0)
+            ]
+        );
+    }
+}
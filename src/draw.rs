@@ -0,0 +1,210 @@
+use crate::geometry::Point2;
+use crate::image::Image;
+use anyhow::Result;
+
+/// Rule used to decide which points are inside a self-intersecting polygon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses the polygon an odd number of times.
+    EvenOdd,
+    /// A point is inside if the polygon winds around it a non-zero number of times.
+    NonZero,
+}
+
+/// Number of samples per pixel side used for the anti-aliased edges.
+const AA_SAMPLES: usize = 4;
+
+/// Fill a polygon into a single channel image.
+///
+/// The pixels are sampled at their centers, i.e. the pixel (x, y) covers the area
+/// [x, x + 1) x [y, y + 1). With anti-aliasing enabled, each pixel is blended with
+/// the fill value according to the fraction of the pixel covered by the polygon.
+///
+/// # Arguments
+///
+/// * `image` - The image to draw into with shape (H, W, 1).
+/// * `polygon` - The vertices of the polygon. The polygon is closed implicitly.
+/// * `value` - The value to fill the polygon with.
+/// * `rule` - The rule used to fill self-intersecting polygons.
+/// * `anti_aliased` - Whether to blend the pixels on the edges by their coverage.
+///
+/// # Errors
+///
+/// Returns an error if the polygon has less than 3 vertices.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::draw::{fill_polygon, FillRule};
+/// use kornia_rs::geometry::Point2;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let mut mask = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 4,
+///         height: 4,
+///     },
+///     0,
+/// )
+/// .unwrap();
+///
+/// let square = [
+///     Point2::new(1.0, 1.0),
+///     Point2::new(3.0, 1.0),
+///     Point2::new(3.0, 3.0),
+///     Point2::new(1.0, 3.0),
+/// ];
+/// fill_polygon(&mut mask, &square, 255, FillRule::EvenOdd, false).unwrap();
+///
+/// assert_eq!(mask.data.iter().filter(|&&v| v == 255).count(), 4);
+/// assert_eq!(mask.get_pixel(1, 1, 0).unwrap(), 255);
+/// assert_eq!(mask.get_pixel(0, 0, 0).unwrap(), 0);
+/// ```
+pub fn fill_polygon(
+    image: &mut Image<u8, 1>,
+    polygon: &[Point2],
+    value: u8,
+    rule: FillRule,
+    anti_aliased: bool,
+) -> Result<()> {
+    if polygon.len() < 3 {
+        return Err(anyhow::anyhow!(
+            "The polygon must have at least 3 vertices, got {}.",
+            polygon.len()
+        ));
+    }
+
+    let (width, height) = (image.width(), image.height());
+    let samples = if anti_aliased { AA_SAMPLES } else { 1 };
+    let step = 1.0 / samples as f32;
+
+    // rows spanned by the polygon
+    let (min_y, max_y) = polygon.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+        (lo.min(p.y), hi.max(p.y))
+    });
+    let y_start = min_y.floor().max(0.0) as usize;
+    let y_end = (max_y.ceil().max(0.0) as usize).min(height);
+
+    // crossings of the scanline as (x, winding direction)
+    let mut crossings: Vec<(f32, i32)> = Vec::with_capacity(polygon.len());
+    let mut coverage = vec![0usize; width];
+
+    for y in y_start..y_end {
+        coverage.iter_mut().for_each(|c| *c = 0);
+
+        for sub_y in 0..samples {
+            let sy = y as f32 + (sub_y as f32 + 0.5) * step;
+
+            crossings.clear();
+            for i in 0..polygon.len() {
+                let (p, q) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                if (p.y <= sy) == (q.y <= sy) {
+                    continue;
+                }
+                let x = p.x + (sy - p.y) * (q.x - p.x) / (q.y - p.y);
+                crossings.push((x, if q.y > p.y { 1 } else { -1 }));
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for (i, &(x0, dir)) in crossings.iter().enumerate() {
+                winding += dir;
+                let inside = match rule {
+                    FillRule::EvenOdd => (i + 1) % 2 == 1,
+                    FillRule::NonZero => winding != 0,
+                };
+                if !inside || i + 1 == crossings.len() {
+                    continue;
+                }
+
+                // sample columns with center in the span [x0, x1)
+                let x1 = crossings[i + 1].0;
+                let first = (x0 * samples as f32 - 0.5).ceil().max(0.0) as usize;
+                let last =
+                    ((x1 * samples as f32 - 0.5).ceil().max(0.0) as usize).min(width * samples);
+                for k in first..last {
+                    coverage[k / samples] += 1;
+                }
+            }
+        }
+
+        let total = (samples * samples) as f32;
+        for (x, &c) in coverage.iter().enumerate() {
+            if c == 0 {
+                continue;
+            }
+            let alpha = c as f32 / total;
+            let pixel = &mut image.data[[y, x, 0]];
+            *pixel = (*pixel as f32 * (1.0 - alpha) + value as f32 * alpha).round() as u8;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point2;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn fill_polygon_rules() -> Result<()> {
+        let size = ImageSize {
+            width: 10,
+            height: 10,
+        };
+
+        // a pentagram: its center is inside for non-zero and outside for even-odd
+        let star = (0..5)
+            .map(|i| {
+                let angle = (i as f32 * 144.0 - 90.0).to_radians();
+                Point2::new(5.0 + 4.5 * angle.cos(), 5.0 + 4.5 * angle.sin())
+            })
+            .collect::<Vec<_>>();
+
+        let mut even_odd = Image::<u8, 1>::from_size_val(size, 0)?;
+        super::fill_polygon(&mut even_odd, &star, 255, super::FillRule::EvenOdd, false)?;
+        assert_eq!(even_odd.get_pixel(5, 5, 0)?, 0);
+
+        let mut non_zero = Image::<u8, 1>::from_size_val(size, 0)?;
+        super::fill_polygon(&mut non_zero, &star, 255, super::FillRule::NonZero, false)?;
+        assert_eq!(non_zero.get_pixel(5, 5, 0)?, 255);
+
+        // the tips of the star are filled with both rules
+        assert_eq!(even_odd.get_pixel(4, 2, 0)?, 255);
+        assert_eq!(non_zero.get_pixel(4, 2, 0)?, 255);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fill_polygon_anti_aliased() -> Result<()> {
+        let mut mask = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 4,
+                height: 2,
+            },
+            0,
+        )?;
+
+        // a rectangle covering half of the pixels of the second column
+        let rect = [
+            Point2::new(0.0, 0.0),
+            Point2::new(1.5, 0.0),
+            Point2::new(1.5, 2.0),
+            Point2::new(0.0, 2.0),
+        ];
+        super::fill_polygon(&mut mask, &rect, 200, super::FillRule::NonZero, true)?;
+
+        assert_eq!(mask.get_pixel(0, 0, 0)?, 200);
+        assert_eq!(mask.get_pixel(1, 1, 0)?, 100);
+        assert_eq!(mask.get_pixel(2, 0, 0)?, 0);
+
+        assert!(
+            super::fill_polygon(&mut mask, &rect[..2], 1, super::FillRule::NonZero, true).is_err()
+        );
+
+        Ok(())
+    }
+}
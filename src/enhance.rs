@@ -1,4 +1,5 @@
 use crate::image::Image;
+use crate::stats::percentile;
 use anyhow::Result;
 
 /// Performs weighted addition of two images `src1` and `src2` with weights `alpha`
@@ -55,6 +56,538 @@ where
     Ok(Image { data: dst })
 }
 
+/// Stretches the histogram of each channel of an image to the full range.
+///
+/// The lowest and highest `cutoff_percent` percent of the values of each channel are
+/// clipped, and the remaining range is mapped linearly to [0, 255]. The channels with a
+/// single value are left unchanged.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `cutoff_percent` - The percentage of values to clip on each side, in the range [0, 50).
+///
+/// # Returns
+///
+/// The enhanced image with the same shape as the input.
+///
+/// # Errors
+///
+/// Returns an error if the cutoff is out of range or the image is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::enhance::autocontrast;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![100, 120, 150],
+/// )
+/// .unwrap();
+///
+/// let enhanced = autocontrast(&image, 0.0).unwrap();
+/// assert_eq!(enhanced.data.as_slice().unwrap(), &[0, 102, 255]);
+/// ```
+pub fn autocontrast<const CHANNELS: usize>(
+    src: &Image<u8, CHANNELS>,
+    cutoff_percent: f32,
+) -> Result<Image<u8, CHANNELS>> {
+    if !(0.0..50.0).contains(&cutoff_percent) {
+        return Err(anyhow::anyhow!(
+            "The cutoff {} must be in the range [0, 50).",
+            cutoff_percent
+        ));
+    }
+
+    let low = percentile(src, cutoff_percent)?;
+    let high = percentile(src, 100.0 - cutoff_percent)?;
+
+    let mut dst = Image::<u8, CHANNELS>::from_size_val(src.size(), 0)?;
+    ndarray::Zip::from(dst.data.rows_mut())
+        .and(src.data.rows())
+        .par_for_each(|mut out, inp| {
+            for c in 0..CHANNELS {
+                let value = inp[c] as f32;
+                out[c] = if high[c] > low[c] {
+                    let scaled = (value - low[c]) * 255.0 / (high[c] - low[c]);
+                    scaled.round().clamp(0.0, 255.0) as u8
+                } else {
+                    inp[c]
+                };
+            }
+        });
+
+    Ok(dst)
+}
+
+/// Apply a lookup table to each pixel value of an 8-bit image.
+fn apply_lut<const CHANNELS: usize>(
+    src: &Image<u8, CHANNELS>,
+    lut: impl Fn(usize, u8) -> u8 + Send + Sync,
+) -> Result<Image<u8, CHANNELS>> {
+    let mut dst = Image::<u8, CHANNELS>::from_size_val(src.size(), 0)?;
+    ndarray::Zip::from(dst.data.rows_mut())
+        .and(src.data.rows())
+        .par_for_each(|mut out, inp| {
+            for c in 0..CHANNELS {
+                out[c] = lut(c, inp[c]);
+            }
+        });
+    Ok(dst)
+}
+
+/// Reduces the number of bits of each pixel value.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `bits` - The number of most significant bits to keep, in the range [1, 8].
+///
+/// # Returns
+///
+/// The posterized image with the same shape as the input.
+///
+/// # Errors
+///
+/// Returns an error if the number of bits is out of range.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::enhance::posterize;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![15, 100, 255],
+/// )
+/// .unwrap();
+///
+/// let posterized = posterize(&image, 2).unwrap();
+/// assert_eq!(posterized.data.as_slice().unwrap(), &[0, 64, 192]);
+/// ```
+pub fn posterize<const CHANNELS: usize>(
+    src: &Image<u8, CHANNELS>,
+    bits: u8,
+) -> Result<Image<u8, CHANNELS>> {
+    if !(1..=8).contains(&bits) {
+        return Err(anyhow::anyhow!(
+            "The number of bits {} must be in the range [1, 8].",
+            bits
+        ));
+    }
+    let mask = !((1u16 << (8 - bits)) - 1) as u8;
+    apply_lut(src, |_, v| v & mask)
+}
+
+/// Inverts the pixel values above a threshold.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `threshold` - The values greater or equal to the threshold are inverted.
+///
+/// # Returns
+///
+/// The solarized image with the same shape as the input.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::enhance::solarize;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![10, 128, 200],
+/// )
+/// .unwrap();
+///
+/// let solarized = solarize(&image, 128).unwrap();
+/// assert_eq!(solarized.data.as_slice().unwrap(), &[10, 127, 55]);
+/// ```
+pub fn solarize<const CHANNELS: usize>(
+    src: &Image<u8, CHANNELS>,
+    threshold: u8,
+) -> Result<Image<u8, CHANNELS>> {
+    apply_lut(src, |_, v| if v >= threshold { 255 - v } else { v })
+}
+
+/// Adjusts the sharpness of an image.
+///
+/// The image is blended with a smoothed version of itself, so that a factor of 0 gives
+/// the smoothed image, 1 the original image and larger factors a sharpened image. The
+/// pixels on the border of the image are left unchanged.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `factor` - The sharpness factor, non-negative.
+///
+/// # Returns
+///
+/// The adjusted image with the same shape as the input.
+///
+/// # Errors
+///
+/// Returns an error if the factor is negative.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::enhance::sharpness;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let mut image = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 3,
+///         height: 3,
+///     },
+///     0,
+/// )
+/// .unwrap();
+/// image.data[[1, 1, 0]] = 130;
+///
+/// // the smoothing kernel keeps 5/13 of the center pixel
+/// let smoothed = sharpness(&image, 0.0).unwrap();
+/// assert_eq!(smoothed.data[[1, 1, 0]], 50);
+/// assert_eq!(sharpness(&image, 1.0).unwrap().data[[1, 1, 0]], 130);
+/// ```
+pub fn sharpness<const CHANNELS: usize>(
+    src: &Image<u8, CHANNELS>,
+    factor: f32,
+) -> Result<Image<u8, CHANNELS>> {
+    if factor < 0.0 {
+        return Err(anyhow::anyhow!(
+            "The sharpness factor {} must be non-negative.",
+            factor
+        ));
+    }
+
+    let (width, height) = (src.width(), src.height());
+    let mut dst = src.clone();
+    if width < 3 || height < 3 {
+        return Ok(dst);
+    }
+
+    ndarray::Zip::indexed(dst.data.rows_mut()).par_for_each(|(y, x), mut out| {
+        if y == 0 || x == 0 || y == height - 1 || x == width - 1 {
+            return;
+        }
+        for c in 0..CHANNELS {
+            // the smoothing kernel [[1, 1, 1], [1, 5, 1], [1, 1, 1]] / 13
+            let mut sum = 4.0 * src.data[[y, x, c]] as f32;
+            for ny in y - 1..=y + 1 {
+                for nx in x - 1..=x + 1 {
+                    sum += src.data[[ny, nx, c]] as f32;
+                }
+            }
+            let smooth = sum / 13.0;
+            let value = smooth + factor * (src.data[[y, x, c]] as f32 - smooth);
+            out[c] = value.round().clamp(0.0, 255.0) as u8;
+        }
+    });
+
+    Ok(dst)
+}
+
+/// Equalizes the histogram of each channel of an image.
+///
+/// The values are remapped with the cumulative histogram of each channel, so that the
+/// cumulative histogram of the result becomes approximately linear over [0, 255].
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+///
+/// # Returns
+///
+/// The equalized image with the same shape as the input.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::enhance::equalize;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 4,
+///         height: 1,
+///     },
+///     vec![10, 10, 11, 12],
+/// )
+/// .unwrap();
+///
+/// let equalized = equalize(&image).unwrap();
+/// assert_eq!(equalized.data.as_slice().unwrap(), &[0, 0, 128, 255]);
+/// ```
+pub fn equalize<const CHANNELS: usize>(src: &Image<u8, CHANNELS>) -> Result<Image<u8, CHANNELS>> {
+    let mut histograms = [[0usize; 256]; CHANNELS];
+    for pixel in src.data.rows() {
+        for (c, histogram) in histograms.iter_mut().enumerate() {
+            histogram[pixel[c] as usize] += 1;
+        }
+    }
+
+    // the lookup table of each channel from its cumulative histogram
+    let luts = histograms.map(|histogram| {
+        let mut lut: [u8; 256] = std::array::from_fn(|i| i as u8);
+
+        // the smallest value is mapped to 0 and the largest to 255
+        let total = histogram.iter().sum::<usize>();
+        let first = histogram.iter().find(|&&n| n > 0).cloned().unwrap_or(0);
+        if total == first {
+            return lut;
+        }
+
+        let mut cumulative = 0;
+        for (v, &count) in lut.iter_mut().zip(histogram.iter()) {
+            cumulative += count;
+            let scaled = cumulative.saturating_sub(first) as f32 * 255.0 / (total - first) as f32;
+            *v = scaled.round() as u8;
+        }
+        lut
+    });
+
+    apply_lut(src, |c, v| luts[c][v as usize])
+}
+
+/// A radial polynomial model of the vignetting of a lens.
+///
+/// The brightness of a pixel is attenuated by the gain `1 + k1 * r^2 + k2 * r^4 + k3 * r^6`,
+/// where `r` is the distance to the center of the image normalized to 1 at the corners.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VignetteModel {
+    /// The second order coefficient.
+    pub k1: f32,
+    /// The fourth order coefficient.
+    pub k2: f32,
+    /// The sixth order coefficient.
+    pub k3: f32,
+}
+
+impl VignetteModel {
+    /// Get the gain of the model at a normalized radius.
+    pub fn gain(&self, r: f32) -> f32 {
+        let r2 = r * r;
+        1.0 + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3))
+    }
+}
+
+/// Scale each pixel by a function of its normalized distance to the center of the image.
+pub(crate) fn apply_radial_gain<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    gain: impl Fn(f32) -> f32 + Send + Sync,
+) -> Result<Image<f32, CHANNELS>> {
+    let (cx, cy) = (
+        (src.width() as f32 - 1.0) / 2.0,
+        (src.height() as f32 - 1.0) / 2.0,
+    );
+    let half_diagonal = cx.hypot(cy).max(f32::EPSILON);
+
+    let mut dst = Image::<f32, CHANNELS>::from_size_val(src.size(), 0.0)?;
+    ndarray::Zip::indexed(dst.data.rows_mut())
+        .and(src.data.rows())
+        .par_for_each(|(y, x), mut out, inp| {
+            let r = (x as f32 - cx).hypot(y as f32 - cy) / half_diagonal;
+            let g = gain(r);
+            for c in 0..CHANNELS {
+                out[c] = inp[c] * g;
+            }
+        });
+
+    Ok(dst)
+}
+
+/// Corrects the vignetting of an image.
+///
+/// Each pixel is divided by the gain of the vignetting model at its position, so that
+/// the corners darkened by the lens recover the brightness of the center.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `model` - The vignetting model of the camera.
+///
+/// # Returns
+///
+/// The corrected image with the same shape as the input.
+///
+/// # Errors
+///
+/// Returns an error if the gain of the model is not positive over the image.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::enhance::{devignette, VignetteModel};
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![0.5, 1.0, 0.5],
+/// )
+/// .unwrap();
+///
+/// // the corners receive half of the light of the center
+/// let model = VignetteModel {
+///     k1: -0.5,
+///     ..Default::default()
+/// };
+/// let corrected = devignette(&image, &model).unwrap();
+/// assert_eq!(corrected.data.as_slice().unwrap(), &[1.0, 1.0, 1.0]);
+/// ```
+pub fn devignette<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    model: &VignetteModel,
+) -> Result<Image<f32, CHANNELS>> {
+    // the gain is checked along the radius, up to the corners
+    if (0..=100).any(|i| model.gain(i as f32 / 100.0) <= 0.0) {
+        return Err(anyhow::anyhow!(
+            "The gain of the vignetting model {:?} must be positive.",
+            model
+        ));
+    }
+
+    apply_radial_gain(src, |r| 1.0 / model.gain(r))
+}
+
+/// The alignment of a color channel relative to the green channel.
+///
+/// A pixel at `(x, y)` of the corrected image takes the channel value at
+/// `center + (p - center) * scale + shift` in the input image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelAlignment {
+    /// The radial magnification around the center of the image.
+    pub scale: f32,
+    /// The horizontal shift in pixels.
+    pub shift_x: f32,
+    /// The vertical shift in pixels.
+    pub shift_y: f32,
+}
+
+impl Default for ChannelAlignment {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            shift_x: 0.0,
+            shift_y: 0.0,
+        }
+    }
+}
+
+/// Bilinear sampling of a channel of an image, replicating the border pixels.
+fn sample_channel<const CHANNELS: usize>(
+    src: &Image<u8, CHANNELS>,
+    channel: usize,
+    x: f32,
+    y: f32,
+) -> f32 {
+    let x = x.clamp(0.0, (src.width() - 1) as f32);
+    let y = y.clamp(0.0, (src.height() - 1) as f32);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = (
+        (x0 + 1).min(src.width() - 1),
+        (y0 + 1).min(src.height() - 1),
+    );
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+    let at = |x: usize, y: usize| src.data[[y, x, channel]] as f32;
+    at(x0, y0) * (1.0 - fx) * (1.0 - fy)
+        + at(x1, y0) * fx * (1.0 - fy)
+        + at(x0, y1) * (1.0 - fx) * fy
+        + at(x1, y1) * fx * fy
+}
+
+/// Corrects the lateral chromatic aberration of an RGB image.
+///
+/// The red and blue channels are warped to align with the green channel, which is kept
+/// as is. Wide-angle lenses magnify each wavelength differently, which shows as colored
+/// fringes along the edges towards the borders of the image.
+///
+/// # Arguments
+///
+/// * `src` - The input RGB image with shape (H, W, 3).
+/// * `red` - The alignment of the red channel.
+/// * `blue` - The alignment of the blue channel.
+///
+/// # Returns
+///
+/// The corrected image with the same shape as the input.
+///
+/// # Errors
+///
+/// Returns an error if a scale is not positive.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::enhance::{correct_chromatic_aberration, ChannelAlignment};
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// // the red channel is shifted one pixel to the left
+/// let image = Image::<u8, 3>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![255, 0, 0, 0, 255, 0, 0, 0, 0],
+/// )
+/// .unwrap();
+///
+/// let red = ChannelAlignment {
+///     shift_x: -1.0,
+///     ..Default::default()
+/// };
+/// let corrected = correct_chromatic_aberration(&image, &red, &Default::default()).unwrap();
+/// assert_eq!(corrected.data[[0, 1, 0]], 255);
+/// assert_eq!(corrected.data[[0, 1, 1]], 255);
+/// ```
+pub fn correct_chromatic_aberration(
+    src: &Image<u8, 3>,
+    red: &ChannelAlignment,
+    blue: &ChannelAlignment,
+) -> Result<Image<u8, 3>> {
+    if red.scale <= 0.0 || blue.scale <= 0.0 {
+        return Err(anyhow::anyhow!(
+            "The channel scales must be positive, got {} and {}",
+            red.scale,
+            blue.scale
+        ));
+    }
+
+    let (cx, cy) = (
+        (src.width() as f32 - 1.0) / 2.0,
+        (src.height() as f32 - 1.0) / 2.0,
+    );
+
+    let mut dst = src.clone();
+    ndarray::Zip::indexed(dst.data.rows_mut()).par_for_each(|(y, x), mut out| {
+        for (c, alignment) in [(0, red), (2, blue)] {
+            let u = cx + (x as f32 - cx) * alignment.scale + alignment.shift_x;
+            let v = cy + (y as f32 - cy) * alignment.scale + alignment.shift_y;
+            out[c] = sample_channel(src, c, u, v).round().clamp(0.0, 255.0) as u8;
+        }
+    });
+
+    Ok(dst)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::image::{Image, ImageSize};
@@ -95,4 +628,135 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn autocontrast() -> Result<()> {
+        let image = Image::<u8, 2>::new(
+            ImageSize {
+                width: 5,
+                height: 1,
+            },
+            vec![0, 50, 60, 50, 70, 50, 80, 50, 255, 50],
+        )?;
+
+        // the outliers are clipped and the constant channel is kept
+        let enhanced = super::autocontrast(&image, 20.0)?;
+        let expected = [0, 50, 46, 50, 84, 50, 122, 50, 255, 50];
+        assert_eq!(enhanced.data.as_slice().unwrap(), &expected);
+
+        assert!(super::autocontrast(&image, 50.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn posterize_solarize() -> Result<()> {
+        let image = Image::<u8, 2>::new(
+            ImageSize {
+                width: 2,
+                height: 1,
+            },
+            vec![0, 127, 128, 255],
+        )?;
+
+        let posterized = super::posterize(&image, 1)?;
+        assert_eq!(posterized.data.as_slice().unwrap(), &[0, 0, 128, 128]);
+        assert_eq!(super::posterize(&image, 8)?.data, image.data);
+        assert!(super::posterize(&image, 0).is_err());
+
+        let solarized = super::solarize(&image, 127)?;
+        assert_eq!(solarized.data.as_slice().unwrap(), &[0, 128, 127, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn sharpness_equalize() -> Result<()> {
+        let image = Image::<u8, 1>::new(
+            ImageSize {
+                width: 3,
+                height: 3,
+            },
+            vec![10, 10, 10, 10, 23, 10, 10, 10, 10],
+        )?;
+
+        // the center is blended with its smoothed value 15
+        let sharpened = super::sharpness(&image, 2.0)?;
+        assert_eq!(sharpened.data[[1, 1, 0]], 31);
+        assert_eq!(sharpened.data[[0, 0, 0]], 10);
+        assert!(super::sharpness(&image, -1.0).is_err());
+
+        // a constant channel is left unchanged
+        let equalized = super::equalize(&image)?;
+        assert_eq!(
+            equalized.data.as_slice().unwrap(),
+            &[0, 0, 0, 0, 255, 0, 0, 0, 0]
+        );
+        let constant = Image::<u8, 1>::from_size_val(image.size(), 7)?;
+        assert_eq!(super::equalize(&constant)?.data, constant.data);
+        Ok(())
+    }
+
+    #[test]
+    fn devignette() -> Result<()> {
+        let image = Image::<f32, 2>::from_size_val(
+            ImageSize {
+                width: 5,
+                height: 5,
+            },
+            0.8,
+        )?;
+        let model = super::VignetteModel {
+            k1: -0.2,
+            k2: -0.1,
+            k3: 0.0,
+        };
+
+        // the center is kept and the corners are brightened by 1 / 0.7
+        let corrected = super::devignette(&image, &model)?;
+        assert!((corrected.data[[2, 2, 1]] - 0.8).abs() < 1e-6);
+        assert!((corrected.data[[0, 4, 0]] - 0.8 / 0.7).abs() < 1e-5);
+
+        let invalid = super::VignetteModel { k1: -1.5, ..model };
+        assert!(super::devignette(&image, &invalid).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn correct_chromatic_aberration() -> Result<()> {
+        // a vertical edge, magnified by 1.25 in the red channel and 0.75 in the blue one
+        let (width, height) = (9, 5);
+        let mut image = Image::<u8, 3>::from_size_val(ImageSize { width, height }, 0)?;
+        for y in 0..height {
+            for x in 0..width {
+                let edge = |scale: f32| 4.0 + (x as f32 - 4.0) * scale;
+                image.data[[y, x, 0]] = if edge(1.0 / 1.25) >= 6.0 { 200 } else { 0 };
+                image.data[[y, x, 1]] = if x >= 6 { 200 } else { 0 };
+                image.data[[y, x, 2]] = if edge(1.0 / 0.75) >= 6.0 { 200 } else { 0 };
+            }
+        }
+
+        let red = super::ChannelAlignment {
+            scale: 1.25,
+            ..Default::default()
+        };
+        let blue = super::ChannelAlignment {
+            scale: 0.75,
+            ..Default::default()
+        };
+        let corrected = super::correct_chromatic_aberration(&image, &red, &blue)?;
+        for x in 0..width {
+            let expected = if x >= 6 { 200 } else { 0 };
+            assert_eq!(corrected.data[[2, x, 1]], expected);
+            for c in [0, 2] {
+                let diff = corrected.data[[2, x, c]] as i32 - expected as i32;
+                assert!(diff.abs() <= 100, "x {} channel {}: {}", x, c, diff);
+            }
+        }
+
+        let invalid = super::ChannelAlignment {
+            scale: 0.0,
+            ..Default::default()
+        };
+        assert!(super::correct_chromatic_aberration(&image, &invalid, &blue).is_err());
+        Ok(())
+    }
 }
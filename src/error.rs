@@ -0,0 +1,65 @@
+use thiserror::Error;
+
+use crate::image::ImageSize;
+
+/// The errors returned by the public API of the crate.
+///
+/// The error converts into `anyhow::Error`, so that it can be propagated with `?` from
+/// functions returning `anyhow::Result`.
+#[derive(Error, Debug)]
+pub enum KorniaError {
+    #[error("Invalid image size {0}.")]
+    InvalidImageSize(ImageSize),
+
+    #[error("The image size {actual} does not match the expected size {expected}.")]
+    SizeMismatch {
+        expected: ImageSize,
+        actual: ImageSize,
+    },
+
+    #[error("Failed to resize the image: {0}")]
+    Resize(String),
+
+    #[error("Failed to decode the image: {0}")]
+    Decode(String),
+
+    #[error("Error in the capture pipeline: {0}")]
+    Pipeline(String),
+
+    #[error("The caps {found} do not match the expected {expected}.")]
+    CapsMismatch { expected: String, found: String },
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(feature = "gstreamer")]
+impl From<gst::glib::Error> for KorniaError {
+    fn from(err: gst::glib::Error) -> Self {
+        KorniaError::Pipeline(err.to_string())
+    }
+}
+
+#[cfg(feature = "gstreamer")]
+impl From<gst::glib::BoolError> for KorniaError {
+    fn from(err: gst::glib::BoolError) -> Self {
+        KorniaError::Pipeline(err.to_string())
+    }
+}
+
+#[cfg(feature = "gstreamer")]
+impl From<gst::StateChangeError> for KorniaError {
+    fn from(err: gst::StateChangeError) -> Self {
+        KorniaError::Pipeline(err.to_string())
+    }
+}
+
+#[cfg(feature = "gstreamer")]
+impl From<gst::FlowError> for KorniaError {
+    fn from(err: gst::FlowError) -> Self {
+        KorniaError::Pipeline(format!("{:?}", err))
+    }
+}
@@ -0,0 +1,212 @@
+use crate::filters::filter2d;
+use crate::geometry::Point2;
+use crate::image::Image;
+use anyhow::Result;
+
+/// Create a normalized 2D Gaussian kernel with a radius of three standard deviations.
+fn gaussian_kernel(sigma: f32) -> ndarray::Array2<f32> {
+    let radius = (3.0 * sigma).ceil() as usize;
+    let weights = (0..2 * radius + 1)
+        .map(|i| {
+            let x = i as f32 - radius as f32;
+            (-x * x / (2.0 * sigma * sigma)).exp()
+        })
+        .collect::<Vec<_>>();
+    let sum = weights.iter().sum::<f32>();
+
+    let size = weights.len();
+    ndarray::Array2::from_shape_fn((size, size), |(y, x)| weights[y] * weights[x] / (sum * sum))
+}
+
+/// Computes the structure tensor of a grayscale image.
+///
+/// The image gradients are computed with the Sobel operator and their products are
+/// averaged with a Gaussian window.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, 1).
+/// * `sigma` - The standard deviation of the Gaussian window.
+///
+/// # Returns
+///
+/// The image with the three distinct elements of the tensor (Ixx, Ixy, Iyy) as channels.
+///
+/// # Errors
+///
+/// Returns an error if sigma is not positive.
+pub fn structure_tensor(src: &Image<f32, 1>, sigma: f32) -> Result<Image<f32, 3>> {
+    if sigma <= 0.0 {
+        return Err(anyhow::anyhow!("The sigma {} must be positive.", sigma));
+    }
+
+    let sobel_x = ndarray::array![[-1.0, 0.0, 1.0], [-2.0, 0.0, 2.0], [-1.0, 0.0, 1.0]] / 8.0;
+    let sobel_y = sobel_x.t().to_owned();
+    let gx = filter2d(src, &sobel_x)?;
+    let gy = filter2d(src, &sobel_y)?;
+
+    let mut products = Image::<f32, 3>::from_size_val(src.size(), 0.0)?;
+    ndarray::Zip::from(products.data.rows_mut())
+        .and(gx.data.rows())
+        .and(gy.data.rows())
+        .par_for_each(|mut out, gx, gy| {
+            let (gx, gy) = (gx[0], gy[0]);
+            out[0] = gx * gx;
+            out[1] = gx * gy;
+            out[2] = gy * gy;
+        });
+
+    filter2d(&products, &gaussian_kernel(sigma))
+}
+
+/// Detects the strongest corners of a grayscale image with the Shi-Tomasi criterion.
+///
+/// The corner response is the smallest eigenvalue of the structure tensor. The corners
+/// are the local maxima above `quality` times the best response, selected from the
+/// strongest so that no two corners are closer than `min_distance`.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, 1).
+/// * `max_corners` - The maximum number of corners to return.
+/// * `quality` - The minimum response relative to the best corner, in (0, 1].
+/// * `min_distance` - The minimum euclidean distance between two corners in pixels.
+///
+/// # Returns
+///
+/// The corners ordered from the strongest.
+///
+/// # Errors
+///
+/// Returns an error if the quality is not in (0, 1].
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::features::good_features_to_track;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let mut image = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 32,
+///         height: 32,
+///     },
+///     0.0,
+/// )
+/// .unwrap();
+///
+/// // a bright square with four corners
+/// image
+///     .data
+///     .slice_mut(ndarray::s![8..24, 8..24, ..])
+///     .fill(1.0);
+///
+/// let corners = good_features_to_track(&image, 10, 0.1, 5.0).unwrap();
+/// assert_eq!(corners.len(), 4);
+/// ```
+pub fn good_features_to_track(
+    src: &Image<f32, 1>,
+    max_corners: usize,
+    quality: f32,
+    min_distance: f32,
+) -> Result<Vec<Point2>> {
+    if quality <= 0.0 || quality > 1.0 {
+        return Err(anyhow::anyhow!(
+            "The quality {} must be in (0, 1].",
+            quality
+        ));
+    }
+
+    let tensor = structure_tensor(src, 1.0)?;
+    let (width, height) = (src.width(), src.height());
+
+    // the smallest eigenvalue of [[a, b], [b, c]]
+    let response = tensor
+        .data
+        .rows()
+        .into_iter()
+        .map(|t| {
+            let (a, b, c) = (t[0], t[1], t[2]);
+            (a + c) / 2.0 - (((a - c) / 2.0).powi(2) + b * b).sqrt()
+        })
+        .collect::<Vec<_>>();
+
+    let max_response = response.iter().cloned().fold(0.0, f32::max);
+    if max_response <= 0.0 {
+        return Ok(Vec::new());
+    }
+    let threshold = quality * max_response;
+
+    // keep the local maxima of a 3x3 neighborhood above the threshold
+    let mut candidates = Vec::new();
+    for y in 1..height.saturating_sub(1) {
+        for x in 1..width.saturating_sub(1) {
+            let r = response[y * width + x];
+            if r < threshold {
+                continue;
+            }
+            let is_max =
+                (y - 1..=y + 1).all(|ny| (x - 1..=x + 1).all(|nx| response[ny * width + nx] <= r));
+            if is_max {
+                candidates.push((r, x, y));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut corners: Vec<Point2> = Vec::new();
+    for (_, x, y) in candidates {
+        if corners.len() >= max_corners {
+            break;
+        }
+        let p = Point2::new(x as f32, y as f32);
+        if corners.iter().all(|c| c.distance(&p) >= min_distance) {
+            corners.push(p);
+        }
+    }
+
+    Ok(corners)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn good_features_to_track() -> Result<()> {
+        let mut image = Image::<f32, 1>::from_size_val(
+            ImageSize {
+                width: 40,
+                height: 30,
+            },
+            0.0,
+        )?;
+        image
+            .data
+            .slice_mut(ndarray::s![10..20, 12..30, ..])
+            .fill(1.0);
+
+        // a flat region has a null tensor, an edge a single strong direction
+        let tensor = super::structure_tensor(&image, 1.0)?;
+        assert_eq!(tensor.data[[2, 2, 0]], 0.0);
+        let edge = tensor.data.slice(ndarray::s![15, 12, ..]).to_vec();
+        assert!(edge[0] > 0.0 && edge[2].abs() < 1e-6);
+
+        let corners = super::good_features_to_track(&image, 10, 0.1, 5.0)?;
+        assert_eq!(corners.len(), 4);
+        for expected in [(12.0, 10.0), (29.0, 10.0), (12.0, 19.0), (29.0, 19.0)] {
+            assert!(corners
+                .iter()
+                .any(|c| (c.x - expected.0).abs() <= 1.5 && (c.y - expected.1).abs() <= 1.5));
+        }
+
+        // the corners are at least min_distance apart
+        let corners = super::good_features_to_track(&image, 10, 0.1, 15.0)?;
+        assert_eq!(corners.len(), 2);
+
+        assert!(super::structure_tensor(&image, 0.0).is_err());
+        assert!(super::good_features_to_track(&image, 10, 0.0, 5.0).is_err());
+        Ok(())
+    }
+}
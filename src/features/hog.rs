@@ -0,0 +1,272 @@
+use crate::image::{Image, ImageSize};
+use crate::tensor::{CpuAllocator, Tensor};
+use anyhow::Result;
+
+/// Clipping value of the L2-Hys block normalization.
+const L2_HYS_CLIP: f32 = 0.2;
+
+/// Compute the orientation histograms of the cells of an image.
+///
+/// The gradients are computed with centered differences and their unsigned orientation
+/// in [0, 180) degrees is linearly interpolated between the two closest bins.
+fn cell_histograms(
+    src: &Image<f32, 1>,
+    cell_size: usize,
+    bins: usize,
+    cells_x: usize,
+    cells_y: usize,
+) -> Vec<f32> {
+    let (width, height) = (src.width(), src.height());
+    let at = |x: isize, y: isize| -> f32 {
+        let x = x.clamp(0, width as isize - 1) as usize;
+        let y = y.clamp(0, height as isize - 1) as usize;
+        src.data[[y, x, 0]]
+    };
+
+    let bin_width = 180.0 / bins as f32;
+    let mut histograms = vec![0f32; cells_y * cells_x * bins];
+
+    for y in 0..cells_y * cell_size {
+        for x in 0..cells_x * cell_size {
+            let (xi, yi) = (x as isize, y as isize);
+            let gx = at(xi + 1, yi) - at(xi - 1, yi);
+            let gy = at(xi, yi + 1) - at(xi, yi - 1);
+
+            let magnitude = (gx * gx + gy * gy).sqrt();
+            if magnitude == 0.0 {
+                continue;
+            }
+            let orientation = gy.atan2(gx).to_degrees().rem_euclid(180.0);
+
+            // split the magnitude between the two closest bin centers
+            let pos = orientation / bin_width - 0.5;
+            let lower = pos.floor();
+            let frac = pos - lower;
+            let b0 = (lower as isize).rem_euclid(bins as isize) as usize;
+            let b1 = (b0 + 1) % bins;
+
+            let cell = ((y / cell_size) * cells_x + x / cell_size) * bins;
+            histograms[cell + b0] += magnitude * (1.0 - frac);
+            histograms[cell + b1] += magnitude * frac;
+        }
+    }
+
+    histograms
+}
+
+/// Render the cell histograms as oriented line segments.
+///
+/// Each bin is drawn as a line through the cell center along the edge direction, i.e.
+/// perpendicular to the gradient, with an intensity proportional to the bin value.
+fn render_histograms(
+    histograms: &[f32],
+    cell_size: usize,
+    bins: usize,
+    cells_x: usize,
+    cells_y: usize,
+) -> Result<Image<f32, 1>> {
+    let (width, height) = (cells_x * cell_size, cells_y * cell_size);
+    let mut canvas = vec![0f32; width * height];
+
+    let max = histograms.iter().cloned().fold(0.0, f32::max);
+    let bin_width = 180.0 / bins as f32;
+    let half = cell_size as f32 / 2.0;
+    let num_samples = 2 * cell_size;
+
+    for cy in 0..cells_y {
+        for cx in 0..cells_x {
+            let (center_x, center_y) = (
+                cx as f32 * cell_size as f32 + half,
+                cy as f32 * cell_size as f32 + half,
+            );
+            for b in 0..bins {
+                let value = histograms[(cy * cells_x + cx) * bins + b];
+                if value <= 0.0 {
+                    continue;
+                }
+                let angle = ((b as f32 + 0.5) * bin_width + 90.0).to_radians();
+                let (sin, cos) = angle.sin_cos();
+                for s in 0..=num_samples {
+                    let t = (s as f32 / num_samples as f32 - 0.5) * 2.0 * (half - 0.5);
+                    let x = (center_x - 0.5 + t * cos).round();
+                    let y = (center_y - 0.5 + t * sin).round();
+                    if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+                        continue;
+                    }
+                    let idx = y as usize * width + x as usize;
+                    canvas[idx] = canvas[idx].max(value / max);
+                }
+            }
+        }
+    }
+
+    Image::new(ImageSize { width, height }, canvas)
+}
+
+/// Compute the histogram of oriented gradients (HOG) descriptor of an image.
+///
+/// The image is divided in square cells where the gradient orientations are accumulated
+/// into histograms weighted by the gradient magnitude. The cells are then grouped in
+/// overlapping blocks, with a stride of one cell, and normalized with the L2-Hys scheme.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, 1).
+/// * `cell_size` - The size in pixels of the square cells.
+/// * `block_size` - The number of cells per side of the square blocks.
+/// * `bins` - The number of orientation bins over [0, 180) degrees.
+/// * `visualize` - Whether to render an image of the cell histograms.
+///
+/// # Returns
+///
+/// The descriptor with shape (blocks_y, blocks_x, block_size, block_size, bins) and,
+/// if requested, the visualization image with the size covered by the cells.
+///
+/// # Errors
+///
+/// Returns an error if any of the sizes is zero or the image is smaller than a block.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::features::hog;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 64,
+///         height: 128,
+///     },
+///     0.0,
+/// )
+/// .unwrap();
+///
+/// let (descriptor, visualization) = hog(&image, 8, 2, 9, false).unwrap();
+/// assert_eq!(descriptor.shape, [15, 7, 2, 2, 9]);
+/// assert_eq!(descriptor.numel(), 3780);
+/// assert!(visualization.is_none());
+/// ```
+pub fn hog(
+    src: &Image<f32, 1>,
+    cell_size: usize,
+    block_size: usize,
+    bins: usize,
+    visualize: bool,
+) -> Result<(Tensor<f32, 5>, Option<Image<f32, 1>>)> {
+    if cell_size == 0 || block_size == 0 || bins == 0 {
+        return Err(anyhow::anyhow!(
+            "The cell size, block size and number of bins must be positive."
+        ));
+    }
+
+    let (cells_x, cells_y) = (src.width() / cell_size, src.height() / cell_size);
+    if cells_x < block_size || cells_y < block_size {
+        return Err(anyhow::anyhow!(
+            "The image size {} is smaller than a block of {}x{} cells of {} pixels.",
+            src.size(),
+            block_size,
+            block_size,
+            cell_size
+        ));
+    }
+
+    let histograms = cell_histograms(src, cell_size, bins, cells_x, cells_y);
+
+    let (blocks_x, blocks_y) = (cells_x - block_size + 1, cells_y - block_size + 1);
+    let block_len = block_size * block_size * bins;
+    let mut descriptor = Vec::with_capacity(blocks_y * blocks_x * block_len);
+
+    for by in 0..blocks_y {
+        for bx in 0..blocks_x {
+            let mut block = Vec::with_capacity(block_len);
+            for cy in by..by + block_size {
+                for cx in bx..bx + block_size {
+                    let cell = (cy * cells_x + cx) * bins;
+                    block.extend_from_slice(&histograms[cell..cell + bins]);
+                }
+            }
+
+            // L2-Hys: normalize, clip and normalize again
+            for clip in [true, false] {
+                let norm = (block.iter().map(|v| v * v).sum::<f32>() + 1e-6).sqrt();
+                block.iter_mut().for_each(|v| {
+                    *v /= norm;
+                    if clip {
+                        *v = v.min(L2_HYS_CLIP);
+                    }
+                });
+            }
+
+            descriptor.extend(block);
+        }
+    }
+
+    let descriptor = Tensor::from_shape_vec(
+        [blocks_y, blocks_x, block_size, block_size, bins],
+        descriptor,
+        CpuAllocator,
+    )?;
+
+    let visualization = if visualize {
+        Some(render_histograms(
+            &histograms,
+            cell_size,
+            bins,
+            cells_x,
+            cells_y,
+        )?)
+    } else {
+        None
+    };
+
+    Ok((descriptor, visualization))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn hog_vertical_edges() -> Result<()> {
+        // vertical stripes: the gradients are horizontal, i.e. 0 degrees
+        let size = ImageSize {
+            width: 16,
+            height: 16,
+        };
+        let data = (0..size.width * size.height)
+            .map(|i| if (i % size.width) % 4 < 2 { 0.0 } else { 1.0 })
+            .collect();
+        let image = Image::<f32, 1>::new(size, data)?;
+
+        let (descriptor, visualization) = super::hog(&image, 8, 2, 9, true)?;
+        assert_eq!(descriptor.shape, [1, 1, 2, 2, 9]);
+
+        // the energy is split between the first and last bins, centered at 10 and 170 degrees
+        let cell = &descriptor.as_slice()[..9];
+        assert!((cell[0] - cell[8]).abs() < 1e-5);
+        assert!(cell[0] > 0.0);
+        assert!(cell[1..8].iter().all(|&v| v == 0.0));
+
+        // the edges are drawn as vertical lines
+        let visualization = visualization.unwrap();
+        assert_eq!(visualization.size(), size);
+        assert!(visualization.get_pixel(3, 0, 0)? > 0.0 || visualization.get_pixel(4, 0, 0)? > 0.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hog_too_small() -> Result<()> {
+        let image = Image::<f32, 1>::from_size_val(
+            ImageSize {
+                width: 15,
+                height: 32,
+            },
+            0.0,
+        )?;
+        assert!(super::hog(&image, 8, 2, 9, false).is_err());
+        assert!(super::hog(&image, 0, 2, 9, false).is_err());
+        Ok(())
+    }
+}
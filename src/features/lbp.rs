@@ -0,0 +1,266 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Build the lookup table from raw LBP codes to uniform pattern labels.
+///
+/// The uniform patterns, with at most two bitwise transitions in the circular code, get
+/// consecutive labels in increasing code order and all the other patterns share the last label.
+fn uniform_labels(points: usize) -> Vec<u8> {
+    let mask = (1u32 << points) - 1;
+    let non_uniform = (points * (points - 1) + 2) as u8;
+
+    let mut next_label = 0u8;
+    (0..1u32 << points)
+        .map(|code| {
+            // circular rotation by one bit to count the transitions
+            let rotated = ((code >> 1) | ((code & 1) << (points - 1))) & mask;
+            if (code ^ rotated).count_ones() <= 2 {
+                next_label += 1;
+                next_label - 1
+            } else {
+                non_uniform
+            }
+        })
+        .collect()
+}
+
+/// Get the number of labels of the uniform LBP with the given number of points.
+fn num_labels(points: usize) -> usize {
+    points * (points - 1) + 3
+}
+
+/// Compute the uniform local binary patterns of an image.
+///
+/// Each pixel is compared against `points` neighbours sampled with bilinear interpolation
+/// on a circle of the given radius. The resulting binary code is mapped to a uniform
+/// pattern label in the range [0, points * (points - 1) + 2], where the last label
+/// gathers all the non-uniform patterns.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, 1).
+/// * `radius` - The radius of the circle of neighbours in pixels.
+/// * `points` - The number of neighbours in the range [1, 16].
+///
+/// # Returns
+///
+/// The image of uniform pattern labels with shape (H, W, 1).
+///
+/// # Errors
+///
+/// Returns an error if the number of points is not in the range [1, 16] or the radius is not positive.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::features::lbp;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 5,
+///         height: 5,
+///     },
+///     10,
+/// )
+/// .unwrap();
+///
+/// // all the neighbours are equal to the center: the code is all ones
+/// let labels = lbp(&image, 1.0, 8).unwrap();
+/// assert!(labels.data.iter().all(|&l| l == 57));
+/// ```
+pub fn lbp(src: &Image<u8, 1>, radius: f32, points: usize) -> Result<Image<u8, 1>> {
+    if !(1..=16).contains(&points) {
+        return Err(anyhow::anyhow!(
+            "The number of points must be in the range [1, 16], got {}.",
+            points
+        ));
+    }
+
+    if radius <= 0.0 {
+        return Err(anyhow::anyhow!(
+            "The radius must be positive, got {}.",
+            radius
+        ));
+    }
+
+    let (width, height) = (src.width(), src.height());
+    let labels = uniform_labels(points);
+
+    // offsets of the neighbours on the circle, snapped to the grid when close to it
+    let snap = |v: f32| {
+        if (v - v.round()).abs() < 1e-5 {
+            v.round()
+        } else {
+            v
+        }
+    };
+    let offsets = (0..points)
+        .map(|p| {
+            let angle = 2.0 * std::f32::consts::PI * p as f32 / points as f32;
+            (snap(radius * angle.cos()), snap(-radius * angle.sin()))
+        })
+        .collect::<Vec<_>>();
+
+    let at = |x: isize, y: isize| -> f32 {
+        let x = x.clamp(0, width as isize - 1) as usize;
+        let y = y.clamp(0, height as isize - 1) as usize;
+        src.data[[y, x, 0]] as f32
+    };
+
+    let mut dst = Image::<u8, 1>::from_size_val(src.size(), 0)?;
+
+    for y in 0..height {
+        for x in 0..width {
+            let center = src.data[[y, x, 0]] as f32;
+
+            let mut code = 0usize;
+            for (p, &(dx, dy)) in offsets.iter().enumerate() {
+                // bilinear interpolation of the neighbour
+                let (sx, sy) = (x as f32 + dx, y as f32 + dy);
+                let (x0, y0) = (sx.floor(), sy.floor());
+                let (fx, fy) = (sx - x0, sy - y0);
+                let (x0, y0) = (x0 as isize, y0 as isize);
+                let value = at(x0, y0) * (1.0 - fx) * (1.0 - fy)
+                    + at(x0 + 1, y0) * fx * (1.0 - fy)
+                    + at(x0, y0 + 1) * (1.0 - fx) * fy
+                    + at(x0 + 1, y0 + 1) * fx * fy;
+
+                if value >= center - 1e-4 {
+                    code |= 1 << p;
+                }
+            }
+
+            dst.data[[y, x, 0]] = labels[code];
+        }
+    }
+
+    Ok(dst)
+}
+
+/// Compute the normalized histograms of uniform LBP labels over a grid of cells.
+///
+/// # Arguments
+///
+/// * `labels` - The uniform pattern labels computed by [`lbp`].
+/// * `points` - The number of points used to compute the labels.
+/// * `cell_size` - The size in pixels of the square cells.
+///
+/// # Returns
+///
+/// The concatenation of the histograms of the cells in row-major order. Each histogram
+/// has `points * (points - 1) + 3` bins and sums to one. The pixels of the partial cells
+/// at the right and bottom borders are ignored.
+///
+/// # Errors
+///
+/// Returns an error if the cell size is zero or larger than the image, or the number
+/// of points is not in the range [1, 16].
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::features::{lbp, lbp_histogram};
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 8,
+///         height: 8,
+///     },
+///     10,
+/// )
+/// .unwrap();
+///
+/// let labels = lbp(&image, 1.0, 8).unwrap();
+/// let histogram = lbp_histogram(&labels, 8, 4).unwrap();
+/// assert_eq!(histogram.len(), 4 * 59);
+/// ```
+pub fn lbp_histogram(labels: &Image<u8, 1>, points: usize, cell_size: usize) -> Result<Vec<f32>> {
+    if !(1..=16).contains(&points) {
+        return Err(anyhow::anyhow!(
+            "The number of points must be in the range [1, 16], got {}.",
+            points
+        ));
+    }
+
+    if cell_size == 0 || cell_size > labels.width() || cell_size > labels.height() {
+        return Err(anyhow::anyhow!(
+            "Invalid cell size {} for an image of size {}.",
+            cell_size,
+            labels.size()
+        ));
+    }
+
+    let bins = num_labels(points);
+    let (cells_x, cells_y) = (labels.width() / cell_size, labels.height() / cell_size);
+    let mut histogram = vec![0f32; cells_x * cells_y * bins];
+    let norm = 1.0 / (cell_size * cell_size) as f32;
+
+    for cy in 0..cells_y {
+        for cx in 0..cells_x {
+            let cell = &mut histogram[(cy * cells_x + cx) * bins..(cy * cells_x + cx + 1) * bins];
+            for y in cy * cell_size..(cy + 1) * cell_size {
+                for x in cx * cell_size..(cx + 1) * cell_size {
+                    let label = (labels.data[[y, x, 0]] as usize).min(bins - 1);
+                    cell[label] += norm;
+                }
+            }
+        }
+    }
+
+    Ok(histogram)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn uniform_labels_count() {
+        let labels = super::uniform_labels(8);
+        assert_eq!(labels.len(), 256);
+        assert_eq!(*labels.iter().max().unwrap(), 58);
+        // 58 uniform patterns
+        assert_eq!(labels.iter().filter(|&&l| l < 58).count(), 58);
+        assert_eq!(labels[0], 0);
+        assert_eq!(labels[0b0101_0101], 58);
+    }
+
+    #[test]
+    fn lbp_edge() -> Result<()> {
+        // a vertical edge: dark on the left and bright on the right
+        #[rustfmt::skip]
+        let data = vec![
+            0u8, 0, 255, 255,
+            0, 0, 255, 255,
+            0, 0, 255, 255,
+        ];
+        let image = Image::<u8, 1>::new(
+            ImageSize {
+                width: 4,
+                height: 3,
+            },
+            data,
+        )?;
+
+        let labels = super::lbp(&image, 1.0, 4)?;
+
+        // on the dark side of the edge only the right neighbour is brighter than
+        // the center while the others are equal
+        let lut = super::uniform_labels(4);
+        assert_eq!(labels.get_pixel(1, 1, 0)?, lut[0b1111]);
+        // on the bright side only the left neighbour is darker
+        assert_eq!(labels.get_pixel(2, 1, 0)?, lut[0b1011]);
+
+        let histogram = super::lbp_histogram(&labels, 4, 2)?;
+        assert_eq!(histogram.len(), 2 * 15);
+        assert!((histogram[..15].iter().sum::<f32>() - 1.0).abs() < 1e-6);
+
+        assert!(super::lbp(&image, 1.0, 17).is_err());
+        assert!(super::lbp_histogram(&labels, 4, 5).is_err());
+
+        Ok(())
+    }
+}
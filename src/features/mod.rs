@@ -0,0 +1,7 @@
+mod corners;
+mod hog;
+mod lbp;
+
+pub use corners::{good_features_to_track, structure_tensor};
+pub use hog::hog;
+pub use lbp::{lbp, lbp_histogram};
@@ -0,0 +1,349 @@
+use crate::tensor::{CpuAllocator, Tensor};
+use anyhow::Result;
+use rustfft::{num_complex::Complex, FftDirection, FftPlanner};
+
+/// Frequency-domain filters applied by [`frequency_filter`].
+///
+/// The cutoffs are given in cycles per pixel, in the range (0, 0.5].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrequencyFilter {
+    /// Keep the frequencies below the cutoff.
+    IdealLowPass(f32),
+    /// Keep the frequencies above the cutoff.
+    IdealHighPass(f32),
+    /// Attenuate the frequencies with a gaussian of the given standard deviation.
+    GaussianLowPass(f32),
+    /// Attenuate the low frequencies with the complement of a gaussian.
+    GaussianHighPass(f32),
+}
+
+impl FrequencyFilter {
+    /// Get the gain of the filter at a frequency radius in cycles per pixel.
+    fn gain(&self, radius: f32) -> f32 {
+        match *self {
+            FrequencyFilter::IdealLowPass(cutoff) => {
+                if radius <= cutoff {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            FrequencyFilter::IdealHighPass(cutoff) => {
+                if radius > cutoff {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            FrequencyFilter::GaussianLowPass(sigma) => {
+                (-(radius * radius) / (2.0 * sigma * sigma)).exp()
+            }
+            FrequencyFilter::GaussianHighPass(sigma) => {
+                1.0 - (-(radius * radius) / (2.0 * sigma * sigma)).exp()
+            }
+        }
+    }
+}
+
+/// Run the 2D FFT in place over a row-major buffer of complex values.
+fn fft2_inplace(data: &mut [Complex<f32>], height: usize, width: usize, direction: FftDirection) {
+    let mut planner = FftPlanner::<f32>::new();
+
+    // the rows are contiguous and are transformed in a single call
+    planner.plan_fft(width, direction).process(data);
+
+    // transpose to transform the columns as rows
+    let mut transposed = vec![Complex::default(); data.len()];
+    for y in 0..height {
+        for x in 0..width {
+            transposed[x * height + y] = data[y * width + x];
+        }
+    }
+    planner.plan_fft(height, direction).process(&mut transposed);
+    for y in 0..height {
+        for x in 0..width {
+            data[y * width + x] = transposed[x * height + y];
+        }
+    }
+}
+
+/// Pack complex values into a tensor with shape (H, W, 2).
+fn to_tensor(data: &[Complex<f32>], height: usize, width: usize) -> Result<Tensor<f32, 3>> {
+    let data = data.iter().flat_map(|c| [c.re, c.im]).collect();
+    Ok(Tensor::from_shape_vec(
+        [height, width, 2],
+        data,
+        CpuAllocator,
+    )?)
+}
+
+/// Unpack a tensor with shape (H, W, 2) into complex values.
+fn from_tensor(src: &Tensor<f32, 3>) -> Result<Vec<Complex<f32>>> {
+    if src.shape[2] != 2 {
+        return Err(anyhow::anyhow!(
+            "Expected a complex tensor with shape (H, W, 2), got {:?}.",
+            src.shape
+        ));
+    }
+    Ok(src
+        .as_slice()
+        .chunks_exact(2)
+        .map(|c| Complex::new(c[0], c[1]))
+        .collect())
+}
+
+/// Compute the 2D discrete Fourier transform of a real signal.
+///
+/// # Arguments
+///
+/// * `src` - The input signal with shape (H, W).
+///
+/// # Returns
+///
+/// The unnormalized spectrum with shape (H, W, 2), holding the real and imaginary parts
+/// in the last dimension. The zero frequency is at the origin.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::fft::fft2;
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// let signal = Tensor::<f32, 2>::from_shape_val([2, 2], 1.0, CpuAllocator).unwrap();
+/// let spectrum = fft2(&signal).unwrap();
+/// assert_eq!(spectrum.shape, [2, 2, 2]);
+/// assert_eq!(spectrum.as_slice(), &[4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+/// ```
+pub fn fft2(src: &Tensor<f32, 2>) -> Result<Tensor<f32, 3>> {
+    let [height, width] = src.shape;
+    let mut data = src
+        .as_slice()
+        .iter()
+        .map(|&v| Complex::new(v, 0.0))
+        .collect::<Vec<_>>();
+
+    if !data.is_empty() {
+        fft2_inplace(&mut data, height, width, FftDirection::Forward);
+    }
+
+    to_tensor(&data, height, width)
+}
+
+/// Compute the inverse 2D discrete Fourier transform of a spectrum.
+///
+/// # Arguments
+///
+/// * `src` - The spectrum with shape (H, W, 2), as returned by [`fft2`].
+///
+/// # Returns
+///
+/// The complex signal with shape (H, W, 2), normalized by the number of elements so
+/// that `ifft2(fft2(x))` recovers `x` in the real part.
+///
+/// # Errors
+///
+/// Returns an error if the last dimension of the tensor is not 2.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::fft::{fft2, ifft2};
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// let signal =
+///     Tensor::<f32, 2>::from_shape_vec([1, 4], vec![1.0, 2.0, 3.0, 4.0], CpuAllocator).unwrap();
+/// let restored = ifft2(&fft2(&signal).unwrap()).unwrap();
+/// assert!((restored.as_slice()[4] - 3.0).abs() < 1e-5);
+/// ```
+pub fn ifft2(src: &Tensor<f32, 3>) -> Result<Tensor<f32, 3>> {
+    let [height, width, _] = src.shape;
+    let mut data = from_tensor(src)?;
+
+    if !data.is_empty() {
+        fft2_inplace(&mut data, height, width, FftDirection::Inverse);
+        let norm = 1.0 / data.len() as f32;
+        data.iter_mut().for_each(|c| *c *= norm);
+    }
+
+    to_tensor(&data, height, width)
+}
+
+/// Shift the zero frequency of a spectrum to the center.
+///
+/// The quadrants of the first two dimensions are swapped so that the element at the
+/// origin moves to (H / 2, W / 2).
+///
+/// # Arguments
+///
+/// * `src` - The spectrum with shape (H, W, C).
+///
+/// # Returns
+///
+/// The shifted spectrum with the same shape.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::fft::fftshift;
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// let t = Tensor::<f32, 3>::from_shape_vec([1, 3, 1], vec![0.0, 1.0, 2.0], CpuAllocator)
+///     .unwrap();
+/// let shifted = fftshift(&t).unwrap();
+/// assert_eq!(shifted.as_slice(), &[2.0, 0.0, 1.0]);
+/// ```
+pub fn fftshift(src: &Tensor<f32, 3>) -> Result<Tensor<f32, 3>> {
+    roll(src, src.shape[0] / 2, src.shape[1] / 2)
+}
+
+/// Undo [`fftshift`], moving the center of a spectrum back to the origin.
+///
+/// # Arguments
+///
+/// * `src` - The centered spectrum with shape (H, W, C).
+///
+/// # Returns
+///
+/// The spectrum with the zero frequency at the origin.
+pub fn ifftshift(src: &Tensor<f32, 3>) -> Result<Tensor<f32, 3>> {
+    roll(src, src.shape[0].div_ceil(2), src.shape[1].div_ceil(2))
+}
+
+/// Circularly shift the first two dimensions of a tensor.
+fn roll(src: &Tensor<f32, 3>, shift_y: usize, shift_x: usize) -> Result<Tensor<f32, 3>> {
+    let [height, width, channels] = src.shape;
+    let input = src.as_slice();
+    let mut data = vec![0f32; input.len()];
+
+    for y in 0..height {
+        let ny = (y + shift_y) % height;
+        for x in 0..width {
+            let nx = (x + shift_x) % width;
+            let (src_idx, dst_idx) = ((y * width + x) * channels, (ny * width + nx) * channels);
+            data[dst_idx..dst_idx + channels].copy_from_slice(&input[src_idx..src_idx + channels]);
+        }
+    }
+
+    Ok(Tensor::from_shape_vec(src.shape, data, CpuAllocator)?)
+}
+
+/// Filter a real signal in the frequency domain.
+///
+/// The spectrum of the signal is multiplied by the radial gain of the filter and
+/// transformed back to the spatial domain.
+///
+/// # Arguments
+///
+/// * `src` - The input signal with shape (H, W).
+/// * `filter` - The frequency filter to apply.
+///
+/// # Returns
+///
+/// The filtered signal with shape (H, W).
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::fft::{frequency_filter, FrequencyFilter};
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// let signal =
+///     Tensor::<f32, 2>::from_shape_vec([1, 4], vec![1.0, 3.0, 1.0, 3.0], CpuAllocator).unwrap();
+///
+/// // removing the high frequencies leaves the mean value
+/// let filtered = frequency_filter(&signal, FrequencyFilter::IdealLowPass(0.1)).unwrap();
+/// assert!(filtered.as_slice().iter().all(|v| (v - 2.0).abs() < 1e-5));
+/// ```
+pub fn frequency_filter(src: &Tensor<f32, 2>, filter: FrequencyFilter) -> Result<Tensor<f32, 2>> {
+    let [height, width] = src.shape;
+    let mut data = src
+        .as_slice()
+        .iter()
+        .map(|&v| Complex::new(v, 0.0))
+        .collect::<Vec<_>>();
+
+    if !data.is_empty() {
+        fft2_inplace(&mut data, height, width, FftDirection::Forward);
+
+        // signed frequency in cycles per pixel of an index along a dimension
+        let frequency = |i: usize, n: usize| {
+            let i = if i <= n / 2 {
+                i as f32
+            } else {
+                i as f32 - n as f32
+            };
+            i / n as f32
+        };
+
+        for y in 0..height {
+            let fy = frequency(y, height);
+            for x in 0..width {
+                let fx = frequency(x, width);
+                data[y * width + x] *= filter.gain((fx * fx + fy * fy).sqrt());
+            }
+        }
+
+        fft2_inplace(&mut data, height, width, FftDirection::Inverse);
+    }
+
+    let norm = 1.0 / data.len().max(1) as f32;
+    let data = data.iter().map(|c| c.re * norm).collect();
+
+    Ok(Tensor::from_shape_vec(src.shape, data, CpuAllocator)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tensor::{CpuAllocator, Tensor};
+    use anyhow::Result;
+
+    #[test]
+    fn fft2_roundtrip() -> Result<()> {
+        let data = (0..12).map(|x| x as f32).collect::<Vec<_>>();
+        let signal = Tensor::<f32, 2>::from_shape_vec([3, 4], data.clone(), CpuAllocator)?;
+
+        let spectrum = super::fft2(&signal)?;
+        assert_eq!(spectrum.shape, [3, 4, 2]);
+        // the zero frequency is the sum of the signal
+        assert!((spectrum.as_slice()[0] - 66.0).abs() < 1e-4);
+
+        let restored = super::ifft2(&spectrum)?;
+        for (i, v) in data.iter().enumerate() {
+            assert!((restored.as_slice()[2 * i] - v).abs() < 1e-4);
+            assert!(restored.as_slice()[2 * i + 1].abs() < 1e-4);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fftshift_roundtrip() -> Result<()> {
+        let data = (0..15).map(|x| x as f32).collect::<Vec<_>>();
+        let t = Tensor::<f32, 3>::from_shape_vec([3, 5, 1], data.clone(), CpuAllocator)?;
+
+        let shifted = super::fftshift(&t)?;
+        // the origin moves to the center
+        assert_eq!(shifted.as_slice()[5 + 2], 0.0);
+
+        let restored = super::ifftshift(&shifted)?;
+        assert_eq!(restored.as_slice(), data.as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn frequency_filter_complementary() -> Result<()> {
+        let data = (0..64).map(|x| ((x * 37) % 11) as f32).collect::<Vec<_>>();
+        let signal = Tensor::<f32, 2>::from_shape_vec([8, 8], data.clone(), CpuAllocator)?;
+
+        let low = super::frequency_filter(&signal, super::FrequencyFilter::GaussianLowPass(0.1))?;
+        let high = super::frequency_filter(&signal, super::FrequencyFilter::GaussianHighPass(0.1))?;
+
+        // the low and high pass filters add up to the identity
+        for (i, v) in data.iter().enumerate() {
+            assert!((low.as_slice()[i] + high.as_slice()[i] - v).abs() < 1e-4);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Correlate an image with a 2D kernel.
+///
+/// The kernel is centered on each pixel and the image borders are replicated.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `kernel` - The kernel with shape (KH, KW) and odd sizes.
+///
+/// # Returns
+///
+/// The filtered image with the same size as the input.
+///
+/// # Errors
+///
+/// Returns an error if the kernel has an even size.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::filters::filter2d;
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![0.0, 3.0, 6.0],
+/// )
+/// .unwrap();
+///
+/// let kernel = ndarray::Array2::from_elem((1, 3), 1.0 / 3.0);
+/// let filtered = filter2d(&image, &kernel).unwrap();
+/// assert_eq!(filtered.get_pixel(1, 0, 0).unwrap(), 3.0);
+/// ```
+pub fn filter2d<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    kernel: &ndarray::Array2<f32>,
+) -> Result<Image<f32, CHANNELS>> {
+    let (kh, kw) = kernel.dim();
+    if kh % 2 == 0 || kw % 2 == 0 {
+        return Err(anyhow::anyhow!(
+            "The kernel size ({}, {}) must be odd.",
+            kh,
+            kw
+        ));
+    }
+
+    let (width, height) = (src.width() as isize, src.height() as isize);
+    let (rh, rw) = ((kh / 2) as isize, (kw / 2) as isize);
+
+    let mut dst = Image::<f32, CHANNELS>::from_size_val(src.size(), 0.0)?;
+
+    ndarray::Zip::indexed(dst.data.rows_mut()).par_for_each(|(y, x), mut out| {
+        for ((ky, kx), &k) in kernel.indexed_iter() {
+            let sy = (y as isize + ky as isize - rh).clamp(0, height - 1) as usize;
+            let sx = (x as isize + kx as isize - rw).clamp(0, width - 1) as usize;
+            for (c, o) in out.iter_mut().enumerate() {
+                *o += k * src.data[[sy, sx, c]];
+            }
+        }
+    });
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn filter2d_identity_and_border() -> Result<()> {
+        let image = Image::<f32, 2>::new(
+            ImageSize {
+                width: 2,
+                height: 2,
+            },
+            vec![1.0, 10.0, 2.0, 20.0, 3.0, 30.0, 4.0, 40.0],
+        )?;
+
+        let mut identity = ndarray::Array2::zeros((3, 3));
+        identity[[1, 1]] = 1.0;
+        let filtered = super::filter2d(&image, &identity)?;
+        assert_eq!(filtered.data, image.data);
+
+        // shift the image to the left, replicating the right border
+        let mut shift = ndarray::Array2::zeros((1, 3));
+        shift[[0, 2]] = 1.0;
+        let filtered = super::filter2d(&image, &shift)?;
+        assert_eq!(filtered.get_pixel(0, 0, 1)?, 20.0);
+        assert_eq!(filtered.get_pixel(1, 1, 0)?, 4.0);
+
+        assert!(super::filter2d(&image, &ndarray::Array2::zeros((2, 3))).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+use crate::image::{Image, ImageSize};
+use anyhow::Result;
+
+/// Ratio between the gaussian envelope and the wavelength for a bandwidth of one octave.
+const SIGMA_PER_WAVELENGTH: f32 = 0.56;
+
+/// Aspect ratio of the kernels of the filter bank.
+const BANK_GAMMA: f32 = 0.5;
+
+/// Create a Gabor kernel.
+///
+/// The kernel is a sinusoidal plane wave modulated by a gaussian envelope:
+///
+/// $ g(x, y) = \exp(-\frac{x'^2 + \gamma^2 y'^2}{2 \sigma^2}) \cos(2 \pi \frac{x'}{\lambda} + \psi) $
+///
+/// where $x' = x \cos\theta + y \sin\theta$ and $y' = -x \sin\theta + y \cos\theta$.
+/// The kernel size is chosen to cover three standard deviations of the envelope.
+///
+/// # Arguments
+///
+/// * `sigma` - The standard deviation of the gaussian envelope.
+/// * `theta` - The orientation of the normal to the stripes in radians.
+/// * `lambda` - The wavelength of the sinusoid in pixels.
+/// * `gamma` - The spatial aspect ratio of the envelope.
+/// * `psi` - The phase offset of the sinusoid in radians.
+///
+/// # Returns
+///
+/// The kernel with shape (K, K) where K is odd.
+///
+/// # Errors
+///
+/// Returns an error if `sigma`, `lambda` or `gamma` are not positive.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::filters::gabor_kernel;
+///
+/// let kernel = gabor_kernel(2.0, 0.0, 4.0, 1.0, 0.0).unwrap();
+/// assert_eq!(kernel.dim(), (13, 13));
+/// assert_eq!(kernel[[6, 6]], 1.0);
+/// ```
+pub fn gabor_kernel(
+    sigma: f32,
+    theta: f32,
+    lambda: f32,
+    gamma: f32,
+    psi: f32,
+) -> Result<ndarray::Array2<f32>> {
+    if sigma <= 0.0 || lambda <= 0.0 || gamma <= 0.0 {
+        return Err(anyhow::anyhow!(
+            "sigma ({}), lambda ({}) and gamma ({}) must be positive.",
+            sigma,
+            lambda,
+            gamma
+        ));
+    }
+
+    let half = (3.0 * sigma * gamma.recip().max(1.0)).ceil() as isize;
+    let size = (2 * half + 1) as usize;
+    let (sin, cos) = theta.sin_cos();
+
+    let kernel = ndarray::Array2::from_shape_fn((size, size), |(row, col)| {
+        let x = (col as isize - half) as f32;
+        let y = (row as isize - half) as f32;
+        let xr = x * cos + y * sin;
+        let yr = -x * sin + y * cos;
+        let envelope = (-(xr * xr + gamma * gamma * yr * yr) / (2.0 * sigma * sigma)).exp();
+        envelope * (2.0 * std::f32::consts::PI * xr / lambda + psi).cos()
+    });
+
+    Ok(kernel)
+}
+
+/// Create a bank of Gabor kernels at several orientations and scales.
+///
+/// The orientations are evenly spaced over [0, pi). Each scale is the wavelength of the
+/// kernels, with a gaussian envelope matching a bandwidth of one octave.
+///
+/// # Arguments
+///
+/// * `orientations` - The number of orientations.
+/// * `scales` - The wavelengths of the kernels in pixels.
+///
+/// # Returns
+///
+/// The kernels ordered by scale and then by orientation.
+///
+/// # Errors
+///
+/// Returns an error if there are no orientations or a scale is not positive.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::filters::gabor_filter_bank;
+///
+/// let bank = gabor_filter_bank(4, &[4.0, 8.0]).unwrap();
+/// assert_eq!(bank.len(), 8);
+/// ```
+pub fn gabor_filter_bank(orientations: usize, scales: &[f32]) -> Result<Vec<ndarray::Array2<f32>>> {
+    if orientations == 0 {
+        return Err(anyhow::anyhow!(
+            "The number of orientations must be positive."
+        ));
+    }
+
+    let mut bank = Vec::with_capacity(orientations * scales.len());
+    for &lambda in scales.iter() {
+        for i in 0..orientations {
+            let theta = std::f32::consts::PI * i as f32 / orientations as f32;
+            bank.push(gabor_kernel(
+                SIGMA_PER_WAVELENGTH * lambda,
+                theta,
+                lambda,
+                BANK_GAMMA,
+                0.0,
+            )?);
+        }
+    }
+
+    Ok(bank)
+}
+
+/// Apply a bank of filters to an image in a single parallel pass.
+///
+/// The responses of all the kernels are computed together for each pixel, so the image
+/// is traversed a single time.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, 1).
+/// * `bank` - The kernels with odd sizes, e.g. from [`gabor_filter_bank`].
+///
+/// # Returns
+///
+/// The response of each kernel, with the same size as the input, using replicated borders.
+///
+/// # Errors
+///
+/// Returns an error if a kernel has an even size.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::filters::{apply_filter_bank, gabor_filter_bank};
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 16,
+///         height: 16,
+///     },
+///     0.5,
+/// )
+/// .unwrap();
+///
+/// let bank = gabor_filter_bank(4, &[4.0]).unwrap();
+/// let responses = apply_filter_bank(&image, &bank).unwrap();
+/// assert_eq!(responses.len(), 4);
+/// assert_eq!(responses[0].size(), image.size());
+/// ```
+pub fn apply_filter_bank(
+    src: &Image<f32, 1>,
+    bank: &[ndarray::Array2<f32>],
+) -> Result<Vec<Image<f32, 1>>> {
+    if let Some(kernel) = bank
+        .iter()
+        .find(|k| k.nrows() % 2 == 0 || k.ncols() % 2 == 0)
+    {
+        return Err(anyhow::anyhow!(
+            "The kernel size {:?} must be odd.",
+            kernel.dim()
+        ));
+    }
+
+    let (width, height) = (src.width(), src.height());
+    let (w, h) = (width as isize, height as isize);
+
+    // responses of all the kernels stacked along the last axis
+    let mut responses = ndarray::Array3::<f32>::zeros((height, width, bank.len()));
+
+    ndarray::Zip::indexed(responses.rows_mut()).par_for_each(|(y, x), mut out| {
+        for (o, kernel) in out.iter_mut().zip(bank.iter()) {
+            let (rh, rw) = ((kernel.nrows() / 2) as isize, (kernel.ncols() / 2) as isize);
+            *o = kernel
+                .indexed_iter()
+                .map(|((ky, kx), &k)| {
+                    let sy = (y as isize + ky as isize - rh).clamp(0, h - 1) as usize;
+                    let sx = (x as isize + kx as isize - rw).clamp(0, w - 1) as usize;
+                    k * src.data[[sy, sx, 0]]
+                })
+                .sum();
+        }
+    });
+
+    (0..bank.len())
+        .map(|i| {
+            let data = responses
+                .slice(ndarray::s![.., .., i])
+                .iter()
+                .cloned()
+                .collect();
+            Image::new(ImageSize { width, height }, data)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn gabor_kernel_symmetry() -> Result<()> {
+        let kernel = super::gabor_kernel(3.0, 0.3, 6.0, 0.5, 0.0)?;
+        let (kh, kw) = kernel.dim();
+        assert_eq!(kh % 2, 1);
+        assert_eq!(kh, kw);
+
+        // the even kernel is symmetric with respect to its center
+        for ((y, x), &v) in kernel.indexed_iter() {
+            assert!((v - kernel[[kh - 1 - y, kw - 1 - x]]).abs() < 1e-6);
+        }
+
+        assert!(super::gabor_kernel(0.0, 0.0, 4.0, 1.0, 0.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn gabor_bank_orientation() -> Result<()> {
+        // vertical stripes with a period of 8 pixels
+        let size = ImageSize {
+            width: 32,
+            height: 32,
+        };
+        let data = (0..size.width * size.height)
+            .map(|i| (2.0 * std::f32::consts::PI * (i % size.width) as f32 / 8.0).cos())
+            .collect();
+        let image = Image::<f32, 1>::new(size, data)?;
+
+        // the orientations are 0 and 90 degrees
+        let bank = super::gabor_filter_bank(2, &[8.0])?;
+        let responses = super::apply_filter_bank(&image, &bank)?;
+
+        // the kernel matching the stripes responds the most in the center of the image
+        let horizontal = responses[0].get_pixel(16, 16, 0)?.abs();
+        let vertical = responses[1].get_pixel(16, 16, 0)?.abs();
+        assert!(horizontal > 10.0 * vertical);
+
+        Ok(())
+    }
+}
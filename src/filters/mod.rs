@@ -0,0 +1,5 @@
+mod filter;
+mod gabor;
+
+pub use filter::filter2d;
+pub use gabor::{apply_filter_bank, gabor_filter_bank, gabor_kernel};
@@ -0,0 +1,385 @@
+use super::linalg::{determinant, svd};
+use super::ransac::{ransac, RansacParams};
+use super::Point2;
+use crate::calibration::{CameraExtrinsic, CameraIntrinsic};
+use anyhow::Result;
+
+/// A 3x3 matrix in row-major order.
+pub type Matrix3 = [[f64; 3]; 3];
+
+fn to_flat(m: &Matrix3) -> Vec<f64> {
+    m.iter().flatten().copied().collect()
+}
+
+fn from_flat(m: &[f64]) -> Matrix3 {
+    [[m[0], m[1], m[2]], [m[3], m[4], m[5]], [m[6], m[7], m[8]]]
+}
+
+fn transpose(m: &Matrix3) -> Matrix3 {
+    [
+        [m[0][0], m[1][0], m[2][0]],
+        [m[0][1], m[1][1], m[2][1]],
+        [m[0][2], m[1][2], m[2][2]],
+    ]
+}
+
+fn matmul(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut c = [[0.0; 3]; 3];
+    for (i, row) in c.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    c
+}
+
+/// Replace the singular values of a matrix, keeping its singular vectors.
+fn with_singular_values<F>(m: &Matrix3, f: F) -> Matrix3
+where
+    F: Fn(&[f64]) -> [f64; 3],
+{
+    let (u, s, v) = svd(&to_flat(m), 3);
+    let s = f(&s);
+
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = (0..3).map(|k| u[i * 3 + k] * s[k] * v[j * 3 + k]).sum();
+        }
+    }
+    out
+}
+
+/// Compute the similarity moving the points to zero mean and a mean distance of
+/// sqrt(2) from the origin, as recommended by Hartley for the 8-point algorithm.
+fn normalize_points(points: &[Point2], indices: &[usize]) -> Option<Matrix3> {
+    let n = indices.len() as f64;
+    let (mx, my) = indices.iter().fold((0.0, 0.0), |(sx, sy), &i| {
+        (sx + points[i].x as f64 / n, sy + points[i].y as f64 / n)
+    });
+    let dist = indices
+        .iter()
+        .map(|&i| (points[i].x as f64 - mx).hypot(points[i].y as f64 - my))
+        .sum::<f64>()
+        / n;
+    if dist <= f64::EPSILON {
+        return None;
+    }
+
+    let s = std::f64::consts::SQRT_2 / dist;
+    Some([[s, 0.0, -s * mx], [0.0, s, -s * my], [0.0, 0.0, 1.0]])
+}
+
+/// Fit a fundamental matrix to at least 8 point pairs with the normalized 8-point
+/// algorithm.
+///
+/// # Returns
+///
+/// The rank 2 matrix with a unit Frobenius norm, or `None` if the points are degenerate.
+fn fit_fundamental(points1: &[Point2], points2: &[Point2], indices: &[usize]) -> Option<Matrix3> {
+    if indices.len() < 8 {
+        return None;
+    }
+    let t1 = normalize_points(points1, indices)?;
+    let t2 = normalize_points(points2, indices)?;
+
+    // accumulate the normal matrix A^T * A of the constraints x2^T * F * x1 = 0
+    let mut ata = vec![0.0; 81];
+    for &i in indices {
+        let (p1, p2) = (&points1[i], &points2[i]);
+        let x1 = t1[0][0] * p1.x as f64 + t1[0][2];
+        let y1 = t1[1][1] * p1.y as f64 + t1[1][2];
+        let x2 = t2[0][0] * p2.x as f64 + t2[0][2];
+        let y2 = t2[1][1] * p2.y as f64 + t2[1][2];
+
+        let row = [x2 * x1, x2 * y1, x2, y2 * x1, y2 * y1, y2, x1, y1, 1.0];
+        for (r, a) in row.iter().enumerate() {
+            for (c, b) in row.iter().enumerate() {
+                ata[r * 9 + c] += a * b;
+            }
+        }
+    }
+
+    // the solution is the singular vector of the smallest singular value
+    let (_, _, v) = svd(&ata, 9);
+    let f = from_flat(&(0..9).map(|i| v[i * 9 + 8]).collect::<Vec<_>>());
+
+    // enforce the rank 2 and undo the normalization
+    let f = with_singular_values(&f, |s| [s[0], s[1], 0.0]);
+    let f = matmul(&matmul(&transpose(&t2), &f), &t1);
+
+    let norm = f.iter().flatten().map(|x| x * x).sum::<f64>().sqrt();
+    if norm <= f64::EPSILON {
+        return None;
+    }
+    Some(f.map(|row| row.map(|x| x / norm)))
+}
+
+/// Compute the Sampson distance of a point pair to the epipolar constraint, a first
+/// order approximation of the reprojection error.
+fn sampson_distance(f: &Matrix3, p1: &Point2, p2: &Point2) -> f32 {
+    let x1 = [p1.x as f64, p1.y as f64, 1.0];
+    let x2 = [p2.x as f64, p2.y as f64, 1.0];
+
+    let fx1 = (0..3).map(|i| (0..3).map(|j| f[i][j] * x1[j]).sum::<f64>());
+    let fx1 = fx1.collect::<Vec<_>>();
+    let ftx2 = (0..3)
+        .map(|j| (0..3).map(|i| f[i][j] * x2[i]).sum::<f64>())
+        .collect::<Vec<_>>();
+    let residual = (0..3).map(|i| x2[i] * fx1[i]).sum::<f64>();
+
+    let denominator = fx1[0].powi(2) + fx1[1].powi(2) + ftx2[0].powi(2) + ftx2[1].powi(2);
+    if denominator <= f64::EPSILON {
+        return f32::INFINITY;
+    }
+    (residual.abs() / denominator.sqrt()) as f32
+}
+
+/// Estimates the fundamental matrix between two views with RANSAC.
+///
+/// The matrix is estimated with the normalized 8-point algorithm on random samples,
+/// keeping the one with the most pairs within the threshold of Sampson distance, and
+/// refined on its inliers.
+///
+/// # Arguments
+///
+/// * `points1` - The points in the first image.
+/// * `points2` - The corresponding points in the second image.
+/// * `params` - The RANSAC parameters, with the threshold in pixels.
+///
+/// # Returns
+///
+/// The fundamental matrix F such that `x2^T * F * x1 = 0`, with a unit Frobenius norm,
+/// and the mask of the inlier pairs.
+///
+/// # Errors
+///
+/// Returns an error if the point sets have different lengths, fewer than eight pairs
+/// or only degenerate samples.
+pub fn find_fundamental(
+    points1: &[Point2],
+    points2: &[Point2],
+    params: &RansacParams,
+) -> Result<(Matrix3, Vec<bool>)> {
+    ransac(
+        points1,
+        points2,
+        params,
+        8,
+        fit_fundamental,
+        sampson_distance,
+    )
+}
+
+/// Estimates the essential matrix between two views of the same camera with RANSAC.
+///
+/// The points are normalized with the intrinsic parameters and the matrices of the
+/// 8-point algorithm are projected to the essential matrices, with two equal singular
+/// values.
+///
+/// # Arguments
+///
+/// * `points1` - The points in the first image.
+/// * `points2` - The corresponding points in the second image.
+/// * `intrinsic` - The intrinsic parameters of the camera.
+/// * `params` - The RANSAC parameters, with the threshold in pixels.
+///
+/// # Returns
+///
+/// The essential matrix E such that `x2^T * E * x1 = 0` for normalized points, with
+/// the singular values (1, 1, 0), and the mask of the inlier pairs.
+///
+/// # Errors
+///
+/// Returns an error if the point sets have different lengths, fewer than eight pairs
+/// or only degenerate samples.
+pub fn find_essential(
+    points1: &[Point2],
+    points2: &[Point2],
+    intrinsic: &CameraIntrinsic,
+    params: &RansacParams,
+) -> Result<(Matrix3, Vec<bool>)> {
+    let normalize = |p: &Point2| {
+        Point2::new(
+            ((p.x as f64 - intrinsic.cx) / intrinsic.fx) as f32,
+            ((p.y as f64 - intrinsic.cy) / intrinsic.fy) as f32,
+        )
+    };
+    let points1 = points1.iter().map(normalize).collect::<Vec<_>>();
+    let points2 = points2.iter().map(normalize).collect::<Vec<_>>();
+
+    // the threshold is given in pixels
+    let focal = (intrinsic.fx + intrinsic.fy) / 2.0;
+    let params = RansacParams {
+        threshold: (params.threshold as f64 / focal) as f32,
+        ..*params
+    };
+
+    let fit = |p1: &[Point2], p2: &[Point2], indices: &[usize]| {
+        fit_fundamental(p1, p2, indices).map(|e| with_singular_values(&e, |_| [1.0, 1.0, 0.0]))
+    };
+
+    ransac(&points1, &points2, &params, 8, fit, sampson_distance)
+}
+
+/// Decomposes an essential matrix into the relative poses of the second camera.
+///
+/// The decomposition has four solutions, combining two rotations with the two
+/// directions of the translation. The physically valid one places the triangulated
+/// points in front of both cameras.
+///
+/// # Arguments
+///
+/// * `e` - The essential matrix.
+///
+/// # Returns
+///
+/// The four candidate poses, with a unit translation.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::epipolar::decompose_essential;
+///
+/// // a pure translation along the x axis
+/// let e = [[0.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]];
+///
+/// let poses = decompose_essential(&e);
+/// let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+/// assert!(poses
+///     .iter()
+///     .any(|pose| pose.rotation == identity && pose.translation == [1.0, 0.0, 0.0]));
+/// ```
+pub fn decompose_essential(e: &Matrix3) -> [CameraExtrinsic; 4] {
+    let (u, _, v) = svd(&to_flat(e), 3);
+
+    // the rotations need proper orthogonal matrices, E is defined up to the sign
+    let proper = |m: Vec<f64>| {
+        if determinant(m.clone(), 3) < 0.0 {
+            m.iter().map(|x| -x).collect::<Vec<_>>()
+        } else {
+            m
+        }
+    };
+    let (u, v) = (from_flat(&proper(u)), from_flat(&proper(v)));
+
+    let w = [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+    let r1 = matmul(&matmul(&u, &w), &transpose(&v));
+    let r2 = matmul(&matmul(&u, &transpose(&w)), &transpose(&v));
+    let t = [u[0][2], u[1][2], u[2][2]];
+    let minus_t = t.map(|x| -x);
+
+    [(r1, t), (r1, minus_t), (r2, t), (r2, minus_t)].map(|(rotation, translation)| {
+        CameraExtrinsic {
+            rotation,
+            translation,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix3;
+    use crate::calibration::CameraIntrinsic;
+    use crate::geometry::{Point2, RansacParams};
+    use anyhow::Result;
+
+    struct Scene {
+        intrinsic: CameraIntrinsic,
+        rotation: Matrix3,
+        translation: [f64; 3],
+        points1: Vec<Point2>,
+        points2: Vec<Point2>,
+    }
+
+    fn scene() -> Scene {
+        let intrinsic = CameraIntrinsic {
+            fx: 500.0,
+            fy: 520.0,
+            cx: 320.0,
+            cy: 240.0,
+        };
+        let (s, c) = 10f64.to_radians().sin_cos();
+        let rotation = [[c, 0.0, s], [0.0, 1.0, 0.0], [-s, 0.0, c]];
+        let translation = [1.0, 0.1, 0.2];
+
+        let project = |x: [f64; 3]| {
+            Point2::new(
+                (intrinsic.fx * x[0] / x[2] + intrinsic.cx) as f32,
+                (intrinsic.fy * x[1] / x[2] + intrinsic.cy) as f32,
+            )
+        };
+
+        let (mut points1, mut points2) = (Vec::new(), Vec::new());
+        for i in 0..60 {
+            let x = [
+                ((i * 37) % 101) as f64 / 101.0 * 4.0 - 2.0,
+                ((i * 53) % 89) as f64 / 89.0 * 3.0 - 1.5,
+                4.0 + ((i * 29) % 97) as f64 / 97.0 * 4.0,
+            ];
+            let x2 = [0, 1, 2]
+                .map(|r| (0..3).map(|k| rotation[r][k] * x[k]).sum::<f64>() + translation[r]);
+            points1.push(project(x));
+            points2.push(project(x2));
+        }
+
+        // every seventh match is wrong
+        for p in points2.iter_mut().step_by(7) {
+            *p = Point2::new(p.x + 30.0, p.y - 20.0);
+        }
+
+        Scene {
+            intrinsic,
+            rotation,
+            translation,
+            points1,
+            points2,
+        }
+    }
+
+    #[test]
+    fn find_fundamental() -> Result<()> {
+        let scene = scene();
+        let (f, inliers) =
+            super::find_fundamental(&scene.points1, &scene.points2, &RansacParams::default())?;
+
+        for (i, inlier) in inliers.iter().enumerate() {
+            assert_eq!(*inlier, i % 7 != 0);
+        }
+        let (p1, p2) = (&scene.points1[1], &scene.points2[1]);
+        assert!(super::sampson_distance(&f, p1, p2) < 0.05);
+
+        assert!(super::find_fundamental(
+            &scene.points1[..7],
+            &scene.points2[..7],
+            &RansacParams::default()
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn find_essential_decompose() -> Result<()> {
+        let scene = scene();
+        let (e, inliers) = super::find_essential(
+            &scene.points1,
+            &scene.points2,
+            &scene.intrinsic,
+            &RansacParams::default(),
+        )?;
+        assert_eq!(inliers.iter().filter(|&&x| x).count(), 60 - 9);
+
+        // one of the candidates is the true pose, with a unit translation
+        let norm = scene.translation.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let poses = super::decompose_essential(&e);
+        let found = poses.iter().any(|pose| {
+            let rotation_ok = (0..3)
+                .all(|i| (0..3).all(|j| (pose.rotation[i][j] - scene.rotation[i][j]).abs() < 1e-3));
+            let translation_ok =
+                (0..3).all(|i| (pose.translation[i] - scene.translation[i] / norm).abs() < 1e-3);
+            rotation_ok && translation_ok
+        });
+        assert!(found);
+        Ok(())
+    }
+}
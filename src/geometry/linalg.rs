@@ -0,0 +1,159 @@
+/// Compute the determinant of a square row-major matrix with the Gaussian elimination.
+pub(crate) fn determinant(mut m: Vec<f64>, d: usize) -> f64 {
+    let mut det = 1.0;
+    for col in 0..d {
+        let pivot = (col..d)
+            .max_by(|&i, &j| m[i * d + col].abs().total_cmp(&m[j * d + col].abs()))
+            .unwrap_or(col);
+        if m[pivot * d + col] == 0.0 {
+            return 0.0;
+        }
+        if pivot != col {
+            for k in 0..d {
+                m.swap(col * d + k, pivot * d + k);
+            }
+            det = -det;
+        }
+        det *= m[col * d + col];
+        for row in col + 1..d {
+            let factor = m[row * d + col] / m[col * d + col];
+            for k in col..d {
+                m[row * d + k] -= factor * m[col * d + k];
+            }
+        }
+    }
+    det
+}
+
+/// Compute the singular value decomposition `A = U * diag(S) * V^T` of a square
+/// row-major matrix with the one-sided Jacobi method.
+///
+/// # Returns
+///
+/// The orthogonal matrices U and V and the singular values in decreasing order.
+pub(crate) fn svd(a: &[f64], d: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut u = a.to_vec();
+    let mut v = (0..d * d)
+        .map(|i| if i / d == i % d { 1.0 } else { 0.0 })
+        .collect::<Vec<_>>();
+
+    // rotate pairs of columns until they are all orthogonal
+    for _ in 0..60 {
+        let mut rotated = false;
+        for p in 0..d {
+            for q in p + 1..d {
+                let (mut alpha, mut beta, mut gamma) = (0.0, 0.0, 0.0);
+                for i in 0..d {
+                    alpha += u[i * d + p] * u[i * d + p];
+                    beta += u[i * d + q] * u[i * d + q];
+                    gamma += u[i * d + p] * u[i * d + q];
+                }
+                if gamma.abs() <= 1e-15 * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+                for m in [&mut u, &mut v] {
+                    for i in 0..d {
+                        let (mp, mq) = (m[i * d + p], m[i * d + q]);
+                        m[i * d + p] = c * mp - s * mq;
+                        m[i * d + q] = s * mp + c * mq;
+                    }
+                }
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    // the singular values are the norms of the columns
+    let norms = (0..d)
+        .map(|j| (0..d).map(|i| u[i * d + j].powi(2)).sum::<f64>().sqrt())
+        .collect::<Vec<_>>();
+    let mut order = (0..d).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+
+    let scale = norms[order[0]].max(f64::MIN_POSITIVE);
+    let mut u_sorted = vec![0.0; d * d];
+    let mut v_sorted = vec![0.0; d * d];
+    let mut s = vec![0.0; d];
+    for (j, &k) in order.iter().enumerate() {
+        s[j] = norms[k];
+        for i in 0..d {
+            v_sorted[i * d + j] = v[i * d + k];
+        }
+
+        if norms[k] > 1e-12 * scale {
+            for i in 0..d {
+                u_sorted[i * d + j] = u[i * d + k] / norms[k];
+            }
+            continue;
+        }
+
+        // complete the basis of U for the null singular values
+        for e in 0..d {
+            let mut col = (0..d)
+                .map(|i| if i == e { 1.0 } else { 0.0 })
+                .collect::<Vec<_>>();
+            for prev in 0..j {
+                let dot = (0..d).map(|i| col[i] * u_sorted[i * d + prev]).sum::<f64>();
+                for (i, x) in col.iter_mut().enumerate() {
+                    *x -= dot * u_sorted[i * d + prev];
+                }
+            }
+            let norm = col.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norm > 1e-6 {
+                for (i, x) in col.iter().enumerate() {
+                    u_sorted[i * d + j] = x / norm;
+                }
+                break;
+            }
+        }
+    }
+
+    (u_sorted, s, v_sorted)
+}
+
+/// Solve a square row-major linear system with the Gaussian elimination and partial
+/// pivoting.
+///
+/// # Returns
+///
+/// The solution of the system, or `None` if the matrix is singular.
+pub(crate) fn solve(mut a: Vec<f64>, mut b: Vec<f64>, n: usize) -> Option<Vec<f64>> {
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))?;
+        if a[pivot * n + col].abs() < 1e-300 {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap(col * n + k, pivot * n + k);
+            }
+            b.swap(col, pivot);
+        }
+        for row in col + 1..n {
+            let factor = a[row * n + col] / a[col * n + col];
+            if factor == 0.0 {
+                continue;
+            }
+            for k in col..n {
+                a[row * n + k] -= factor * a[col * n + k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n).map(|k| a[row * n + k] * x[k]).sum::<f64>();
+        x[row] = (b[row] - sum) / a[row * n + row];
+    }
+    Some(x)
+}
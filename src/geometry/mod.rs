@@ -0,0 +1,14 @@
+pub mod epipolar;
+pub(crate) mod linalg;
+mod point;
+mod ransac;
+mod rect;
+mod rotated_box;
+mod umeyama;
+
+pub use point::Point2;
+pub(crate) use ransac::SplitMix64;
+pub use ransac::{estimate_affine_2d, estimate_affine_partial_2d, RansacParams};
+pub use rect::Rect;
+pub use rotated_box::{nms_rotated, RotatedBox};
+pub use umeyama::umeyama;
@@ -0,0 +1,47 @@
+/// A point in 2D image coordinates.
+///
+/// # Examples
+///
+/// ```
+/// use kornia_rs::geometry::Point2;
+///
+/// let p = Point2 { x: 3.0, y: 4.0 };
+/// assert_eq!(p.norm(), 5.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point2 {
+    /// The x coordinate of the point
+    pub x: f32,
+    /// The y coordinate of the point
+    pub y: f32,
+}
+
+impl Point2 {
+    /// Create a new point from its coordinates.
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Get the euclidean norm of the point seen as a vector.
+    pub fn norm(&self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+
+    /// Get the euclidean distance to another point.
+    pub fn distance(&self, other: &Point2) -> f32 {
+        Point2::new(self.x - other.x, self.y - other.y).norm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Point2;
+
+    #[test]
+    fn point_distance() {
+        let a = Point2::new(1.0, 1.0);
+        let b = Point2::new(4.0, 5.0);
+        assert_eq!(a.distance(&b), 5.0);
+        assert_eq!(b.distance(&a), 5.0);
+    }
+}
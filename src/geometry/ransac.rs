@@ -0,0 +1,405 @@
+use super::Point2;
+use anyhow::Result;
+
+/// A 2x3 affine transformation matrix in row-major order.
+type AffineMatrix = (f32, f32, f32, f32, f32, f32);
+
+/// The parameters of the RANSAC estimators.
+///
+/// # Examples
+///
+/// ```
+/// use kornia_rs::geometry::RansacParams;
+///
+/// let params = RansacParams {
+///     threshold: 2.0,
+///     ..Default::default()
+/// };
+/// assert_eq!(params.max_iterations, 2000);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RansacParams {
+    /// The maximum reprojection error in pixels of an inlier
+    pub threshold: f32,
+    /// The maximum number of random samples
+    pub max_iterations: usize,
+    /// The probability of drawing at least one sample free of outliers, used to stop early
+    pub confidence: f32,
+    /// The seed of the random sampling, making the estimation reproducible
+    pub seed: u64,
+}
+
+impl Default for RansacParams {
+    fn default() -> Self {
+        Self {
+            threshold: 3.0,
+            max_iterations: 2000,
+            confidence: 0.99,
+            seed: 0,
+        }
+    }
+}
+
+/// A small SplitMix64 generator, enough to draw the RANSAC and augmentation samples.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Draw a uniform value in `[0, 1)`.
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Draw `k` distinct indices in `[0, n)`.
+    pub(crate) fn sample(&mut self, n: usize, k: usize) -> Vec<usize> {
+        let mut indices = Vec::with_capacity(k);
+        while indices.len() < k {
+            let i = (self.next_u64() % n as u64) as usize;
+            if !indices.contains(&i) {
+                indices.push(i);
+            }
+        }
+        indices
+    }
+}
+
+/// The number of iterations needed to reach the confidence with the given inlier ratio.
+pub(crate) fn ransac_iterations(confidence: f32, inlier_ratio: f32, sample_size: usize) -> usize {
+    let p_good = (inlier_ratio as f64).powi(sample_size as i32);
+    if p_good >= 1.0 {
+        return 1;
+    }
+    if p_good <= 0.0 {
+        return usize::MAX;
+    }
+    let num = (1.0 - confidence as f64).max(f64::MIN_POSITIVE).ln();
+    (num / (1.0 - p_good).ln()).ceil().max(1.0) as usize
+}
+
+/// Fit a full affine transformation to point pairs in the least-squares sense.
+///
+/// # Returns
+///
+/// The matrix, or `None` if the points are degenerate.
+fn fit_affine(src: &[Point2], dst: &[Point2], indices: &[usize]) -> Option<AffineMatrix> {
+    let n = indices.len() as f64;
+    let mean = |pts: &[Point2]| {
+        let (sx, sy) = indices.iter().fold((0.0, 0.0), |(sx, sy), &i| {
+            (sx + pts[i].x as f64, sy + pts[i].y as f64)
+        });
+        (sx / n, sy / n)
+    };
+    let (mx, my) = mean(src);
+    let (mu, mv) = mean(dst);
+
+    // the centered normal equations split into two 2x2 systems sharing the same matrix
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    let (mut sxu, mut syu, mut sxv, mut syv) = (0.0, 0.0, 0.0, 0.0);
+    for &i in indices {
+        let (x, y) = (src[i].x as f64 - mx, src[i].y as f64 - my);
+        let (u, v) = (dst[i].x as f64 - mu, dst[i].y as f64 - mv);
+        sxx += x * x;
+        sxy += x * y;
+        syy += y * y;
+        sxu += x * u;
+        syu += y * u;
+        sxv += x * v;
+        syv += y * v;
+    }
+
+    let det = sxx * syy - sxy * sxy;
+    if det.abs() <= 1e-9 * (sxx * syy).max(f64::MIN_POSITIVE) {
+        return None;
+    }
+
+    let a = (syy * sxu - sxy * syu) / det;
+    let b = (sxx * syu - sxy * sxu) / det;
+    let d = (syy * sxv - sxy * syv) / det;
+    let e = (sxx * syv - sxy * sxv) / det;
+    let c = mu - a * mx - b * my;
+    let f = mv - d * mx - e * my;
+
+    Some((a as f32, b as f32, c as f32, d as f32, e as f32, f as f32))
+}
+
+/// Fit a similarity transformation (rotation, uniform scale and translation) to point
+/// pairs in the least-squares sense.
+///
+/// # Returns
+///
+/// The matrix, or `None` if the points are degenerate.
+fn fit_similarity(src: &[Point2], dst: &[Point2], indices: &[usize]) -> Option<AffineMatrix> {
+    let n = indices.len() as f64;
+    let mean = |pts: &[Point2]| {
+        let (sx, sy) = indices.iter().fold((0.0, 0.0), |(sx, sy), &i| {
+            (sx + pts[i].x as f64, sy + pts[i].y as f64)
+        });
+        (sx / n, sy / n)
+    };
+    let (mx, my) = mean(src);
+    let (mu, mv) = mean(dst);
+
+    // u = a * x - b * y + tx, v = b * x + a * y + ty
+    let (mut norm, mut sa, mut sb) = (0.0, 0.0, 0.0);
+    for &i in indices {
+        let (x, y) = (src[i].x as f64 - mx, src[i].y as f64 - my);
+        let (u, v) = (dst[i].x as f64 - mu, dst[i].y as f64 - mv);
+        norm += x * x + y * y;
+        sa += x * u + y * v;
+        sb += x * v - y * u;
+    }
+
+    if norm <= f64::EPSILON {
+        return None;
+    }
+
+    let (a, b) = (sa / norm, sb / norm);
+    let tx = mu - a * mx + b * my;
+    let ty = mv - b * mx - a * my;
+
+    Some((
+        a as f32, -b as f32, tx as f32, b as f32, a as f32, ty as f32,
+    ))
+}
+
+/// Compute the distance between a destination point and the mapped source point.
+fn affine_error(m: &AffineMatrix, p: &Point2, q: &Point2) -> f32 {
+    let u = m.0 * p.x + m.1 * p.y + m.2;
+    let v = m.3 * p.x + m.4 * p.y + m.5;
+    q.distance(&Point2::new(u, v))
+}
+
+/// Compute which point pairs are explained by a model within the threshold.
+fn find_inliers<M, E>(src: &[Point2], dst: &[Point2], m: &M, error: &E, threshold: f32) -> Vec<bool>
+where
+    E: Fn(&M, &Point2, &Point2) -> f32,
+{
+    src.iter()
+        .zip(dst.iter())
+        .map(|(p, q)| error(m, p, q) <= threshold)
+        .collect()
+}
+
+/// Run RANSAC on point pairs with the given minimal sample size, model fitter and
+/// model error.
+///
+/// The fitter must accept any number of pairs from the sample size, as it is also
+/// used to refine the best model on all its inliers.
+pub(crate) fn ransac<M, F, E>(
+    src: &[Point2],
+    dst: &[Point2],
+    params: &RansacParams,
+    sample_size: usize,
+    fit: F,
+    error: E,
+) -> Result<(M, Vec<bool>)>
+where
+    F: Fn(&[Point2], &[Point2], &[usize]) -> Option<M>,
+    E: Fn(&M, &Point2, &Point2) -> f32,
+{
+    if src.len() != dst.len() {
+        return Err(anyhow::anyhow!(
+            "The number of source and destination points differ: {} != {}",
+            src.len(),
+            dst.len()
+        ));
+    }
+    if src.len() < sample_size {
+        return Err(anyhow::anyhow!(
+            "At least {} point pairs are needed, got {}",
+            sample_size,
+            src.len()
+        ));
+    }
+
+    let mut rng = SplitMix64::new(params.seed);
+    let mut best: Option<(usize, M, Vec<bool>)> = None;
+    let mut max_iterations = params.max_iterations;
+
+    let mut iteration = 0;
+    while iteration < max_iterations {
+        iteration += 1;
+
+        let sample = rng.sample(src.len(), sample_size);
+        let Some(m) = fit(src, dst, &sample) else {
+            continue;
+        };
+
+        let inliers = find_inliers(src, dst, &m, &error, params.threshold);
+        let num_inliers = inliers.iter().filter(|&&x| x).count();
+        if best.as_ref().is_some_and(|(n, _, _)| num_inliers <= *n) {
+            continue;
+        }
+
+        // stop once a sample free of outliers has likely been drawn
+        let ratio = num_inliers as f32 / src.len() as f32;
+        max_iterations =
+            max_iterations.min(ransac_iterations(params.confidence, ratio, sample_size));
+        best = Some((num_inliers, m, inliers));
+    }
+
+    let (_, m, inliers) =
+        best.ok_or_else(|| anyhow::anyhow!("Failed to find a non-degenerate sample"))?;
+
+    // refine the model on all the inliers
+    let indices = (0..src.len()).filter(|&i| inliers[i]).collect::<Vec<_>>();
+    let m = if indices.len() > sample_size {
+        fit(src, dst, &indices).unwrap_or(m)
+    } else {
+        m
+    };
+    let inliers = find_inliers(src, dst, &m, &error, params.threshold);
+
+    Ok((m, inliers))
+}
+
+/// Estimates a full affine transformation between two point sets with RANSAC.
+///
+/// The transformation is estimated from random samples of three point pairs, keeping the
+/// one agreeing with most pairs, and refined with least squares on its inliers.
+///
+/// # Arguments
+///
+/// * `src` - The points in the source image.
+/// * `dst` - The corresponding points in the destination image.
+/// * `params` - The RANSAC parameters.
+///
+/// # Returns
+///
+/// The 2x3 affine matrix src -> dst and the mask of the inlier pairs.
+///
+/// # Errors
+///
+/// Returns an error if the point sets have different lengths, fewer than three pairs
+/// or only degenerate samples.
+///
+/// # Examples
+///
+/// ```
+/// use kornia_rs::geometry::{estimate_affine_2d, Point2, RansacParams};
+///
+/// let src = (0..20)
+///     .map(|i| Point2::new((i % 5) as f32 * 10.0, (i / 5) as f32 * 10.0))
+///     .collect::<Vec<_>>();
+/// let mut dst = src
+///     .iter()
+///     .map(|p| Point2::new(2.0 * p.x + 5.0, p.y - 3.0))
+///     .collect::<Vec<_>>();
+///
+/// // a wrong match
+/// dst[7] = Point2::new(100.0, 100.0);
+///
+/// let (m, inliers) = estimate_affine_2d(&src, &dst, &RansacParams::default()).unwrap();
+/// assert!((m.0 - 2.0).abs() < 1e-4 && (m.2 - 5.0).abs() < 1e-3);
+/// assert!(!inliers[7]);
+/// assert_eq!(inliers.iter().filter(|&&x| x).count(), 19);
+/// ```
+pub fn estimate_affine_2d(
+    src: &[Point2],
+    dst: &[Point2],
+    params: &RansacParams,
+) -> Result<(AffineMatrix, Vec<bool>)> {
+    ransac(src, dst, params, 3, fit_affine, affine_error)
+}
+
+/// Estimates a similarity transformation between two point sets with RANSAC.
+///
+/// The transformation is restricted to a rotation, a uniform scale and a translation,
+/// and is estimated from random samples of two point pairs.
+///
+/// # Arguments
+///
+/// * `src` - The points in the source image.
+/// * `dst` - The corresponding points in the destination image.
+/// * `params` - The RANSAC parameters.
+///
+/// # Returns
+///
+/// The 2x3 affine matrix src -> dst and the mask of the inlier pairs.
+///
+/// # Errors
+///
+/// Returns an error if the point sets have different lengths, fewer than two pairs
+/// or only degenerate samples.
+pub fn estimate_affine_partial_2d(
+    src: &[Point2],
+    dst: &[Point2],
+    params: &RansacParams,
+) -> Result<(AffineMatrix, Vec<bool>)> {
+    ransac(src, dst, params, 2, fit_similarity, affine_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Point2, RansacParams};
+    use anyhow::Result;
+
+    fn scene(m: (f32, f32, f32, f32, f32, f32)) -> (Vec<Point2>, Vec<Point2>) {
+        let src = (0..50)
+            .map(|i| Point2::new(((i * 37) % 101) as f32, ((i * 53) % 89) as f32))
+            .collect::<Vec<_>>();
+        let mut dst = src
+            .iter()
+            .map(|p| Point2::new(m.0 * p.x + m.1 * p.y + m.2, m.3 * p.x + m.4 * p.y + m.5))
+            .collect::<Vec<_>>();
+
+        // every fifth match is wrong
+        for (i, q) in dst.iter_mut().enumerate().step_by(5) {
+            *q = Point2::new(q.y + 40.0, q.x - 25.0 + i as f32);
+        }
+        (src, dst)
+    }
+
+    #[test]
+    fn estimate_affine_2d() -> Result<()> {
+        let m = (1.2, 0.3, -4.0, -0.2, 0.9, 12.5);
+        let (src, dst) = scene(m);
+
+        let (m_est, inliers) = super::estimate_affine_2d(&src, &dst, &RansacParams::default())?;
+        let (a, b) = (
+            [m.0, m.1, m.2, m.3, m.4, m.5],
+            [m_est.0, m_est.1, m_est.2, m_est.3, m_est.4, m_est.5],
+        );
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-3);
+        }
+        for (i, inlier) in inliers.iter().enumerate() {
+            assert_eq!(*inlier, i % 5 != 0);
+        }
+
+        assert!(super::estimate_affine_2d(&src[..2], &dst[..2], &RansacParams::default()).is_err());
+        assert!(super::estimate_affine_2d(&src, &dst[1..], &RansacParams::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn estimate_affine_partial_2d() -> Result<()> {
+        let (sin, cos) = 25f32.to_radians().sin_cos();
+        let s = 0.8;
+        let m = (s * cos, -s * sin, 7.0, s * sin, s * cos, -3.0);
+        let (src, dst) = scene(m);
+
+        let (m_est, inliers) =
+            super::estimate_affine_partial_2d(&src, &dst, &RansacParams::default())?;
+        let (a, b) = (
+            [m.0, m.1, m.2, m.3, m.4, m.5],
+            [m_est.0, m_est.1, m_est.2, m_est.3, m_est.4, m_est.5],
+        );
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-3);
+        }
+        assert_eq!(inliers.iter().filter(|&&x| x).count(), 40);
+        Ok(())
+    }
+}
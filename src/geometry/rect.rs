@@ -0,0 +1,63 @@
+/// An axis-aligned rectangle in pixel coordinates.
+///
+/// The rectangle spans the pixels `[x, x + width)` horizontally and
+/// `[y, y + height)` vertically.
+///
+/// # Examples
+///
+/// ```
+/// use kornia_rs::geometry::Rect;
+///
+/// let rect = Rect {
+///     x: 2,
+///     y: 3,
+///     width: 4,
+///     height: 5,
+/// };
+/// assert_eq!(rect.area(), 20);
+/// assert!(rect.contains(2, 3));
+/// assert!(!rect.contains(6, 3));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rect {
+    /// The x coordinate of the top-left corner
+    pub x: usize,
+    /// The y coordinate of the top-left corner
+    pub y: usize,
+    /// The width of the rectangle in pixels
+    pub width: usize,
+    /// The height of the rectangle in pixels
+    pub height: usize,
+}
+
+impl Rect {
+    /// Get the number of pixels covered by the rectangle.
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// Check whether the pixel `(x, y)` lies inside the rectangle.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rect;
+
+    #[test]
+    fn rect_smoke() {
+        let rect = Rect {
+            x: 1,
+            y: 1,
+            width: 2,
+            height: 3,
+        };
+        assert_eq!(rect.area(), 6);
+        assert!(rect.contains(1, 1));
+        assert!(rect.contains(2, 3));
+        assert!(!rect.contains(3, 3));
+        assert!(!rect.contains(0, 1));
+    }
+}
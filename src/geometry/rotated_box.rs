@@ -0,0 +1,278 @@
+use super::Point2;
+use anyhow::Result;
+
+/// A rectangle rotated around its center.
+///
+/// The angle is given in degrees and rotates the width axis from the x axis
+/// towards the y axis.
+///
+/// # Examples
+///
+/// ```
+/// use kornia_rs::geometry::RotatedBox;
+///
+/// let rbox = RotatedBox {
+///     cx: 5.0,
+///     cy: 5.0,
+///     width: 4.0,
+///     height: 2.0,
+///     angle: 0.0,
+/// };
+/// assert_eq!(rbox.area(), 8.0);
+/// assert_eq!(rbox.corners()[0].x, 3.0);
+/// assert_eq!(rbox.corners()[0].y, 4.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RotatedBox {
+    /// The x coordinate of the center
+    pub cx: f32,
+    /// The y coordinate of the center
+    pub cy: f32,
+    /// The width of the box
+    pub width: f32,
+    /// The height of the box
+    pub height: f32,
+    /// The rotation angle in degrees
+    pub angle: f32,
+}
+
+impl RotatedBox {
+    /// Get the area of the box.
+    pub fn area(&self) -> f32 {
+        self.width * self.height
+    }
+
+    /// Get the four corners of the box in clockwise order in image coordinates,
+    /// starting from the top-left corner of the unrotated box.
+    pub fn corners(&self) -> [Point2; 4] {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let (hw, hh) = (self.width / 2.0, self.height / 2.0);
+
+        [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)].map(|(dx, dy)| Point2 {
+            x: self.cx + dx * cos - dy * sin,
+            y: self.cy + dx * sin + dy * cos,
+        })
+    }
+
+    /// Compute the intersection over union with another box.
+    ///
+    /// The intersection is computed exactly by clipping the polygon of one box
+    /// against the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other box.
+    ///
+    /// # Returns
+    ///
+    /// The intersection over union in the range [0, 1].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kornia_rs::geometry::RotatedBox;
+    ///
+    /// let a = RotatedBox {
+    ///     cx: 0.0,
+    ///     cy: 0.0,
+    ///     width: 2.0,
+    ///     height: 2.0,
+    ///     angle: 0.0,
+    /// };
+    /// let b = RotatedBox { cx: 1.0, ..a };
+    /// assert!((a.iou(&b) - 1.0 / 3.0).abs() < 1e-5);
+    /// ```
+    pub fn iou(&self, other: &RotatedBox) -> f32 {
+        let intersection = polygon_area(&clip_polygon(&self.corners(), &other.corners()));
+        let union = self.area() + other.area() - intersection;
+        if union <= 0.0 {
+            return 0.0;
+        }
+        (intersection / union).clamp(0.0, 1.0)
+    }
+}
+
+/// Clip a polygon against a convex polygon with the Sutherland-Hodgman algorithm.
+///
+/// Both polygons must have the same orientation as [`RotatedBox::corners`].
+fn clip_polygon(subject: &[Point2], clip: &[Point2]) -> Vec<Point2> {
+    // positive when the point lies on the inner side of the edge a -> b
+    let side =
+        |a: &Point2, b: &Point2, p: &Point2| (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+
+    let mut output = subject.to_vec();
+
+    for i in 0..clip.len() {
+        if output.is_empty() {
+            break;
+        }
+        let (a, b) = (clip[i], clip[(i + 1) % clip.len()]);
+        let input = std::mem::take(&mut output);
+
+        for j in 0..input.len() {
+            let (p, q) = (input[j], input[(j + 1) % input.len()]);
+            let (sp, sq) = (side(&a, &b, &p), side(&a, &b, &q));
+
+            if sp >= 0.0 {
+                output.push(p);
+            }
+            // the edge p -> q crosses the clipping line
+            if (sp >= 0.0) != (sq >= 0.0) {
+                let t = sp / (sp - sq);
+                output.push(Point2::new(p.x + t * (q.x - p.x), p.y + t * (q.y - p.y)));
+            }
+        }
+    }
+
+    output
+}
+
+/// Compute the area of a simple polygon with the shoelace formula.
+fn polygon_area(polygon: &[Point2]) -> f32 {
+    let n = polygon.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let twice_area = (0..n)
+        .map(|i| {
+            let (p, q) = (polygon[i], polygon[(i + 1) % n]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum::<f32>();
+    twice_area.abs() / 2.0
+}
+
+/// Apply non-maximum suppression to a set of rotated boxes.
+///
+/// The boxes are visited by decreasing score and a box is discarded if its
+/// intersection over union with an already kept box exceeds the threshold.
+///
+/// # Arguments
+///
+/// * `boxes` - The rotated boxes.
+/// * `scores` - The score of each box.
+/// * `iou_threshold` - The maximum intersection over union between kept boxes.
+///
+/// # Returns
+///
+/// The indices of the kept boxes sorted by decreasing score.
+///
+/// # Errors
+///
+/// Returns an error if the number of boxes and scores differ.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::{nms_rotated, RotatedBox};
+///
+/// let a = RotatedBox {
+///     cx: 0.0,
+///     cy: 0.0,
+///     width: 4.0,
+///     height: 2.0,
+///     angle: 30.0,
+/// };
+/// let b = RotatedBox { cx: 0.1, ..a };
+/// let c = RotatedBox { cx: 10.0, ..a };
+///
+/// let keep = nms_rotated(&[a, b, c], &[0.8, 0.9, 0.5], 0.5).unwrap();
+/// assert_eq!(keep, vec![1, 2]);
+/// ```
+pub fn nms_rotated(boxes: &[RotatedBox], scores: &[f32], iou_threshold: f32) -> Result<Vec<usize>> {
+    if boxes.len() != scores.len() {
+        return Err(anyhow::anyhow!(
+            "The number of boxes {} does not match the number of scores {}.",
+            boxes.len(),
+            scores.len()
+        ));
+    }
+
+    let mut order = (0..boxes.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+    let mut keep: Vec<usize> = Vec::new();
+    for idx in order {
+        if keep
+            .iter()
+            .all(|&k| boxes[k].iou(&boxes[idx]) <= iou_threshold)
+        {
+            keep.push(idx);
+        }
+    }
+
+    Ok(keep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RotatedBox;
+    use anyhow::Result;
+
+    #[test]
+    fn rotated_box_corners() {
+        let rbox = RotatedBox {
+            cx: 0.0,
+            cy: 0.0,
+            width: 2.0,
+            height: 2.0,
+            angle: 45.0,
+        };
+        let corners = rbox.corners();
+        let expected = [
+            (0.0, -std::f32::consts::SQRT_2),
+            (std::f32::consts::SQRT_2, 0.0),
+            (0.0, std::f32::consts::SQRT_2),
+            (-std::f32::consts::SQRT_2, 0.0),
+        ];
+        for (c, e) in corners.iter().zip(expected.iter()) {
+            assert!((c.x - e.0).abs() < 1e-5);
+            assert!((c.y - e.1).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn rotated_box_iou() {
+        let a = RotatedBox {
+            cx: 0.0,
+            cy: 0.0,
+            width: 2.0,
+            height: 2.0,
+            angle: 0.0,
+        };
+        assert!((a.iou(&a) - 1.0).abs() < 1e-5);
+
+        // the same square rotated by 45 degrees: the intersection is a regular octagon
+        let b = RotatedBox { angle: 45.0, ..a };
+        let octagon = 8.0 * (std::f32::consts::SQRT_2 - 1.0);
+        let expected = octagon / (8.0 - octagon);
+        assert!((a.iou(&b) - expected).abs() < 1e-4);
+
+        // disjoint boxes
+        let c = RotatedBox { cx: 5.0, ..b };
+        assert_eq!(a.iou(&c), 0.0);
+    }
+
+    #[test]
+    fn nms_rotated() -> Result<()> {
+        let a = RotatedBox {
+            cx: 10.0,
+            cy: 10.0,
+            width: 6.0,
+            height: 2.0,
+            angle: 60.0,
+        };
+        let boxes = [
+            a,
+            RotatedBox { angle: 65.0, ..a },
+            RotatedBox { angle: -30.0, ..a },
+        ];
+
+        // the second box overlaps the first one while the third one is orthogonal
+        let keep = super::nms_rotated(&boxes, &[0.9, 0.8, 0.7], 0.5)?;
+        assert_eq!(keep, vec![0, 2]);
+
+        assert!(super::nms_rotated(&boxes, &[0.9], 0.5).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use super::linalg::{determinant, svd};
+use crate::tensor::{CpuAllocator, Tensor};
+use anyhow::Result;
+
+/// Estimate the similarity transformation between two row-major point sets of
+/// dimension `d`.
+///
+/// # Returns
+///
+/// The row-major homogeneous matrix of shape (d + 1, d + 1).
+fn umeyama_impl(src: &[f32], dst: &[f32], d: usize, with_scale: bool) -> Result<Vec<f64>> {
+    let n = src.len() / d;
+    let mean = |pts: &[f32]| {
+        (0..d)
+            .map(|k| (0..n).map(|i| pts[i * d + k] as f64).sum::<f64>() / n as f64)
+            .collect::<Vec<_>>()
+    };
+    let (mu_src, mu_dst) = (mean(src), mean(dst));
+
+    // the variance of the source and the covariance between the point sets
+    let mut var_src = 0.0;
+    let mut cov = vec![0.0; d * d];
+    for i in 0..n {
+        for r in 0..d {
+            let y = dst[i * d + r] as f64 - mu_dst[r];
+            for c in 0..d {
+                let x = src[i * d + c] as f64 - mu_src[c];
+                cov[r * d + c] += y * x / n as f64;
+            }
+        }
+        for c in 0..d {
+            var_src += (src[i * d + c] as f64 - mu_src[c]).powi(2) / n as f64;
+        }
+    }
+
+    if var_src <= f64::EPSILON {
+        return Err(anyhow::anyhow!(
+            "The source points are all equal, the transform is undefined"
+        ));
+    }
+
+    let (u, s, v) = svd(&cov, d);
+
+    // flip the smallest singular direction to get a rotation instead of a reflection
+    let mut signs = vec![1.0; d];
+    if determinant(u.clone(), d) * determinant(v.clone(), d) < 0.0 {
+        signs[d - 1] = -1.0;
+    }
+
+    // R = U * S * V^T
+    let mut rotation = vec![0.0; d * d];
+    for r in 0..d {
+        for c in 0..d {
+            rotation[r * d + c] = (0..d).map(|k| u[r * d + k] * signs[k] * v[c * d + k]).sum();
+        }
+    }
+
+    let scale = if with_scale {
+        s.iter().zip(signs.iter()).map(|(s, e)| s * e).sum::<f64>() / var_src
+    } else {
+        1.0
+    };
+
+    let mut m = vec![0.0; (d + 1) * (d + 1)];
+    for r in 0..d {
+        for c in 0..d {
+            m[r * (d + 1) + c] = scale * rotation[r * d + c];
+        }
+        let r_mu = (0..d).map(|c| rotation[r * d + c] * mu_src[c]).sum::<f64>();
+        m[r * (d + 1) + d] = mu_dst[r] - scale * r_mu;
+    }
+    m[(d + 1) * (d + 1) - 1] = 1.0;
+
+    Ok(m)
+}
+
+/// Estimates the least-squares similarity transformation between two point sets.
+///
+/// Implements the method of Umeyama, "Least-squares estimation of transformation
+/// parameters between two point patterns", PAMI 1991. The rotation is always proper,
+/// i.e. the point sets are never aligned with a reflection.
+///
+/// # Arguments
+///
+/// * `src` - The source points with shape (N, D).
+/// * `dst` - The corresponding destination points with shape (N, D).
+/// * `with_scale` - Whether to estimate a uniform scale, otherwise the transformation is rigid.
+///
+/// # Returns
+///
+/// The homogeneous transformation matrix src -> dst with shape (D + 1, D + 1).
+///
+/// # Errors
+///
+/// Returns an error if the point sets have different shapes, no points or all the
+/// source points are equal.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::umeyama;
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// let src = Tensor::<f32, 2>::from_shape_vec(
+///     [3, 2],
+///     vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+///     CpuAllocator,
+/// )
+/// .unwrap();
+///
+/// // rotated by 90 degrees, scaled by 2 and shifted by (1, 1)
+/// let dst = Tensor::<f32, 2>::from_shape_vec(
+///     [3, 2],
+///     vec![1.0, 1.0, 1.0, 3.0, -1.0, 1.0],
+///     CpuAllocator,
+/// )
+/// .unwrap();
+///
+/// let m = umeyama(&src, &dst, true).unwrap();
+/// assert_eq!(m.shape, [3, 3]);
+///
+/// let expected = [0.0, -2.0, 1.0, 2.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+/// for (a, b) in m.as_slice().iter().zip(expected.iter()) {
+///     assert!((a - b).abs() < 1e-5);
+/// }
+/// ```
+pub fn umeyama(
+    src: &Tensor<f32, 2>,
+    dst: &Tensor<f32, 2>,
+    with_scale: bool,
+) -> Result<Tensor<f32, 2>> {
+    if src.shape != dst.shape {
+        return Err(anyhow::anyhow!(
+            "The point sets have different shapes: {:?} != {:?}",
+            src.shape,
+            dst.shape
+        ));
+    }
+
+    let [n, d] = src.shape;
+    if n == 0 || d == 0 {
+        return Err(anyhow::anyhow!("The point sets are empty"));
+    }
+
+    let m = umeyama_impl(src.as_slice(), dst.as_slice(), d, with_scale)?;
+
+    Ok(Tensor::from_shape_vec(
+        [d + 1, d + 1],
+        m.into_iter().map(|x| x as f32).collect(),
+        CpuAllocator,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    #[test]
+    fn umeyama_3d() -> Result<()> {
+        // a rotation of 40 degrees around the axis (1, 2, 2) / 3
+        let (axis, angle) = ([1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0], 40f64.to_radians());
+        let (s, c) = angle.sin_cos();
+        let [x, y, z] = axis;
+        let rotation = [
+            c + x * x * (1.0 - c),
+            x * y * (1.0 - c) - z * s,
+            x * z * (1.0 - c) + y * s,
+            y * x * (1.0 - c) + z * s,
+            c + y * y * (1.0 - c),
+            y * z * (1.0 - c) - x * s,
+            z * x * (1.0 - c) - y * s,
+            z * y * (1.0 - c) + x * s,
+            c + z * z * (1.0 - c),
+        ];
+        let (scale, translation) = (1.7, [0.5, -2.0, 3.0]);
+
+        let src = (0..10 * 3)
+            .map(|i| ((i * 29) % 17) as f32 - 8.0)
+            .collect::<Vec<_>>();
+        let dst = src
+            .chunks(3)
+            .flat_map(|p| {
+                (0..3).map(move |r| {
+                    let rp = (0..3)
+                        .map(|c| rotation[r * 3 + c] * p[c] as f64)
+                        .sum::<f64>();
+                    (scale * rp + translation[r]) as f32
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let m = super::umeyama_impl(&src, &dst, 3, true)?;
+        for r in 0..3 {
+            for c in 0..3 {
+                assert!((m[r * 4 + c] - scale * rotation[r * 3 + c]).abs() < 1e-4);
+            }
+            assert!((m[r * 4 + 3] - translation[r]).abs() < 1e-3);
+        }
+        assert_eq!(&m[12..], &[0.0, 0.0, 0.0, 1.0]);
+
+        // without scale the rotation is kept
+        let m = super::umeyama_impl(&src, &dst, 3, false)?;
+        for r in 0..3 {
+            for c in 0..3 {
+                assert!((m[r * 4 + c] - rotation[r * 3 + c]).abs() < 1e-4);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn umeyama_no_reflection() -> Result<()> {
+        // the destination is mirrored, the best proper rotation must be returned
+        let src = vec![0.0, 0.0, 2.0, 0.0, 0.0, 1.0, 2.0, 1.0];
+        let dst = vec![0.0, 0.0, -2.0, 0.0, 0.0, 1.0, -2.0, 1.0];
+
+        let m = super::umeyama_impl(&src, &dst, 2, false)?;
+        let det = m[0] * m[4] - m[1] * m[3];
+        assert!((det - 1.0).abs() < 1e-6);
+
+        assert!(super::umeyama_impl(&[1.0, 1.0, 1.0, 1.0], &dst[..4], 2, true).is_err());
+        Ok(())
+    }
+}
@@ -57,6 +57,66 @@ pub fn compute_histogram(image: &Image<u8, 1>, num_bins: usize) -> Result<Vec<us
     Ok(histogram)
 }
 
+/// Compute the back projection of a histogram on an image.
+///
+/// Each pixel is replaced by the value of its bin in the histogram, normalized by the
+/// highest bin, giving the likelihood that the pixel belongs to the modeled region.
+///
+/// NOTE: this is limited to 8-bit 1-channel images.
+///
+/// # Arguments
+///
+/// * `image` - The input image with shape (H, W, 1).
+/// * `histogram` - The histogram of the region, e.g. from [`compute_histogram`].
+///
+/// # Returns
+///
+/// The likelihood image with values in the range [0, 1].
+///
+/// # Errors
+///
+/// Returns an error if the number of bins is invalid.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::histogram::back_project;
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![0, 128, 255],
+/// )
+/// .unwrap();
+///
+/// let likelihood = back_project(&image, &[4, 2]).unwrap();
+/// assert_eq!(likelihood.data.as_slice().unwrap(), &[1.0, 0.5, 0.5]);
+/// ```
+pub fn back_project(image: &Image<u8, 1>, histogram: &[usize]) -> Result<Image<f32, 1>> {
+    let num_bins = histogram.len();
+    if num_bins == 0 || num_bins > 256 {
+        return Err(anyhow::anyhow!(
+            "Invalid number of bins. Must be in the range [1, 256]."
+        ));
+    }
+
+    let scale = 256.0 / num_bins as f32;
+    let max_count = histogram.iter().max().cloned().unwrap_or(0).max(1) as f32;
+
+    let mut dst = Image::<f32, 1>::from_size_val(image.size(), 0.0)?;
+    ndarray::Zip::from(&mut dst.data)
+        .and(&image.data)
+        .par_for_each(|out, &pixel| {
+            let bin_pos = (pixel as f32 / scale).floor() as usize;
+            *out = histogram[bin_pos] as f32 / max_count;
+        });
+
+    Ok(dst)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::image::{Image, ImageSize};
@@ -76,4 +136,26 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_back_project() -> Result<()> {
+        let image = Image::new(
+            ImageSize {
+                width: 4,
+                height: 1,
+            },
+            vec![10, 20, 200, 250],
+        )?;
+        let histogram = super::compute_histogram(&image, 4)?;
+        assert_eq!(histogram, vec![2, 0, 0, 2]);
+
+        let likelihood = super::back_project(&image, &histogram)?;
+        assert_eq!(likelihood.data.as_slice().unwrap(), &[1.0, 1.0, 1.0, 1.0]);
+
+        let likelihood = super::back_project(&image, &[0, 0, 0, 0, 0, 0, 3, 6])?;
+        assert_eq!(likelihood.data.as_slice().unwrap(), &[0.0, 0.0, 0.5, 1.0]);
+
+        assert!(super::back_project(&image, &[]).is_err());
+        Ok(())
+    }
 }
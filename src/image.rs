@@ -1,5 +1,6 @@
 //use crate::io;
 use anyhow::Result;
+use ndarray::ShapeBuilder;
 use num_traits::Float;
 
 /// Image size in pixels
@@ -65,6 +66,9 @@ pub struct Image<T, const CHANNELS: usize> {
     pub data: ndarray::Array<T, ndarray::Dim<[ndarray::Ix; 3]>>,
 }
 
+/// An image shared with copy-on-write semantics, see [`Shared`](crate::shared::Shared).
+pub type SharedImage<T, const CHANNELS: usize> = crate::shared::Shared<Image<T, CHANNELS>>;
+
 impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
     /// Create a new image from pixel data.
     ///
@@ -199,9 +203,7 @@ impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
         U: Clone + Default + num_traits::NumCast + std::fmt::Debug,
         T: Copy + num_traits::NumCast + std::fmt::Debug,
     {
-        let casted_data = self
-            .data
-            .map(|&x| U::from(x).expect("Failed to cast image data"));
+        let casted_data = self.map_values(|&x| U::from(x).expect("Failed to cast image data"));
 
         Ok(Image { data: casted_data })
     }
@@ -249,7 +251,7 @@ impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
             + std::ops::Mul<Output = U>,
         T: Copy + num_traits::NumCast + std::fmt::Debug,
     {
-        let casted_data = self.data.map(|&x| {
+        let casted_data = self.map_values(|&x| {
             let xu = U::from(x).expect("Failed to cast image data");
             xu * scale
         });
@@ -321,12 +323,217 @@ impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
         Ok(channels)
     }
 
+    /// Merge single channel images into a multi-channel image.
+    ///
+    /// This is the inverse of [`Image::split_channels`].
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - The channels of the image, all with the same size.
+    ///
+    /// # Returns
+    ///
+    /// A new image with the given channels.
+    ///
+    /// # Errors
+    ///
+    /// If the channels have different sizes, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kornia_rs::image::{Image, ImageSize};
+    ///
+    /// let size = ImageSize {
+    ///     width: 2,
+    ///     height: 1,
+    /// };
+    /// let r = Image::<u8, 1>::new(size, vec![1, 4]).unwrap();
+    /// let g = Image::<u8, 1>::new(size, vec![2, 5]).unwrap();
+    /// let b = Image::<u8, 1>::new(size, vec![3, 6]).unwrap();
+    ///
+    /// let image = Image::<u8, 3>::from_channels(&[r, g, b]).unwrap();
+    /// assert_eq!(image.data.as_slice().unwrap(), &[1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn from_channels(channels: &[Image<T, 1>; CHANNELS]) -> Result<Self>
+    where
+        T: Clone + Default,
+    {
+        let size = match channels.first() {
+            Some(channel) => channel.size(),
+            None => return Err(anyhow::anyhow!("No channels to merge.")),
+        };
+
+        let mut image = Image::from_size_val(size, T::default())?;
+
+        for (i, channel) in channels.iter().enumerate() {
+            if channel.size() != size {
+                return Err(anyhow::anyhow!(
+                    "Channel {} size {} does not match the image size {}.",
+                    i,
+                    channel.size(),
+                    size
+                ));
+            }
+
+            image
+                .data
+                .slice_mut(ndarray::s![.., .., i])
+                .assign(&channel.data.slice(ndarray::s![.., .., 0]));
+        }
+
+        Ok(image)
+    }
+
+    /// Create a new image from planar pixel data without copying it.
+    ///
+    /// The data is laid out channel by channel, i.e. with shape (C, H, W), and the image
+    /// is a strided view of it with shape (H, W, C).
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size of the image in pixels.
+    /// * `data` - The planar pixel data of the image.
+    ///
+    /// # Returns
+    ///
+    /// A new image, not contiguous in memory if it has more than one channel.
+    ///
+    /// # Errors
+    ///
+    /// If the length of the pixel data does not match the image size, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kornia_rs::image::{Image, ImageSize};
+    ///
+    /// let image = Image::<u8, 3>::from_planar(
+    ///     ImageSize {
+    ///         width: 2,
+    ///         height: 1,
+    ///     },
+    ///     vec![1, 4, 2, 5, 3, 6],
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(!image.is_contiguous());
+    /// assert_eq!(image.get_pixel(1, 0, 0).unwrap(), 4);
+    /// assert_eq!(image.as_contiguous_slice().as_ref(), &[1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn from_planar(size: ImageSize, data: Vec<T>) -> Result<Self> {
+        if data.len() != size.width * size.height * CHANNELS {
+            return Err(anyhow::anyhow!(
+                "Data length ({}) does not match the image size ({})",
+                data.len(),
+                size.width * size.height * CHANNELS
+            ));
+        }
+
+        let data =
+            ndarray::Array::<T, _>::from_shape_vec((CHANNELS, size.height, size.width), data)?
+                .permuted_axes([1, 2, 0]);
+
+        Ok(Image { data })
+    }
+
+    /// Check if the pixel data is contiguous in row-major (H, W, C) order.
+    pub fn is_contiguous(&self) -> bool {
+        self.data.is_standard_layout()
+    }
+
+    /// Get the pixel data as a contiguous slice in row-major (H, W, C) order.
+    ///
+    /// The data is borrowed when it is already contiguous and copied otherwise, e.g. for
+    /// planar images or images built from strided views.
+    pub fn as_contiguous_slice(&self) -> std::borrow::Cow<'_, [T]>
+    where
+        T: Clone,
+    {
+        match self.data.as_slice() {
+            Some(data) => std::borrow::Cow::Borrowed(data),
+            None => std::borrow::Cow::Owned(self.data.iter().cloned().collect()),
+        }
+    }
+
+    /// Get the pixel data as rows of interleaved pixels separated by a row pitch.
+    ///
+    /// The data is borrowed without copying when each row is contiguous, e.g. for images
+    /// built from a region of interest of a larger image, so that it can be passed to the
+    /// libraries taking a row pitch.
+    ///
+    /// # Returns
+    ///
+    /// The data from the first to the last pixel and the row pitch in elements, or `None`
+    /// if the pixels of a row are not contiguous, e.g. for planar or flipped images.
+    pub fn as_pitched_slice(&self) -> Option<(&[T], usize)> {
+        if let Some(data) = self.data.as_slice() {
+            return Some((data, self.width() * CHANNELS));
+        }
+
+        let row_len = self.width() * CHANNELS;
+        if self.data.is_empty() {
+            return Some((&[], row_len));
+        }
+        // the strides of the axes of length 1 are meaningless, e.g. 0 for the channels
+        let (shape, strides) = (self.data.shape(), self.data.strides());
+        let stride_ok = |axis: usize, ok: bool| shape[axis] == 1 || ok;
+        if !stride_ok(2, strides[2] == 1)
+            || !stride_ok(1, strides[1] == CHANNELS as isize)
+            || !stride_ok(0, strides[0] >= row_len as isize)
+        {
+            return None;
+        }
+        let pitch = if shape[0] == 1 {
+            row_len
+        } else {
+            strides[0] as usize
+        };
+        let len = (self.height() - 1) * pitch + row_len;
+
+        // SAFETY: the rows are inside the allocation of the array, starting at the first
+        // element, and the elements between the rows are initialized values of the array
+        let data = unsafe { std::slice::from_raw_parts(self.data.as_ptr(), len) };
+        Some((data, pitch))
+    }
+
+    /// Apply a function to each pixel value, producing contiguous pixel data.
+    ///
+    /// Contiguous data is mapped directly over its memory while strided data is traversed
+    /// in logical (H, W, C) order.
+    fn map_values<U, F>(&self, f: F) -> ndarray::Array3<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let values = match self.data.as_slice() {
+            Some(data) => data.iter().map(f).collect(),
+            None => self.data.iter().map(f).collect(),
+        };
+        ndarray::Array::from_shape_vec(self.data.raw_dim(), values)
+            .expect("The mapped data has the shape of the image")
+    }
+
+    /// Convert the image to a contiguous row-major (H, W, C) layout.
+    ///
+    /// The image is returned as is if it is already contiguous.
+    pub fn into_contiguous(self) -> Self
+    where
+        T: Clone,
+    {
+        if self.is_contiguous() {
+            return self;
+        }
+        Image {
+            data: self.data.as_standard_layout().into_owned(),
+        }
+    }
+
     // TODO: optimize this
     pub fn mul(&self, scale: T) -> Self
     where
         T: Copy + std::ops::Mul<Output = T>,
     {
-        let scaled_data = self.data.map(|&x| x * scale);
+        let scaled_data = self.map_values(|&x| x * scale);
         Image { data: scaled_data }
     }
 
@@ -335,7 +542,7 @@ impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
     where
         T: Copy + std::ops::Div<Output = T>,
     {
-        let scaled_data = self.data.map(|&x| x / scale);
+        let scaled_data = self.map_values(|&x| x / scale);
         Image { data: scaled_data }
     }
 
@@ -353,7 +560,7 @@ impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
     where
         T: Copy + Float,
     {
-        let powered_data = self.data.map(|&x| x.powi(n));
+        let powered_data = self.map_values(|&x| x.powi(n));
         Image { data: powered_data }
     }
 
@@ -369,7 +576,7 @@ impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
     where
         T: Copy + Float,
     {
-        let abs_data = self.data.map(|&x| x.abs());
+        let abs_data = self.map_values(|&x| x.abs());
         Image { data: abs_data }
     }
 
@@ -488,9 +695,258 @@ impl<T, const CHANNELS: usize> Image<T, CHANNELS> {
     }
 }
 
+/// Check that a row stride fits the pixels of a row and get the minimum buffer length.
+fn strided_len<const CHANNELS: usize>(size: ImageSize, stride: usize) -> Result<usize> {
+    if stride < size.width * CHANNELS {
+        return Err(anyhow::anyhow!(
+            "The row stride ({}) is smaller than the row length ({}).",
+            stride,
+            size.width * CHANNELS
+        ));
+    }
+
+    Ok(match size.height {
+        0 => 0,
+        h => (h - 1) * stride + size.width * CHANNELS,
+    })
+}
+
+/// Represents an image borrowing its pixel data from an external buffer.
+///
+/// The rows of the buffer can be padded, e.g. to match the pitch of a camera frame, and
+/// the pixel data is never copied until converted with [`ImageView::to_image`].
+pub struct ImageView<'a, T, const CHANNELS: usize> {
+    /// The strided view of the pixel data with shape (H, W, C).
+    pub data: ndarray::ArrayView3<'a, T>,
+}
+
+impl<'a, T, const CHANNELS: usize> ImageView<'a, T, CHANNELS> {
+    /// Create a new image view over a slice of interleaved pixel data.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size of the image in pixels.
+    /// * `stride` - The distance in elements between the start of two rows.
+    /// * `data` - The pixel data of the image.
+    ///
+    /// # Returns
+    ///
+    /// A new image view borrowing the pixel data.
+    ///
+    /// # Errors
+    ///
+    /// If the stride is smaller than a row or the slice is too short, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kornia_rs::image::{ImageSize, ImageView};
+    ///
+    /// // two rows of two pixels, each row padded with one element
+    /// let data = [1u8, 2, 0, 3, 4, 0];
+    /// let view = ImageView::<u8, 1>::from_slice(
+    ///     ImageSize {
+    ///         width: 2,
+    ///         height: 2,
+    ///     },
+    ///     3,
+    ///     &data,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(view.data[[1, 0, 0]], 3);
+    /// assert_eq!(view.to_image().data.as_slice().unwrap(), &[1, 2, 3, 4]);
+    /// ```
+    pub fn from_slice(size: ImageSize, stride: usize, data: &'a [T]) -> Result<Self> {
+        let len = strided_len::<CHANNELS>(size, stride)?;
+        if data.len() < len {
+            return Err(anyhow::anyhow!(
+                "Data length ({}) is smaller than the strided image size ({})",
+                data.len(),
+                len
+            ));
+        }
+
+        let shape = (size.height, size.width, CHANNELS).strides((stride, CHANNELS, 1));
+        let data = ndarray::ArrayView3::from_shape(shape, &data[..len])?;
+
+        Ok(Self { data })
+    }
+
+    /// Create a new image view over a raw pointer to interleaved pixel data.
+    ///
+    /// # Arguments
+    ///
+    /// * `ptr` - The pointer to the first pixel of the image.
+    /// * `size` - The size of the image in pixels.
+    /// * `stride` - The distance in elements between the start of two rows.
+    ///
+    /// # Returns
+    ///
+    /// A new image view borrowing the pixel data for the lifetime `'a`.
+    ///
+    /// # Errors
+    ///
+    /// If the pointer is null or the stride is smaller than a row, an error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be aligned for `T` and valid for reads of `(H - 1) * stride + W * C`
+    /// elements, which must not be mutated for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(ptr: *const T, size: ImageSize, stride: usize) -> Result<Self> {
+        if ptr.is_null() {
+            return Err(anyhow::anyhow!("The pixel data pointer is null."));
+        }
+
+        let len = strided_len::<CHANNELS>(size, stride)?;
+        let data = std::slice::from_raw_parts(ptr, len);
+
+        Self::from_slice(size, stride, data)
+    }
+
+    /// Get the size of the image in pixels.
+    pub fn size(&self) -> ImageSize {
+        ImageSize {
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+
+    /// Get the width of the image in pixels.
+    pub fn width(&self) -> usize {
+        self.data.shape()[1]
+    }
+
+    /// Get the height of the image in pixels.
+    pub fn height(&self) -> usize {
+        self.data.shape()[0]
+    }
+
+    /// Check if the pixel data is contiguous, i.e. the rows are not padded.
+    pub fn is_contiguous(&self) -> bool {
+        self.data.is_standard_layout()
+    }
+
+    /// Copy the pixel data into a new contiguous image.
+    pub fn to_image(&self) -> Image<T, CHANNELS>
+    where
+        T: Clone,
+    {
+        Image {
+            data: self.data.as_standard_layout().into_owned(),
+        }
+    }
+}
+
+/// Represents an image wrapping pixel data owned by an external allocator.
+///
+/// The deleter is called when the image is dropped to release the buffer, e.g. to queue
+/// a V4L2 buffer back to the driver or to unmap a shared memory region.
+pub struct ExternalImage<T, const CHANNELS: usize> {
+    ptr: std::ptr::NonNull<T>,
+    size: ImageSize,
+    stride: usize,
+    deleter: Option<Box<dyn FnOnce()>>,
+}
+
+impl<T, const CHANNELS: usize> ExternalImage<T, CHANNELS> {
+    /// Wrap an external buffer of interleaved pixel data without copying it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ptr` - The pointer to the first pixel of the image.
+    /// * `size` - The size of the image in pixels.
+    /// * `stride` - The distance in elements between the start of two rows.
+    /// * `deleter` - The function releasing the buffer when the image is dropped.
+    ///
+    /// # Returns
+    ///
+    /// A new image owning the external buffer.
+    ///
+    /// # Errors
+    ///
+    /// If the pointer is null or the stride is smaller than a row, an error is returned
+    /// and the deleter is called.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be aligned for `T` and valid for reads of `(H - 1) * stride + W * C`
+    /// elements until the deleter is called, and the data must not be mutated meanwhile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kornia_rs::image::{ExternalImage, ImageSize};
+    ///
+    /// let buffer = vec![1u8, 2, 3, 4, 5, 6];
+    /// let ptr = buffer.as_ptr();
+    ///
+    /// let image = unsafe {
+    ///     ExternalImage::<u8, 3>::from_raw_parts(
+    ///         ptr,
+    ///         ImageSize {
+    ///             width: 2,
+    ///             height: 1,
+    ///         },
+    ///         6,
+    ///         move || drop(buffer),
+    ///     )
+    /// }
+    /// .unwrap();
+    ///
+    /// assert_eq!(image.view().data[[0, 1, 2]], 6);
+    /// ```
+    pub unsafe fn from_raw_parts(
+        ptr: *const T,
+        size: ImageSize,
+        stride: usize,
+        deleter: impl FnOnce() + 'static,
+    ) -> Result<Self> {
+        let ptr = match std::ptr::NonNull::new(ptr as *mut T) {
+            Some(ptr) => ptr,
+            None => {
+                deleter();
+                return Err(anyhow::anyhow!("The pixel data pointer is null."));
+            }
+        };
+
+        if let Err(err) = strided_len::<CHANNELS>(size, stride) {
+            deleter();
+            return Err(err);
+        }
+
+        Ok(Self {
+            ptr,
+            size,
+            stride,
+            deleter: Some(Box::new(deleter)),
+        })
+    }
+
+    /// Get a view of the pixel data.
+    pub fn view(&self) -> ImageView<'_, T, CHANNELS> {
+        // SAFETY: the buffer was checked on construction and lives until the deleter is called
+        unsafe { ImageView::from_raw_parts(self.ptr.as_ptr(), self.size, self.stride) }
+            .expect("The external buffer was validated on construction")
+    }
+
+    /// Get the size of the image in pixels.
+    pub fn size(&self) -> ImageSize {
+        self.size
+    }
+}
+
+impl<T, const CHANNELS: usize> Drop for ExternalImage<T, CHANNELS> {
+    fn drop(&mut self) {
+        if let Some(deleter) = self.deleter.take() {
+            deleter();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::image::{Image, ImageSize};
+    use crate::image::{ExternalImage, Image, ImageSize, ImageView};
     use anyhow::Result;
 
     #[test]
@@ -607,6 +1063,88 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn image_from_channels() -> Result<()> {
+        let image = Image::<f32, 3>::new(
+            ImageSize {
+                height: 2,
+                width: 1,
+            },
+            vec![0., 1., 2., 3., 4., 5.],
+        )?;
+        let channels = image.split_channels()?;
+        let merged = Image::<f32, 3>::from_channels(&[
+            channels[0].clone(),
+            channels[1].clone(),
+            channels[2].clone(),
+        ])?;
+        assert_eq!(merged.data, image.data);
+
+        let other = Image::<f32, 1>::from_size_val(
+            ImageSize {
+                height: 1,
+                width: 1,
+            },
+            0.0,
+        )?;
+        assert!(Image::<f32, 2>::from_channels(&[channels[0].clone(), other]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn image_planar() -> Result<()> {
+        let size = ImageSize {
+            height: 2,
+            width: 1,
+        };
+        let planar = Image::<f32, 3>::from_planar(size, vec![0., 3., 1., 4., 2., 5.])?;
+        let image = Image::<f32, 3>::new(size, vec![0., 1., 2., 3., 4., 5.])?;
+        assert!(!planar.is_contiguous());
+        assert_eq!(planar.data, image.data);
+        assert_eq!(
+            planar.as_contiguous_slice().as_ref(),
+            &[0., 1., 2., 3., 4., 5.]
+        );
+
+        let contiguous = planar.clone().into_contiguous();
+        assert!(contiguous.is_contiguous());
+        assert_eq!(contiguous.data.as_slice(), image.data.as_slice());
+
+        // element-wise operations produce contiguous images
+        let scaled = planar.mul(2.0);
+        assert!(scaled.is_contiguous());
+        assert_eq!(scaled.data.as_slice().unwrap(), &[0., 2., 4., 6., 8., 10.]);
+
+        assert!(Image::<f32, 3>::from_planar(size, vec![0.; 5]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn image_pitched_slice() -> Result<()> {
+        let size = ImageSize {
+            width: 3,
+            height: 2,
+        };
+        let image = Image::<u8, 1>::new(size, vec![0, 1, 2, 3, 4, 5])?;
+        let (data, pitch) = image.as_pitched_slice().unwrap();
+        assert_eq!((data.len(), pitch), (6, 3));
+
+        // a region of interest keeps the row pitch of the full image
+        let roi = Image::<u8, 1> {
+            data: image.data.clone().slice_move(ndarray::s![.., 1.., ..]),
+        };
+        assert!(!roi.is_contiguous());
+        assert_eq!(roi.as_pitched_slice(), Some((&[1u8, 2, 3, 4, 5][..], 3)));
+
+        // the pixels of the planar images are not interleaved
+        let planar = Image::<u8, 3>::from_planar(size, vec![0; 18])?;
+        assert!(planar.as_pitched_slice().is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn convert_to_tensor() -> Result<()> {
         let image = Image::<f32, 3>::new(
@@ -627,4 +1165,61 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn image_view_strided() -> Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+        // rows of two rgb pixels padded to 8 elements
+        let data = (0..16u8).collect::<Vec<_>>();
+        let view = ImageView::<u8, 3>::from_slice(size, 8, &data)?;
+        assert_eq!(view.size(), size);
+        assert!(!view.is_contiguous());
+        assert_eq!(view.data[[1, 1, 2]], 13);
+        assert_eq!(
+            view.to_image().data.as_slice().unwrap(),
+            &[0, 1, 2, 3, 4, 5, 8, 9, 10, 11, 12, 13]
+        );
+
+        assert!(ImageView::<u8, 3>::from_slice(size, 5, &data).is_err());
+        assert!(ImageView::<u8, 3>::from_slice(size, 8, &data[..13]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn external_image_deleter() -> Result<()> {
+        let released = std::rc::Rc::new(std::cell::Cell::new(false));
+        let buffer = vec![7f32; 4];
+        let ptr = buffer.as_ptr();
+
+        let flag = released.clone();
+        let image = unsafe {
+            ExternalImage::<f32, 1>::from_raw_parts(
+                ptr,
+                ImageSize {
+                    width: 2,
+                    height: 2,
+                },
+                2,
+                move || {
+                    drop(buffer);
+                    flag.set(true);
+                },
+            )?
+        };
+
+        let view = image.view();
+        assert!(view.is_contiguous());
+        assert_eq!(view.data.as_ptr(), ptr);
+        assert_eq!(view.to_image().get_pixel(1, 1, 0)?, 7.0);
+
+        assert!(!released.get());
+        drop(image);
+        assert!(released.get());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,322 @@
+use crate::image::{Image, ImageDtype};
+use anyhow::Result;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Inpainting algorithm used to fill the masked pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InpaintMethod {
+    /// Fast marching method by Telea (2004).
+    Telea,
+    /// Fast marching with weights that propagate the values along the isophotes,
+    /// following the fluid dynamics interpretation by Bertalmio et al. (2001).
+    NavierStokes,
+}
+
+// state of each pixel during the fast marching
+const KNOWN: u8 = 0;
+const BAND: u8 = 1;
+const INSIDE: u8 = 2;
+
+/// Solve the eikonal equation from two neighbours of a pixel.
+fn solve_eikonal(flags: &[u8], dist: &[f32], i1: Option<usize>, i2: Option<usize>) -> f32 {
+    let value = |i: Option<usize>| match i {
+        Some(i) if flags[i] != INSIDE => Some(dist[i]),
+        _ => None,
+    };
+
+    match (value(i1), value(i2)) {
+        (Some(a), Some(b)) => {
+            if (a - b).abs() >= 1.0 {
+                1.0 + a.min(b)
+            } else {
+                (a + b + (2.0 - (a - b) * (a - b)).sqrt()) * 0.5
+            }
+        }
+        (Some(a), None) | (None, Some(a)) => 1.0 + a,
+        (None, None) => f32::INFINITY,
+    }
+}
+
+/// Fill the masked regions of an image from their surroundings.
+///
+/// The pixels are filled from the boundary of the mask towards its center using the fast
+/// marching method. Each pixel is computed as a weighted average of the known pixels in
+/// a neighbourhood of the given radius.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `mask` - The mask with the pixels to inpaint set to a non-zero value.
+/// * `radius` - The radius of the neighbourhood used to fill each pixel.
+/// * `method` - The inpainting algorithm.
+///
+/// # Returns
+///
+/// The inpainted image.
+///
+/// # Errors
+///
+/// Returns an error if the mask and the image have different sizes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::inpaint::{inpaint, InpaintMethod};
+///
+/// let size = ImageSize {
+///     width: 5,
+///     height: 5,
+/// };
+/// let image = Image::<u8, 3>::from_size_val(size, 100).unwrap();
+/// let mut mask = Image::<u8, 1>::from_size_val(size, 0).unwrap();
+/// mask.set_pixel(2, 2, 0, 255).unwrap();
+///
+/// let inpainted = inpaint(&image, &mask, 3, InpaintMethod::Telea).unwrap();
+/// assert_eq!(inpainted.get_pixel(2, 2, 0).unwrap(), 100);
+/// ```
+pub fn inpaint<T, const CHANNELS: usize>(
+    src: &Image<T, CHANNELS>,
+    mask: &Image<u8, 1>,
+    radius: usize,
+    method: InpaintMethod,
+) -> Result<Image<T, CHANNELS>>
+where
+    T: ImageDtype,
+{
+    if src.size() != mask.size() {
+        return Err(anyhow::anyhow!(
+            "The mask size {} does not match the image size {}.",
+            mask.size(),
+            src.size()
+        ));
+    }
+
+    let (width, height) = (src.width(), src.height());
+    let num_pixels = width * height;
+
+    let mut values = src.data.iter().map(|&v| v.into()).collect::<Vec<f32>>();
+
+    let mut flags = mask
+        .data
+        .iter()
+        .map(|&m| if m != 0 { INSIDE } else { KNOWN })
+        .collect::<Vec<_>>();
+
+    let mut dist = flags
+        .iter()
+        .map(|&f| if f == INSIDE { f32::INFINITY } else { 0.0 })
+        .collect::<Vec<_>>();
+
+    let neighbour = |idx: usize, dx: isize, dy: isize| -> Option<usize> {
+        let x = (idx % width) as isize + dx;
+        let y = (idx / width) as isize + dy;
+        if x < 0 || y < 0 || x >= width as isize || y >= height as isize {
+            None
+        } else {
+            Some(y as usize * width + x as usize)
+        }
+    };
+
+    // the narrow band is made of the known pixels next to the masked ones.
+    // NOTE: the distances are non-negative so their bit representation preserves the order.
+    let mut heap = BinaryHeap::new();
+    for idx in 0..num_pixels {
+        if flags[idx] != KNOWN {
+            continue;
+        }
+        let is_boundary = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .iter()
+            .any(|&(dx, dy)| neighbour(idx, dx, dy).is_some_and(|n| flags[n] == INSIDE));
+        if is_boundary {
+            flags[idx] = BAND;
+            heap.push(Reverse((dist[idx].to_bits(), idx)));
+        }
+    }
+
+    // average intensity gradient over the pixels that are not inside the mask
+    let intensity_gradient = |values: &[f32], flags: &[u8], idx: usize| -> (f32, f32) {
+        let intensity = |i: usize| {
+            (0..CHANNELS).map(|c| values[i * CHANNELS + c]).sum::<f32>() / CHANNELS as f32
+        };
+        let derivative = |prev: Option<usize>, next: Option<usize>| {
+            let prev = prev.filter(|&i| flags[i] != INSIDE);
+            let next = next.filter(|&i| flags[i] != INSIDE);
+            match (prev, next) {
+                (Some(p), Some(n)) => (intensity(n) - intensity(p)) * 0.5,
+                (Some(p), None) => intensity(idx) - intensity(p),
+                (None, Some(n)) => intensity(n) - intensity(idx),
+                (None, None) => 0.0,
+            }
+        };
+        (
+            derivative(neighbour(idx, -1, 0), neighbour(idx, 1, 0)),
+            derivative(neighbour(idx, 0, -1), neighbour(idx, 0, 1)),
+        )
+    };
+
+    let radius = radius.max(1) as isize;
+
+    while let Some(Reverse((_, idx))) = heap.pop() {
+        if flags[idx] == KNOWN {
+            continue;
+        }
+        flags[idx] = KNOWN;
+
+        for &(dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)].iter() {
+            let n = match neighbour(idx, dx, dy) {
+                Some(n) if flags[n] != KNOWN => n,
+                _ => continue,
+            };
+
+            // update the distance to the boundary of the mask
+            let up = neighbour(n, 0, -1);
+            let down = neighbour(n, 0, 1);
+            let left = neighbour(n, -1, 0);
+            let right = neighbour(n, 1, 0);
+            let d = solve_eikonal(&flags, &dist, up, left)
+                .min(solve_eikonal(&flags, &dist, down, left))
+                .min(solve_eikonal(&flags, &dist, up, right))
+                .min(solve_eikonal(&flags, &dist, down, right));
+            dist[n] = dist[n].min(d);
+
+            if flags[n] == INSIDE {
+                // gradient of the distance map at the pixel to fill
+                let grad_dist = {
+                    let at = |i: Option<usize>| i.filter(|&i| flags[i] != INSIDE).map(|i| dist[i]);
+                    let diff = |prev: Option<f32>, next: Option<f32>| match (prev, next) {
+                        (Some(p), Some(q)) => (q - p) * 0.5,
+                        (Some(p), None) => dist[n] - p,
+                        (None, Some(q)) => q - dist[n],
+                        (None, None) => 0.0,
+                    };
+                    (diff(at(left), at(right)), diff(at(up), at(down)))
+                };
+
+                let (nx, ny) = ((n % width) as isize, (n / width) as isize);
+                let mut acc = [0f32; CHANNELS];
+                let mut sum_weights = 0f32;
+
+                for ky in (ny - radius).max(0)..(ny + radius + 1).min(height as isize) {
+                    for kx in (nx - radius).max(0)..(nx + radius + 1).min(width as isize) {
+                        let k = ky as usize * width + kx as usize;
+                        if flags[k] == INSIDE {
+                            continue;
+                        }
+
+                        let (rx, ry) = ((nx - kx) as f32, (ny - ky) as f32);
+                        let len2 = rx * rx + ry * ry;
+                        if len2 == 0.0 || len2 > (radius * radius) as f32 {
+                            continue;
+                        }
+
+                        let dst = 1.0 / (len2 * len2.sqrt());
+
+                        let w = match method {
+                            InpaintMethod::Telea => {
+                                let lev = 1.0 / (1.0 + (dist[k] - dist[n]).abs());
+                                let dir = (rx * grad_dist.0 + ry * grad_dist.1).abs().max(1e-6);
+                                dst * lev * dir
+                            }
+                            InpaintMethod::NavierStokes => {
+                                // favour the pixels lying along the isophote through them
+                                let (gx, gy) = intensity_gradient(&values, &flags, k);
+                                let norm = (gx * gx + gy * gy).sqrt() * len2.sqrt();
+                                let dir = if norm > 0.0 {
+                                    ((rx * -gy + ry * gx) / norm).abs().max(1e-6)
+                                } else {
+                                    1.0
+                                };
+                                dst * dir
+                            }
+                        };
+
+                        for (c, a) in acc.iter_mut().enumerate() {
+                            *a += w * values[k * CHANNELS + c];
+                        }
+                        sum_weights += w;
+                    }
+                }
+
+                if sum_weights > 0.0 {
+                    for (c, a) in acc.iter().enumerate() {
+                        values[n * CHANNELS + c] = a / sum_weights;
+                    }
+                }
+
+                flags[n] = BAND;
+            }
+
+            heap.push(Reverse((dist[n].to_bits(), n)));
+        }
+    }
+
+    let data = values.into_iter().map(T::from_f32).collect();
+
+    Image::new(src.size(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn inpaint_constant() -> Result<()> {
+        let size = ImageSize {
+            width: 7,
+            height: 6,
+        };
+        let image = Image::<f32, 3>::from_size_val(size, 0.5)?;
+
+        let mut mask = Image::<u8, 1>::from_size_val(size, 0)?;
+        for y in 2..4 {
+            for x in 2..5 {
+                mask.set_pixel(x, y, 0, 255)?;
+            }
+        }
+
+        for method in [
+            super::InpaintMethod::Telea,
+            super::InpaintMethod::NavierStokes,
+        ] {
+            let inpainted = super::inpaint(&image, &mask, 3, method)?;
+            assert_eq!(inpainted.size(), size);
+            for v in inpainted.data.iter() {
+                assert!((v - 0.5).abs() < 1e-5);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn inpaint_keeps_known_pixels() -> Result<()> {
+        let size = ImageSize {
+            width: 6,
+            height: 4,
+        };
+        let image = Image::<u8, 1>::new(size, (0..24).map(|x| x as u8 * 10).collect())?;
+
+        let mut mask = Image::<u8, 1>::from_size_val(size, 0)?;
+        mask.set_pixel(2, 1, 0, 1)?;
+        mask.set_pixel(3, 1, 0, 1)?;
+
+        let inpainted = super::inpaint(&image, &mask, 2, super::InpaintMethod::Telea)?;
+
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let v = inpainted.get_pixel(x, y, 0)?;
+                if mask.get_pixel(x, y, 0)? == 0 {
+                    assert_eq!(v, image.get_pixel(x, y, 0)?);
+                } else {
+                    // the filled values must be within the range of the neighbourhood
+                    assert!((10..=150).contains(&v));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -10,6 +10,40 @@ pub enum InterpolationMode {
     Nearest,
 }
 
+/// Border mode for the samples falling outside of the image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BorderMode {
+    /// Fill with a constant value: `iii|abcd|iii`.
+    Constant(f32),
+    /// Repeat the edge pixels: `aaa|abcd|ddd`.
+    Replicate,
+    /// Mirror the image at its edges: `cba|abcd|dcb`.
+    Reflect,
+    /// Tile the image: `bcd|abcd|abc`.
+    Wrap,
+}
+
+/// Map an index to the image according to the border mode.
+///
+/// # Returns
+///
+/// The index inside `[0, n)`, or `None` if the constant value must be used.
+fn border_index(i: i64, n: usize, border: BorderMode) -> Option<usize> {
+    let n = n as i64;
+    if (0..n).contains(&i) {
+        return Some(i as usize);
+    }
+    match border {
+        BorderMode::Constant(_) => None,
+        BorderMode::Replicate => Some(i.clamp(0, n - 1) as usize),
+        BorderMode::Reflect => {
+            let i = i.rem_euclid(2 * n);
+            Some(if i < n { i } else { 2 * n - 1 - i } as usize)
+        }
+        BorderMode::Wrap => Some(i.rem_euclid(n) as usize),
+    }
+}
+
 /// Kernel for interpolating a pixel value
 ///
 /// # Arguments
@@ -35,3 +69,113 @@ pub(crate) fn interpolate_pixel<T: ImageDtype>(
         InterpolationMode::Nearest => nearest_neighbor_interpolation(image, u, v, c),
     }
 }
+
+/// Kernel for interpolating a pixel value that may fall outside of the image
+///
+/// # Arguments
+///
+/// * `image` - The input image container with shape (height, width, channels).
+/// * `u` - The x coordinate of the pixel to interpolate.
+/// * `v` - The y coordinate of the pixel to interpolate.
+/// * `c` - The channel of the pixel to interpolate.
+/// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode giving the values outside of the image.
+///
+/// # Returns
+///
+/// The interpolated pixel value.
+pub(crate) fn interpolate_pixel_border<T: ImageDtype>(
+    image: &Array3<T>,
+    u: f32,
+    v: f32,
+    c: usize,
+    interpolation: InterpolationMode,
+    border: BorderMode,
+) -> T {
+    let (height, width, _) = image.dim();
+    let fill = match border {
+        BorderMode::Constant(value) => value,
+        _ => 0.0,
+    };
+    if width == 0 || height == 0 {
+        return T::from_f32(fill);
+    }
+
+    // the samples inside the image do not need the border
+    if u >= 0.0 && v >= 0.0 && u <= (width - 1) as f32 && v <= (height - 1) as f32 {
+        return interpolate_pixel(image, u, v, c, interpolation);
+    }
+
+    let sample = |iu: i64, iv: i64| -> f32 {
+        match (
+            border_index(iu, width, border),
+            border_index(iv, height, border),
+        ) {
+            (Some(iu), Some(iv)) => image[[iv, iu, c]].into(),
+            _ => fill,
+        }
+    };
+
+    match interpolation {
+        InterpolationMode::Nearest => T::from_f32(sample(u.round() as i64, v.round() as i64)),
+        InterpolationMode::Bilinear => {
+            let (iu, iv) = (u.floor() as i64, v.floor() as i64);
+            let (frac_u, frac_v) = (u - u.floor(), v - v.floor());
+            let frac_uu = 1. - frac_u;
+            let frac_vv = 1. - frac_v;
+
+            T::from_f32(
+                sample(iu, iv) * frac_uu * frac_vv
+                    + sample(iu + 1, iv) * frac_u * frac_vv
+                    + sample(iu, iv + 1) * frac_uu * frac_v
+                    + sample(iu + 1, iv + 1) * frac_u * frac_v,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interpolate_pixel_border, BorderMode, InterpolationMode};
+
+    #[test]
+    fn border_modes() {
+        let image = ndarray::Array3::from_shape_vec((1, 4, 1), vec![0f32, 1.0, 2.0, 3.0]).unwrap();
+        let sample = |u: f32, border: BorderMode| {
+            interpolate_pixel_border(&image, u, 0.0, 0, InterpolationMode::Nearest, border)
+        };
+
+        let columns = |border: BorderMode| {
+            (-3..7)
+                .map(|u| sample(u as f32, border))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            columns(BorderMode::Constant(9.0)),
+            vec![9.0, 9.0, 9.0, 0.0, 1.0, 2.0, 3.0, 9.0, 9.0, 9.0]
+        );
+        assert_eq!(
+            columns(BorderMode::Replicate),
+            vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 3.0, 3.0]
+        );
+        assert_eq!(
+            columns(BorderMode::Reflect),
+            vec![2.0, 1.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 2.0, 1.0]
+        );
+        assert_eq!(
+            columns(BorderMode::Wrap),
+            vec![1.0, 2.0, 3.0, 0.0, 1.0, 2.0, 3.0, 0.0, 1.0, 2.0]
+        );
+
+        // the bilinear samples blend the border with the edge pixels
+        let value = interpolate_pixel_border(
+            &image,
+            3.5,
+            0.0,
+            0,
+            InterpolationMode::Bilinear,
+            BorderMode::Constant(5.0),
+        );
+        assert_eq!(value, 4.0);
+    }
+}
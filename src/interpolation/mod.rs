@@ -5,7 +5,7 @@ mod nearest;
 mod remap;
 
 pub use grid::meshgrid;
-pub use interpolate::InterpolationMode;
+pub use interpolate::{BorderMode, InterpolationMode};
 pub use remap::remap;
 
-pub(crate) use interpolate::interpolate_pixel;
+pub(crate) use interpolate::{interpolate_pixel, interpolate_pixel_border};
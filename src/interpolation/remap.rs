@@ -1,5 +1,5 @@
-use super::interpolate::interpolate_pixel;
-use super::InterpolationMode;
+use super::interpolate::interpolate_pixel_border;
+use super::{BorderMode, InterpolationMode};
 use crate::image::Image;
 use anyhow::Result;
 
@@ -11,6 +11,7 @@ use anyhow::Result;
 /// * `map_x` - The x coordinates of the pixels to interpolate.
 /// * `map_y` - The y coordinates of the pixels to interpolate.
 /// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the coordinates outside of the image.
 ///
 /// # Returns
 ///
@@ -24,6 +25,7 @@ pub fn remap<const CHANNELS: usize>(
     map_x: &Image<f32, 1>,
     map_y: &Image<f32, 1>,
     interpolation: InterpolationMode,
+    border: BorderMode,
 ) -> Result<Image<f32, CHANNELS>> {
     if map_x.size() != map_y.size() {
         return Err(anyhow::anyhow!("map_x and map_y must have the same size"));
@@ -37,7 +39,7 @@ pub fn remap<const CHANNELS: usize>(
         .par_for_each(|mut out, u, v| {
             let (u, v) = (u[0], v[0]);
             for c in 0..CHANNELS {
-                out[c] = interpolate_pixel(&src.data, u, v, c, interpolation);
+                out[c] = interpolate_pixel_border(&src.data, u, v, c, interpolation, border);
             }
         });
 
@@ -81,8 +83,13 @@ mod tests {
             vec![0.0, 2.0, 6.0, 8.0],
         )?;
 
-        let image_transformed =
-            super::remap(&image, &map_x, &map_y, super::InterpolationMode::Bilinear)?;
+        let image_transformed = super::remap(
+            &image,
+            &map_x,
+            &map_y,
+            super::InterpolationMode::Bilinear,
+            super::BorderMode::Constant(0.0),
+        )?;
         assert_eq!(image_transformed.num_channels(), 1);
         assert_eq!(image_transformed.size().width, 2);
         assert_eq!(image_transformed.size().height, 2);
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::image::{Image, ImageSize};
+
+/// Reads an AVIF image from the given file path.
+///
+/// The AV1 bitstream is decoded with dav1d through the image crate. Images with more
+/// than 8 bits per sample are converted to 8 bits and the alpha channel is discarded.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the AVIF image.
+///
+/// # Returns
+///
+/// An RGB image containing the AVIF image data.
+///
+/// # Errors
+///
+/// Returns an error if the file does not exist or cannot be decoded.
+pub fn read_image_avif(file_path: &Path) -> Result<Image<u8, 3>> {
+    if !file_path.exists() {
+        return Err(anyhow::anyhow!(
+            "File does not exist: {}",
+            file_path.to_string_lossy()
+        ));
+    }
+
+    // open the file and map it to memory
+    let file = std::fs::File::open(file_path)?;
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+    let img = image::io::Reader::with_format(std::io::Cursor::new(&mmap), image::ImageFormat::Avif)
+        .decode()?;
+
+    Image::new(
+        ImageSize {
+            width: img.width() as usize,
+            height: img.height() as usize,
+        },
+        img.to_rgb8().into_raw(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use std::path::Path;
+
+    #[test]
+    fn read_avif_invalid() -> Result<()> {
+        assert!(super::read_image_avif(Path::new("missing.avif")).is_err());
+        // a JPEG file is not a valid AVIF file
+        assert!(super::read_image_avif(Path::new("tests/data/dog.jpeg")).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::image::{Image, ImageSize};
+
+/// The size of the BMP file header.
+const FILE_HEADER_SIZE: usize = 14;
+
+/// The size of the BITMAPINFOHEADER, the smallest supported info header.
+const INFO_HEADER_SIZE: usize = 40;
+
+/// The uncompressed BMP compression type.
+const BI_RGB: u32 = 0;
+
+/// The BMP compression type with explicit channel masks.
+const BI_BITFIELDS: u32 = 3;
+
+fn read_u16(data: &[u8], pos: usize) -> Result<u16> {
+    data.get(pos..pos + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| anyhow::anyhow!("The BMP file is truncated."))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Result<u32> {
+    data.get(pos..pos + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| anyhow::anyhow!("The BMP file is truncated."))
+}
+
+/// Decode the pixels of a BMP file as RGB.
+fn decode_bmp(data: &[u8]) -> Result<Image<u8, 3>> {
+    if data.get(..2) != Some(b"BM") {
+        return Err(anyhow::anyhow!("Not a BMP file."));
+    }
+
+    let pixel_offset = read_u32(data, 10)? as usize;
+    let info_size = read_u32(data, FILE_HEADER_SIZE)? as usize;
+    if info_size < INFO_HEADER_SIZE {
+        return Err(anyhow::anyhow!(
+            "Unsupported BMP info header of {} bytes.",
+            info_size
+        ));
+    }
+
+    let width = read_u32(data, 18)? as i32;
+    let height = read_u32(data, 22)? as i32;
+    let bits_per_pixel = read_u16(data, 28)?;
+    let compression = read_u32(data, 30)?;
+    let colors_used = read_u32(data, 46)? as usize;
+
+    if width <= 0 || height == 0 {
+        return Err(anyhow::anyhow!("Invalid BMP size {}x{}.", width, height));
+    }
+
+    // the rows are stored bottom-up unless the height is negative
+    let top_down = height < 0;
+    let (width, height) = (width as usize, height.unsigned_abs() as usize);
+
+    match (bits_per_pixel, compression) {
+        (8 | 24 | 32, BI_RGB) => {}
+        (32, BI_BITFIELDS) => {
+            // only the default layout of the channels is supported
+            let masks = [
+                read_u32(data, 54)?,
+                read_u32(data, 58)?,
+                read_u32(data, 62)?,
+            ];
+            if masks != [0x00ff_0000, 0x0000_ff00, 0x0000_00ff] {
+                return Err(anyhow::anyhow!(
+                    "Unsupported BMP channel masks {:x?}.",
+                    masks
+                ));
+            }
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported BMP with {} bits per pixel and compression {}.",
+                bits_per_pixel,
+                compression
+            ))
+        }
+    }
+
+    // the palette entries are stored as BGR0 after the info header
+    let palette = if bits_per_pixel == 8 {
+        let num_colors = if colors_used == 0 { 256 } else { colors_used };
+        let start = FILE_HEADER_SIZE + info_size;
+        let entries = data
+            .get(start..start + 4 * num_colors)
+            .ok_or_else(|| anyhow::anyhow!("The BMP palette is truncated."))?;
+        entries
+            .chunks_exact(4)
+            .map(|c| [c[2], c[1], c[0]])
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+
+    // each row is padded to a multiple of 4 bytes
+    let bytes_per_pixel = bits_per_pixel as usize / 8;
+    let row_size = (width * bytes_per_pixel).div_ceil(4) * 4;
+    let pixels = data
+        .get(pixel_offset..pixel_offset + row_size * height)
+        .ok_or_else(|| anyhow::anyhow!("The BMP pixel data is truncated."))?;
+
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        let row_index = if top_down { y } else { height - 1 - y };
+        let row = &pixels[row_index * row_size..row_index * row_size + width * bytes_per_pixel];
+        if bits_per_pixel == 8 {
+            for &index in row {
+                let color = palette.get(index as usize).ok_or_else(|| {
+                    anyhow::anyhow!("The BMP palette index {} is out of range.", index)
+                })?;
+                rgb.extend_from_slice(color);
+            }
+        } else {
+            for bgr in row.chunks_exact(bytes_per_pixel) {
+                rgb.extend_from_slice(&[bgr[2], bgr[1], bgr[0]]);
+            }
+        }
+    }
+
+    Image::new(ImageSize { width, height }, rgb)
+}
+
+/// Reads a BMP image from the given file path.
+///
+/// Uncompressed 24-bit and 32-bit images are supported, as well as 8-bit images with a
+/// color palette. The alpha channel of 32-bit images is discarded.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the BMP image.
+///
+/// # Returns
+///
+/// An RGB image containing the BMP image data.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a valid BMP file or uses an unsupported
+/// compression or pixel format.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::bmp::{read_image_bmp, write_image_bmp};
+///
+/// let image = Image::<u8, 3>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![255, 0, 0, 0, 255, 0, 0, 0, 255],
+/// )
+/// .unwrap();
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("image.bmp");
+/// write_image_bmp(&file_path, &image).unwrap();
+///
+/// let image_back = read_image_bmp(&file_path).unwrap();
+/// assert_eq!(image_back.data, image.data);
+/// ```
+pub fn read_image_bmp(file_path: &Path) -> Result<Image<u8, 3>> {
+    let data = std::fs::read(file_path)?;
+    decode_bmp(&data)
+}
+
+/// Encode an RGB image as an uncompressed 24-bit bottom-up BMP.
+fn encode_bmp(image: &Image<u8, 3>) -> Vec<u8> {
+    let (width, height) = (image.width(), image.height());
+    let row_size = (width * 3).div_ceil(4) * 4;
+    let pixel_offset = FILE_HEADER_SIZE + INFO_HEADER_SIZE;
+    let file_size = pixel_offset + row_size * height;
+
+    let mut data = Vec::with_capacity(file_size);
+
+    // file header
+    data.extend_from_slice(b"BM");
+    data.extend_from_slice(&(file_size as u32).to_le_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&(pixel_offset as u32).to_le_bytes());
+
+    // info header, with a resolution of 72 dpi
+    data.extend_from_slice(&(INFO_HEADER_SIZE as u32).to_le_bytes());
+    data.extend_from_slice(&(width as i32).to_le_bytes());
+    data.extend_from_slice(&(height as i32).to_le_bytes());
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&24u16.to_le_bytes());
+    data.extend_from_slice(&BI_RGB.to_le_bytes());
+    data.extend_from_slice(&((row_size * height) as u32).to_le_bytes());
+    data.extend_from_slice(&2835i32.to_le_bytes());
+    data.extend_from_slice(&2835i32.to_le_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes());
+
+    // pixel data, bottom-up and in BGR order
+    let padding = row_size - width * 3;
+    for y in (0..height).rev() {
+        for x in 0..width {
+            data.extend((0..3).rev().map(|c| image.data[[y, x, c]]));
+        }
+        data.resize(data.len() + padding, 0);
+    }
+
+    data
+}
+
+/// Writes an RGB image to an uncompressed 24-bit BMP file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the BMP image.
+/// * `image` - The image to write.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_image_bmp(file_path: &Path, image: &Image<u8, 3>) -> Result<()> {
+    std::fs::write(file_path, encode_bmp(image))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn bmp_roundtrip() -> Result<()> {
+        // a width of 3 pixels needs 3 bytes of padding per row
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 3,
+                height: 2,
+            },
+            (0..18).map(|i| (i * 10) as u8).collect(),
+        )?;
+
+        let data = super::encode_bmp(&image);
+        assert_eq!(data.len(), 54 + 2 * 12);
+        assert_eq!(super::decode_bmp(&data)?.data, image.data);
+
+        // a top-down 32-bit image
+        let mut data = data[..54].to_vec();
+        data[22..26].copy_from_slice(&(-2i32).to_le_bytes());
+        data[28..30].copy_from_slice(&32u16.to_le_bytes());
+        for (i, bgra) in image.data.as_slice().unwrap().chunks(3).enumerate() {
+            data.extend_from_slice(&[bgra[2], bgra[1], bgra[0], i as u8]);
+        }
+        assert_eq!(super::decode_bmp(&data)?.data, image.data);
+
+        assert!(super::decode_bmp(b"PNG").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn bmp_palette() -> Result<()> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 2,
+                height: 2,
+            },
+            vec![0; 12],
+        )?;
+
+        // an 8-bit image with a palette of two colors
+        let mut data = super::encode_bmp(&image)[..54].to_vec();
+        data[10..14].copy_from_slice(&(54u32 + 8).to_le_bytes());
+        data[28..30].copy_from_slice(&8u16.to_le_bytes());
+        data[46..50].copy_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[0, 0, 255, 0, 255, 0, 0, 0]);
+        // bottom row first, each padded to 4 bytes
+        data.extend_from_slice(&[1, 1, 0, 0, 0, 1, 0, 0]);
+
+        let decoded = super::decode_bmp(&data)?;
+        assert_eq!(
+            decoded.data.as_slice().unwrap(),
+            &[255, 0, 0, 0, 0, 255, 0, 0, 255, 0, 0, 255]
+        );
+
+        // the palette index 2 is out of range
+        let last = data.len() - 4;
+        data[last] = 2;
+        assert!(super::decode_bmp(&data).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,585 @@
+use anyhow::Result;
+use arrow_array::{Array, ArrayRef, LargeBinaryArray, RecordBatch, UInt32Array};
+use arrow_buffer::{Buffer, OffsetBuffer};
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightClient, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaResult, Ticket,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::image::{Image, ImageSize};
+use crate::pipeline::ImageBatch;
+use crate::tensor::{CpuAllocator, Tensor, TensorAllocator};
+
+/// The record batches served for a ticket.
+pub type BatchIterator = Box<dyn Iterator<Item = Result<RecordBatch>> + Send>;
+
+/// The default limit of the size of the messages, large enough for raw 4K RGB frames.
+///
+/// The tonic default of 4 MiB is smaller than a record batch holding a raw 1080p RGB frame.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+type FlightStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+/// The (height, width, channels) of each row and the pixels of a record batch.
+type ImageColumns<'a> = (Vec<(usize, usize, usize)>, &'a LargeBinaryArray);
+
+/// The schema of the record batches of images.
+///
+/// Each row is an image with its size and its interleaved pixels, so that a batch of
+/// images of the same size holds its pixels in a single contiguous buffer.
+pub fn image_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("height", DataType::UInt32, false),
+        Field::new("width", DataType::UInt32, false),
+        Field::new("channels", DataType::UInt32, false),
+        Field::new("data", DataType::LargeBinary, false),
+    ]))
+}
+
+/// Create a record batch from the size columns and the pixels of the images.
+fn image_record_batch(
+    sizes: Vec<(u32, u32, u32)>,
+    offsets: OffsetBuffer<i64>,
+    values: Buffer,
+) -> Result<RecordBatch> {
+    let column = |f: fn(&(u32, u32, u32)) -> u32| -> ArrayRef {
+        Arc::new(UInt32Array::from_iter_values(sizes.iter().map(f)))
+    };
+    Ok(RecordBatch::try_new(
+        image_schema(),
+        vec![
+            column(|s| s.0),
+            column(|s| s.1),
+            column(|s| s.2),
+            Arc::new(LargeBinaryArray::try_new(offsets, values, None)?),
+        ],
+    )?)
+}
+
+/// Convert a batch of images to a record batch.
+///
+/// # Arguments
+///
+/// * `batch` - The images to convert.
+///
+/// # Returns
+///
+/// The record batch with one row per image, see [`image_schema`].
+pub fn record_batch_from_images<const CHANNELS: usize>(
+    batch: &ImageBatch<CHANNELS>,
+) -> Result<RecordBatch> {
+    let size = batch.image_size();
+    let mut values = Vec::with_capacity(batch.shape().iter().product());
+    for image in batch.images() {
+        values.extend_from_slice(&image.as_contiguous_slice());
+    }
+
+    let dims = (size.height as u32, size.width as u32, CHANNELS as u32);
+    let lengths = std::iter::repeat_n(size.height * size.width * CHANNELS, batch.len());
+    image_record_batch(
+        vec![dims; batch.len()],
+        OffsetBuffer::from_lengths(lengths),
+        Buffer::from_vec(values),
+    )
+}
+
+/// Convert a tensor of images to a record batch without copying the pixels.
+///
+/// # Arguments
+///
+/// * `tensor` - The contiguous images, with shape `[N, H, W, C]`.
+///
+/// # Returns
+///
+/// The record batch sharing the storage of the tensor, see [`image_schema`].
+///
+/// # Errors
+///
+/// If the tensor is not contiguous, an error is returned.
+pub fn record_batch_from_tensor<A: TensorAllocator>(
+    tensor: &Tensor<u8, 4, A>,
+) -> Result<RecordBatch> {
+    if !tensor.layout().is_contiguous() {
+        return Err(anyhow::anyhow!("The tensor must be contiguous."));
+    }
+
+    let [n, height, width, channels] = tensor.shape;
+    let lengths = std::iter::repeat_n(height * width * channels, n);
+    image_record_batch(
+        vec![(height as u32, width as u32, channels as u32); n],
+        OffsetBuffer::from_lengths(lengths),
+        tensor.storage.data.clone(),
+    )
+}
+
+/// Read the size columns and the pixels of a record batch.
+fn image_columns(batch: &RecordBatch) -> Result<ImageColumns<'_>> {
+    let uint32 = |name: &str| {
+        batch
+            .column_by_name(name)
+            .and_then(|c| c.as_any().downcast_ref::<UInt32Array>())
+            .ok_or_else(|| anyhow::anyhow!("The record batch has no `{}` column", name))
+    };
+    let (height, width, channels) = (uint32("height")?, uint32("width")?, uint32("channels")?);
+    let data = batch
+        .column_by_name("data")
+        .and_then(|c| c.as_any().downcast_ref::<LargeBinaryArray>())
+        .ok_or_else(|| anyhow::anyhow!("The record batch has no `data` column"))?;
+
+    let sizes = (0..batch.num_rows())
+        .map(|i| {
+            (
+                height.value(i) as usize,
+                width.value(i) as usize,
+                channels.value(i) as usize,
+            )
+        })
+        .collect();
+
+    Ok((sizes, data))
+}
+
+/// Convert a record batch of images of the same size to a tensor without copying the pixels.
+///
+/// # Arguments
+///
+/// * `batch` - The record batch, see [`image_schema`].
+///
+/// # Returns
+///
+/// The images as a tensor with shape `[N, H, W, C]` sharing the buffer of the batch.
+///
+/// # Errors
+///
+/// If the batch is empty, the images have different sizes or the pixels are not
+/// contiguous, an error is returned.
+pub fn record_batch_to_tensor(batch: &RecordBatch) -> Result<Tensor<u8, 4>> {
+    let (sizes, data) = image_columns(batch)?;
+    let (height, width, channels) = *sizes
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("The record batch is empty"))?;
+    if sizes.iter().any(|s| *s != (height, width, channels)) {
+        return Err(anyhow::anyhow!(
+            "The images of the record batch have different sizes"
+        ));
+    }
+
+    // the pixels must be stored back to back to share the buffer
+    let len = height * width * channels;
+    let offsets = data.value_offsets();
+    let start = offsets[0] as usize;
+    let contiguous = offsets
+        .iter()
+        .enumerate()
+        .all(|(i, &offset)| offset as usize == start + i * len);
+    if !contiguous || data.null_count() > 0 {
+        return Err(anyhow::anyhow!(
+            "The pixels of the record batch are not contiguous"
+        ));
+    }
+
+    let values = data.values().slice_with_length(start, sizes.len() * len);
+    Ok(Tensor::from_shape_buffer(
+        [sizes.len(), height, width, channels],
+        values,
+        CpuAllocator,
+    )?)
+}
+
+/// Convert a record batch to images, which may have different sizes.
+///
+/// # Arguments
+///
+/// * `batch` - The record batch, see [`image_schema`].
+///
+/// # Returns
+///
+/// The images of the batch.
+///
+/// # Errors
+///
+/// If an image has a number of channels different from `CHANNELS`, an error is returned.
+pub fn record_batch_to_images<const CHANNELS: usize>(
+    batch: &RecordBatch,
+) -> Result<Vec<Image<u8, CHANNELS>>> {
+    let (sizes, data) = image_columns(batch)?;
+    sizes
+        .into_iter()
+        .enumerate()
+        .map(|(i, (height, width, channels))| {
+            if channels != CHANNELS {
+                return Err(anyhow::anyhow!(
+                    "The image {} has {} channels but {} were requested",
+                    i,
+                    channels,
+                    CHANNELS
+                ));
+            }
+            Image::new(ImageSize { width, height }, data.value(i).to_vec())
+        })
+        .collect()
+}
+
+/// A Flight service streaming record batches of images.
+///
+/// The ticket of a `DoGet` request is passed as a string to the source, which returns
+/// the record batches to stream. The source runs on a blocking thread and up to
+/// `prefetch` batches are prepared ahead of the client.
+pub struct ImageFlightService<F> {
+    source: Arc<F>,
+    prefetch: usize,
+    max_message_size: usize,
+}
+
+impl<F> ImageFlightService<F>
+where
+    F: Fn(&str) -> Result<BatchIterator> + Send + Sync + 'static,
+{
+    /// Creates a new ImageFlightService object.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The function returning the record batches of a ticket
+    pub fn new(source: F) -> Self {
+        Self {
+            source: Arc::new(source),
+            prefetch: 2,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+
+    /// Sets the number of batches prepared ahead of the client.
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch.max(1);
+        self
+    }
+
+    /// Sets the limit of the size of the received and sent messages in bytes.
+    ///
+    /// Defaults to [`DEFAULT_MAX_MESSAGE_SIZE`].
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Serves the service until the task is cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address to listen on, e.g. `0.0.0.0:50051`
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let max_message_size = self.max_message_size;
+        let server = FlightServiceServer::new(self)
+            .max_decoding_message_size(max_message_size)
+            .max_encoding_message_size(max_message_size);
+        tonic::transport::Server::builder()
+            .add_service(server)
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+}
+
+// the large `Status` errors are imposed by the signatures of the tonic service
+#[allow(clippy::result_large_err)]
+#[tonic::async_trait]
+impl<F> FlightService for ImageFlightService<F>
+where
+    F: Fn(&str) -> Result<BatchIterator> + Send + Sync + 'static,
+{
+    type HandshakeStream = FlightStream<HandshakeResponse>;
+    type ListFlightsStream = FlightStream<FlightInfo>;
+    type DoGetStream = FlightStream<FlightData>;
+    type DoPutStream = FlightStream<PutResult>;
+    type DoActionStream = FlightStream<arrow_flight::Result>;
+    type ListActionsStream = FlightStream<ActionType>;
+    type DoExchangeStream = FlightStream<FlightData>;
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let ticket = String::from_utf8(request.into_inner().ticket.to_vec())
+            .map_err(|_| Status::invalid_argument("The ticket is not valid UTF-8"))?;
+
+        // produce the batches on a blocking thread, since loading images blocks
+        let (tx, rx) = tokio::sync::mpsc::channel(self.prefetch);
+        let source = self.source.clone();
+        tokio::task::spawn_blocking(move || {
+            let batches = match source(&ticket) {
+                Ok(batches) => batches,
+                Err(err) => {
+                    let _ = tx.blocking_send(Err(FlightError::ExternalError(err.into())));
+                    return;
+                }
+            };
+            for batch in batches {
+                let batch = batch.map_err(|err| FlightError::ExternalError(err.into()));
+                if tx.blocking_send(batch).is_err() {
+                    // the client disconnected
+                    break;
+                }
+            }
+        });
+
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(image_schema())
+            .build(tokio_stream::wrappers::ReceiverStream::new(rx))
+            .map(|data| data.map_err(|err| Status::internal(err.to_string())));
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        Err(Status::unimplemented("get_schema"))
+    }
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("handshake"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("list_flights"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Err(Status::unimplemented("get_flight_info"))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("poll_flight_info"))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("do_put"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("do_action"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Err(Status::unimplemented("list_actions"))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("do_exchange"))
+    }
+}
+
+/// A Flight client fetching record batches of images from an [`ImageFlightService`].
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::io::flight::ImageFlightClient;
+/// use tokio_stream::StreamExt;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut client = ImageFlightClient::connect("http://localhost:50051")
+///     .await
+///     .unwrap();
+///
+/// let mut batches = client.fetch_tensors("train/epoch-0").await.unwrap();
+/// while let Some(batch) = batches.next().await {
+///     let batch = batch.unwrap();
+///     println!("received a batch with shape {:?}", batch.shape);
+/// }
+/// # });
+/// ```
+pub struct ImageFlightClient {
+    client: FlightClient,
+}
+
+impl ImageFlightClient {
+    /// Connects to a Flight service.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The url of the service, e.g. `http://localhost:50051`
+    pub async fn connect(url: &str) -> Result<Self> {
+        let channel = tonic::transport::Endpoint::from_shared(url.to_string())?
+            .connect()
+            .await?;
+        Ok(Self {
+            client: FlightClient::new(channel),
+        }
+        .with_max_message_size(DEFAULT_MAX_MESSAGE_SIZE))
+    }
+
+    /// Sets the limit of the size of the sent and received messages in bytes.
+    ///
+    /// Defaults to [`DEFAULT_MAX_MESSAGE_SIZE`].
+    pub fn with_max_message_size(self, max_message_size: usize) -> Self {
+        let client = self
+            .client
+            .into_inner()
+            .max_decoding_message_size(max_message_size)
+            .max_encoding_message_size(max_message_size);
+        Self {
+            client: FlightClient::new_from_inner(client),
+        }
+    }
+
+    /// Fetches the record batches of a ticket.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticket` - The ticket passed to the source of the service
+    ///
+    /// # Returns
+    ///
+    /// The stream of record batches, see [`image_schema`].
+    pub async fn fetch(&mut self, ticket: &str) -> Result<impl Stream<Item = Result<RecordBatch>>> {
+        let stream = self.client.do_get(Ticket::new(ticket.to_string())).await?;
+        Ok(stream.map(|batch| batch.map_err(anyhow::Error::from)))
+    }
+
+    /// Fetches the record batches of a ticket as tensors, without copying the pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticket` - The ticket passed to the source of the service
+    ///
+    /// # Returns
+    ///
+    /// The stream of tensors with shape `[N, H, W, C]`, see [`record_batch_to_tensor`].
+    pub async fn fetch_tensors(
+        &mut self,
+        ticket: &str,
+    ) -> Result<impl Stream<Item = Result<Tensor<u8, 4>>>> {
+        let stream = self.fetch(ticket).await?;
+        Ok(stream.map(|batch| record_batch_to_tensor(&batch?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        record_batch_from_images, record_batch_from_tensor, record_batch_to_images,
+        record_batch_to_tensor, BatchIterator, ImageFlightClient, ImageFlightService,
+    };
+    use crate::image::{Image, ImageSize};
+    use crate::pipeline::ImageBatch;
+    use crate::tensor::{CpuAllocator, Tensor};
+    use tokio_stream::StreamExt;
+
+    #[test]
+    fn tensor_record_batch_roundtrip() -> anyhow::Result<()> {
+        let data = (0..2 * 3 * 4 * 3).map(|x| x as u8).collect::<Vec<_>>();
+        let tensor = Tensor::<u8, 4>::from_shape_vec([2, 3, 4, 3], data, CpuAllocator)?;
+
+        let batch = record_batch_from_tensor(&tensor)?;
+        assert_eq!(batch.num_rows(), 2);
+
+        // the pixels are shared, not copied
+        let decoded = record_batch_to_tensor(&batch)?;
+        assert_eq!(decoded.shape, [2, 3, 4, 3]);
+        assert_eq!(decoded.as_slice().as_ptr(), tensor.as_slice().as_ptr());
+
+        let images = record_batch_to_images::<3>(&batch)?;
+        assert_eq!(
+            images[1].size(),
+            ImageSize {
+                width: 4,
+                height: 3
+            }
+        );
+        assert_eq!(images[1].data[[0, 0, 0]], 36);
+        assert!(record_batch_to_images::<1>(&batch).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn image_batch_record_batch() -> anyhow::Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+        let images = (0..3)
+            .map(|i| Image::<u8, 1>::from_size_val(size, i))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let batch = record_batch_from_images(&ImageBatch::new(images)?)?;
+
+        let tensor = record_batch_to_tensor(&batch)?;
+        assert_eq!(tensor.shape, [3, 2, 2, 1]);
+        assert_eq!(tensor.as_slice()[8..12], [2, 2, 2, 2]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn flight_large_batch() -> anyhow::Result<()> {
+        // a raw frame of about 5 MiB, above the tonic default limit of 4 MiB
+        let size = ImageSize {
+            width: 1280,
+            height: 1280,
+        };
+        let service = ImageFlightService::new(move |_: &str| {
+            let image = Image::<u8, 3>::from_size_val(size, 7)?;
+            let batch = record_batch_from_images(&ImageBatch::new(vec![image])?)?;
+            Ok(Box::new(std::iter::once(Ok(batch))) as BatchIterator)
+        });
+
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let server = tokio::spawn(service.serve(addr));
+
+        // wait for the server to listen
+        let url = format!("http://{}", addr);
+        let mut client = loop {
+            match ImageFlightClient::connect(&url).await {
+                Ok(client) => break client,
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(20)).await,
+            }
+        };
+
+        let batches = client
+            .fetch_tensors("frames")
+            .await?
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(batches.len(), 1);
+        let tensor = batches.into_iter().next().unwrap()?;
+        assert_eq!(tensor.shape, [1, 1280, 1280, 3]);
+
+        server.abort();
+        Ok(())
+    }
+}
@@ -21,6 +21,9 @@ impl FpsCounter {
 
         if duration >= Duration::new(1, 0) {
             let fps = self.frame_count as f32 / duration.as_secs_f32();
+            #[cfg(feature = "tracing")]
+            tracing::info!(fps, "frame rate");
+            #[cfg(not(feature = "tracing"))]
             println!("FPS: {:.2}", fps);
 
             // Reset for the next calculation
@@ -0,0 +1,147 @@
+use anyhow::Result;
+use std::time::Duration;
+
+use super::functional::{decode_image, encode_image, ImageFormat};
+use crate::image::{Image, ImageSize};
+
+/// The encoding of the pixels of a transported or recorded frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameEncoding {
+    /// The raw interleaved pixels.
+    #[default]
+    Raw,
+    /// A JPEG image with the given quality in [1, 100].
+    Jpeg {
+        /// The JPEG quality.
+        quality: u8,
+    },
+}
+
+/// The header describing the payload of a frame.
+///
+/// The header is 24 bytes in little-endian: the width, the height and the number of
+/// channels as `u32`, the encoding as `u32` (0 for raw, 1 for JPEG) and the timestamp in
+/// nanoseconds as `u64`, so that it can be parsed from other languages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FrameHeader {
+    pub(crate) size: ImageSize,
+    pub(crate) channels: u32,
+    pub(crate) jpeg: bool,
+    pub(crate) timestamp: Duration,
+}
+
+impl FrameHeader {
+    pub(crate) const LEN: usize = 24;
+
+    pub(crate) fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0..4].copy_from_slice(&(self.size.width as u32).to_le_bytes());
+        bytes[4..8].copy_from_slice(&(self.size.height as u32).to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.channels.to_le_bytes());
+        bytes[12..16].copy_from_slice(&(self.jpeg as u32).to_le_bytes());
+        bytes[16..24].copy_from_slice(&(self.timestamp.as_nanos() as u64).to_le_bytes());
+        bytes
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::LEN {
+            return Err(anyhow::anyhow!(
+                "Invalid frame header of {} bytes, expected {}",
+                bytes.len(),
+                Self::LEN
+            ));
+        }
+        let u32_at =
+            |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(&bytes[16..24]);
+
+        let jpeg = match u32_at(12) {
+            0 => false,
+            1 => true,
+            encoding => return Err(anyhow::anyhow!("Unknown frame encoding: {}", encoding)),
+        };
+
+        Ok(Self {
+            size: ImageSize {
+                width: u32_at(0) as usize,
+                height: u32_at(4) as usize,
+            },
+            channels: u32_at(8),
+            jpeg,
+            timestamp: Duration::from_nanos(u64::from_le_bytes(timestamp)),
+        })
+    }
+}
+
+/// Encode a frame to its header and payload.
+///
+/// The raw payload is a contiguous copy of the pixels, gathered in a single pass for the
+/// strided images.
+pub(crate) fn encode_frame<const CHANNELS: usize>(
+    image: &Image<u8, CHANNELS>,
+    encoding: FrameEncoding,
+    timestamp: Duration,
+) -> Result<(FrameHeader, Vec<u8>)> {
+    let payload = match encoding {
+        FrameEncoding::Raw => image.as_contiguous_slice().into_owned(),
+        FrameEncoding::Jpeg { quality } => encode_image(image, ImageFormat::Jpeg, quality)?,
+    };
+    let header = FrameHeader {
+        size: image.size(),
+        channels: CHANNELS as u32,
+        jpeg: matches!(encoding, FrameEncoding::Jpeg { .. }),
+        timestamp,
+    };
+
+    Ok((header, payload))
+}
+
+/// Decode the payload of a frame described by its header.
+pub(crate) fn decode_frame_payload<const CHANNELS: usize>(
+    header: &FrameHeader,
+    payload: Vec<u8>,
+) -> Result<Image<u8, CHANNELS>> {
+    if header.channels as usize != CHANNELS {
+        return Err(anyhow::anyhow!(
+            "The frame has {} channels but {} were requested",
+            header.channels,
+            CHANNELS
+        ));
+    }
+
+    if header.jpeg {
+        Ok(decode_image(&payload)?)
+    } else {
+        Image::new(header.size, payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_frame_payload, FrameHeader};
+    use crate::image::ImageSize;
+    use std::time::Duration;
+
+    #[test]
+    fn frame_header_roundtrip() -> anyhow::Result<()> {
+        let header = FrameHeader {
+            size: ImageSize {
+                width: 2,
+                height: 1,
+            },
+            channels: 3,
+            jpeg: false,
+            timestamp: Duration::from_micros(1500),
+        };
+        let bytes = header.to_bytes();
+        assert_eq!(FrameHeader::from_bytes(&bytes)?, header);
+        assert!(FrameHeader::from_bytes(&bytes[..8]).is_err());
+
+        let image = decode_frame_payload::<3>(&header, vec![1, 2, 3, 4, 5, 6])?;
+        assert_eq!(image.data[[0, 1, 2]], 6);
+        assert!(decode_frame_payload::<1>(&header, vec![0; 6]).is_err());
+
+        Ok(())
+    }
+}
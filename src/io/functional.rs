@@ -1,10 +1,15 @@
 use anyhow::Result;
 use std::path::Path;
 
+use crate::error::KorniaError;
 use crate::image::{Image, ImageSize};
 
+use super::mmap::MappedImage;
+
+#[cfg(feature = "jpegturbo")]
+use super::jpeg::{ImageDecoder, ImageEncoder, JpegScale};
 #[cfg(feature = "jpegturbo")]
-use super::jpeg::{ImageDecoder, ImageEncoder};
+use crate::geometry::Rect;
 
 #[cfg(feature = "jpegturbo")]
 /// Reads a JPEG image from the given file path.
@@ -31,23 +36,8 @@ use super::jpeg::{ImageDecoder, ImageEncoder};
 /// assert_eq!(image.size().height, 195);
 /// assert_eq!(image.num_channels(), 3);
 /// ```
-pub fn read_image_jpeg(file_path: &Path) -> Result<Image<u8, 3>> {
-    // verify the file exists and is a JPEG
-    if !file_path.exists() {
-        return Err(anyhow::anyhow!(
-            "File does not exist: {}",
-            file_path.to_str().unwrap()
-        ));
-    }
-
-    if file_path.extension().map_or(true, |ext| {
-        ext.to_ascii_lowercase() != "jpg" && ext.to_ascii_lowercase() != "jpeg"
-    }) {
-        return Err(anyhow::anyhow!(
-            "File is not a JPEG: {}",
-            file_path.to_str().unwrap()
-        ));
-    }
+pub fn read_image_jpeg(file_path: &Path) -> Result<Image<u8, 3>, KorniaError> {
+    check_jpeg_path(file_path)?;
 
     // open the file and map it to memory
     let file = std::fs::File::open(file_path)?;
@@ -62,6 +52,106 @@ pub fn read_image_jpeg(file_path: &Path) -> Result<Image<u8, 3>> {
     Ok(image)
 }
 
+#[cfg(feature = "jpegturbo")]
+/// Reads a JPEG image from the given file path at a reduced resolution.
+///
+/// The image is downscaled by libjpeg-turbo while decoding, which is much faster than
+/// decoding the full resolution image to resize it afterwards, e.g. to create thumbnails.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JPEG image.
+/// * `scale` - The scaling factor applied while decoding.
+///
+/// # Returns
+///
+/// An image containing the scaled JPEG image data, with each side rounded up.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::io::functional as F;
+/// use kornia_rs::io::jpeg::JpegScale;
+///
+/// let image_path = std::path::Path::new("tests/data/dog.jpeg");
+/// let image = F::read_image_jpeg_scaled(image_path, JpegScale::Quarter).unwrap();
+/// assert_eq!(image.size().width, 65);
+/// assert_eq!(image.size().height, 49);
+/// ```
+pub fn read_image_jpeg_scaled(
+    file_path: &Path,
+    scale: JpegScale,
+) -> Result<Image<u8, 3>, KorniaError> {
+    check_jpeg_path(file_path)?;
+
+    // open the file and map it to memory
+    let file = std::fs::File::open(file_path)?;
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+    ImageDecoder::new()?.decode_scaled(&mmap, scale)
+}
+
+#[cfg(feature = "jpegturbo")]
+/// Reads a region of interest of a JPEG image from the given file path.
+///
+/// Only the MCU blocks covering the region are decoded, which is much faster than
+/// decoding the full image to crop it afterwards, e.g. to sample patches of large images.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JPEG image.
+/// * `rect` - The region to decode in pixel coordinates.
+///
+/// # Returns
+///
+/// An image containing the region of the JPEG image.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Rect;
+/// use kornia_rs::io::functional as F;
+///
+/// let image_path = std::path::Path::new("tests/data/dog.jpeg");
+/// let rect = Rect {
+///     x: 100,
+///     y: 50,
+///     width: 64,
+///     height: 32,
+/// };
+/// let patch = F::read_image_jpeg_roi(image_path, rect).unwrap();
+/// assert_eq!(patch.size().width, 64);
+/// assert_eq!(patch.size().height, 32);
+/// ```
+pub fn read_image_jpeg_roi(file_path: &Path, rect: Rect) -> Result<Image<u8, 3>, KorniaError> {
+    check_jpeg_path(file_path)?;
+
+    // open the file and map it to memory
+    let file = std::fs::File::open(file_path)?;
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+    ImageDecoder::new()?.decode_roi(&mmap, rect)
+}
+
+#[cfg(feature = "jpegturbo")]
+/// Verify that the file exists and has a JPEG extension.
+fn check_jpeg_path(file_path: &Path) -> Result<(), KorniaError> {
+    if !file_path.exists() {
+        return Err(file_not_found(file_path));
+    }
+
+    if file_path.extension().map_or(true, |ext| {
+        ext.to_ascii_lowercase() != "jpg" && ext.to_ascii_lowercase() != "jpeg"
+    }) {
+        return Err(KorniaError::InvalidArgument(format!(
+            "File is not a JPEG: {}",
+            file_path.to_string_lossy()
+        )));
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "jpegturbo")]
 /// Writes the given JPEG data to the given file path.
 ///
@@ -79,9 +169,106 @@ pub fn write_image_jpeg(file_path: &Path, image: &Image<u8, 3>) -> Result<()> {
     Ok(())
 }
 
+/// The error of a missing image file.
+fn file_not_found(file_path: &Path) -> KorniaError {
+    KorniaError::Io(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("File does not exist: {}", file_path.to_string_lossy()),
+    ))
+}
+
+/// The format of an encoded image, as detected from its header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// A JPEG image.
+    Jpeg,
+    /// A PNG image.
+    Png,
+    /// A TIFF image, in little or big endian.
+    Tiff,
+    /// A BMP image.
+    Bmp,
+    /// A GIF image.
+    Gif,
+    /// A WebP image.
+    WebP,
+    /// A binary or ASCII PBM, PGM or PPM image.
+    Pnm,
+    /// A HEIF image, e.g. a HEIC photo.
+    Heif,
+    /// An AVIF image.
+    Avif,
+    /// A JPEG XL image, as a bare codestream or in a container.
+    Jxl,
+}
+
+/// Detects the format of an encoded image from the magic bytes of its header.
+///
+/// # Arguments
+///
+/// * `header` - The first bytes of the encoded image, at least 16 to tell all the formats apart.
+///
+/// # Returns
+///
+/// The format of the image, or `None` if the header matches no supported format.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::io::functional::{detect_image_format, ImageFormat};
+///
+/// let data = std::fs::read("tests/data/dog.jpeg").unwrap();
+/// assert_eq!(detect_image_format(&data), Some(ImageFormat::Jpeg));
+/// assert_eq!(detect_image_format(b"GIF89a"), Some(ImageFormat::Gif));
+/// assert_eq!(detect_image_format(b"hello"), None);
+/// ```
+pub fn detect_image_format(header: &[u8]) -> Option<ImageFormat> {
+    const JXL_CONTAINER: &[u8] = b"\x00\x00\x00\x0cJXL \x0d\x0a\x87\x0a";
+
+    let format = match header {
+        [0xff, 0xd8, 0xff, ..] => ImageFormat::Jpeg,
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', ..] => ImageFormat::Png,
+        [b'I', b'I', b'*', 0, ..] | [b'M', b'M', 0, b'*', ..] => ImageFormat::Tiff,
+        [b'B', b'M', ..] => ImageFormat::Bmp,
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => ImageFormat::Gif,
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => ImageFormat::WebP,
+        [b'P', b'1'..=b'6', b' ' | b'\t' | b'\n' | b'\r', ..] => ImageFormat::Pnm,
+        [0xff, 0x0a, ..] => ImageFormat::Jxl,
+        _ if header.starts_with(JXL_CONTAINER) => ImageFormat::Jxl,
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => {
+            // the major and compatible brands of the file type box, around the minor version
+            let box_size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+            let major = header.get(8..12).unwrap_or(&[]);
+            let compatible = header
+                .get(16..(box_size as usize).min(header.len()))
+                .unwrap_or(&[]);
+            let has_brand = |names: &[&[u8; 4]]| {
+                std::iter::once(major)
+                    .chain(compatible.chunks_exact(4))
+                    .any(|brand| names.iter().any(|name| brand == *name))
+            };
+
+            if has_brand(&[b"avif", b"avis"]) {
+                ImageFormat::Avif
+            } else if has_brand(&[
+                b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"mif1", b"msf1",
+            ]) {
+                ImageFormat::Heif
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    };
+
+    Some(format)
+}
+
 /// Reads an image from the given file path.
 ///
-/// The method tries to read from any image format supported by the image crate.
+/// The format is detected from the header of the file, regardless of its extension, and
+/// the image is decoded with the dedicated decoder of the format when its feature is
+/// enabled, e.g. libjpeg-turbo for JPEG with `jpegturbo`, or with the image crate.
 ///
 /// # Arguments
 ///
@@ -91,6 +278,11 @@ pub fn write_image_jpeg(file_path: &Path, image: &Image<u8, 3>) -> Result<()> {
 ///
 /// A tensor containing the image data.
 ///
+/// # Errors
+///
+/// Returns an error if the file does not exist, its format is not recognized or it
+/// cannot be decoded.
+///
 /// # Example
 ///
 /// ```
@@ -103,35 +295,460 @@ pub fn write_image_jpeg(file_path: &Path, image: &Image<u8, 3>) -> Result<()> {
 /// assert_eq!(image.size().height, 195);
 /// assert_eq!(image.num_channels(), 3);
 /// ```
-pub fn read_image_any(file_path: &Path) -> Result<Image<u8, 3>> {
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(path = %file_path.display())))]
+pub fn read_image_any(file_path: &Path) -> Result<Image<u8, 3>, KorniaError> {
     // verify the file exists
     if !file_path.exists() {
-        return Err(anyhow::anyhow!(
-            "File does not exist: {}",
-            file_path.to_str().unwrap()
-        ));
+        return Err(file_not_found(file_path));
     }
 
     // open the file and map it to memory
     let file = std::fs::File::open(file_path)?;
     let mmap = unsafe { memmap2::Mmap::map(&file)? };
 
+    let format = detect_image_format(&mmap).ok_or_else(|| {
+        KorniaError::Decode(format!(
+            "Unrecognized image format: {}",
+            file_path.to_string_lossy()
+        ))
+    })?;
+
+    // dispatch to the dedicated decoders
+    match format {
+        #[cfg(feature = "jpegturbo")]
+        ImageFormat::Jpeg => return ImageDecoder::new()?.decode(&mmap),
+        #[cfg(feature = "heif")]
+        ImageFormat::Heif => {
+            return super::heif::read_image_heif(file_path)
+                .map_err(|e| KorniaError::Decode(e.to_string()))
+        }
+        #[cfg(feature = "avif")]
+        ImageFormat::Avif => {
+            return super::avif::read_image_avif(file_path)
+                .map_err(|e| KorniaError::Decode(e.to_string()))
+        }
+        #[cfg(feature = "jxl")]
+        ImageFormat::Jxl => {
+            return super::jxl::read_image_jxl(file_path)
+                .map_err(|e| KorniaError::Decode(e.to_string()))
+        }
+        _ => {}
+    }
+
+    let image_format = match format {
+        ImageFormat::Jpeg => image::ImageFormat::Jpeg,
+        ImageFormat::Png => image::ImageFormat::Png,
+        ImageFormat::Tiff => image::ImageFormat::Tiff,
+        ImageFormat::Bmp => image::ImageFormat::Bmp,
+        ImageFormat::Gif => image::ImageFormat::Gif,
+        ImageFormat::WebP => image::ImageFormat::WebP,
+        ImageFormat::Pnm => image::ImageFormat::Pnm,
+        ImageFormat::Avif => image::ImageFormat::Avif,
+        ImageFormat::Heif | ImageFormat::Jxl => {
+            return Err(KorniaError::Decode(format!(
+                "Decoding {:?} images requires the `{}` feature",
+                format,
+                if format == ImageFormat::Heif {
+                    "heif"
+                } else {
+                    "jxl"
+                }
+            )))
+        }
+    };
+
     // decode the data directly from memory
-    let img = image::io::Reader::new(std::io::Cursor::new(&mmap))
-        .with_guessed_format()?
-        .decode()?;
+    let img = image::load_from_memory_with_format(&mmap, image_format)
+        .map_err(|e| KorniaError::Decode(e.to_string()))?;
 
     // return the image data
-    let data = img.to_rgb8().to_vec();
-    let image = Image::new(
+    let size = ImageSize {
+        width: img.width() as usize,
+        height: img.height() as usize,
+    };
+    Image::new(size, img.to_rgb8().to_vec()).map_err(|_| KorniaError::InvalidImageSize(size))
+}
+
+/// The encoding of a WebP image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebpEncoding {
+    /// Lossy encoding with a quality in [0, 100], requiring the `webp` feature.
+    Lossy {
+        /// The quality of the encoding, from the smallest files at 0 to the best at 100.
+        quality: f32,
+    },
+    /// Lossless encoding.
+    Lossless,
+}
+
+/// Reads a WebP image from the given file path.
+///
+/// Both the lossy and the lossless images are supported, the alpha channel is discarded.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the WebP image.
+///
+/// # Returns
+///
+/// An RGB image containing the image data.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::functional::{read_image_webp, write_image_webp, WebpEncoding};
+///
+/// let image = Image::<u8, 3>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 1,
+///     },
+///     vec![255, 0, 0, 0, 0, 255],
+/// )
+/// .unwrap();
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("image.webp");
+/// write_image_webp(&file_path, &image, WebpEncoding::Lossless).unwrap();
+///
+/// let image_back = read_image_webp(&file_path).unwrap();
+/// assert_eq!(image_back.data, image.data);
+/// ```
+pub fn read_image_webp(file_path: &Path) -> Result<Image<u8, 3>> {
+    let data = std::fs::read(file_path)?;
+    let img = image::load_from_memory_with_format(&data, image::ImageFormat::WebP)?;
+
+    Image::new(
         ImageSize {
             width: img.width() as usize,
             height: img.height() as usize,
         },
-        data,
-    )?;
+        img.to_rgb8().into_raw(),
+    )
+}
 
-    Ok(image)
+/// Writes an RGB image to a WebP file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the WebP image.
+/// * `image` - The image to write.
+/// * `encoding` - The lossy or lossless encoding of the image.
+///
+/// # Errors
+///
+/// Returns an error if the quality is out of range, the lossy encoding is requested
+/// without the `webp` feature, or the file cannot be written.
+pub fn write_image_webp(
+    file_path: &Path,
+    image: &Image<u8, 3>,
+    encoding: WebpEncoding,
+) -> Result<()> {
+    let (width, height) = (image.width() as u32, image.height() as u32);
+    let data = image.as_contiguous_slice();
+
+    let encoded = match encoding {
+        WebpEncoding::Lossless => {
+            let mut encoded = Vec::new();
+            image::codecs::webp::WebPEncoder::new_lossless(&mut encoded).encode(
+                &data,
+                width,
+                height,
+                image::ExtendedColorType::Rgb8,
+            )?;
+            encoded
+        }
+        WebpEncoding::Lossy { quality } => {
+            if !(0.0..=100.0).contains(&quality) {
+                return Err(anyhow::anyhow!(
+                    "The WebP quality should be in [0, 100], got {}",
+                    quality
+                ));
+            }
+
+            #[cfg(feature = "webp")]
+            {
+                webp::Encoder::from_rgb(&data, width, height)
+                    .encode(quality)
+                    .to_vec()
+            }
+            #[cfg(not(feature = "webp"))]
+            return Err(anyhow::anyhow!(
+                "The lossy WebP encoding requires the `webp` feature"
+            ));
+        }
+    };
+
+    std::fs::write(file_path, encoded)?;
+
+    Ok(())
+}
+
+/// Encodes an image to the bytes of a compressed format.
+///
+/// # Arguments
+///
+/// * `image` - The grayscale, RGB or RGBA image to encode.
+/// * `format` - The format of the encoded image, [`ImageFormat::Jpeg`] or [`ImageFormat::Png`].
+/// * `quality` - The JPEG quality in [1, 100], ignored for PNG.
+///
+/// # Returns
+///
+/// The encoded image.
+///
+/// # Errors
+///
+/// Returns an error if the format or the number of channels is not supported.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::functional::{decode_image, encode_image, ImageFormat};
+///
+/// let size = ImageSize {
+///     width: 8,
+///     height: 8,
+/// };
+/// let image = Image::<u8, 3>::from_size_val(size, 128).unwrap();
+///
+/// let encoded = encode_image(&image, ImageFormat::Png, 90).unwrap();
+/// let decoded = decode_image::<3>(&encoded).unwrap();
+/// assert_eq!(decoded.data, image.data);
+/// ```
+pub fn encode_image<const CHANNELS: usize>(
+    image: &Image<u8, CHANNELS>,
+    format: ImageFormat,
+    quality: u8,
+) -> Result<Vec<u8>> {
+    use image::ImageEncoder as _;
+
+    let color = match (format, CHANNELS) {
+        (_, 1) => image::ExtendedColorType::L8,
+        (_, 3) => image::ExtendedColorType::Rgb8,
+        (ImageFormat::Png, 4) => image::ExtendedColorType::Rgba8,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Cannot encode an image with {} channels to {:?}",
+                CHANNELS,
+                format
+            ))
+        }
+    };
+    let (width, height) = (image.width() as u32, image.height() as u32);
+    let data = image.as_contiguous_slice();
+
+    let mut encoded = Vec::new();
+    match format {
+        ImageFormat::Jpeg => {
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, quality.clamp(1, 100))
+                .write_image(&data, width, height, color)?
+        }
+        ImageFormat::Png => image::codecs::png::PngEncoder::new(&mut encoded)
+            .write_image(&data, width, height, color)?,
+        _ => return Err(anyhow::anyhow!("Cannot encode an image to {:?}", format)),
+    }
+
+    Ok(encoded)
+}
+
+/// Decodes an image from the bytes of a compressed format.
+///
+/// The format is detected from the header of the data and the image is converted to
+/// `CHANNELS` channels: 1 for grayscale, 3 for RGB or 4 for RGBA.
+///
+/// # Arguments
+///
+/// * `data` - The encoded image.
+///
+/// # Returns
+///
+/// The decoded image.
+///
+/// # Errors
+///
+/// Returns [`KorniaError::Decode`] if the data cannot be decoded, or
+/// [`KorniaError::InvalidArgument`] if the number of channels is not supported.
+pub fn decode_image<const CHANNELS: usize>(
+    data: &[u8],
+) -> Result<Image<u8, CHANNELS>, KorniaError> {
+    let img = image::load_from_memory(data).map_err(|e| KorniaError::Decode(e.to_string()))?;
+    let size = ImageSize {
+        width: img.width() as usize,
+        height: img.height() as usize,
+    };
+
+    let pixels = match CHANNELS {
+        1 => img.to_luma8().into_raw(),
+        3 => img.to_rgb8().into_raw(),
+        4 => img.to_rgba8().into_raw(),
+        _ => {
+            return Err(KorniaError::InvalidArgument(format!(
+                "Cannot decode an image to {} channels",
+                CHANNELS
+            )))
+        }
+    };
+
+    Image::new(size, pixels).map_err(|_| KorniaError::InvalidImageSize(size))
+}
+
+/// Maps an uncompressed image file to memory.
+///
+/// The method avoids decoding and copying the pixel data, which is paged in from the
+/// file on access. Binary PGM and PPM files with 8 bits per sample are supported.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the image.
+///
+/// # Returns
+///
+/// The mapped image, exposing the pixel data as a read-only image view.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::io::functional as F;
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("image.pgm");
+/// std::fs::write(&file_path, b"P5\n2 1\n255\n\x01\x02").unwrap();
+///
+/// let image = F::read_image_mmap::<1>(&file_path).unwrap();
+/// assert_eq!(image.size().width, 2);
+/// assert_eq!(image.view().data[[0, 1, 0]], 2);
+/// ```
+pub fn read_image_mmap<const CHANNELS: usize>(file_path: &Path) -> Result<MappedImage<CHANNELS>> {
+    // verify the file exists
+    if !file_path.exists() {
+        return Err(anyhow::anyhow!(
+            "File does not exist: {}",
+            file_path.to_str().unwrap()
+        ));
+    }
+
+    MappedImage::open(file_path)
+}
+
+#[cfg(all(feature = "async", feature = "jpegturbo"))]
+/// Reads a JPEG image from the given file path asynchronously.
+///
+/// The file is read with the tokio file system API and the decoding runs on the
+/// blocking thread pool, so the caller does not stall the async runtime.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JPEG image.
+///
+/// # Returns
+///
+/// An image containing the JPEG image data.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::io::functional as F;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let image_path = std::path::Path::new("tests/data/dog.jpeg");
+/// let image = F::read_image_jpeg_async(image_path).await.unwrap();
+/// assert_eq!(image.size().width, 258);
+/// assert_eq!(image.size().height, 195);
+/// # });
+/// ```
+pub async fn read_image_jpeg_async(file_path: &Path) -> Result<Image<u8, 3>, KorniaError> {
+    let jpeg_data = tokio::fs::read(file_path).await.map_err(|e| {
+        let message = format!("Failed to read {}: {}", file_path.to_string_lossy(), e);
+        KorniaError::Io(std::io::Error::new(e.kind(), message))
+    })?;
+
+    // decode the data on the blocking thread pool
+    tokio::task::spawn_blocking(move || ImageDecoder::new()?.decode(&jpeg_data))
+        .await
+        .map_err(|e| KorniaError::Decode(format!("The decoding task failed: {}", e)))?
+}
+
+#[cfg(all(feature = "async", feature = "jpegturbo"))]
+/// Writes the given image to a JPEG file asynchronously.
+///
+/// The encoding runs on the blocking thread pool and the file is written with the
+/// tokio file system API.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JPEG image.
+/// * `image` - The image to encode.
+pub async fn write_image_jpeg_async(file_path: &Path, image: &Image<u8, 3>) -> Result<()> {
+    // encode the image on the blocking thread pool
+    let image = image.clone();
+    let jpeg_data =
+        tokio::task::spawn_blocking(move || ImageEncoder::new()?.encode(&image)).await??;
+
+    tokio::fs::write(file_path, jpeg_data).await?;
+
+    Ok(())
+}
+
+#[cfg(all(feature = "async", feature = "jpegturbo"))]
+/// Reads the JPEG images of a directory asynchronously as a stream.
+///
+/// The files with a `jpg` or `jpeg` extension are decoded in lexicographic order of
+/// their paths by a background task, which stays a few images ahead of the consumer.
+///
+/// # Arguments
+///
+/// * `dir_path` - The path to the directory.
+///
+/// # Returns
+///
+/// A stream yielding the decoded images, or the error of each file that failed.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::io::functional as F;
+/// use tokio_stream::StreamExt;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut images = F::read_image_dir_async(std::path::Path::new("tests/data"))
+///     .await
+///     .unwrap();
+/// while let Some(image) = images.next().await {
+///     assert_eq!(image.unwrap().num_channels(), 3);
+/// }
+/// # });
+/// ```
+pub async fn read_image_dir_async(
+    dir_path: &Path,
+) -> Result<impl tokio_stream::Stream<Item = Result<Image<u8, 3>, KorniaError>>> {
+    // collect the JPEG files of the directory
+    let mut entries = tokio::fs::read_dir(dir_path).await?;
+    let mut paths = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_jpeg = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("jpg") || ext.eq_ignore_ascii_case("jpeg"));
+        if is_jpeg {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    // decode the images in the background and send them through a bounded channel
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tokio::spawn(async move {
+        for path in paths {
+            let image = read_image_jpeg_async(&path).await;
+            if tx.send(image).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(tokio_stream::wrappers::ReceiverStream::new(rx))
 }
 
 #[cfg(test)]
@@ -139,7 +756,9 @@ mod tests {
     use anyhow::Result;
     use std::path::Path;
 
-    use crate::io::functional::read_image_any;
+    use crate::io::functional::{
+        detect_image_format, read_image_any, read_image_mmap, ImageFormat,
+    };
 
     #[cfg(feature = "jpegturbo")]
     use crate::io::functional::{read_image_jpeg, write_image_jpeg};
@@ -151,6 +770,86 @@ mod tests {
         assert_eq!(image.size().width, 258);
         assert_eq!(image.size().height, 195);
 
+        // the format is detected regardless of the extension
+        let tmp_dir = tempfile::tempdir()?;
+        let file_path = tmp_dir.path().join("dog.png");
+        std::fs::copy(image_path, &file_path)?;
+        assert_eq!(read_image_any(&file_path)?.size(), image.size());
+
+        let file_path = tmp_dir.path().join("notes.jpg");
+        std::fs::write(&file_path, "not an image")?;
+        assert!(read_image_any(&file_path).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn detect_format() {
+        let cases: [(&[u8], Option<ImageFormat>); 8] = [
+            (b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR", Some(ImageFormat::Png)),
+            (b"II*\0\x08\0\0\0", Some(ImageFormat::Tiff)),
+            (b"MM\0*\0\0\0\x08", Some(ImageFormat::Tiff)),
+            (b"RIFF\x24\0\0\0WEBPVP8 ", Some(ImageFormat::WebP)),
+            (b"P6\n2 2\n255\n", Some(ImageFormat::Pnm)),
+            (
+                b"\0\0\0\x18ftypmif1\0\0\0\0mif1heic",
+                Some(ImageFormat::Heif),
+            ),
+            (
+                b"\0\0\0\x1cftypavif\0\0\0\0avifmif1miaf",
+                Some(ImageFormat::Avif),
+            ),
+            (b"\0\0\0\x14ftypisom\0\0\0\0isom", None),
+        ];
+        for (header, format) in cases {
+            assert_eq!(detect_image_format(header), format);
+        }
+    }
+
+    #[test]
+    fn read_write_webp() -> Result<()> {
+        use crate::io::functional::{read_image_webp, write_image_webp, WebpEncoding};
+
+        let tmp_dir = tempfile::tempdir()?;
+        let file_path = tmp_dir.path().join("dog.webp");
+        let image = read_image_any(Path::new("tests/data/dog.jpeg"))?;
+
+        write_image_webp(&file_path, &image, WebpEncoding::Lossless)?;
+        assert_eq!(read_image_webp(&file_path)?.data, image.data);
+        assert_eq!(read_image_any(&file_path)?.data, image.data);
+
+        let lossy = WebpEncoding::Lossy { quality: 80.0 };
+        let result = write_image_webp(&file_path, &image, lossy);
+        if cfg!(feature = "webp") {
+            result?;
+            assert_eq!(read_image_webp(&file_path)?.size(), image.size());
+        } else {
+            assert!(result.is_err());
+        }
+        assert!(
+            write_image_webp(&file_path, &image, WebpEncoding::Lossy { quality: 101.0 }).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_mmap() -> Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let file_path = tmp_dir.path().join("image.ppm");
+        let mut data = b"P6\n2 2\n255\n".to_vec();
+        data.extend(0..12u8);
+        std::fs::write(&file_path, data)?;
+
+        let image = read_image_mmap::<3>(&file_path)?;
+        assert_eq!(image.size().width, 2);
+        assert_eq!(image.size().height, 2);
+        assert_eq!(image.view().data[[1, 0, 2]], 8);
+        assert_eq!(image.view().to_image().data.as_slice().unwrap().len(), 12);
+
+        assert!(read_image_mmap::<1>(&file_path).is_err());
+        assert!(read_image_mmap::<3>(Path::new("tests/data/dog.jpeg")).is_err());
+
         Ok(())
     }
 
@@ -165,6 +864,39 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[cfg(all(feature = "async", feature = "jpegturbo"))]
+    async fn read_write_jpeg_async() -> Result<()> {
+        use crate::io::functional::{
+            read_image_dir_async, read_image_jpeg_async, write_image_jpeg_async,
+        };
+        use tokio_stream::StreamExt;
+
+        let tmp_dir = tempfile::tempdir()?;
+        let image = read_image_jpeg_async(Path::new("tests/data/dog.jpeg")).await?;
+        for name in ["b.jpg", "a.jpeg"] {
+            write_image_jpeg_async(&tmp_dir.path().join(name), &image).await?;
+        }
+        std::fs::write(tmp_dir.path().join("notes.txt"), "not an image")?;
+
+        let images = read_image_dir_async(tmp_dir.path())
+            .await?
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(images.len(), 2);
+        for image in images {
+            let image = image?;
+            assert_eq!(image.size().width, 258);
+            assert_eq!(image.size().height, 195);
+        }
+
+        assert!(read_image_jpeg_async(Path::new("missing.jpg"))
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "jpegturbo")]
     fn read_write_jpeg() -> Result<()> {
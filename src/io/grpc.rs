@@ -0,0 +1,600 @@
+use anyhow::Result;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+
+use super::functional::{decode_image, encode_image, ImageFormat};
+use crate::image::{Image, ImageSize};
+use crate::tensor::{CpuAllocator, Tensor};
+
+/// The messages and the service generated from `proto/kornia.proto`.
+pub mod proto {
+    tonic::include_proto!("kornia.v1");
+}
+
+use proto::image_processing_client::ImageProcessingClient;
+use proto::image_processing_server::{ImageProcessing, ImageProcessingServer};
+use proto::{Compression, DataType};
+
+/// The default limit of the size of the messages, large enough for raw 4K RGB frames.
+///
+/// The tonic default of 4 MiB is smaller than a raw 1080p RGB frame.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// The element types of the tensors exchanged as protobuf messages.
+pub trait ProtoElement: arrow_buffer::ArrowNativeType + std::panic::RefUnwindSafe {
+    /// The data type of the element in the schema.
+    const DTYPE: DataType;
+
+    /// Appends the element as little-endian bytes.
+    fn write_le(&self, out: &mut Vec<u8>);
+
+    /// Reads an element from little-endian bytes of the size of the element.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_proto_element {
+    ($ty:ty, $dtype:expr) => {
+        impl ProtoElement for $ty {
+            const DTYPE: DataType = $dtype;
+
+            fn write_le(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Self {
+                let mut array = [0u8; std::mem::size_of::<$ty>()];
+                array.copy_from_slice(bytes);
+                <$ty>::from_le_bytes(array)
+            }
+        }
+    };
+}
+
+impl_proto_element!(u8, DataType::U8);
+impl_proto_element!(u16, DataType::U16);
+impl_proto_element!(f32, DataType::F32);
+
+/// Converts a tensor to a protobuf message.
+///
+/// # Arguments
+///
+/// * `tensor` - The tensor to convert.
+///
+/// # Returns
+///
+/// The message with the shape, the data type and the elements of the tensor.
+pub fn tensor_to_proto<T: ProtoElement, const N: usize>(tensor: &Tensor<T, N>) -> proto::Tensor {
+    let mut data = Vec::with_capacity(tensor.numel() * std::mem::size_of::<T>());
+    tensor.as_slice().iter().for_each(|x| x.write_le(&mut data));
+
+    proto::Tensor {
+        shape: tensor.shape.iter().map(|&d| d as u64).collect(),
+        dtype: T::DTYPE as i32,
+        data,
+    }
+}
+
+/// Converts a protobuf message to a tensor.
+///
+/// # Arguments
+///
+/// * `message` - The message to convert.
+///
+/// # Returns
+///
+/// The tensor with the shape and the elements of the message.
+///
+/// # Errors
+///
+/// Returns an error if the rank or the data type does not match, or if the data does
+/// not match the shape.
+pub fn tensor_from_proto<T: ProtoElement, const N: usize>(
+    message: &proto::Tensor,
+) -> Result<Tensor<T, N>> {
+    if message.dtype() != T::DTYPE {
+        return Err(anyhow::anyhow!(
+            "The tensor has the data type {:?} but {:?} was requested",
+            message.dtype(),
+            T::DTYPE
+        ));
+    }
+
+    let shape: [usize; N] = message
+        .shape
+        .iter()
+        .map(|&d| d as usize)
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "The tensor has {} dimensions but {} were requested",
+                message.shape.len(),
+                N
+            )
+        })?;
+
+    let size = std::mem::size_of::<T>();
+    if message.data.len() % size != 0 {
+        return Err(anyhow::anyhow!(
+            "The tensor data is not a multiple of the element size"
+        ));
+    }
+    let data = message.data.chunks_exact(size).map(T::read_le).collect();
+
+    Ok(Tensor::from_shape_vec(shape, data, CpuAllocator)?)
+}
+
+/// Converts an image to a protobuf message.
+///
+/// # Arguments
+///
+/// * `image` - The image to convert.
+/// * `compression` - The compression of the pixels.
+/// * `timestamp` - The capture timestamp of the image.
+///
+/// # Returns
+///
+/// The message with the size and the pixels of the image.
+///
+/// # Errors
+///
+/// Returns an error if the image cannot be compressed, e.g. RGBA images to JPEG.
+pub fn image_to_proto<const CHANNELS: usize>(
+    image: &Image<u8, CHANNELS>,
+    compression: Compression,
+    timestamp: Duration,
+) -> Result<proto::Image> {
+    // the JPEG quality used by the helpers, a good trade-off for the network
+    const JPEG_QUALITY: u8 = 90;
+
+    let data = match compression {
+        Compression::None => image.as_contiguous_slice().into_owned(),
+        Compression::Jpeg => encode_image(image, ImageFormat::Jpeg, JPEG_QUALITY)?,
+        Compression::Png => encode_image(image, ImageFormat::Png, JPEG_QUALITY)?,
+    };
+
+    Ok(proto::Image {
+        height: image.height() as u32,
+        width: image.width() as u32,
+        channels: CHANNELS as u32,
+        dtype: DataType::U8 as i32,
+        compression: compression as i32,
+        data,
+        timestamp_ns: timestamp.as_nanos() as u64,
+    })
+}
+
+/// Converts a protobuf message to an image.
+///
+/// # Arguments
+///
+/// * `message` - The message to convert, with raw or compressed pixels.
+///
+/// # Returns
+///
+/// The image of the message.
+///
+/// # Errors
+///
+/// Returns an error if the number of channels or the data type does not match, or if the
+/// pixels cannot be decoded.
+pub fn image_from_proto<const CHANNELS: usize>(
+    message: &proto::Image,
+) -> Result<Image<u8, CHANNELS>> {
+    if message.dtype() != DataType::U8 || message.channels as usize != CHANNELS {
+        return Err(anyhow::anyhow!(
+            "The image has {} channels of {:?} but {} channels of u8 were requested",
+            message.channels,
+            message.dtype(),
+            CHANNELS
+        ));
+    }
+
+    let image = match message.compression() {
+        Compression::None => Image::new(
+            ImageSize {
+                width: message.width as usize,
+                height: message.height as usize,
+            },
+            message.data.clone(),
+        )?,
+        Compression::Jpeg | Compression::Png => decode_image(&message.data)?,
+    };
+
+    Ok(image)
+}
+
+type ProcessFn<const C: usize> = dyn Fn(Image<u8, C>) -> Result<Image<u8, C>> + Send + Sync;
+
+/// Process an image message, replying with the compression of the request.
+fn process_message<const C: usize>(
+    process: &ProcessFn<C>,
+    message: proto::Image,
+) -> Result<proto::Image, Status> {
+    let image =
+        image_from_proto::<C>(&message).map_err(|err| Status::invalid_argument(err.to_string()))?;
+    let output = process(image).map_err(|err| Status::internal(err.to_string()))?;
+    image_to_proto(
+        &output,
+        message.compression(),
+        Duration::from_nanos(message.timestamp_ns),
+    )
+    .map_err(|err| Status::internal(err.to_string()))
+}
+
+/// A gRPC service applying a function to the received images.
+///
+/// The function runs on a blocking thread, so it can be a CPU intensive operation. The
+/// images are replied with the compression of the request and keep their timestamp.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::io::grpc::ImageProcessingService;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let service = ImageProcessingService::<3>::new(|image| {
+///     // a remote preprocessing step
+///     Ok(image)
+/// });
+/// service.serve("0.0.0.0:50051".parse().unwrap()).await.unwrap();
+/// # });
+/// ```
+pub struct ImageProcessingService<const CHANNELS: usize> {
+    process: Arc<ProcessFn<CHANNELS>>,
+    max_message_size: usize,
+}
+
+impl<const CHANNELS: usize> ImageProcessingService<CHANNELS> {
+    /// Creates a new ImageProcessingService object.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - The function applied to each image
+    pub fn new<F>(process: F) -> Self
+    where
+        F: Fn(Image<u8, CHANNELS>) -> Result<Image<u8, CHANNELS>> + Send + Sync + 'static,
+    {
+        Self {
+            process: Arc::new(process),
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+
+    /// Sets the limit of the size of the received and sent messages in bytes.
+    ///
+    /// Defaults to [`DEFAULT_MAX_MESSAGE_SIZE`].
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Serves the service until the task is cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address to listen on, e.g. `0.0.0.0:50051`
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let max_message_size = self.max_message_size;
+        let server = ImageProcessingServer::new(self)
+            .max_decoding_message_size(max_message_size)
+            .max_encoding_message_size(max_message_size);
+        tonic::transport::Server::builder()
+            .add_service(server)
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl<const CHANNELS: usize> ImageProcessing for ImageProcessingService<CHANNELS> {
+    type ProcessStreamStream =
+        Pin<Box<dyn Stream<Item = Result<proto::Image, Status>> + Send + 'static>>;
+
+    async fn process(
+        &self,
+        request: Request<proto::Image>,
+    ) -> Result<Response<proto::Image>, Status> {
+        let process = self.process.clone();
+        let message = request.into_inner();
+        let reply = tokio::task::spawn_blocking(move || process_message(&*process, message))
+            .await
+            .map_err(|err| Status::internal(err.to_string()))??;
+        Ok(Response::new(reply))
+    }
+
+    async fn process_stream(
+        &self,
+        request: Request<Streaming<proto::Image>>,
+    ) -> Result<Response<Self::ProcessStreamStream>, Status> {
+        let process = self.process.clone();
+        let mut incoming = request.into_inner();
+
+        // process the images in order on a blocking thread, replying through a channel
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        tokio::spawn(async move {
+            while let Some(message) = incoming.next().await {
+                let reply = match message {
+                    Ok(message) => {
+                        let process = process.clone();
+                        tokio::task::spawn_blocking(move || process_message(&*process, message))
+                            .await
+                            .unwrap_or_else(|err| Err(Status::internal(err.to_string())))
+                    }
+                    Err(status) => Err(status),
+                };
+                if tx.send(reply).await.is_err() {
+                    // the client disconnected
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+        )))
+    }
+}
+
+/// A client of a remote [`ImageProcessingService`].
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::grpc::{proto::Compression, RemoteProcessor};
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut processor = RemoteProcessor::connect("http://localhost:50051")
+///     .await
+///     .unwrap()
+///     .with_compression(Compression::Jpeg);
+///
+/// let size = ImageSize {
+///     width: 640,
+///     height: 480,
+/// };
+/// let image = Image::<u8, 3>::from_size_val(size, 0).unwrap();
+/// let output = processor.process(&image).await.unwrap();
+/// # });
+/// ```
+pub struct RemoteProcessor {
+    client: ImageProcessingClient<tonic::transport::Channel>,
+    compression: Compression,
+}
+
+impl RemoteProcessor {
+    /// Connects to a remote service.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The url of the service, e.g. `http://localhost:50051`
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = ImageProcessingClient::connect(url.to_string()).await?;
+        Ok(Self {
+            client,
+            compression: Compression::None,
+        }
+        .with_max_message_size(DEFAULT_MAX_MESSAGE_SIZE))
+    }
+
+    /// Sets the limit of the size of the sent and received messages in bytes.
+    ///
+    /// Defaults to [`DEFAULT_MAX_MESSAGE_SIZE`].
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.client = self
+            .client
+            .max_decoding_message_size(max_message_size)
+            .max_encoding_message_size(max_message_size);
+        self
+    }
+
+    /// Sets the compression of the images sent to the service, none by default.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Processes an image with the remote service.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to process
+    ///
+    /// # Returns
+    ///
+    /// The image returned by the service.
+    pub async fn process<const CHANNELS: usize>(
+        &mut self,
+        image: &Image<u8, CHANNELS>,
+    ) -> Result<Image<u8, CHANNELS>> {
+        let message = image_to_proto(image, self.compression, Duration::ZERO)?;
+        let reply = self.client.process(message).await?.into_inner();
+        image_from_proto(&reply)
+    }
+
+    /// Processes a stream of images with the remote service.
+    ///
+    /// # Arguments
+    ///
+    /// * `images` - The images to process with their timestamps
+    ///
+    /// # Returns
+    ///
+    /// The stream of processed images with their timestamps, in the order of the requests.
+    ///
+    /// If an image cannot be converted to a message, no further images are sent and the
+    /// error is yielded after the replies of the images sent before it.
+    pub async fn process_stream<const CHANNELS: usize, S>(
+        &mut self,
+        images: S,
+    ) -> Result<impl Stream<Item = Result<(Image<u8, CHANNELS>, Duration)>>>
+    where
+        S: Stream<Item = (Image<u8, CHANNELS>, Duration)> + Send + 'static,
+    {
+        let compression = self.compression;
+        // the request stream cannot fail, so it ends at the first image failing to convert
+        // and the error is kept to be yielded once the pending replies are received
+        let failure = Arc::new(std::sync::Mutex::new(None));
+        let requests = {
+            let failure = failure.clone();
+            images.map_while(move |(image, timestamp)| {
+                match image_to_proto(&image, compression, timestamp) {
+                    Ok(message) => Some(message),
+                    Err(err) => {
+                        *failure.lock().unwrap() = Some(err);
+                        None
+                    }
+                }
+            })
+        };
+        let replies = self.client.process_stream(requests).await?.into_inner();
+
+        let replies = replies.map(|reply| {
+            let reply = reply?;
+            let timestamp = Duration::from_nanos(reply.timestamp_ns);
+            Ok((image_from_proto(&reply)?, timestamp))
+        });
+        let failure =
+            tokio_stream::iter(std::iter::from_fn(move || failure.lock().unwrap().take()));
+
+        Ok(replies.chain(failure.map(Err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::proto::Compression;
+    use super::{image_from_proto, image_to_proto, tensor_from_proto, tensor_to_proto};
+    use super::{ImageProcessingService, RemoteProcessor};
+    use crate::image::{Image, ImageSize};
+    use crate::tensor::{CpuAllocator, Tensor};
+    use std::time::Duration;
+    use tokio_stream::StreamExt;
+
+    #[test]
+    fn image_proto_roundtrip() -> anyhow::Result<()> {
+        let size = ImageSize {
+            width: 4,
+            height: 3,
+        };
+        let data = (0..4 * 3 * 3).map(|x| x as u8).collect();
+        let image = Image::<u8, 3>::new(size, data)?;
+
+        for compression in [Compression::None, Compression::Png] {
+            let message = image_to_proto(&image, compression, Duration::from_millis(5))?;
+            assert_eq!(message.timestamp_ns, 5_000_000);
+            let decoded = image_from_proto::<3>(&message)?;
+            assert_eq!(decoded.data, image.data);
+        }
+
+        let message = image_to_proto(&image, Compression::None, Duration::ZERO)?;
+        assert!(image_from_proto::<1>(&message).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn tensor_proto_roundtrip() -> anyhow::Result<()> {
+        let tensor = Tensor::<f32, 2>::from_shape_vec(
+            [2, 3],
+            vec![0.5, 1.0, 2.0, 3.0, 4.0, -1.0],
+            CpuAllocator,
+        )?;
+        let message = tensor_to_proto(&tensor);
+        assert_eq!(message.shape, vec![2, 3]);
+        assert_eq!(message.data.len(), 24);
+
+        let decoded = tensor_from_proto::<f32, 2>(&message)?;
+        assert_eq!(decoded.as_slice(), tensor.as_slice());
+        assert!(tensor_from_proto::<u8, 2>(&message).is_err());
+        assert!(tensor_from_proto::<f32, 3>(&message).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn remote_processor_large_frames() -> anyhow::Result<()> {
+        // a raw frame of about 5 MiB, above the tonic default limit of 4 MiB
+        let size = ImageSize {
+            width: 1280,
+            height: 1280,
+        };
+        let image = Image::<u8, 3>::from_size_val(size, 7)?;
+
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let service = ImageProcessingService::<3>::new(Ok);
+        let server = tokio::spawn(service.serve(addr));
+
+        // wait for the server to listen
+        let url = format!("http://{}", addr);
+        let mut processor = loop {
+            match RemoteProcessor::connect(&url).await {
+                Ok(processor) => break processor,
+                Err(_) => tokio::time::sleep(Duration::from_millis(20)).await,
+            }
+        };
+
+        let output = processor.process(&image).await?;
+        assert_eq!(output.size(), size);
+        assert_eq!(output.data, image.data);
+
+        let images = tokio_stream::iter(vec![(image.clone(), Duration::from_millis(1))]);
+        let outputs = processor
+            .process_stream(images)
+            .await?
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].as_ref().unwrap().1, Duration::from_millis(1));
+
+        server.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn remote_processor_stream_error() -> anyhow::Result<()> {
+        let size = ImageSize {
+            width: 4,
+            height: 2,
+        };
+        let image = Image::<u8, 4>::from_size_val(size, 1)?;
+
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let service = ImageProcessingService::<4>::new(Ok);
+        let server = tokio::spawn(service.serve(addr));
+
+        let url = format!("http://{}", addr);
+        let mut processor = loop {
+            match RemoteProcessor::connect(&url).await {
+                Ok(processor) => break processor,
+                Err(_) => tokio::time::sleep(Duration::from_millis(20)).await,
+            }
+        };
+
+        // the raw frame is sent but the RGBA frame cannot be compressed to JPEG
+        let outputs = processor
+            .process_stream(tokio_stream::iter(vec![(image.clone(), Duration::ZERO)]))
+            .await?
+            .collect::<Vec<_>>()
+            .await;
+        assert!(outputs[0].is_ok());
+
+        let mut processor = processor.with_compression(Compression::Jpeg);
+        let outputs = processor
+            .process_stream(tokio_stream::iter(vec![(image, Duration::ZERO)]))
+            .await?
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(outputs.len(), 1);
+        assert!(outputs[0].is_err());
+
+        server.abort();
+        Ok(())
+    }
+}
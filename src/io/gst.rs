@@ -0,0 +1,546 @@
+use crate::error::KorniaError;
+use crate::image::ImageSize;
+use anyhow::Result;
+use gst::prelude::*;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The source stage of a pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// A V4L2 camera given its device id, e.g. 0 for `/dev/video0`.
+    Camera {
+        /// The camera device id.
+        device_id: usize,
+    },
+    /// A video file decoded with the available demuxers and decoders.
+    File {
+        /// The path to the video file.
+        path: PathBuf,
+    },
+    /// An RTSP stream.
+    Rtsp {
+        /// The url of the stream, starting with `rtsp://`.
+        url: String,
+    },
+}
+
+/// A filter stage applied to the raw frames of a pipeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    /// Convert the frames between color formats.
+    Convert,
+    /// Scale the frames to the given size.
+    Scale(ImageSize),
+    /// Drop or duplicate frames to reach the given frames per second.
+    Rate(u32),
+}
+
+/// The kind of decoders used for the compressed streams.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecoderPreference {
+    /// Use the decoders with the highest rank, as chosen by GStreamer.
+    #[default]
+    Auto,
+    /// Prefer the available hardware decoders, e.g. VAAPI, NVDEC or V4L2 M2M, falling
+    /// back to software if none can decode the stream.
+    Hardware,
+    /// Only use software decoders.
+    Software,
+}
+
+/// The hardware decoders preferred by [`DecoderPreference::Hardware`].
+const HARDWARE_DECODERS: &[&str] = &[
+    // NVDEC
+    "nvh264dec",
+    "nvh265dec",
+    "nvvp9dec",
+    "nvav1dec",
+    // Jetson
+    "nvv4l2decoder",
+    // VA-API
+    "vah264dec",
+    "vah265dec",
+    "vavp9dec",
+    "vaav1dec",
+    "vaapidecodebin",
+    // V4L2 memory to memory
+    "v4l2h264dec",
+    "v4l2h265dec",
+    "v4l2vp9dec",
+];
+
+/// Checks whether an element factory is a hardware video decoder.
+fn is_hardware_decoder(name: &str, klass: &str) -> bool {
+    HARDWARE_DECODERS.contains(&name) || (klass.contains("Decoder") && klass.contains("Hardware"))
+}
+
+/// Checks whether a signal argument holds the factory of a hardware video decoder.
+fn is_hardware_factory(value: &gst::glib::Value) -> bool {
+    value
+        .get::<gst::ElementFactory>()
+        .map(|factory| {
+            let klass = factory.metadata("klass").unwrap_or_default();
+            is_hardware_decoder(factory.name().as_str(), klass)
+        })
+        .unwrap_or(false)
+}
+
+/// Applies a decoder preference to a `decodebin` or `uridecodebin` element.
+///
+/// The preference only affects the given element: the candidate decoders are reordered
+/// or filtered with the `autoplug-sort` and `autoplug-select` signals, and the ranks of
+/// the registry are left untouched. With [`DecoderPreference::Hardware`], the software
+/// decoders stay after the hardware ones, so that the decodebin falls back to them if a
+/// hardware decoder fails to start. Only the decoders ranked by their plugin are
+/// candidates.
+pub(crate) fn apply_decoder_preference(decodebin: &gst::Element, preference: DecoderPreference) {
+    match preference {
+        DecoderPreference::Auto => (),
+        DecoderPreference::Hardware => {
+            decodebin.connect("autoplug-sort", false, |values| {
+                let factories = values.get(3)?.get::<gst::glib::ValueArray>().ok()?;
+                // a stable partition keeps the order of the ranks within each group
+                let (mut sorted, software): (Vec<_>, Vec<_>) =
+                    factories.iter().cloned().partition(is_hardware_factory);
+                sorted.extend(software);
+                Some(gst::glib::ValueArray::new(sorted).to_value())
+            });
+        }
+        DecoderPreference::Software => {
+            decodebin.connect("autoplug-select", false, |values| {
+                // GstAutoplugSelectResult: 0 to try the factory, 2 to skip it
+                let skip = values.get(3).map(is_hardware_factory).unwrap_or(false);
+                let result = if skip { 2 } else { 0 };
+                // the signal returns an enum, built from its registered type
+                let ty = gst::glib::Type::from_name("GstAutoplugSelectResult")?;
+                gst::glib::EnumClass::with_type(ty)?.to_value(result)
+            });
+        }
+    }
+}
+
+/// Applies a decoder preference to the decodebins of a pipeline parsed from a description.
+pub(crate) fn apply_decoder_preference_to_bin(bin: &gst::Bin, preference: DecoderPreference) {
+    bin.iterate_recurse()
+        .into_iter()
+        .flatten()
+        .filter(|element| {
+            element
+                .factory()
+                .is_some_and(|f| matches!(f.name().as_str(), "decodebin" | "uridecodebin"))
+        })
+        .for_each(|decodebin| apply_decoder_preference(&decodebin, preference));
+}
+
+/// Quotes a property value for a pipeline description.
+pub(crate) fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The caps of the frames pulled from the appsinks.
+const RGB_CAPS: &str = "video/x-raw, format=(string)RGB with a width and a height";
+
+/// Gets the size of the frames from the structure of video caps.
+///
+/// # Errors
+///
+/// Returns [`KorniaError::CapsMismatch`] if the caps are not sized RGB frames.
+pub(crate) fn caps_frame_size(structure: &gst::StructureRef) -> Result<ImageSize, KorniaError> {
+    let format = structure.get::<&str>("format").ok();
+    let (width, height) = (
+        structure.get::<i32>("width").ok(),
+        structure.get::<i32>("height").ok(),
+    );
+    match (format, width, height) {
+        (Some("RGB"), Some(width), Some(height))
+            if structure.has_name("video/x-raw") && width > 0 && height > 0 =>
+        {
+            Ok(ImageSize {
+                width: width as usize,
+                height: height as usize,
+            })
+        }
+        _ => Err(KorniaError::CapsMismatch {
+            expected: RGB_CAPS.to_string(),
+            found: structure.to_string(),
+        }),
+    }
+}
+
+/// Finds the video decoder selected inside a bin, once the stream is prerolled.
+pub(crate) fn active_video_decoder(bin: &gst::Bin) -> Option<String> {
+    bin.iterate_recurse()
+        .into_iter()
+        .flatten()
+        .find_map(|element| {
+            let factory = element.factory()?;
+            let klass = factory.metadata("klass")?;
+            (klass.contains("Decoder") && klass.contains("Video"))
+                .then(|| factory.name().to_string())
+        })
+}
+
+/// Decides which frames to keep to honor the frame skip and the maximum frame rate.
+pub(crate) struct FrameDecimator {
+    frame_skip: usize,
+    min_interval: Option<Duration>,
+    to_skip: usize,
+    next: Duration,
+}
+
+impl FrameDecimator {
+    pub(crate) fn new(frame_skip: usize, max_fps: Option<u32>) -> Self {
+        Self {
+            frame_skip,
+            min_interval: max_fps.map(|fps| Duration::from_secs(1) / fps),
+            to_skip: 0,
+            next: Duration::ZERO,
+        }
+    }
+
+    /// Returns whether the frame arriving at the given time is kept.
+    pub(crate) fn keep(&mut self, timestamp: Duration) -> bool {
+        if self.to_skip > 0 {
+            self.to_skip -= 1;
+            return false;
+        }
+        self.to_skip = self.frame_skip;
+
+        let Some(min_interval) = self.min_interval else {
+            return true;
+        };
+        if timestamp < self.next {
+            return false;
+        }
+
+        // follow the deadlines to keep the average rate despite the arrival jitter
+        self.next = if timestamp - self.next > min_interval {
+            timestamp + min_interval
+        } else {
+            self.next + min_interval
+        };
+        true
+    }
+}
+
+/// A builder assembling a GStreamer pipeline from typed stages.
+///
+/// The pipeline reads from a [`Source`], applies the [`Filter`] stages in order and ends
+/// in an appsink producing RGB frames. The stages are validated and linked by
+/// [`PipelineBuilder::build`], so mistakes are reported before the pipeline is started.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::image::ImageSize;
+/// use kornia_rs::io::gst::{Filter, PipelineBuilder, Source};
+///
+/// let (pipeline, appsink) = PipelineBuilder::new()
+///     .with_source(Source::Camera { device_id: 0 })
+///     .with_filter(Filter::Rate(15))
+///     .with_filter(Filter::Scale(ImageSize {
+///         width: 640,
+///         height: 480,
+///     }))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PipelineBuilder {
+    source: Option<Source>,
+    filters: Vec<Filter>,
+    decoder: DecoderPreference,
+}
+
+impl PipelineBuilder {
+    /// Creates a new PipelineBuilder object without stages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the source of the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The desired source
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Appends a filter stage to the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter applied after the previous stages
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Sets the kind of decoders used for file and RTSP sources.
+    ///
+    /// # Arguments
+    ///
+    /// * `preference` - The decoder preference, [`DecoderPreference::Auto`] by default
+    pub fn with_decoder_preference(mut self, preference: DecoderPreference) -> Self {
+        self.decoder = preference;
+        self
+    }
+
+    /// Checks the stages before creating any element.
+    fn validate(&self) -> Result<&Source> {
+        let source = self
+            .source
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("The pipeline has no source"))?;
+
+        if let Source::Rtsp { url } = source {
+            if !url.starts_with("rtsp://") && !url.starts_with("rtsps://") {
+                return Err(anyhow::anyhow!("Invalid RTSP url: {}", url));
+            }
+        }
+
+        for filter in &self.filters {
+            match filter {
+                Filter::Scale(size) if size.width == 0 || size.height == 0 => {
+                    return Err(anyhow::anyhow!("Invalid scale size: {}", size));
+                }
+                Filter::Rate(0) => return Err(anyhow::anyhow!("Invalid frame rate: 0")),
+                _ => (),
+            }
+        }
+
+        Ok(source)
+    }
+
+    /// Create the pipeline and link its elements.
+    ///
+    /// # Returns
+    ///
+    /// The pipeline in the Null state and the appsink producing the RGB frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a stage is invalid, an element is not available or two
+    /// stages cannot be linked.
+    pub fn build(self) -> Result<(gst::Pipeline, gst_app::AppSink)> {
+        let source = self.validate()?;
+        gst::init()?;
+
+        // the elements following the source, ending with the rgb conversion
+        let mut elements = Vec::new();
+        for filter in &self.filters {
+            match filter {
+                Filter::Convert => elements.push(make("videoconvert")?),
+                Filter::Scale(size) => {
+                    elements.push(make("videoscale")?);
+                    elements.push(capsfilter(
+                        gst::Caps::builder("video/x-raw")
+                            .field("width", size.width as i32)
+                            .field("height", size.height as i32)
+                            .build(),
+                    )?);
+                }
+                Filter::Rate(fps) => {
+                    elements.push(make("videorate")?);
+                    elements.push(capsfilter(
+                        gst::Caps::builder("video/x-raw")
+                            .field("framerate", gst::Fraction::new(*fps as i32, 1))
+                            .build(),
+                    )?);
+                }
+            }
+        }
+        elements.push(make("videoconvert")?);
+
+        let appsink = gst_app::AppSink::builder()
+            .name("sink")
+            .caps(
+                &gst::Caps::builder("video/x-raw")
+                    .field("format", "RGB")
+                    .build(),
+            )
+            .build();
+
+        let pipeline = gst::Pipeline::new();
+        pipeline.add_many(elements.iter())?;
+        pipeline.add(&appsink)?;
+        gst::Element::link_many(elements.iter())?;
+        elements
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("Failed to get the last element"))?
+            .link(&appsink)?;
+
+        let first = &elements[0];
+        match source {
+            Source::Camera { device_id } => {
+                let src = gst::ElementFactory::make("v4l2src")
+                    .property("device", format!("/dev/video{}", device_id))
+                    .build()?;
+                pipeline.add(&src)?;
+                src.link(first)?;
+            }
+            Source::File { path } => {
+                let uri = gst::glib::filename_to_uri(path, None)?;
+                link_decodebin(&pipeline, &uri, first, self.decoder)?;
+            }
+            Source::Rtsp { url } => link_decodebin(&pipeline, url, first, self.decoder)?,
+        }
+
+        Ok((pipeline, appsink))
+    }
+}
+
+/// Create an element from its factory name.
+fn make(factory: &str) -> Result<gst::Element> {
+    gst::ElementFactory::make(factory)
+        .build()
+        .map_err(|_| anyhow::anyhow!("Failed to create the {} element", factory))
+}
+
+/// Create a capsfilter restricting the frames to the given caps.
+fn capsfilter(caps: gst::Caps) -> Result<gst::Element> {
+    Ok(gst::ElementFactory::make("capsfilter")
+        .property("caps", &caps)
+        .build()?)
+}
+
+/// Add a uridecodebin to the pipeline, linking its video pad once it is exposed.
+fn link_decodebin(
+    pipeline: &gst::Pipeline,
+    uri: &str,
+    next: &gst::Element,
+    decoder: DecoderPreference,
+) -> Result<()> {
+    let decodebin = gst::ElementFactory::make("uridecodebin")
+        .property("uri", uri)
+        .build()?;
+    apply_decoder_preference(&decodebin, decoder);
+    pipeline.add(&decodebin)?;
+
+    let sink_pad = next
+        .static_pad("sink")
+        .ok_or_else(|| anyhow::anyhow!("Failed to get the sink pad"))?;
+
+    // the decoded pads are only known once the stream is parsed
+    decodebin.connect_pad_added(move |_, src_pad| {
+        let is_video = src_pad
+            .current_caps()
+            .and_then(|caps| caps.structure(0).map(|s| s.name().starts_with("video/")))
+            .unwrap_or(false);
+        if is_video && !sink_pad.is_linked() {
+            if let Err(err) = src_pad.link(&sink_pad) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = ?err, "failed to link the decoded video pad");
+                #[cfg(not(feature = "tracing"))]
+                eprintln!("Failed to link the decoded video pad: {:?}", err);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        caps_frame_size, is_hardware_decoder, quote, Filter, FrameDecimator, PipelineBuilder,
+        Source,
+    };
+    use crate::error::KorniaError;
+    use crate::image::ImageSize;
+    use std::time::Duration;
+
+    #[test]
+    fn pipeline_builder_validate() {
+        // the stages are checked before any element is created
+        assert!(PipelineBuilder::new().build().is_err());
+
+        let builder = PipelineBuilder::new().with_source(Source::Camera { device_id: 0 });
+        assert!(builder.clone().validate().is_ok());
+        assert!(builder
+            .clone()
+            .with_filter(Filter::Rate(0))
+            .build()
+            .is_err());
+
+        let empty = ImageSize {
+            width: 0,
+            height: 480,
+        };
+        assert!(builder.with_filter(Filter::Scale(empty)).build().is_err());
+
+        let rtsp = Source::Rtsp {
+            url: "http://camera".to_string(),
+        };
+        assert!(PipelineBuilder::new().with_source(rtsp).build().is_err());
+    }
+
+    #[test]
+    fn hardware_decoders() {
+        assert!(is_hardware_decoder(
+            "nvh264dec",
+            "Codec/Decoder/Video/Hardware"
+        ));
+        assert!(is_hardware_decoder("v4l2h264dec", "Codec/Decoder/Video"));
+        assert!(is_hardware_decoder(
+            "qsvh264dec",
+            "Codec/Decoder/Video/Hardware"
+        ));
+        assert!(!is_hardware_decoder("avdec_h264", "Codec/Decoder/Video"));
+    }
+
+    #[test]
+    fn caps_size() -> Result<(), KorniaError> {
+        gst::init()?;
+
+        let caps = gst::Structure::builder("video/x-raw")
+            .field("format", "RGB")
+            .field("width", 4i32)
+            .field("height", 2i32)
+            .build();
+        let size = caps_frame_size(&caps)?;
+        assert_eq!((size.width, size.height), (4, 2));
+
+        // the frames must be converted to RGB before the appsink
+        let caps = gst::Structure::builder("video/x-raw")
+            .field("format", "NV12")
+            .field("width", 4i32)
+            .field("height", 2i32)
+            .build();
+        assert!(matches!(
+            caps_frame_size(&caps),
+            Err(KorniaError::CapsMismatch { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn quote_property() {
+        assert_eq!(quote("out.mp4"), "\"out.mp4\"");
+        // a quote cannot end the value and inject other elements
+        assert_eq!(quote("a\" ! fakesink \\"), "\"a\\\" ! fakesink \\\\\"");
+    }
+
+    #[test]
+    fn frame_decimator() {
+        let ms = Duration::from_millis;
+
+        // keep one frame out of three
+        let mut decimator = FrameDecimator::new(2, None);
+        let kept = (0..7)
+            .filter(|&i| decimator.keep(ms(i)))
+            .collect::<Vec<_>>();
+        assert_eq!(kept, vec![0, 3, 6]);
+
+        // a 30 fps camera with jitter limited to 10 fps
+        let mut decimator = FrameDecimator::new(0, Some(10));
+        let kept = (0..30u64)
+            .map(|i| i * 1000 / 30 + if i % 2 == 0 { 0 } else { 3 })
+            .filter(|&t| decimator.keep(ms(t)))
+            .count();
+        assert_eq!(kept, 10);
+    }
+}
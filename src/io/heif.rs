@@ -0,0 +1,68 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::image::{Image, ImageSize, ImageView};
+
+/// Reads a HEIF image, e.g. a HEIC photo, from the given file path.
+///
+/// The primary image of the file is decoded with libheif. The alpha channel, the
+/// thumbnails and the auxiliary images such as depth maps are discarded.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the HEIF image.
+///
+/// # Returns
+///
+/// An RGB image containing the primary image data.
+///
+/// # Errors
+///
+/// Returns an error if the file does not exist or cannot be decoded.
+pub fn read_image_heif(file_path: &Path) -> Result<Image<u8, 3>> {
+    if !file_path.exists() {
+        return Err(anyhow::anyhow!(
+            "File does not exist: {}",
+            file_path.to_string_lossy()
+        ));
+    }
+
+    let data = std::fs::read(file_path)?;
+
+    let lib_heif = libheif_rs::LibHeif::new();
+    let context = libheif_rs::HeifContext::read_from_bytes(&data)?;
+    let handle = context.primary_image_handle()?;
+    let decoded = lib_heif.decode(
+        &handle,
+        libheif_rs::ColorSpace::Rgb(libheif_rs::RgbChroma::Rgb),
+        None,
+    )?;
+
+    let plane = decoded
+        .planes()
+        .interleaved
+        .ok_or_else(|| anyhow::anyhow!("The decoded HEIF image is not interleaved."))?;
+
+    // the rows of the decoded plane can be padded
+    let size = ImageSize {
+        width: plane.width as usize,
+        height: plane.height as usize,
+    };
+    let view = ImageView::<u8, 3>::from_slice(size, plane.stride, plane.data)?;
+
+    Ok(view.to_image())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use std::path::Path;
+
+    #[test]
+    fn read_heif_invalid() -> Result<()> {
+        assert!(super::read_image_heif(Path::new("missing.heic")).is_err());
+        // a JPEG file is not a valid HEIF file
+        assert!(super::read_image_heif(Path::new("tests/data/dog.jpeg")).is_err());
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use turbojpeg;
 
+use crate::error::KorniaError;
+use crate::geometry::Rect;
 use crate::image::{Image, ImageSize};
 
 /// A JPEG decoder using the turbojpeg library.
@@ -8,6 +10,43 @@ pub struct ImageDecoder {
     pub decompressor: turbojpeg::Decompressor,
 }
 
+/// The scaling factor applied by the decoder in the DCT domain.
+///
+/// Decoding at a reduced scale skips most of the inverse DCT work, which is much faster
+/// than decoding at full resolution and resizing afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JpegScale {
+    /// Decode at full resolution.
+    Full,
+    /// Decode at 1/2 of the resolution.
+    Half,
+    /// Decode at 1/4 of the resolution.
+    Quarter,
+    /// Decode at 1/8 of the resolution.
+    Eighth,
+}
+
+impl JpegScale {
+    fn scaling_factor(&self) -> turbojpeg::ScalingFactor {
+        match self {
+            JpegScale::Full => turbojpeg::ScalingFactor::ONE,
+            JpegScale::Half => turbojpeg::ScalingFactor::ONE_HALF,
+            JpegScale::Quarter => turbojpeg::ScalingFactor::ONE_QUARTER,
+            JpegScale::Eighth => turbojpeg::ScalingFactor::ONE_EIGHTH,
+        }
+    }
+
+    /// Scale an image side, rounding up as libjpeg-turbo does.
+    fn scale(&self, dimension: usize) -> usize {
+        match self {
+            JpegScale::Full => dimension,
+            JpegScale::Half => dimension.div_ceil(2),
+            JpegScale::Quarter => dimension.div_ceil(4),
+            JpegScale::Eighth => dimension.div_ceil(8),
+        }
+    }
+}
+
 /// A JPEG encoder using the turbojpeg library.
 pub struct ImageEncoder {
     pub compressor: turbojpeg::Compressor,
@@ -50,18 +89,24 @@ impl ImageEncoder {
     /// # Returns
     ///
     /// The encoded data as `Vec<u8>`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(size = %image.size())))]
     pub fn encode(&mut self, image: &Image<u8, 3>) -> Result<Vec<u8>> {
-        // get the image data
-        let image_data = match image.data.as_slice() {
-            Some(d) => d,
-            None => Err(anyhow::anyhow!("Image data is not contiguous"))?,
+        // pass the rows with their pitch, e.g. for a region of interest, and copy the
+        // image only if the pixels of a row are not contiguous
+        let contiguous;
+        let (pixels, pitch) = match image.as_pitched_slice() {
+            Some(pitched) => pitched,
+            None => {
+                contiguous = image.as_contiguous_slice();
+                (contiguous.as_ref(), 3 * image.width())
+            }
         };
 
         // create a turbojpeg image
         let buf = turbojpeg::Image {
-            pixels: image_data,
+            pixels,
             width: image.width(),
-            pitch: 3 * image.width(),
+            pitch,
             height: image.height(),
             format: turbojpeg::PixelFormat::RGB,
         };
@@ -87,8 +132,8 @@ impl ImageDecoder {
     /// # Returns
     ///
     /// A new `ImageDecoder` instance.
-    pub fn new() -> Result<Self> {
-        let decompressor = turbojpeg::Decompressor::new()?;
+    pub fn new() -> Result<Self, KorniaError> {
+        let decompressor = turbojpeg::Decompressor::new().map_err(decode_error)?;
         Ok(ImageDecoder { decompressor })
     }
 
@@ -102,12 +147,15 @@ impl ImageDecoder {
     ///
     /// The image size.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the header cannot be read.
-    pub fn read_header(&mut self, jpeg_data: &[u8]) -> Result<ImageSize> {
+    /// Returns [`KorniaError::Decode`] if the header cannot be read.
+    pub fn read_header(&mut self, jpeg_data: &[u8]) -> Result<ImageSize, KorniaError> {
         // read the JPEG header with image size
-        let header = self.decompressor.read_header(jpeg_data)?;
+        let header = self
+            .decompressor
+            .read_header(jpeg_data)
+            .map_err(decode_error)?;
         Ok(ImageSize {
             width: header.width,
             height: header.height,
@@ -123,7 +171,12 @@ impl ImageDecoder {
     /// # Returns
     ///
     /// The decoded data as Tensor.
-    pub fn decode(&mut self, jpeg_data: &[u8]) -> Result<Image<u8, 3>> {
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KorniaError::Decode`] if the data is not a valid JPEG image.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(bytes = jpeg_data.len())))]
+    pub fn decode(&mut self, jpeg_data: &[u8]) -> Result<Image<u8, 3>, KorniaError> {
         // get the image size to allocate th data storage
         let image_size: ImageSize = self.read_header(jpeg_data)?;
 
@@ -140,15 +193,154 @@ impl ImageDecoder {
         };
 
         // decompress the JPEG data
-        self.decompressor.decompress(jpeg_data, buf)?;
+        self.decompressor
+            .decompress(jpeg_data, buf)
+            .map_err(decode_error)?;
 
-        Image::new(image_size, pixels)
+        Image::new(image_size, pixels).map_err(|_| KorniaError::InvalidImageSize(image_size))
+    }
+
+    /// Decodes the given JPEG data at a reduced resolution.
+    ///
+    /// # Arguments
+    ///
+    /// * `jpeg_data` - The JPEG data to decode.
+    /// * `scale` - The scaling factor applied while decoding.
+    ///
+    /// # Returns
+    ///
+    /// The decoded image, with each side scaled and rounded up.
+    pub fn decode_scaled(
+        &mut self,
+        jpeg_data: &[u8],
+        scale: JpegScale,
+    ) -> Result<Image<u8, 3>, KorniaError> {
+        let full_size = self.read_header(jpeg_data)?;
+        let image_size = ImageSize {
+            width: scale.scale(full_size.width),
+            height: scale.scale(full_size.height),
+        };
+
+        self.decompressor
+            .set_scaling_factor(scale.scaling_factor())
+            .map_err(decode_error)?;
+
+        let mut pixels = vec![0u8; image_size.height * image_size.width * 3];
+        let buf = turbojpeg::Image {
+            pixels: pixels.as_mut_slice(),
+            width: image_size.width,
+            pitch: 3 * image_size.width,
+            height: image_size.height,
+            format: turbojpeg::PixelFormat::RGB,
+        };
+        let result = self.decompressor.decompress(jpeg_data, buf);
+
+        // restore the full resolution for the next images
+        self.decompressor
+            .set_scaling_factor(turbojpeg::ScalingFactor::ONE)
+            .map_err(decode_error)?;
+        result.map_err(decode_error)?;
+
+        Image::new(image_size, pixels).map_err(|_| KorniaError::InvalidImageSize(image_size))
+    }
+
+    /// Decodes a region of interest of the given JPEG data.
+    ///
+    /// Only the MCU blocks covering the region are decoded. The left edge of the decoded
+    /// region is aligned to the MCU grid and the extra columns are dropped afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `jpeg_data` - The JPEG data to decode.
+    /// * `rect` - The region to decode in pixel coordinates.
+    ///
+    /// # Returns
+    ///
+    /// The decoded region with the size of the rectangle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KorniaError::InvalidArgument`] if the rectangle is empty or not fully
+    /// inside the image, or [`KorniaError::Decode`] if the data cannot be decoded.
+    pub fn decode_roi(
+        &mut self,
+        jpeg_data: &[u8],
+        rect: Rect,
+    ) -> Result<Image<u8, 3>, KorniaError> {
+        let header = self
+            .decompressor
+            .read_header(jpeg_data)
+            .map_err(decode_error)?;
+        if rect.width == 0
+            || rect.height == 0
+            || rect.x + rect.width > header.width
+            || rect.y + rect.height > header.height
+        {
+            return Err(KorniaError::InvalidArgument(format!(
+                "The region {:?} is empty or outside of the image of size {}x{}.",
+                rect, header.width, header.height
+            )));
+        }
+
+        // the horizontal offset must be a multiple of the MCU width
+        let mcu_width = match header.subsamp {
+            turbojpeg::Subsamp::None | turbojpeg::Subsamp::Gray | turbojpeg::Subsamp::Sub1x2 => 8,
+            turbojpeg::Subsamp::Sub2x1 | turbojpeg::Subsamp::Sub2x2 => 16,
+            _ => 32,
+        };
+        let x0 = rect.x - rect.x % mcu_width;
+        let crop_size = ImageSize {
+            width: rect.x + rect.width - x0,
+            height: rect.height,
+        };
+
+        // use a dedicated decompressor to leave the shared one uncropped
+        let mut decompressor = turbojpeg::Decompressor::new().map_err(decode_error)?;
+        decompressor
+            .set_cropping_region(turbojpeg::CroppingRegion {
+                x: x0,
+                y: rect.y,
+                width: crop_size.width,
+                height: crop_size.height,
+            })
+            .map_err(decode_error)?;
+
+        let mut pixels = vec![0u8; crop_size.height * crop_size.width * 3];
+        let buf = turbojpeg::Image {
+            pixels: pixels.as_mut_slice(),
+            width: crop_size.width,
+            pitch: 3 * crop_size.width,
+            height: crop_size.height,
+            format: turbojpeg::PixelFormat::RGB,
+        };
+        decompressor
+            .decompress(jpeg_data, buf)
+            .map_err(decode_error)?;
+
+        // drop the columns decoded for the alignment
+        let cropped = Image::<u8, 3>::new(crop_size, pixels)
+            .map_err(|_| KorniaError::InvalidImageSize(crop_size))?;
+        let offset = rect.x - x0;
+        let data = cropped
+            .data
+            .slice(ndarray::s![.., offset.., ..])
+            .as_standard_layout()
+            .into_owned();
+
+        Ok(Image { data })
     }
 }
 
+/// Maps an error of libjpeg-turbo while decoding.
+fn decode_error(err: turbojpeg::Error) -> KorniaError {
+    KorniaError::Decode(err.to_string())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::io::jpeg::{ImageDecoder, ImageEncoder};
+    use crate::geometry::Rect;
+    use crate::image::Image;
+    use crate::io::jpeg::{ImageDecoder, ImageEncoder, JpegScale};
     use anyhow::Result;
 
     #[test]
@@ -166,6 +358,56 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn image_decoder_scaled() -> Result<()> {
+        let jpeg_data = std::fs::read("tests/data/dog.jpeg")?;
+        let mut decoder = ImageDecoder::new()?;
+        for (scale, width, height) in [
+            (JpegScale::Half, 129, 98),
+            (JpegScale::Quarter, 65, 49),
+            (JpegScale::Eighth, 33, 25),
+        ] {
+            let image = decoder.decode_scaled(&jpeg_data, scale)?;
+            assert_eq!(image.size().width, width);
+            assert_eq!(image.size().height, height);
+        }
+
+        // the decoder is back to full resolution
+        let image = decoder.decode(&jpeg_data)?;
+        assert_eq!(image.size().width, 258);
+        Ok(())
+    }
+
+    #[test]
+    fn image_decoder_roi() -> Result<()> {
+        let jpeg_data = std::fs::read("tests/data/dog.jpeg")?;
+        let mut decoder = ImageDecoder::new()?;
+        let image = decoder.decode(&jpeg_data)?;
+
+        let rect = Rect {
+            x: 37,
+            y: 21,
+            width: 50,
+            height: 40,
+        };
+        let roi = decoder.decode_roi(&jpeg_data, rect)?;
+        assert_eq!(roi.size().width, 50);
+        assert_eq!(roi.size().height, 40);
+
+        // the region matches the crop of the full image up to the decoding rounding
+        let max_diff = roi
+            .data
+            .indexed_iter()
+            .map(|((y, x, c), &v)| (v as i32 - image.data[[y + 21, x + 37, c]] as i32).abs())
+            .max()
+            .unwrap();
+        assert!(max_diff <= 8, "max diff {}", max_diff);
+
+        let outside = Rect { x: 250, ..rect };
+        assert!(decoder.decode_roi(&jpeg_data, outside).is_err());
+        Ok(())
+    }
+
     #[test]
     fn image_encoder() -> Result<()> {
         let jpeg_data_fs = std::fs::read("tests/data/dog.jpeg")?;
@@ -175,6 +417,15 @@ mod tests {
         assert_eq!(image_back.size().width, 258);
         assert_eq!(image_back.size().height, 195);
         assert_eq!(image_back.num_channels(), 3);
+
+        // a region of interest is encoded with the row pitch of the full image
+        let roi = Image::<u8, 3> {
+            data: image.data.slice_move(ndarray::s![10..110, 20..220, ..]),
+        };
+        assert!(roi.as_pitched_slice().is_some());
+        let image_back = ImageDecoder::new()?.decode(&ImageEncoder::new()?.encode(&roi)?)?;
+        assert_eq!(image_back.size().width, 200);
+        assert_eq!(image_back.size().height, 100);
         Ok(())
     }
 }
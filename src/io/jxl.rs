@@ -0,0 +1,154 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::image::{Image, ImageSize};
+
+/// The compression applied by the JPEG XL encoder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JxlEncoding {
+    /// Mathematically lossless compression.
+    Lossless,
+    /// Lossy compression with a butteraugli distance, from 0.0 (best quality) to 15.0.
+    ///
+    /// A distance of 1.0 is visually lossless.
+    Lossy {
+        /// The target butteraugli distance.
+        distance: f32,
+    },
+}
+
+/// Reads a JPEG XL image from the given file path.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JPEG XL image.
+///
+/// # Returns
+///
+/// An RGB image containing the JPEG XL image data, with the alpha channel discarded.
+///
+/// # Errors
+///
+/// Returns an error if the file does not exist or cannot be decoded.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::jxl::{read_image_jxl, write_image_jxl, JxlEncoding};
+///
+/// let image = Image::<u8, 3>::new(
+///     ImageSize {
+///         width: 4,
+///         height: 2,
+///     },
+///     (0..24).map(|i| (i * 10) as u8).collect(),
+/// )
+/// .unwrap();
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("image.jxl");
+/// write_image_jxl(&file_path, &image, JxlEncoding::Lossless).unwrap();
+///
+/// let image_back = read_image_jxl(&file_path).unwrap();
+/// assert_eq!(image_back.data, image.data);
+/// ```
+pub fn read_image_jxl(file_path: &Path) -> Result<Image<u8, 3>> {
+    if !file_path.exists() {
+        return Err(anyhow::anyhow!(
+            "File does not exist: {}",
+            file_path.to_string_lossy()
+        ));
+    }
+
+    let data = std::fs::read(file_path)?;
+
+    // request interleaved RGB pixels regardless of the stored channels
+    let decoder = jpegxl_rs::decoder_builder()
+        .pixel_format(jpegxl_rs::decode::PixelFormat {
+            num_channels: 3,
+            ..Default::default()
+        })
+        .build()?;
+    let (metadata, pixels) = decoder.decode_with::<u8>(&data)?;
+
+    Image::new(
+        ImageSize {
+            width: metadata.width as usize,
+            height: metadata.height as usize,
+        },
+        pixels,
+    )
+}
+
+/// Writes an RGB image to a JPEG XL file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the JPEG XL image.
+/// * `image` - The image to write.
+/// * `encoding` - The lossless or lossy compression to apply.
+///
+/// # Errors
+///
+/// Returns an error if the distance is out of range or the image cannot be encoded.
+pub fn write_image_jxl(
+    file_path: &Path,
+    image: &Image<u8, 3>,
+    encoding: JxlEncoding,
+) -> Result<()> {
+    let mut encoder = match encoding {
+        JxlEncoding::Lossless => jpegxl_rs::encoder_builder()
+            .lossless(true)
+            .uses_original_profile(true)
+            .build()?,
+        JxlEncoding::Lossy { distance } => {
+            if !(0.0..=15.0).contains(&distance) {
+                return Err(anyhow::anyhow!(
+                    "The JPEG XL distance must be in the range [0, 15], got {}.",
+                    distance
+                ));
+            }
+            jpegxl_rs::encoder_builder().quality(distance).build()?
+        }
+    };
+
+    let pixels = image.as_contiguous_slice();
+    let encoded: jpegxl_rs::encode::EncoderResult<u8> =
+        encoder.encode(&pixels, image.width() as u32, image.height() as u32)?;
+
+    std::fs::write(file_path, encoded.data)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JxlEncoding;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn read_write_jxl() -> Result<()> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 16,
+                height: 8,
+            },
+            (0..16 * 8 * 3).map(|i| (i % 251) as u8).collect(),
+        )?;
+
+        let tmp_dir = tempfile::tempdir()?;
+        let file_path = tmp_dir.path().join("image.jxl");
+
+        super::write_image_jxl(&file_path, &image, JxlEncoding::Lossless)?;
+        assert_eq!(super::read_image_jxl(&file_path)?.data, image.data);
+
+        super::write_image_jxl(&file_path, &image, JxlEncoding::Lossy { distance: 1.0 })?;
+        assert_eq!(super::read_image_jxl(&file_path)?.size(), image.size());
+
+        let invalid = JxlEncoding::Lossy { distance: 20.0 };
+        assert!(super::write_image_jxl(&file_path, &image, invalid).is_err());
+        Ok(())
+    }
+}
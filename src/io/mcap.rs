@@ -0,0 +1,342 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use super::frame::{decode_frame_payload, encode_frame, FrameEncoding, FrameHeader};
+use crate::image::Image;
+
+/// The message encoding of the recorded frames.
+///
+/// Each message holds a [`FrameHeader`] followed by the raw or JPEG payload.
+const MESSAGE_ENCODING: &str = "kornia.frame";
+
+/// A frame read from an MCAP recording.
+pub struct McapFrame<const CHANNELS: usize> {
+    /// The topic the frame was recorded on.
+    pub topic: String,
+    /// The timestamp of the frame when it was recorded.
+    pub timestamp: Duration,
+    /// The decoded frame.
+    pub image: Image<u8, CHANNELS>,
+}
+
+/// A recorder writing frames to an MCAP file, with one channel per topic.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::frame::FrameEncoding;
+/// use kornia_rs::io::mcap::McapRecorder;
+/// use std::time::Duration;
+///
+/// let mut recorder = McapRecorder::create("recording.mcap")
+///     .unwrap()
+///     .with_encoding(FrameEncoding::Jpeg { quality: 90 });
+///
+/// let size = ImageSize {
+///     width: 640,
+///     height: 480,
+/// };
+/// for i in 0..10 {
+///     let frame = Image::<u8, 3>::from_size_val(size, i).unwrap();
+///     let timestamp = Duration::from_millis(33 * i as u64);
+///     recorder.record("camera/front", &frame, timestamp).unwrap();
+/// }
+/// recorder.finish().unwrap();
+/// ```
+pub struct McapRecorder {
+    writer: mcap::Writer<'static, BufWriter<std::fs::File>>,
+    channels: HashMap<String, u16>,
+    sequence: u32,
+    encoding: FrameEncoding,
+}
+
+impl McapRecorder {
+    /// Creates a new McapRecorder object writing to a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the MCAP file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = std::fs::File::create(path)?;
+        Ok(Self {
+            writer: mcap::Writer::new(BufWriter::new(file))?,
+            channels: HashMap::new(),
+            sequence: 0,
+            encoding: FrameEncoding::Raw,
+        })
+    }
+
+    /// Sets the encoding of the frames, raw by default.
+    pub fn with_encoding(mut self, encoding: FrameEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Records a frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The topic of the frame, a channel is created for each new topic
+    /// * `image` - The frame to record
+    /// * `timestamp` - The timestamp of the frame, used to replay the recording
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame cannot be encoded or written.
+    pub fn record<const CHANNELS: usize>(
+        &mut self,
+        topic: &str,
+        image: &Image<u8, CHANNELS>,
+        timestamp: Duration,
+    ) -> Result<()> {
+        let channel_id = match self.channels.get(topic) {
+            Some(&id) => id,
+            None => {
+                let id = self.writer.add_channel(&mcap::Channel {
+                    topic: topic.to_string(),
+                    schema: None,
+                    message_encoding: MESSAGE_ENCODING.to_string(),
+                    metadata: BTreeMap::new(),
+                })?;
+                self.channels.insert(topic.to_string(), id);
+                id
+            }
+        };
+
+        let (header, payload) = encode_frame(image, self.encoding, timestamp)?;
+        let mut data = Vec::with_capacity(FrameHeader::LEN + payload.len());
+        data.extend_from_slice(&header.to_bytes());
+        data.extend_from_slice(&payload);
+
+        let time = timestamp.as_nanos() as u64;
+        self.writer.write_to_known_channel(
+            &mcap::records::MessageHeader {
+                channel_id,
+                sequence: self.sequence,
+                log_time: time,
+                publish_time: time,
+            },
+            &data,
+        )?;
+        self.sequence += 1;
+
+        Ok(())
+    }
+
+    /// Writes the summary of the recording and closes the file.
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.finish()?;
+        Ok(())
+    }
+}
+
+/// A player replaying the frames of an MCAP recording.
+///
+/// The frames are replayed in the order of the file, as fast as possible by default so
+/// that a recording is re-processed deterministically, or paced with their timestamps
+/// to simulate a live stream.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::io::mcap::McapPlayer;
+///
+/// let player = McapPlayer::open("recording.mcap")
+///     .unwrap()
+///     .with_topics(&["camera/front"]);
+///
+/// for frame in player.frames::<3>().unwrap() {
+///     let frame = frame.unwrap();
+///     println!("{} at {:?}", frame.topic, frame.timestamp);
+/// }
+/// ```
+pub struct McapPlayer {
+    mmap: memmap2::Mmap,
+    topics: Option<Vec<String>>,
+    realtime: bool,
+}
+
+impl McapPlayer {
+    /// Opens an MCAP recording.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the MCAP file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the recording must not be modified while it is replayed
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self {
+            mmap,
+            topics: None,
+            realtime: false,
+        })
+    }
+
+    /// Replays only the frames of the given topics, all the topics by default.
+    pub fn with_topics(mut self, topics: &[&str]) -> Self {
+        self.topics = Some(topics.iter().map(|t| t.to_string()).collect());
+        self
+    }
+
+    /// Paces the frames with their timestamps in [`McapPlayer::run_with_cancel`].
+    pub fn with_realtime(mut self, realtime: bool) -> Self {
+        self.realtime = realtime;
+        self
+    }
+
+    /// Returns the frames of the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is not an MCAP recording. The frames that cannot be
+    /// decoded are returned as errors by the iterator.
+    pub fn frames<const CHANNELS: usize>(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<McapFrame<CHANNELS>>> + '_> {
+        let stream = mcap::MessageStream::new(&self.mmap)?;
+        Ok(stream.filter_map(move |message| {
+            let message = match message {
+                Ok(message) => message,
+                Err(err) => return Some(Err(err.into())),
+            };
+            let channel = &message.channel;
+            if channel.message_encoding != MESSAGE_ENCODING {
+                return None;
+            }
+            if let Some(topics) = &self.topics {
+                if !topics.contains(&channel.topic) {
+                    return None;
+                }
+            }
+            Some(decode_message(
+                &channel.topic,
+                message.log_time,
+                &message.data,
+            ))
+        }))
+    }
+
+    /// Replays the recording until the token is cancelled or the recording ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The token stopping the replay once cancelled
+    /// * `f` - A function that takes a frame
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a frame cannot be decoded or the function returns an error.
+    pub async fn run_with_cancel<const CHANNELS: usize, F>(
+        &self,
+        token: CancellationToken,
+        mut f: F,
+    ) -> Result<()>
+    where
+        F: FnMut(McapFrame<CHANNELS>) -> Result<()>,
+    {
+        // the wall clock time and the timestamp of the first frame
+        let mut origin: Option<(Instant, Duration)> = None;
+
+        for frame in self.frames::<CHANNELS>()? {
+            if token.is_cancelled() {
+                break;
+            }
+            let frame = frame?;
+
+            if self.realtime {
+                let (start, first) = *origin.get_or_insert((Instant::now(), frame.timestamp));
+                let due = start + frame.timestamp.saturating_sub(first);
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = tokio::time::sleep_until(due.into()) => (),
+                }
+            }
+
+            f(frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Replays the whole recording.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function that takes a frame
+    pub async fn run<const CHANNELS: usize, F>(&self, f: F) -> Result<()>
+    where
+        F: FnMut(McapFrame<CHANNELS>) -> Result<()>,
+    {
+        self.run_with_cancel(CancellationToken::new(), f).await
+    }
+}
+
+/// Decode a recorded message to a frame.
+fn decode_message<const CHANNELS: usize>(
+    topic: &str,
+    log_time: u64,
+    data: &[u8],
+) -> Result<McapFrame<CHANNELS>> {
+    if data.len() < FrameHeader::LEN {
+        return Err(anyhow::anyhow!("The message of {} is truncated", topic));
+    }
+    let (header, payload) = data.split_at(FrameHeader::LEN);
+    let header = FrameHeader::from_bytes(header)?;
+
+    Ok(McapFrame {
+        topic: topic.to_string(),
+        timestamp: Duration::from_nanos(log_time),
+        image: decode_frame_payload(&header, payload.to_vec())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{McapPlayer, McapRecorder};
+    use crate::image::{Image, ImageSize};
+    use std::time::Duration;
+
+    #[test]
+    fn record_and_replay() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let path = tmp_dir.path().join("recording.mcap");
+
+        let size = ImageSize {
+            width: 4,
+            height: 2,
+        };
+        let mut recorder = McapRecorder::create(&path)?;
+        for i in 0..4u8 {
+            let frame = Image::<u8, 3>::from_size_val(size, i)?;
+            let topic = if i % 2 == 0 { "left" } else { "right" };
+            recorder.record(topic, &frame, Duration::from_millis(10 * i as u64))?;
+        }
+        recorder.finish()?;
+
+        let player = McapPlayer::open(&path)?;
+        let frames = player.frames::<3>()?.collect::<anyhow::Result<Vec<_>>>()?;
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[3].topic, "right");
+        assert_eq!(frames[3].timestamp, Duration::from_millis(30));
+        assert_eq!(frames[3].image.data[[1, 3, 2]], 3);
+
+        let player = player.with_topics(&["left"]);
+        let timestamps = player
+            .frames::<3>()?
+            .map(|frame| frame.map(|f| f.timestamp))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        assert_eq!(timestamps, vec![Duration::ZERO, Duration::from_millis(20)]);
+
+        Ok(())
+    }
+}
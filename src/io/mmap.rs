@@ -0,0 +1,129 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::error::KorniaError;
+use crate::image::{ImageSize, ImageView};
+
+use super::pnm::{parse_header, PnmEncoding};
+
+/// An image file mapped to memory.
+///
+/// The pixel data is read lazily by the operating system when accessed through
+/// [`MappedImage::view`], and is never copied unless converted to an owned image.
+pub struct MappedImage<const CHANNELS: usize> {
+    mmap: memmap2::Mmap,
+    size: ImageSize,
+    offset: usize,
+}
+
+impl<const CHANNELS: usize> MappedImage<CHANNELS> {
+    /// Map an uncompressed image file to memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - The path to a binary PGM (1 channel) or PPM (3 channels) file.
+    ///
+    /// # Returns
+    ///
+    /// The mapped image.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be mapped, is not a supported format, has a number of
+    /// channels different from `CHANNELS`, has a size overflowing the address space or
+    /// is truncated, an error is returned.
+    pub fn open(file_path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(file_path)?;
+
+        // SAFETY: the file is mapped read-only and must not be truncated while mapped
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+        let header = parse_header(&mmap)?;
+        if header.encoding() != PnmEncoding::Binary || header.format == 4 || header.max_value > 255
+        {
+            return Err(anyhow::anyhow!(
+                "Only binary PGM (P5) and PPM (P6) files with 8 bits per sample can be mapped."
+            ));
+        }
+
+        let (size, channels, offset) = (header.size, header.channels(), header.offset);
+        if channels != CHANNELS {
+            return Err(anyhow::anyhow!(
+                "The file has {} channels but {} were requested.",
+                channels,
+                CHANNELS
+            ));
+        }
+
+        // the header is untrusted, so the size must not overflow
+        let (len, end) = size
+            .width
+            .checked_mul(size.height)
+            .and_then(|n| n.checked_mul(CHANNELS))
+            .and_then(|len| Some((len, offset.checked_add(len)?)))
+            .ok_or(KorniaError::InvalidImageSize(size))?;
+        if mmap.len() < end {
+            return Err(anyhow::anyhow!(
+                "The file is truncated: expected {} bytes of pixel data, got {}.",
+                len,
+                mmap.len().saturating_sub(offset)
+            ));
+        }
+
+        Ok(Self { mmap, size, offset })
+    }
+
+    /// Get the size of the image in pixels.
+    pub fn size(&self) -> ImageSize {
+        self.size
+    }
+
+    /// Get a read-only view of the mapped pixel data.
+    pub fn view(&self) -> ImageView<'_, u8, CHANNELS> {
+        ImageView::from_slice(
+            self.size,
+            self.size.width * CHANNELS,
+            &self.mmap[self.offset..],
+        )
+        .expect("The mapped file was validated on open")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MappedImage;
+    use crate::error::KorniaError;
+    use anyhow::Result;
+
+    #[test]
+    fn mapped_image() -> Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let file_path = tmp_dir.path().join("image.pgm");
+        std::fs::write(&file_path, b"P5\n3 2\n255\n\x00\x01\x02\x03\x04\x05")?;
+
+        let image = MappedImage::<1>::open(&file_path)?;
+        assert_eq!((image.size().width, image.size().height), (3, 2));
+        assert!(MappedImage::<3>::open(&file_path).is_err());
+
+        // a truncated file
+        std::fs::write(&file_path, b"P5\n3 2\n255\n\x00\x01")?;
+        assert!(MappedImage::<1>::open(&file_path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn mapped_image_oversized_header() -> Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let file_path = tmp_dir.path().join("image.ppm");
+        // the number of bytes overflows a usize instead of reading out of the mapping
+        let header = format!("P6\n{} {}\n255\n", usize::MAX / 2, 3);
+        std::fs::write(&file_path, header)?;
+
+        let err = MappedImage::<3>::open(&file_path).err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<KorniaError>(),
+            Some(KorniaError::InvalidImageSize(_))
+        ));
+        Ok(())
+    }
+}
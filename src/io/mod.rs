@@ -1,6 +1,38 @@
+#[cfg(feature = "avif")]
+pub mod avif;
+pub mod bmp;
+#[cfg(feature = "flight")]
+pub mod flight;
 pub mod fps_counter;
+#[cfg(any(feature = "mcap", feature = "zmq"))]
+pub mod frame;
 pub mod functional;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "gstreamer")]
+pub mod gst;
+#[cfg(feature = "heif")]
+pub mod heif;
 #[cfg(feature = "jpegturbo")]
 pub mod jpeg;
+#[cfg(feature = "jxl")]
+pub mod jxl;
+#[cfg(feature = "mcap")]
+pub mod mcap;
+pub mod mmap;
+pub mod pfm;
+pub mod pnm;
+#[cfg(feature = "async")]
+pub mod reader;
+pub mod recorder;
+#[cfg(unix)]
+pub mod shm;
+#[cfg(feature = "gstreamer")]
+pub mod stream;
+pub mod tiff;
+#[cfg(feature = "gstreamer")]
+pub mod video;
 #[cfg(feature = "gstreamer")]
 pub mod webcam;
+#[cfg(feature = "zmq")]
+pub mod zmq;
@@ -0,0 +1,213 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::image::{Image, ImageSize};
+
+/// Read the whitespace separated header token starting at the given position.
+///
+/// # Returns
+///
+/// The token and the position of the whitespace character ending it.
+fn read_token(data: &[u8], pos: usize) -> Result<(&str, usize)> {
+    let mut start = pos;
+    while data.get(start).is_some_and(|c| c.is_ascii_whitespace()) {
+        start += 1;
+    }
+    let mut end = start;
+    while data.get(end).is_some_and(|c| !c.is_ascii_whitespace()) {
+        end += 1;
+    }
+
+    if start == end || end >= data.len() {
+        return Err(anyhow::anyhow!("The PFM header is truncated."));
+    }
+
+    Ok((std::str::from_utf8(&data[start..end])?, end))
+}
+
+/// Decode a PFM file.
+///
+/// # Returns
+///
+/// The image size, the number of channels and the samples in top-down row order.
+fn decode_pfm(data: &[u8]) -> Result<(ImageSize, usize, Vec<f32>)> {
+    let channels = match data.get(..2) {
+        Some(b"PF") => 3,
+        Some(b"Pf") => 1,
+        _ => return Err(anyhow::anyhow!("Not a PFM file.")),
+    };
+
+    let invalid = |token: &str| anyhow::anyhow!("Invalid PFM header value {}.", token);
+    let (token, pos) = read_token(data, 2)?;
+    let width = token.parse::<usize>().map_err(|_| invalid(token))?;
+    let (token, pos) = read_token(data, pos)?;
+    let height = token.parse::<usize>().map_err(|_| invalid(token))?;
+    let (token, pos) = read_token(data, pos)?;
+    let scale = token.parse::<f32>().map_err(|_| invalid(token))?;
+
+    // the sign of the scale gives the byte order, negative meaning little-endian
+    if scale == 0.0 || !scale.is_finite() {
+        return Err(invalid(token));
+    }
+    let from_bytes = if scale < 0.0 {
+        f32::from_le_bytes
+    } else {
+        f32::from_be_bytes
+    };
+
+    // a single whitespace separates the header from the pixel data
+    let row_len = width * channels;
+    let raster = data
+        .get(pos + 1..pos + 1 + 4 * row_len * height)
+        .ok_or_else(|| anyhow::anyhow!("The PFM pixel data is truncated."))?;
+
+    // the rows are stored bottom-up
+    let mut samples = Vec::with_capacity(row_len * height);
+    for row in raster.chunks_exact(4 * row_len.max(1)).rev() {
+        samples.extend(
+            row.chunks_exact(4)
+                .map(|b| from_bytes([b[0], b[1], b[2], b[3]])),
+        );
+    }
+
+    Ok((ImageSize { width, height }, channels, samples))
+}
+
+/// Reads a PFM image from the given file path.
+///
+/// Both big-endian and little-endian files are supported.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the PFM image.
+///
+/// # Returns
+///
+/// An image with 1 channel for grayscale (`Pf`) files or 3 channels for color (`PF`) files.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a valid PFM file or its number of channels
+/// differs from `CHANNELS`.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::pfm::{read_image_pfm, write_image_pfm};
+///
+/// let disparity = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 2,
+///     },
+///     vec![0.5, 1.5, 2.5, f32::INFINITY],
+/// )
+/// .unwrap();
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("disparity.pfm");
+/// write_image_pfm(&file_path, &disparity).unwrap();
+///
+/// let disparity_back = read_image_pfm::<1>(&file_path).unwrap();
+/// assert_eq!(disparity_back.data, disparity.data);
+/// ```
+pub fn read_image_pfm<const CHANNELS: usize>(file_path: &Path) -> Result<Image<f32, CHANNELS>> {
+    let data = std::fs::read(file_path)?;
+    let (size, channels, samples) = decode_pfm(&data)?;
+
+    if channels != CHANNELS {
+        return Err(anyhow::anyhow!(
+            "The file has {} channels but {} were requested.",
+            channels,
+            CHANNELS
+        ));
+    }
+
+    Image::new(size, samples)
+}
+
+/// Encode the samples of an image as a little-endian PFM file.
+fn encode_pfm(size: ImageSize, channels: usize, samples: &[f32]) -> Vec<u8> {
+    let magic = if channels == 3 { "PF" } else { "Pf" };
+    let mut data = format!("{}\n{} {}\n-1.0\n", magic, size.width, size.height).into_bytes();
+
+    let row_len = (size.width * channels).max(1);
+    for row in samples.chunks(row_len).rev() {
+        data.extend(row.iter().flat_map(|v| v.to_le_bytes()));
+    }
+
+    data
+}
+
+/// Writes an image to a little-endian PFM file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the PFM image.
+/// * `image` - The image to write, with 1 or 3 channels.
+///
+/// # Errors
+///
+/// Returns an error if the image does not have 1 or 3 channels or the file cannot be written.
+pub fn write_image_pfm<const CHANNELS: usize>(
+    file_path: &Path,
+    image: &Image<f32, CHANNELS>,
+) -> Result<()> {
+    if CHANNELS != 1 && CHANNELS != 3 {
+        return Err(anyhow::anyhow!(
+            "Only images with 1 or 3 channels can be written as PFM, got {}.",
+            CHANNELS
+        ));
+    }
+
+    let data = encode_pfm(image.size(), CHANNELS, &image.as_contiguous_slice());
+    std::fs::write(file_path, data)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn pfm_roundtrip() -> Result<()> {
+        let image = Image::<f32, 3>::new(
+            ImageSize {
+                width: 2,
+                height: 3,
+            },
+            (0..18).map(|i| i as f32 * 0.25 - 1.0).collect(),
+        )?;
+
+        let data = super::encode_pfm(image.size(), 3, image.data.as_slice().unwrap());
+        assert!(data.starts_with(b"PF\n2 3\n-1.0\n"));
+
+        let (size, channels, samples) = super::decode_pfm(&data)?;
+        assert_eq!(size, image.size());
+        assert_eq!(channels, 3);
+        assert_eq!(samples, image.data.as_slice().unwrap());
+
+        assert!(super::decode_pfm(&data[..data.len() - 1]).is_err());
+        assert!(super::decode_pfm(b"P6\n2 3\n255\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn pfm_big_endian() -> Result<()> {
+        // two rows stored bottom-up with a positive scale
+        let mut data = b"Pf\n2 2\n1.0\n".to_vec();
+        for v in [3.0f32, 4.0, 1.0, 2.0] {
+            data.extend_from_slice(&v.to_be_bytes());
+        }
+
+        let (size, channels, samples) = super::decode_pfm(&data)?;
+        assert_eq!(size.width, 2);
+        assert_eq!(size.height, 2);
+        assert_eq!(channels, 1);
+        assert_eq!(samples, vec![1.0, 2.0, 3.0, 4.0]);
+        Ok(())
+    }
+}
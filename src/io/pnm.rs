@@ -0,0 +1,431 @@
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+
+use crate::image::{Image, ImageSize};
+
+/// The encoding of the pixel data of a PNM file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PnmEncoding {
+    /// Plain text samples separated by whitespace (P1, P2, P3).
+    Ascii,
+    /// Raw bytes (P4, P5, P6).
+    Binary,
+}
+
+/// The header of a PNM file.
+pub(crate) struct PnmHeader {
+    /// The format number, from 1 for P1 to 6 for P6.
+    pub format: u8,
+    /// The size of the image in pixels.
+    pub size: ImageSize,
+    /// The maximum sample value, 1 for bitmaps.
+    pub max_value: usize,
+    /// The offset of the pixel data in the file.
+    pub offset: usize,
+}
+
+impl PnmHeader {
+    /// Get the number of channels of the image.
+    pub fn channels(&self) -> usize {
+        match self.format {
+            3 | 6 => 3,
+            _ => 1,
+        }
+    }
+
+    /// Get the encoding of the pixel data.
+    pub fn encoding(&self) -> PnmEncoding {
+        if self.format <= 3 {
+            PnmEncoding::Ascii
+        } else {
+            PnmEncoding::Binary
+        }
+    }
+}
+
+/// Skip the whitespace and comments starting at the given position.
+fn skip_whitespace(data: &[u8], mut pos: usize) -> usize {
+    loop {
+        match data.get(pos) {
+            Some(b'#') => {
+                while data.get(pos).is_some_and(|&c| c != b'\n') {
+                    pos += 1;
+                }
+            }
+            Some(c) if c.is_ascii_whitespace() => pos += 1,
+            _ => return pos,
+        }
+    }
+}
+
+/// Parse the unsigned integer starting at the given position, skipping whitespace first.
+///
+/// # Returns
+///
+/// The value and the position after it.
+fn parse_number(data: &[u8], pos: usize) -> Result<(usize, usize)> {
+    let start = skip_whitespace(data, pos);
+    let mut end = start;
+    while data.get(end).is_some_and(|c| c.is_ascii_digit()) {
+        end += 1;
+    }
+
+    let value = std::str::from_utf8(&data[start..end])?
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid PNM number at byte {}.", start))?;
+
+    Ok((value, end))
+}
+
+/// Parse the header of a PNM file.
+pub(crate) fn parse_header(data: &[u8]) -> Result<PnmHeader> {
+    let format = match data.get(..2) {
+        Some([b'P', c @ b'1'..=b'6']) => c - b'0',
+        _ => return Err(anyhow::anyhow!("Not a PNM file.")),
+    };
+
+    let (width, pos) = parse_number(data, 2)?;
+    let (height, pos) = parse_number(data, pos)?;
+    let (max_value, pos) = match format {
+        1 | 4 => (1, pos),
+        _ => parse_number(data, pos)?,
+    };
+
+    if max_value == 0 || max_value > 65535 {
+        return Err(anyhow::anyhow!("Invalid PNM maximum value {}.", max_value));
+    }
+
+    // a single whitespace separates the header from the pixel data
+    if !data.get(pos).is_some_and(|c| c.is_ascii_whitespace()) {
+        return Err(anyhow::anyhow!("Invalid PNM header at byte {}.", pos));
+    }
+
+    Ok(PnmHeader {
+        format,
+        size: ImageSize { width, height },
+        max_value,
+        offset: pos + 1,
+    })
+}
+
+/// Decode the samples of a PNM file, scaled to 8 bits.
+fn decode_samples(data: &[u8], header: &PnmHeader) -> Result<Vec<u8>> {
+    let (width, height) = (header.size.width, header.size.height);
+    let num_samples = width * height * header.channels();
+    let raster = &data[header.offset..];
+    let truncated = || anyhow::anyhow!("The PNM pixel data is truncated.");
+
+    // bitmaps store black as 1
+    let bit_to_u8 = |bit: bool| if bit { 0 } else { 255 };
+    let scale = |v: usize| -> Result<u8> {
+        if v > header.max_value {
+            return Err(anyhow::anyhow!(
+                "The PNM sample {} exceeds the maximum value {}.",
+                v,
+                header.max_value
+            ));
+        }
+        Ok(((v * 255 + header.max_value / 2) / header.max_value) as u8)
+    };
+
+    match header.format {
+        1 => {
+            // the bits can be written without separators
+            let bits = raster.iter().filter(|&&c| c == b'0' || c == b'1');
+            let samples = bits
+                .take(num_samples)
+                .map(|&c| bit_to_u8(c == b'1'))
+                .collect::<Vec<_>>();
+            if samples.len() < num_samples {
+                return Err(truncated());
+            }
+            Ok(samples)
+        }
+        2 | 3 => {
+            let mut samples = Vec::with_capacity(num_samples);
+            let mut pos = 0;
+            while samples.len() < num_samples {
+                if skip_whitespace(raster, pos) >= raster.len() {
+                    return Err(truncated());
+                }
+                let (v, next) = parse_number(raster, pos)?;
+                samples.push(scale(v)?);
+                pos = next;
+            }
+            Ok(samples)
+        }
+        4 => {
+            // each row is padded to a whole number of bytes
+            let row_bytes = width.div_ceil(8);
+            if raster.len() < row_bytes * height {
+                return Err(truncated());
+            }
+            Ok(raster
+                .chunks_exact(row_bytes)
+                .take(height)
+                .flat_map(|row| {
+                    (0..width).map(move |x| bit_to_u8(row[x / 8] & (0x80 >> (x % 8)) != 0))
+                })
+                .collect())
+        }
+        _ => {
+            // 16-bit samples are stored in big-endian order
+            let bytes_per_sample = if header.max_value > 255 { 2 } else { 1 };
+            if raster.len() < num_samples * bytes_per_sample {
+                return Err(truncated());
+            }
+            raster
+                .chunks_exact(bytes_per_sample)
+                .take(num_samples)
+                .map(|b| scale(b.iter().fold(0, |acc, &v| (acc << 8) | v as usize)))
+                .collect()
+        }
+    }
+}
+
+/// Reads a PNM (PBM, PGM or PPM) image from the given file path.
+///
+/// Both the ASCII and binary encodings are supported. The bitmaps are read as black (0)
+/// and white (255) and the samples of more than 8 bits are scaled to 8 bits.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the PNM image.
+///
+/// # Returns
+///
+/// An image with 1 channel for PBM and PGM files, or 3 channels for PPM files.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a valid PNM file or its number of channels
+/// differs from `CHANNELS`.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::io::pnm::read_image_pnm;
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("image.pgm");
+/// std::fs::write(&file_path, "P2\n# a comment\n2 1\n15\n0 15\n").unwrap();
+///
+/// let image = read_image_pnm::<1>(&file_path).unwrap();
+/// assert_eq!(image.data.as_slice().unwrap(), &[0, 255]);
+/// ```
+pub fn read_image_pnm<const CHANNELS: usize>(file_path: &Path) -> Result<Image<u8, CHANNELS>> {
+    let data = std::fs::read(file_path)?;
+    let header = parse_header(&data)?;
+
+    if header.channels() != CHANNELS {
+        return Err(anyhow::anyhow!(
+            "The file has {} channels but {} were requested.",
+            header.channels(),
+            CHANNELS
+        ));
+    }
+
+    Image::new(header.size, decode_samples(&data, &header)?)
+}
+
+/// Write the samples of an image in the given PNM format.
+fn write_pnm(file_path: &Path, format: u8, size: ImageSize, samples: &[u8]) -> Result<()> {
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(file_path)?);
+    writeln!(writer, "P{}\n{} {}", format, size.width, size.height)?;
+
+    match format {
+        1 => {
+            for row in samples.chunks(size.width.max(1)) {
+                let line = row
+                    .iter()
+                    .map(|&v| if v < 128 { "1" } else { "0" })
+                    .collect::<Vec<_>>();
+                writeln!(writer, "{}", line.join(" "))?;
+            }
+        }
+        4 => {
+            for row in samples.chunks(size.width.max(1)) {
+                let mut bytes = vec![0u8; size.width.div_ceil(8)];
+                for (x, &v) in row.iter().enumerate() {
+                    if v < 128 {
+                        bytes[x / 8] |= 0x80 >> (x % 8);
+                    }
+                }
+                writer.write_all(&bytes)?;
+            }
+        }
+        2 | 3 => {
+            writeln!(writer, "255")?;
+            let row_len = (size.width * if format == 3 { 3 } else { 1 }).max(1);
+            for row in samples.chunks(row_len) {
+                let line = row.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                writeln!(writer, "{}", line.join(" "))?;
+            }
+        }
+        _ => {
+            writeln!(writer, "255")?;
+            writer.write_all(samples)?;
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Writes an image to a PGM (1 channel) or PPM (3 channels) file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the PNM image.
+/// * `image` - The image to write.
+/// * `encoding` - The encoding of the pixel data.
+///
+/// # Errors
+///
+/// Returns an error if the image does not have 1 or 3 channels or the file cannot be written.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::pnm::{read_image_pnm, write_image_pnm, PnmEncoding};
+///
+/// let image = Image::<u8, 3>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 1,
+///     },
+///     vec![255, 0, 0, 0, 0, 255],
+/// )
+/// .unwrap();
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("image.ppm");
+/// write_image_pnm(&file_path, &image, PnmEncoding::Binary).unwrap();
+///
+/// let image_back = read_image_pnm::<3>(&file_path).unwrap();
+/// assert_eq!(image_back.data, image.data);
+/// ```
+pub fn write_image_pnm<const CHANNELS: usize>(
+    file_path: &Path,
+    image: &Image<u8, CHANNELS>,
+    encoding: PnmEncoding,
+) -> Result<()> {
+    let format = match (CHANNELS, encoding) {
+        (1, PnmEncoding::Ascii) => 2,
+        (3, PnmEncoding::Ascii) => 3,
+        (1, PnmEncoding::Binary) => 5,
+        (3, PnmEncoding::Binary) => 6,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Only images with 1 or 3 channels can be written as PNM, got {}.",
+                CHANNELS
+            ))
+        }
+    };
+
+    write_pnm(
+        file_path,
+        format,
+        image.size(),
+        &image.as_contiguous_slice(),
+    )
+}
+
+/// Writes a binary image to a PBM file.
+///
+/// The pixels below 128 are written as black and the others as white.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the PBM image.
+/// * `image` - The image to write.
+/// * `encoding` - The encoding of the pixel data.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_image_pbm(
+    file_path: &Path,
+    image: &Image<u8, 1>,
+    encoding: PnmEncoding,
+) -> Result<()> {
+    let format = match encoding {
+        PnmEncoding::Ascii => 1,
+        PnmEncoding::Binary => 4,
+    };
+
+    write_pnm(
+        file_path,
+        format,
+        image.size(),
+        &image.as_contiguous_slice(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PnmEncoding;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn parse_header() -> Result<()> {
+        let header = super::parse_header(b"P6\n# comment\n3 2\n255\n")?;
+        assert_eq!(header.size.width, 3);
+        assert_eq!(header.size.height, 2);
+        assert_eq!(header.channels(), 3);
+        assert_eq!(header.encoding(), PnmEncoding::Binary);
+        assert_eq!(header.offset, 21);
+
+        let header = super::parse_header(b"P1 8 1\n")?;
+        assert_eq!(header.max_value, 1);
+        assert_eq!(header.offset, 7);
+
+        assert!(super::parse_header(b"P7\n3 2\n255\n").is_err());
+        assert!(super::parse_header(b"P5\n3 2\n0\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn read_write_pnm() -> Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let size = ImageSize {
+            width: 10,
+            height: 3,
+        };
+        let gray = Image::<u8, 1>::new(size, (0..30).map(|i| (i * 8) as u8).collect())?;
+        let rgb = Image::<u8, 3>::new(size, (0..90).map(|i| (i * 2) as u8).collect())?;
+
+        for encoding in [PnmEncoding::Ascii, PnmEncoding::Binary] {
+            let path = tmp_dir.path().join("gray.pgm");
+            super::write_image_pnm(&path, &gray, encoding)?;
+            assert_eq!(super::read_image_pnm::<1>(&path)?.data, gray.data);
+            assert!(super::read_image_pnm::<3>(&path).is_err());
+
+            let path = tmp_dir.path().join("rgb.ppm");
+            super::write_image_pnm(&path, &rgb, encoding)?;
+            assert_eq!(super::read_image_pnm::<3>(&path)?.data, rgb.data);
+
+            // the bitmaps keep the thresholded pixels
+            let path = tmp_dir.path().join("mask.pbm");
+            super::write_image_pbm(&path, &gray, encoding)?;
+            let mask = super::read_image_pnm::<1>(&path)?;
+            for (m, g) in mask.data.iter().zip(gray.data.iter()) {
+                assert_eq!(*m, if *g < 128 { 0 } else { 255 });
+            }
+        }
+
+        // 16-bit samples are scaled to 8 bits
+        let path = tmp_dir.path().join("deep.pgm");
+        std::fs::write(&path, b"P5\n2 1\n65535\n\xff\xff\x00\x00")?;
+        assert_eq!(
+            super::read_image_pnm::<1>(&path)?.data.as_slice().unwrap(),
+            &[255, 0]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use crate::error::KorniaError;
+use crate::image::Image;
+use anyhow::Result;
+use std::path::PathBuf;
+
+use super::functional::read_image_any;
+
+/// A reader decoding a list of images in parallel and yielding them as a stream.
+///
+/// The images are decoded by a pool of blocking workers while a bounded channel keeps
+/// a number of decoded images ready ahead of the consumer.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::io::reader::ImageStreamReader;
+/// use tokio_stream::StreamExt;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let paths = vec![std::path::PathBuf::from("tests/data/dog.jpeg"); 4];
+/// let mut images = ImageStreamReader::new(paths)
+///     .with_workers(2)
+///     .with_prefetch(4)
+///     .into_stream()
+///     .unwrap();
+///
+/// while let Some(image) = images.next().await {
+///     assert_eq!(image.unwrap().size().width, 258);
+/// }
+/// # });
+/// ```
+pub struct ImageStreamReader {
+    paths: Vec<PathBuf>,
+    workers: usize,
+    prefetch: usize,
+    ordered: bool,
+}
+
+impl ImageStreamReader {
+    /// Creates a new ImageStreamReader object with default values.
+    ///
+    /// Note: The default number of workers is the available parallelism, the default
+    /// prefetch is twice the number of workers and the images are yielded in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The paths of the images to read
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self {
+            paths,
+            workers,
+            prefetch: 2 * workers,
+            ordered: true,
+        }
+    }
+
+    /// Sets the number of images decoded concurrently.
+    ///
+    /// # Arguments
+    ///
+    /// * `workers` - The desired number of workers
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Sets the number of decoded images buffered ahead of the consumer.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefetch` - The desired number of buffered images
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Sets whether the images are yielded in the order of the paths.
+    ///
+    /// Unordered reading yields each image as soon as it is decoded, which avoids waiting
+    /// for a slow file while the others are ready.
+    ///
+    /// # Arguments
+    ///
+    /// * `ordered` - Whether to keep the order of the paths
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Start reading the images in the background.
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Returns
+    ///
+    /// A stream yielding the decoded images, or the error of each file that failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of workers or the prefetch is zero.
+    pub fn into_stream(self) -> Result<impl tokio_stream::Stream<Item = Result<Image<u8, 3>>>> {
+        if self.workers == 0 || self.prefetch == 0 {
+            return Err(anyhow::anyhow!(
+                "The number of workers ({}) and the prefetch ({}) must be positive.",
+                self.workers,
+                self.prefetch
+            ));
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::channel(self.prefetch);
+        let decode = |path: PathBuf| tokio::task::spawn_blocking(move || read_image_any(&path));
+        let flatten =
+            |result: Result<Result<Image<u8, 3>, KorniaError>, tokio::task::JoinError>| {
+                result
+                    .map_err(anyhow::Error::from)
+                    .and_then(|image| image.map_err(anyhow::Error::from))
+            };
+
+        let (workers, ordered) = (self.workers, self.ordered);
+        let mut paths = self.paths.into_iter();
+
+        tokio::spawn(async move {
+            if ordered {
+                // keep the tasks in a queue and wait for them in order
+                let mut pending = std::collections::VecDeque::new();
+                pending.extend(paths.by_ref().take(workers).map(decode));
+                while let Some(task) = pending.pop_front() {
+                    if tx.send(flatten(task.await)).await.is_err() {
+                        break;
+                    }
+                    pending.extend(paths.next().map(decode));
+                }
+            } else {
+                // yield the tasks as soon as they complete
+                let mut pending = tokio::task::JoinSet::new();
+                for path in paths.by_ref().take(workers) {
+                    pending.spawn_blocking(move || read_image_any(&path));
+                }
+                while let Some(result) = pending.join_next().await {
+                    if tx.send(flatten(result)).await.is_err() {
+                        break;
+                    }
+                    if let Some(path) = paths.next() {
+                        pending.spawn_blocking(move || read_image_any(&path));
+                    }
+                }
+            }
+        });
+
+        Ok(tokio_stream::wrappers::ReceiverStream::new(rx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use std::path::PathBuf;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn stream_reader_ordered() -> Result<()> {
+        let paths = vec![
+            PathBuf::from("tests/data/dog.jpeg"),
+            PathBuf::from("missing.jpeg"),
+            PathBuf::from("tests/data/dog.jpeg"),
+        ];
+
+        let images = super::ImageStreamReader::new(paths)
+            .with_workers(2)
+            .with_prefetch(1)
+            .into_stream()?
+            .collect::<Vec<_>>()
+            .await;
+
+        // the failure is reported at the position of its path
+        assert_eq!(images.len(), 3);
+        assert!(images[0].is_ok());
+        assert!(images[1].is_err());
+        assert!(images[2].is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stream_reader_unordered() -> Result<()> {
+        let paths = vec![PathBuf::from("tests/data/dog.jpeg"); 5];
+
+        let images = super::ImageStreamReader::new(paths)
+            .with_workers(3)
+            .with_ordered(false)
+            .into_stream()?
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(images.len(), 5);
+        assert!(images.iter().all(|image| image.is_ok()));
+
+        let reader = super::ImageStreamReader::new(vec![]).with_workers(0);
+        assert!(reader.into_stream().is_err());
+
+        Ok(())
+    }
+}
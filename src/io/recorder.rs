@@ -0,0 +1,225 @@
+use crate::image::Image;
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// A recorder keeping the most recent frames of a stream in memory.
+///
+/// The frames older than the window are dropped as new frames arrive, so that the
+/// moments before an event can be saved once the event is detected. With the
+/// `gstreamer` feature, the recorder is also exported as `io::stream::RingRecorder` and
+/// the RGB frames can be written to a video with `RingRecorder::dump_to`.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::recorder::RingRecorder;
+/// use std::time::Duration;
+///
+/// let mut recorder = RingRecorder::new(Duration::from_secs(10));
+///
+/// let size = ImageSize {
+///     width: 4,
+///     height: 4,
+/// };
+/// for i in 0..30 {
+///     let frame = Image::<u8, 3>::from_size_val(size, i as u8).unwrap();
+///     recorder.push(frame, Duration::from_secs(i)).unwrap();
+/// }
+///
+/// // the detection fired: save the frames of the last 10 seconds
+/// let mut saved = Vec::new();
+/// recorder
+///     .dump(|frame, timestamp| {
+///         saved.push((timestamp, frame.data[[0, 0, 0]]));
+///         Ok(())
+///     })
+///     .unwrap();
+///
+/// assert_eq!(saved.len(), 11);
+/// assert_eq!(saved[0], (Duration::from_secs(19), 19));
+/// assert!(recorder.is_empty());
+/// ```
+pub struct RingRecorder<T = u8, const CHANNELS: usize = 3> {
+    window: Duration,
+    frames: VecDeque<(Duration, Image<T, CHANNELS>)>,
+}
+
+impl<T, const CHANNELS: usize> RingRecorder<T, CHANNELS> {
+    /// Creates a new RingRecorder object.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The duration of the stream kept in memory
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Adds a frame to the recorder, dropping the frames older than the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to add
+    /// * `timestamp` - The presentation timestamp of the frame
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamp is not after the one of the last frame.
+    pub fn push(&mut self, frame: Image<T, CHANNELS>, timestamp: Duration) -> Result<()> {
+        if let Some((last, _)) = self.frames.back() {
+            // the writers reject the frames sharing a timestamp
+            if timestamp <= *last {
+                return Err(anyhow::anyhow!(
+                    "The timestamp {:?} is not after the last frame at {:?}",
+                    timestamp,
+                    last
+                ));
+            }
+        }
+
+        self.frames.push_back((timestamp, frame));
+
+        // keep the frames within the window from the newest one
+        while self
+            .frames
+            .front()
+            .is_some_and(|(t, _)| timestamp - *t > self.window)
+        {
+            self.frames.pop_front();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of frames in the recorder.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns whether the recorder has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the time span between the oldest and the newest frames.
+    pub fn duration(&self) -> Duration {
+        match (self.frames.front(), self.frames.back()) {
+            (Some((first, _)), Some((last, _))) => *last - *first,
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Removes all the frames and passes them to a sink, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function that takes each frame with its timestamp, e.g. to write it
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the sink, the remaining frames are dropped.
+    pub fn dump<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&Image<T, CHANNELS>, Duration) -> Result<()>,
+    {
+        for (timestamp, frame) in self.frames.drain(..) {
+            f(&frame, timestamp)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "gstreamer")]
+impl RingRecorder<u8, 3> {
+    /// Removes all the frames and writes them to a video, oldest first.
+    ///
+    /// The frames keep their timing. The oldest frame is written one frame interval after
+    /// the last frame of the writer, or at the start of the video for a fresh writer, so
+    /// that several dumps can be appended to the same video.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The started writer, with the size of the frames
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the writer, the remaining frames are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kornia_rs::image::ImageSize;
+    /// use kornia_rs::io::stream::RingRecorder;
+    /// use kornia_rs::io::video::VideoWriter;
+    /// use std::time::Duration;
+    ///
+    /// let mut recorder: RingRecorder = RingRecorder::new(Duration::from_secs(10));
+    /// // ... push the frames of the stream, until the detection fires
+    ///
+    /// let size = ImageSize {
+    ///     width: 640,
+    ///     height: 480,
+    /// };
+    /// let mut writer = VideoWriter::new("event.mp4", size, 30.0).unwrap();
+    /// writer.start().unwrap();
+    /// recorder.dump_to(&mut writer).unwrap();
+    /// writer.close().unwrap();
+    /// ```
+    pub fn dump_to(&mut self, writer: &mut super::video::VideoWriter) -> Result<()> {
+        let start = self.frames.front().map(|(t, _)| *t).unwrap_or_default();
+        let offset = writer.next_pts();
+        let mut frames = self.frames.drain(..).peekable();
+        while let Some((timestamp, frame)) = frames.next() {
+            // the last frame lasts until the end of the stream
+            let duration = frames.peek().map(|(next, _)| *next - timestamp);
+            writer.write_with_pts(&frame, offset + timestamp - start, duration)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+    use std::time::Duration;
+
+    #[test]
+    fn ring_recorder() -> Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 1,
+        };
+        let mut recorder = super::RingRecorder::<u8, 3>::new(Duration::from_millis(100));
+        assert_eq!(recorder.duration(), Duration::ZERO);
+
+        for i in 0..10 {
+            let frame = Image::<u8, 3>::from_size_val(size, i as u8)?;
+            recorder.push(frame, Duration::from_millis(40 * i))?;
+        }
+        assert_eq!(recorder.len(), 3);
+        assert_eq!(recorder.duration(), Duration::from_millis(80));
+
+        // the timestamps must not go back in time
+        let frame = Image::<u8, 3>::from_size_val(size, 0)?;
+        assert!(recorder
+            .push(frame.clone(), Duration::from_millis(10))
+            .is_err());
+        // nor repeat the last one
+        assert!(recorder.push(frame, Duration::from_millis(360)).is_err());
+        assert_eq!(recorder.len(), 3);
+
+        let mut values = Vec::new();
+        recorder.dump(|frame, _| {
+            values.push(frame.data[[0, 0, 0]]);
+            Ok(())
+        })?;
+        assert_eq!(values, vec![7, 8, 9]);
+        assert!(recorder.is_empty());
+        Ok(())
+    }
+}
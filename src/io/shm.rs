@@ -0,0 +1,586 @@
+use anyhow::Result;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::image::{Image, ImageSize};
+
+// The segment starts with a header followed by `slot_count` slots. Each slot
+// holds a small frame header and up to `slot_size` bytes of pixel data.
+//
+// The slots are guarded by a seqlock: the state of a slot is odd while it is
+// written and the readers validate the state after copying the slot. As the
+// readers race with the writer by design, every access to the segment, the
+// pixels included, is an atomic access of 8 bytes so that the races are not
+// undefined behaviour; the relaxed loads and stores compile to plain moves.
+const MAGIC: u64 = u64::from_le_bytes(*b"KORNSHM1");
+const HEADER_SIZE: usize = 64;
+const SLOT_HEADER_SIZE: usize = 64;
+
+// segment header offsets
+const OFF_MAGIC: usize = 0;
+const OFF_SLOT_COUNT: usize = 8;
+const OFF_SLOT_SIZE: usize = 16;
+const OFF_WRITE_SEQ: usize = 24;
+
+// slot header offsets
+const OFF_STATE: usize = 0;
+const OFF_TIMESTAMP: usize = 8;
+const OFF_WIDTH: usize = 16;
+const OFF_HEIGHT: usize = 24;
+const OFF_FORMAT: usize = 32;
+const OFF_LEN: usize = 40;
+
+/// The pixel format of a frame in the shared-memory ring buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    /// One 8 bits channel.
+    Gray8 = 1,
+    /// Three interleaved 8 bits channels.
+    Rgb8 = 3,
+    /// Four interleaved 8 bits channels.
+    Rgba8 = 4,
+}
+
+impl FrameFormat {
+    fn from_channels(channels: usize) -> Result<Self> {
+        match channels {
+            1 => Ok(FrameFormat::Gray8),
+            3 => Ok(FrameFormat::Rgb8),
+            4 => Ok(FrameFormat::Rgba8),
+            _ => Err(anyhow::anyhow!(
+                "Unsupported number of channels for shared memory: {}",
+                channels
+            )),
+        }
+    }
+
+    fn from_code(code: u64) -> Option<Self> {
+        match code {
+            1 => Some(FrameFormat::Gray8),
+            3 => Some(FrameFormat::Rgb8),
+            4 => Some(FrameFormat::Rgba8),
+            _ => None,
+        }
+    }
+
+    /// The number of channels of the format.
+    pub fn channels(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// A frame read from the shared-memory ring buffer.
+pub struct ShmFrame<const CHANNELS: usize> {
+    /// The sequence number assigned by the writer, starting at 1.
+    pub seqno: u64,
+    /// The timestamp given by the writer.
+    pub timestamp: Duration,
+    /// The frame pixels.
+    pub image: Image<u8, CHANNELS>,
+}
+
+/// Resolve a segment name to a path.
+///
+/// Plain names are placed in `/dev/shm`, as done by `shm_open`, while names
+/// containing a path separator are used as is.
+fn segment_path(name: &str) -> PathBuf {
+    if name.contains('/') {
+        PathBuf::from(name)
+    } else {
+        Path::new("/dev/shm").join(name)
+    }
+}
+
+/// The size of a slot, aligned to a cache line, or `None` on overflow.
+fn slot_stride(slot_size: usize) -> Option<usize> {
+    SLOT_HEADER_SIZE
+        .checked_add(slot_size)?
+        .div_ceil(64)
+        .checked_mul(64)
+}
+
+/// The size of a segment, or `None` on overflow.
+fn segment_len(slot_count: usize, slot_size: usize) -> Option<usize> {
+    slot_stride(slot_size)?
+        .checked_mul(slot_count)?
+        .checked_add(HEADER_SIZE)
+}
+
+/// A mapped shared-memory segment, writable for the writer and read-only for the readers.
+struct Segment<M> {
+    mmap: M,
+    slot_count: usize,
+    slot_size: usize,
+    slot_stride: usize,
+}
+
+impl<M: Deref<Target = [u8]>> Segment<M> {
+    fn atomic(&self, offset: usize) -> &AtomicU64 {
+        assert!(offset.is_multiple_of(8) && offset + 8 <= self.mmap.len());
+        // SAFETY: the mapping is page aligned, the offset is 8 bytes aligned and in bounds
+        unsafe { &*(self.mmap.as_ptr().add(offset) as *const AtomicU64) }
+    }
+
+    fn slot_offset(&self, seqno: u64) -> usize {
+        let index = ((seqno - 1) % self.slot_count as u64) as usize;
+        HEADER_SIZE + index * self.slot_stride
+    }
+
+    fn write_seq(&self) -> &AtomicU64 {
+        self.atomic(OFF_WRITE_SEQ)
+    }
+
+    /// Copy bytes out of the segment with relaxed atomic loads.
+    ///
+    /// The bytes may be torn by a concurrent write, which the caller detects with the
+    /// seqlock. The last word is read whole, so `offset + len` rounded up to 8 bytes must
+    /// be in the segment.
+    fn load_bytes(&self, offset: usize, len: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(len.div_ceil(8) * 8);
+        for word in (offset..offset + len).step_by(8) {
+            let value = self.atomic(word).load(Ordering::Relaxed);
+            data.extend_from_slice(&value.to_ne_bytes());
+        }
+        data.truncate(len);
+        data
+    }
+}
+
+impl Segment<memmap2::MmapMut> {
+    /// Copy bytes into the segment with relaxed atomic stores.
+    ///
+    /// The padding of the last word is zeroed, so `offset + data.len()` rounded up to 8
+    /// bytes must be in the segment.
+    fn store_bytes(&self, offset: usize, data: &[u8]) {
+        for (i, chunk) in data.chunks(8).enumerate() {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.atomic(offset + i * 8)
+                .store(u64::from_ne_bytes(word), Ordering::Relaxed);
+        }
+    }
+}
+
+/// The producer side of a shared-memory frame ring buffer.
+///
+/// The writer creates the segment and owns it: the segment is unlinked when the
+/// writer is dropped. Frames are never blocked on readers; slow readers skip the
+/// frames that were overwritten.
+pub struct ShmWriter {
+    segment: Segment<memmap2::MmapMut>,
+    path: PathBuf,
+    next_seqno: u64,
+}
+
+impl ShmWriter {
+    /// Create a new shared-memory segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The segment name, e.g. `"camera0"` for `/dev/shm/camera0`, or a path.
+    /// * `slot_count` - The number of frames kept in the ring buffer.
+    /// * `max_frame_size` - The largest frame size, in bytes, that can be written.
+    ///
+    /// # Returns
+    ///
+    /// The writer.
+    ///
+    /// # Errors
+    ///
+    /// If the segment cannot be created or mapped, an error is returned.
+    pub fn create(name: &str, slot_count: usize, max_frame_size: usize) -> Result<Self> {
+        if slot_count == 0 || max_frame_size == 0 {
+            return Err(anyhow::anyhow!(
+                "The slot count and the maximum frame size must be positive."
+            ));
+        }
+
+        let slot_stride = slot_stride(max_frame_size)
+            .ok_or_else(|| anyhow::anyhow!("The maximum frame size is too large."))?;
+        let len = segment_len(slot_count, max_frame_size)
+            .ok_or_else(|| anyhow::anyhow!("The shared memory segment is too large."))?;
+        let path = segment_path(name);
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        file.set_len(len as u64)?;
+
+        // SAFETY: the segment is owned by the writer and is never truncated while mapped
+        let mmap = unsafe { memmap2::MmapMut::map_mut(&file)? };
+
+        let segment = Segment {
+            mmap,
+            slot_count,
+            slot_size: max_frame_size,
+            slot_stride,
+        };
+        segment
+            .atomic(OFF_SLOT_COUNT)
+            .store(slot_count as u64, Ordering::Relaxed);
+        segment
+            .atomic(OFF_SLOT_SIZE)
+            .store(max_frame_size as u64, Ordering::Relaxed);
+        segment.write_seq().store(0, Ordering::Relaxed);
+        // publish the magic last so readers never see a partial header
+        segment.atomic(OFF_MAGIC).store(MAGIC, Ordering::Release);
+
+        Ok(Self {
+            segment,
+            path,
+            next_seqno: 1,
+        })
+    }
+
+    /// Write a frame to the ring buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The frame to write.
+    /// * `timestamp` - The frame timestamp, passed through to the readers.
+    ///
+    /// # Returns
+    ///
+    /// The sequence number of the frame.
+    ///
+    /// # Errors
+    ///
+    /// If the frame is larger than the slots or has an unsupported number of
+    /// channels, an error is returned.
+    pub fn write<const CHANNELS: usize>(
+        &mut self,
+        image: &Image<u8, CHANNELS>,
+        timestamp: Duration,
+    ) -> Result<u64> {
+        let format = FrameFormat::from_channels(CHANNELS)?;
+        let data = image.as_contiguous_slice();
+        if data.len() > self.segment.slot_size {
+            return Err(anyhow::anyhow!(
+                "The frame has {} bytes but the slots hold at most {}.",
+                data.len(),
+                self.segment.slot_size
+            ));
+        }
+
+        let seqno = self.next_seqno;
+        let offset = self.segment.slot_offset(seqno);
+
+        // mark the slot as being written: an odd state invalidates concurrent reads
+        self.segment
+            .atomic(offset + OFF_STATE)
+            .store((seqno << 1) | 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        let fields = [
+            (OFF_TIMESTAMP, timestamp.as_nanos() as u64),
+            (OFF_WIDTH, image.width() as u64),
+            (OFF_HEIGHT, image.height() as u64),
+            (OFF_FORMAT, format as u64),
+            (OFF_LEN, data.len() as u64),
+        ];
+        for (field, value) in fields {
+            self.segment
+                .atomic(offset + field)
+                .store(value, Ordering::Relaxed);
+        }
+        self.segment.store_bytes(offset + SLOT_HEADER_SIZE, &data);
+
+        self.segment
+            .atomic(offset + OFF_STATE)
+            .store(seqno << 1, Ordering::Release);
+        self.segment.write_seq().store(seqno, Ordering::Release);
+        self.next_seqno += 1;
+
+        Ok(seqno)
+    }
+
+    /// The path of the shared-memory segment.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ShmWriter {
+    fn drop(&mut self) {
+        // existing readers keep their mapping, new readers cannot open the segment
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The consumer side of a shared-memory frame ring buffer.
+///
+/// Frames are read in order; when the writer laps the reader, the overwritten
+/// frames are skipped and counted in [`ShmReader::dropped`].
+pub struct ShmReader {
+    segment: Segment<memmap2::Mmap>,
+    last_seqno: u64,
+    dropped: u64,
+}
+
+impl ShmReader {
+    /// Open an existing shared-memory segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The segment name given to [`ShmWriter::create`].
+    ///
+    /// # Returns
+    ///
+    /// The reader, positioned after the frames already written.
+    ///
+    /// # Errors
+    ///
+    /// If the segment does not exist or is not a frame ring buffer, an error is returned.
+    pub fn open(name: &str) -> Result<Self> {
+        let file = std::fs::File::open(segment_path(name))?;
+
+        // SAFETY: the writer never truncates the segment while it is mapped
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        if mmap.len() < HEADER_SIZE {
+            return Err(anyhow::anyhow!("The shared memory segment is truncated."));
+        }
+
+        let mut segment = Segment {
+            mmap,
+            slot_count: 0,
+            slot_size: 0,
+            slot_stride: 0,
+        };
+        if segment.atomic(OFF_MAGIC).load(Ordering::Acquire) != MAGIC {
+            return Err(anyhow::anyhow!(
+                "The shared memory segment is not a frame ring buffer."
+            ));
+        }
+
+        // the header is not trusted: the sizes are checked against the mapping
+        let header =
+            |offset: usize| usize::try_from(segment.atomic(offset).load(Ordering::Relaxed));
+        let (slot_count, slot_size) = match (header(OFF_SLOT_COUNT), header(OFF_SLOT_SIZE)) {
+            (Ok(slot_count), Ok(slot_size)) => (slot_count, slot_size),
+            _ => return Err(anyhow::anyhow!("The shared memory segment is corrupted.")),
+        };
+        let expected = segment_len(slot_count, slot_size)
+            .ok_or_else(|| anyhow::anyhow!("The shared memory segment is corrupted."))?;
+        if slot_count == 0 || segment.mmap.len() < expected {
+            return Err(anyhow::anyhow!("The shared memory segment is truncated."));
+        }
+        segment.slot_count = slot_count;
+        segment.slot_size = slot_size;
+        segment.slot_stride = (expected - HEADER_SIZE) / slot_count;
+
+        let last_seqno = segment.write_seq().load(Ordering::Acquire);
+
+        Ok(Self {
+            segment,
+            last_seqno,
+            dropped: 0,
+        })
+    }
+
+    /// Read the next frame.
+    ///
+    /// # Returns
+    ///
+    /// The oldest frame not read yet, or `None` if the reader is up to date.
+    ///
+    /// # Errors
+    ///
+    /// If the frame format does not match `CHANNELS` or the slot is corrupted,
+    /// an error is returned.
+    pub fn read_next<const CHANNELS: usize>(&mut self) -> Result<Option<ShmFrame<CHANNELS>>> {
+        let slot_count = self.segment.slot_count as u64;
+        loop {
+            let latest = self.segment.write_seq().load(Ordering::Acquire);
+            if latest <= self.last_seqno {
+                return Ok(None);
+            }
+
+            // skip the frames overwritten since the last read
+            let oldest = latest.saturating_sub(slot_count - 1).max(1);
+            let seqno = (self.last_seqno + 1).max(oldest);
+
+            if let Some(frame) = self.read_slot(seqno)? {
+                self.dropped += seqno - self.last_seqno - 1;
+                self.last_seqno = seqno;
+                return Ok(Some(frame));
+            }
+            // the slot was overwritten while reading, retry with the newer frames
+        }
+    }
+
+    /// Read the most recent frame, skipping all the frames in between.
+    ///
+    /// # Returns
+    ///
+    /// The latest frame, or `None` if no new frame was written.
+    ///
+    /// # Errors
+    ///
+    /// If the frame format does not match `CHANNELS`, an error is returned.
+    pub fn latest<const CHANNELS: usize>(&mut self) -> Result<Option<ShmFrame<CHANNELS>>> {
+        let latest = self.segment.write_seq().load(Ordering::Acquire);
+        if latest > self.last_seqno + 1 {
+            self.dropped += latest - self.last_seqno - 1;
+            self.last_seqno = latest - 1;
+        }
+        self.read_next()
+    }
+
+    /// The number of frames skipped because they were overwritten or by [`ShmReader::latest`].
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    // Copy a slot out of the segment, returning `None` if it changed while reading.
+    fn read_slot<const CHANNELS: usize>(&self, seqno: u64) -> Result<Option<ShmFrame<CHANNELS>>> {
+        let offset = self.segment.slot_offset(seqno);
+        let state = self.segment.atomic(offset + OFF_STATE);
+
+        let before = state.load(Ordering::Acquire);
+        if before != seqno << 1 {
+            return Ok(None);
+        }
+
+        let field = |f: usize| self.segment.atomic(offset + f).load(Ordering::Relaxed);
+        let (timestamp, width, height) =
+            (field(OFF_TIMESTAMP), field(OFF_WIDTH), field(OFF_HEIGHT));
+        let (format, len) = (field(OFF_FORMAT), field(OFF_LEN) as usize);
+
+        let consistent = len <= self.segment.slot_size;
+        let data = if consistent {
+            self.segment.load_bytes(offset + SLOT_HEADER_SIZE, len)
+        } else {
+            Vec::new()
+        };
+
+        fence(Ordering::Acquire);
+        if state.load(Ordering::Relaxed) != before {
+            return Ok(None);
+        }
+        if !consistent {
+            return Err(anyhow::anyhow!("The frame {} is corrupted.", seqno));
+        }
+
+        let format = FrameFormat::from_code(format)
+            .ok_or_else(|| anyhow::anyhow!("Unknown frame format code: {}", format))?;
+        if format.channels() != CHANNELS {
+            return Err(anyhow::anyhow!(
+                "The frame has {} channels but {} were requested.",
+                format.channels(),
+                CHANNELS
+            ));
+        }
+
+        let size = ImageSize {
+            width: width as usize,
+            height: height as usize,
+        };
+        let image = Image::new(size, data)?;
+
+        Ok(Some(ShmFrame {
+            seqno,
+            timestamp: Duration::from_nanos(timestamp),
+            image,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShmReader, ShmWriter};
+    use crate::image::{Image, ImageSize};
+    use std::time::Duration;
+
+    #[test]
+    fn ring_buffer_order_and_overrun() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let name = tmp_dir.path().join("frames");
+        let name = name.to_str().unwrap();
+
+        let size = ImageSize {
+            width: 4,
+            height: 2,
+        };
+        let mut writer = ShmWriter::create(name, 2, 4 * 2 * 3)?;
+        let mut reader = ShmReader::open(name)?;
+        assert!(reader.read_next::<3>()?.is_none());
+
+        for i in 0..3u8 {
+            let image = Image::<u8, 3>::from_size_val(size, i)?;
+            writer.write(&image, Duration::from_millis(i as u64 * 10))?;
+        }
+
+        // the first frame was overwritten
+        let frame = reader.read_next::<3>()?.unwrap();
+        assert_eq!(frame.seqno, 2);
+        assert_eq!(frame.timestamp, Duration::from_millis(10));
+        assert_eq!(frame.image.size(), size);
+        assert!(frame.image.data.iter().all(|&v| v == 1));
+        assert_eq!(reader.dropped(), 1);
+
+        let frame = reader.read_next::<3>()?.unwrap();
+        assert_eq!(frame.seqno, 3);
+        assert!(reader.read_next::<3>()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn corrupted_header() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let path = tmp_dir.path().join("frames");
+
+        // a header whose slot count overflows the size of the segment
+        let mut header = vec![0u8; 64];
+        header[0..8].copy_from_slice(b"KORNSHM1");
+        header[8..16].copy_from_slice(&(u64::MAX / 2).to_le_bytes());
+        header[16..24].copy_from_slice(&(1u64 << 32).to_le_bytes());
+        std::fs::write(&path, &header)?;
+        assert!(ShmReader::open(path.to_str().unwrap()).is_err());
+
+        // a header announcing more slots than the segment holds
+        header[8..16].copy_from_slice(&4u64.to_le_bytes());
+        header[16..24].copy_from_slice(&16u64.to_le_bytes());
+        std::fs::write(&path, &header)?;
+        assert!(ShmReader::open(path.to_str().unwrap()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_mismatch() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let name = tmp_dir.path().join("frames");
+        let name = name.to_str().unwrap();
+
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+        let mut writer = ShmWriter::create(name, 4, 16)?;
+        let mut reader = ShmReader::open(name)?;
+
+        let rgba = Image::<u8, 4>::from_size_val(size, 0)?;
+        writer.write(&rgba, Duration::ZERO)?;
+        assert!(writer
+            .write(
+                &Image::<u8, 3>::from_size_val(
+                    ImageSize {
+                        width: 3,
+                        height: 2
+                    },
+                    0
+                )?,
+                Duration::ZERO
+            )
+            .is_err());
+        assert!(reader.read_next::<1>().is_err());
+
+        Ok(())
+    }
+}
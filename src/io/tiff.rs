@@ -0,0 +1,213 @@
+use anyhow::Result;
+use std::io::{Seek, Write};
+use std::path::Path;
+
+use ::tiff::decoder::{Decoder, DecodingResult};
+use ::tiff::encoder::{colortype, TiffEncoder};
+use ::tiff::ColorType;
+
+use crate::image::{Image, ImageSize};
+
+/// A sample type of the TIFF images, either `u8`, `u16` or `f32`.
+pub trait TiffSample: Copy + Default + Sized {
+    /// Extract the samples of a decoded image if they have this type.
+    fn from_decoding_result(result: DecodingResult) -> Option<Vec<Self>>;
+
+    /// Encode an image with the given number of samples per pixel.
+    fn encode<W: Write + Seek>(
+        encoder: &mut TiffEncoder<W>,
+        size: ImageSize,
+        channels: usize,
+        data: &[Self],
+    ) -> Result<()>;
+}
+
+macro_rules! impl_tiff_sample {
+    ($sample:ty, $variant:ident, $gray:ty, $rgb:ty, $rgba:ty) => {
+        impl TiffSample for $sample {
+            fn from_decoding_result(result: DecodingResult) -> Option<Vec<Self>> {
+                match result {
+                    DecodingResult::$variant(data) => Some(data),
+                    _ => None,
+                }
+            }
+
+            fn encode<W: Write + Seek>(
+                encoder: &mut TiffEncoder<W>,
+                size: ImageSize,
+                channels: usize,
+                data: &[Self],
+            ) -> Result<()> {
+                let (width, height) = (size.width as u32, size.height as u32);
+                match channels {
+                    1 => encoder.write_image::<$gray>(width, height, data)?,
+                    3 => encoder.write_image::<$rgb>(width, height, data)?,
+                    4 => encoder.write_image::<$rgba>(width, height, data)?,
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "TIFF images with {} channels are not supported",
+                            channels
+                        ))
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_tiff_sample!(u8, U8, colortype::Gray8, colortype::RGB8, colortype::RGBA8);
+impl_tiff_sample!(
+    u16,
+    U16,
+    colortype::Gray16,
+    colortype::RGB16,
+    colortype::RGBA16
+);
+impl_tiff_sample!(
+    f32,
+    F32,
+    colortype::Gray32Float,
+    colortype::RGB32Float,
+    colortype::RGBA32Float
+);
+
+/// Reads the first page of a TIFF image from the given file path.
+///
+/// The samples are read without conversion, e.g. 16-bit depth maps into `Image<u16, 1>`
+/// and 32-bit float images into `Image<f32, 1>`.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the TIFF image.
+///
+/// # Returns
+///
+/// The image with the samples of the file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be decoded, or its sample type or number of
+/// channels differs from the requested ones.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::tiff::{read_image_tiff, write_image_tiff};
+///
+/// let depth = Image::<u16, 1>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 1,
+///     },
+///     vec![1000, 65535],
+/// )
+/// .unwrap();
+///
+/// let tmp_dir = tempfile::tempdir().unwrap();
+/// let file_path = tmp_dir.path().join("depth.tiff");
+/// write_image_tiff(&file_path, &depth).unwrap();
+///
+/// let depth_back = read_image_tiff::<u16, 1>(&file_path).unwrap();
+/// assert_eq!(depth_back.data, depth.data);
+/// ```
+pub fn read_image_tiff<T: TiffSample, const CHANNELS: usize>(
+    file_path: &Path,
+) -> Result<Image<T, CHANNELS>> {
+    let file = std::fs::File::open(file_path)?;
+    let mut decoder = Decoder::new(std::io::BufReader::new(file))?;
+
+    let (width, height) = decoder.dimensions()?;
+    let channels = match decoder.colortype()? {
+        ColorType::Gray(_) => 1,
+        ColorType::GrayA(_) => 2,
+        ColorType::RGB(_) => 3,
+        ColorType::RGBA(_) => 4,
+        colortype => {
+            return Err(anyhow::anyhow!(
+                "The TIFF color type {:?} is not supported",
+                colortype
+            ))
+        }
+    };
+    if channels != CHANNELS {
+        return Err(anyhow::anyhow!(
+            "The file has {} channels but {} were requested.",
+            channels,
+            CHANNELS
+        ));
+    }
+
+    let data = T::from_decoding_result(decoder.read_image()?).ok_or_else(|| {
+        anyhow::anyhow!(
+            "The samples of the file are not of type {}",
+            std::any::type_name::<T>()
+        )
+    })?;
+
+    Image::new(
+        ImageSize {
+            width: width as usize,
+            height: height as usize,
+        },
+        data,
+    )
+}
+
+/// Writes an image to an uncompressed single-page TIFF file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the TIFF image.
+/// * `image` - The image to write, with 1, 3 or 4 channels.
+///
+/// # Errors
+///
+/// Returns an error if the number of channels is not supported or the file cannot be
+/// written.
+pub fn write_image_tiff<T: TiffSample, const CHANNELS: usize>(
+    file_path: &Path,
+    image: &Image<T, CHANNELS>,
+) -> Result<()> {
+    let file = std::fs::File::create(file_path)?;
+    let mut encoder = TiffEncoder::new(std::io::BufWriter::new(file))?;
+    T::encode(
+        &mut encoder,
+        image.size(),
+        CHANNELS,
+        &image.as_contiguous_slice(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_image_tiff, write_image_tiff};
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn read_write_tiff() -> Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let size = ImageSize {
+            width: 3,
+            height: 2,
+        };
+
+        let depth = Image::<f32, 1>::new(size, vec![0.5, 1.25, -3.0, 1e6, 0.0, f32::MAX])?;
+        let file_path = tmp_dir.path().join("depth.tiff");
+        write_image_tiff(&file_path, &depth)?;
+        assert_eq!(read_image_tiff::<f32, 1>(&file_path)?.data, depth.data);
+
+        // the sample type and the channels should match the file
+        assert!(read_image_tiff::<u16, 1>(&file_path).is_err());
+        assert!(read_image_tiff::<f32, 3>(&file_path).is_err());
+
+        let rgb = Image::<u8, 3>::new(size, (0..18).collect())?;
+        let file_path = tmp_dir.path().join("rgb.tif");
+        write_image_tiff(&file_path, &rgb)?;
+        assert_eq!(read_image_tiff::<u8, 3>(&file_path)?.data, rgb.data);
+
+        Ok(())
+    }
+}
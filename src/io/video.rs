@@ -0,0 +1,1180 @@
+use super::gst::{
+    active_video_decoder, apply_decoder_preference, caps_frame_size, quote, DecoderPreference,
+};
+use crate::error::KorniaError;
+use crate::image::{Image, ImageSize};
+use gst::prelude::*;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The properties of a video stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoMetadata {
+    /// The size of the decoded frames.
+    pub size: ImageSize,
+    /// The frame rate in frames per second, or 0 for a variable frame rate.
+    pub fps: f64,
+    /// The duration of the stream, if known.
+    pub duration: Option<Duration>,
+    /// The number of frames estimated from the duration and the frame rate, if known.
+    pub frame_count: Option<u64>,
+    /// The name of the video codec, e.g. "H.264", if reported by the demuxer.
+    pub codec: Option<String>,
+}
+
+/// Estimates the number of frames of a stream from its duration and frame rate.
+fn estimate_frame_count(duration: Option<Duration>, fps: f64) -> Option<u64> {
+    match duration {
+        Some(duration) if fps > 0.0 => Some((duration.as_secs_f64() * fps).round() as u64),
+        _ => None,
+    }
+}
+
+/// Gets the timestamp of a frame of a stream with a constant frame rate.
+fn frame_timestamp(index: u64, fps: f64) -> Duration {
+    Duration::from_secs_f64(index as f64 / fps)
+}
+
+/// Checks that the timestamp of a frame is after the one of the previous frame.
+fn check_monotonic_pts(last: Option<Duration>, pts: Duration) -> Result<(), KorniaError> {
+    match last {
+        Some(last) if pts <= last => Err(KorniaError::InvalidArgument(format!(
+            "The timestamp {:?} is not after the previous frame at {:?}",
+            pts, last
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// A reader decoding the frames of a video file.
+///
+/// The file is decoded with the available GStreamer demuxers and decoders, and the
+/// frames are converted to RGB. The properties of the stream can be queried with
+/// [`VideoReader::metadata`] before grabbing any frame, e.g. to preallocate buffers
+/// or to configure a writer.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::io::gst::DecoderPreference;
+/// use kornia_rs::io::video::VideoReader;
+///
+/// let mut reader = VideoReader::new("video.mp4")
+///     .unwrap()
+///     .with_decoder_preference(DecoderPreference::Hardware);
+///
+/// let metadata = reader.metadata().unwrap();
+/// println!("decoded with {:?}", reader.decoder());
+/// println!(
+///     "{} at {} fps, {:?} frames",
+///     metadata.size, metadata.fps, metadata.frame_count
+/// );
+///
+/// // read the second half of the video
+/// if let Some(frame_count) = metadata.frame_count {
+///     reader.seek_to_frame(frame_count / 2).unwrap();
+/// }
+/// reader.start().unwrap();
+/// while let Some(frame) = reader.grab().unwrap() {
+///     assert_eq!(frame.size(), metadata.size);
+/// }
+/// reader.close().unwrap();
+/// ```
+///
+/// The reader is also an iterator over the frames, started on the first call, which
+/// yields the decoding errors instead of ending silently:
+///
+/// ```no_run
+/// use kornia_rs::io::video::VideoReader;
+///
+/// let reader = VideoReader::new("video.mp4").unwrap();
+/// for frame in reader {
+///     let frame = frame.unwrap();
+///     println!("{}", frame.size());
+/// }
+/// ```
+pub struct VideoReader {
+    pipeline: gst::Pipeline,
+    appsink: gst_app::AppSink,
+    metadata: Option<VideoMetadata>,
+    started: bool,
+}
+
+impl VideoReader {
+    /// Creates a new VideoReader object.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the video file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or the pipeline cannot be created.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, KorniaError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(KorniaError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File does not exist: {}", path.to_string_lossy()),
+            )));
+        }
+
+        gst::init()?;
+
+        let uri = gst::glib::filename_to_uri(std::fs::canonicalize(path)?, None)?;
+        let pipeline_str = format!(
+            "uridecodebin name=decoder uri={} ! videoconvert ! video/x-raw,format=RGB ! appsink name=sink sync=false",
+            quote(&uri)
+        );
+        let pipeline = gst::parse::launch(&pipeline_str)?
+            .downcast::<gst::Pipeline>()
+            .map_err(|_| KorniaError::Pipeline("Failed to downcast pipeline".to_string()))?;
+
+        let appsink = pipeline
+            .by_name("sink")
+            .ok_or_else(|| KorniaError::Pipeline("Failed to get sink".to_string()))?
+            .dynamic_cast::<gst_app::AppSink>()
+            .map_err(|_| KorniaError::Pipeline("Failed to cast to AppSink".to_string()))?;
+
+        Ok(Self {
+            pipeline,
+            appsink,
+            metadata: None,
+            started: false,
+        })
+    }
+
+    /// Sets the kind of decoders used for the video stream.
+    ///
+    /// With [`DecoderPreference::Hardware`] the hardware decoders of the platform, e.g.
+    /// VAAPI, NVDEC or V4L2 M2M, are tried first and the software decoders are used if
+    /// none is available. Use [`VideoReader::decoder`] to check the selected decoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `preference` - The decoder preference, [`DecoderPreference::Auto`] by default
+    pub fn with_decoder_preference(self, preference: DecoderPreference) -> Self {
+        if let Some(decodebin) = self.pipeline.by_name("decoder") {
+            apply_decoder_preference(&decodebin, preference);
+        }
+        self
+    }
+
+    /// Returns the name of the video decoder element, e.g. "nvh264dec".
+    ///
+    /// The decoder is selected while prerolling, see [`VideoReader::metadata`].
+    pub fn decoder(&self) -> Option<String> {
+        active_video_decoder(self.pipeline.upcast_ref())
+    }
+
+    /// Returns the properties of the video stream.
+    ///
+    /// The first call prerolls the pipeline, decoding the first frame without consuming
+    /// it, and queries the negotiated caps, the duration and the tags of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream cannot be prerolled, e.g. for an invalid file.
+    pub fn metadata(&mut self) -> Result<VideoMetadata, KorniaError> {
+        if let Some(metadata) = &self.metadata {
+            return Ok(metadata.clone());
+        }
+
+        // preroll the pipeline to negotiate the caps
+        if matches!(
+            self.pipeline.current_state(),
+            gst::State::Null | gst::State::Ready
+        ) {
+            self.pipeline.set_state(gst::State::Paused)?;
+        }
+        let (result, _, _) = self.pipeline.state(gst::ClockTime::from_seconds(10));
+        result.map_err(|_| {
+            KorniaError::Pipeline("Failed to preroll the video pipeline".to_string())
+        })?;
+
+        let caps = self
+            .appsink
+            .static_pad("sink")
+            .and_then(|pad| pad.current_caps())
+            .ok_or_else(|| {
+                KorniaError::Pipeline("Failed to get the negotiated caps".to_string())
+            })?;
+        let structure = caps
+            .structure(0)
+            .ok_or_else(|| KorniaError::Pipeline("Failed to get structure".to_string()))?;
+        let size = caps_frame_size(structure)?;
+        let fps = structure
+            .get::<gst::Fraction>("framerate")
+            .map(|f| f.numer() as f64 / f.denom().max(1) as f64)
+            .unwrap_or(0.0);
+
+        let duration = self
+            .pipeline
+            .query_duration::<gst::ClockTime>()
+            .map(|d| Duration::from_nanos(d.nseconds()));
+
+        // the codec is reported in the tags posted by the demuxer while prerolling
+        let mut codec = None;
+        if let Some(bus) = self.pipeline.bus() {
+            while let Some(msg) = bus.pop_filtered(&[gst::MessageType::Tag]) {
+                if let gst::MessageView::Tag(tag) = msg.view() {
+                    if let Some(name) = tag.tags().get::<gst::tags::VideoCodec>() {
+                        codec.get_or_insert_with(|| name.get().to_string());
+                    }
+                }
+            }
+        }
+
+        let metadata = VideoMetadata {
+            size,
+            fps,
+            duration,
+            frame_count: estimate_frame_count(duration, fps),
+            codec,
+        };
+        self.metadata = Some(metadata.clone());
+
+        Ok(metadata)
+    }
+
+    /// Starts decoding the frames.
+    pub fn start(&mut self) -> Result<(), KorniaError> {
+        self.pipeline.set_state(gst::State::Playing)?;
+        self.started = true;
+        Ok(())
+    }
+
+    /// Pauses the decoding, keeping the position in the stream.
+    ///
+    /// The frames already decoded can still be grabbed while paused.
+    pub fn pause(&self) -> Result<(), KorniaError> {
+        self.pipeline.set_state(gst::State::Paused)?;
+        Ok(())
+    }
+
+    /// Resumes a paused decoding, the same as [`VideoReader::start`].
+    pub fn resume(&mut self) -> Result<(), KorniaError> {
+        self.start()
+    }
+
+    /// Returns whether the decoding is paused.
+    pub fn is_paused(&self) -> bool {
+        self.pipeline.current_state() == gst::State::Paused
+    }
+
+    /// Seeks to a timestamp of the stream.
+    ///
+    /// The seek is accurate: the next grabbed frame is the one displayed at the given
+    /// timestamp, at the cost of decoding from the previous keyframe.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The timestamp from the start of the stream
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamp is beyond the end of the stream or the seek fails.
+    pub fn seek_to_time(&mut self, time: Duration) -> Result<(), KorniaError> {
+        // the pipeline accepts seeks once prerolled
+        let metadata = self.metadata()?;
+        if let Some(duration) = metadata.duration {
+            if time > duration {
+                return Err(KorniaError::InvalidArgument(format!(
+                    "Cannot seek to {:?} beyond the duration {:?}",
+                    time, duration
+                )));
+            }
+        }
+
+        self.pipeline.seek_simple(
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            gst::ClockTime::from_nseconds(time.as_nanos() as u64),
+        )?;
+        let (result, _, _) = self.pipeline.state(gst::ClockTime::from_seconds(10));
+        result.map_err(|_| KorniaError::Pipeline(format!("Failed to seek to {:?}", time)))?;
+
+        Ok(())
+    }
+
+    /// Seeks to a frame of the stream.
+    ///
+    /// The timestamp of the frame is computed from the frame rate of the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the frame from the start of the stream
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream has a variable frame rate, the index is beyond the
+    /// estimated number of frames or the seek fails.
+    pub fn seek_to_frame(&mut self, index: u64) -> Result<(), KorniaError> {
+        let metadata = self.metadata()?;
+        if metadata.fps <= 0.0 {
+            return Err(KorniaError::InvalidArgument(
+                "Cannot seek to a frame of a stream with a variable frame rate".to_string(),
+            ));
+        }
+        if let Some(frame_count) = metadata.frame_count {
+            if index >= frame_count {
+                return Err(KorniaError::InvalidArgument(format!(
+                    "Cannot seek to the frame {} of a stream with {} frames",
+                    index, frame_count
+                )));
+            }
+        }
+
+        self.seek_to_time(frame_timestamp(index, metadata.fps))
+    }
+
+    /// Grabs the next frame, blocking until it is decoded.
+    ///
+    /// # Returns
+    ///
+    /// The next frame, or `None` at the end of the stream.
+    pub fn grab(&mut self) -> Result<Option<Image<u8, 3>>, KorniaError> {
+        if self.appsink.is_eos() {
+            return Ok(None);
+        }
+
+        let sample = match self.appsink.pull_sample() {
+            Ok(sample) => sample,
+            Err(_) if self.appsink.is_eos() => return Ok(None),
+            Err(_) => return Err(self.pipeline_error()),
+        };
+
+        Self::extract_image_frame(&sample).map(Some)
+    }
+
+    /// Returns an asynchronous stream of the frames, starting the decoding.
+    ///
+    /// The stream ends at the end of the video. If the pipeline fails before, e.g. on a
+    /// corrupted file, the error is yielded as the last item.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipeline cannot be started.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kornia_rs::io::video::VideoReader;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), kornia_rs::error::KorniaError> {
+    /// let mut reader = VideoReader::new("video.mp4")?;
+    /// let mut frames = reader.frames()?;
+    /// while let Some(frame) = frames.next().await {
+    ///     println!("{}", frame?.size());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn frames(&mut self) -> Result<VideoFrames<'_>, KorniaError> {
+        self.start()?;
+        Ok(VideoFrames {
+            samples: self.appsink.stream(),
+            reader: self,
+            done: false,
+        })
+    }
+
+    /// Returns the error posted on the bus by the pipeline, or a generic one.
+    fn pipeline_error(&self) -> KorniaError {
+        let message = self
+            .pipeline
+            .bus()
+            .and_then(|bus| bus.pop_filtered(&[gst::MessageType::Error]));
+        match message.as_ref().map(|message| message.view()) {
+            Some(gst::MessageView::Error(err)) => KorniaError::Pipeline(format!(
+                "Failed to decode the video: {} ({:?})",
+                err.error(),
+                err.debug()
+            )),
+            _ => KorniaError::Pipeline("Failed to pull a frame from the video".to_string()),
+        }
+    }
+
+    /// Closes the video reader.
+    pub fn close(&mut self) -> Result<(), KorniaError> {
+        self.pipeline.set_state(gst::State::Null)?;
+        Ok(())
+    }
+
+    /// Extracts an image frame from a sample of the appsink
+    fn extract_image_frame(sample: &gst::Sample) -> Result<Image<u8, 3>, KorniaError> {
+        let caps = sample
+            .caps()
+            .ok_or_else(|| KorniaError::Pipeline("Failed to get caps from sample".to_string()))?;
+        let structure = caps
+            .structure(0)
+            .ok_or_else(|| KorniaError::Pipeline("Failed to get structure".to_string()))?;
+        let size = caps_frame_size(structure)?;
+
+        let buffer = sample
+            .buffer()
+            .ok_or_else(|| KorniaError::Pipeline("Failed to get buffer from sample".to_string()))?;
+        let map = buffer.map_readable()?;
+        Image::<u8, 3>::new(size, map.as_slice().to_vec())
+            .map_err(|_| KorniaError::InvalidImageSize(size))
+    }
+}
+
+impl Iterator for VideoReader {
+    type Item = Result<Image<u8, 3>, KorniaError>;
+
+    /// Blocks until the next frame is decoded, starting the decoding on the first call.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            if let Err(err) = self.start() {
+                // do not retry a pipeline that cannot be started
+                self.started = true;
+                return Some(Err(err));
+            }
+        }
+        self.grab().transpose()
+    }
+}
+
+impl Drop for VideoReader {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+/// An asynchronous stream of the frames of a [`VideoReader`], see [`VideoReader::frames`].
+pub struct VideoFrames<'a> {
+    reader: &'a VideoReader,
+    samples: gst_app::AppSinkStream,
+    done: bool,
+}
+
+impl tokio_stream::Stream for VideoFrames<'_> {
+    type Item = Result<Image<u8, 3>, KorniaError>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        if self.done {
+            return std::task::Poll::Ready(None);
+        }
+
+        match tokio_stream::Stream::poll_next(std::pin::Pin::new(&mut self.samples), cx) {
+            std::task::Poll::Ready(Some(sample)) => {
+                std::task::Poll::Ready(Some(VideoReader::extract_image_frame(&sample)))
+            }
+            std::task::Poll::Ready(None) => {
+                self.done = true;
+                // the appsink stream also ends when the pipeline fails
+                if self.reader.appsink.is_eos() {
+                    std::task::Poll::Ready(None)
+                } else {
+                    std::task::Poll::Ready(Some(Err(self.reader.pipeline_error())))
+                }
+            }
+            std::task::Poll::Pending => std::task::Poll::Pending,
+        }
+    }
+}
+
+/// The H.264 encoders probed by [`VideoWriterBuilder::with_hw_encoder`], in order.
+const HW_H264_ENCODERS: &[&str] = &["nvh264enc", "vaapih264enc", "qsvh264enc"];
+
+/// The H.265 encoders probed by [`VideoWriterBuilder::with_hw_encoder`], in order.
+const HW_H265_ENCODERS: &[&str] = &["nvh265enc", "vaapih265enc", "qsvh265enc"];
+
+/// Finds the first hardware encoder of a codec installed in the registry.
+fn find_hw_encoder(codec: VideoCodec) -> Option<&'static str> {
+    let candidates = match codec {
+        VideoCodec::H264 => HW_H264_ENCODERS,
+        VideoCodec::H265 => HW_H265_ENCODERS,
+        VideoCodec::Vp9 | VideoCodec::Av1 => &[],
+    };
+    candidates
+        .iter()
+        .copied()
+        .find(|name| gst::ElementFactory::find(name).is_some())
+}
+
+/// The codec used to encode a video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoCodec {
+    /// H.264 / AVC, encoded with `x264enc`.
+    H264,
+    /// H.265 / HEVC, encoded with `x265enc`.
+    H265,
+    /// VP9, encoded with `vp9enc`.
+    Vp9,
+    /// AV1, encoded with `av1enc`.
+    Av1,
+}
+
+/// The container a video is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoContainer {
+    /// MPEG-4 Part 14, written with `mp4mux`.
+    Mp4,
+    /// Matroska, written with `matroskamux`.
+    Mkv,
+    /// WebM, written with `webmmux`. Only supports VP9 and AV1.
+    Webm,
+}
+
+/// The rate control of the encoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateControl {
+    /// A target bitrate in kbit/s.
+    Bitrate(u32),
+    /// A constant quality, lower is better. The range depends on the codec:
+    /// 0-51 for H.264 and H.265, 0-63 for VP9 and AV1.
+    Crf(u32),
+}
+
+/// The speed/quality trade-off of the encoder, following the x264 preset names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncoderPreset {
+    /// The fastest encoding with the largest files.
+    Ultrafast,
+    /// A very fast encoding.
+    Veryfast,
+    /// A fast encoding.
+    Fast,
+    /// The default trade-off.
+    Medium,
+    /// A slow encoding with smaller files.
+    Slow,
+    /// The slowest encoding with the smallest files.
+    Veryslow,
+}
+
+impl EncoderPreset {
+    /// The `speed-preset` name of x264enc and x265enc.
+    fn x26x_name(&self) -> &'static str {
+        match self {
+            EncoderPreset::Ultrafast => "ultrafast",
+            EncoderPreset::Veryfast => "veryfast",
+            EncoderPreset::Fast => "fast",
+            EncoderPreset::Medium => "medium",
+            EncoderPreset::Slow => "slow",
+            EncoderPreset::Veryslow => "veryslow",
+        }
+    }
+
+    /// The `cpu-used` value of vp9enc and av1enc, higher is faster.
+    fn cpu_used(&self) -> u32 {
+        match self {
+            EncoderPreset::Ultrafast => 8,
+            EncoderPreset::Veryfast => 6,
+            EncoderPreset::Fast => 4,
+            EncoderPreset::Medium => 2,
+            EncoderPreset::Slow => 1,
+            EncoderPreset::Veryslow => 0,
+        }
+    }
+}
+
+/// A builder configuring the encoder and the container of a [`VideoWriter`].
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::video::{
+///     EncoderPreset, RateControl, VideoCodec, VideoContainer, VideoWriterBuilder,
+/// };
+///
+/// let size = ImageSize {
+///     width: 640,
+///     height: 480,
+/// };
+/// let mut writer = VideoWriterBuilder::new("video.webm", size, 30.0)
+///     .with_codec(VideoCodec::Vp9)
+///     .with_container(VideoContainer::Webm)
+///     .with_rate_control(RateControl::Crf(30))
+///     .with_preset(EncoderPreset::Fast)
+///     .with_keyframe_interval(60)
+///     .build()
+///     .unwrap();
+///
+/// writer.start().unwrap();
+/// let frame = Image::<u8, 3>::from_size_val(size, 0).unwrap();
+/// writer.write(&frame).unwrap();
+/// writer.close().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct VideoWriterBuilder {
+    path: PathBuf,
+    size: ImageSize,
+    fps: f64,
+    codec: VideoCodec,
+    container: VideoContainer,
+    rate_control: Option<RateControl>,
+    preset: Option<EncoderPreset>,
+    keyframe_interval: Option<u32>,
+    hw_encoder: bool,
+}
+
+impl VideoWriterBuilder {
+    /// Creates a new VideoWriterBuilder object writing H.264 to an mp4 file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the output file
+    /// * `size` - The size of the frames
+    /// * `fps` - The frame rate in frames per second
+    pub fn new(path: impl AsRef<Path>, size: ImageSize, fps: f64) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            size,
+            fps,
+            codec: VideoCodec::H264,
+            container: VideoContainer::Mp4,
+            rate_control: None,
+            preset: None,
+            keyframe_interval: None,
+            hw_encoder: false,
+        }
+    }
+
+    /// Sets the codec of the video.
+    pub fn with_codec(mut self, codec: VideoCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Sets the container of the video.
+    pub fn with_container(mut self, container: VideoContainer) -> Self {
+        self.container = container;
+        self
+    }
+
+    /// Sets the rate control of the encoder, the encoder default is used otherwise.
+    pub fn with_rate_control(mut self, rate_control: RateControl) -> Self {
+        self.rate_control = Some(rate_control);
+        self
+    }
+
+    /// Sets the speed/quality trade-off of the encoder.
+    pub fn with_preset(mut self, preset: EncoderPreset) -> Self {
+        self.preset = Some(preset);
+        self
+    }
+
+    /// Sets the maximum number of frames between two keyframes.
+    pub fn with_keyframe_interval(mut self, interval: u32) -> Self {
+        self.keyframe_interval = Some(interval);
+        self
+    }
+
+    /// Uses a hardware encoder when one is available for the codec.
+    ///
+    /// For H.264 and H.265 the NVENC, VA-API and Quick Sync encoders are probed in that
+    /// order, falling back to the software encoder if none is installed. The presets are
+    /// only applied to the software encoders. Use [`VideoWriter::encoder`] to check the
+    /// selected encoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to probe the hardware encoders, disabled by default
+    pub fn with_hw_encoder(mut self, enabled: bool) -> Self {
+        self.hw_encoder = enabled;
+        self
+    }
+
+    /// Checks the options before creating the pipeline.
+    fn validate(&self) -> Result<(), KorniaError> {
+        if self.size.width == 0 || self.size.height == 0 {
+            return Err(KorniaError::InvalidImageSize(self.size));
+        }
+        if self.fps.is_nan() || self.fps <= 0.0 {
+            return Err(KorniaError::InvalidArgument(format!(
+                "Invalid frame rate: {}",
+                self.fps
+            )));
+        }
+        if self.container == VideoContainer::Webm
+            && matches!(self.codec, VideoCodec::H264 | VideoCodec::H265)
+        {
+            return Err(KorniaError::InvalidArgument(format!(
+                "The {:?} codec cannot be written to a WebM container",
+                self.codec
+            )));
+        }
+        match self.rate_control {
+            Some(RateControl::Bitrate(0)) => {
+                return Err(KorniaError::InvalidArgument(
+                    "Invalid bitrate: 0".to_string(),
+                ));
+            }
+            Some(RateControl::Crf(crf)) => {
+                let max = match self.codec {
+                    VideoCodec::H264 | VideoCodec::H265 => 51,
+                    VideoCodec::Vp9 | VideoCodec::Av1 => 63,
+                };
+                if crf > max {
+                    return Err(KorniaError::InvalidArgument(format!(
+                        "Invalid CRF {} for {:?}, the maximum is {}",
+                        crf, self.codec, max
+                    )));
+                }
+            }
+            _ => (),
+        }
+        if self.keyframe_interval == Some(0) {
+            return Err(KorniaError::InvalidArgument(
+                "Invalid keyframe interval: 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The encoder element with its properties, for a software or a hardware encoder.
+    fn encoder_description(&self, hw_encoder: Option<&str>) -> String {
+        if let Some(name) = hw_encoder {
+            return self.hw_encoder_description(name);
+        }
+
+        let mut props = Vec::new();
+        let name = match self.codec {
+            VideoCodec::H264 | VideoCodec::H265 => {
+                let x265 = self.codec == VideoCodec::H265;
+                match self.rate_control {
+                    Some(RateControl::Bitrate(kbps)) => props.push(format!("bitrate={}", kbps)),
+                    // x264enc has a constant quality mode, x265enc takes the crf as an option
+                    Some(RateControl::Crf(crf)) if x265 => {
+                        props.push(format!("option-string=\"crf={}\"", crf))
+                    }
+                    Some(RateControl::Crf(crf)) => {
+                        props.push("pass=qual".to_string());
+                        props.push(format!("quantizer={}", crf));
+                    }
+                    None => (),
+                }
+                if let Some(preset) = self.preset {
+                    props.push(format!("speed-preset={}", preset.x26x_name()));
+                }
+                if let Some(interval) = self.keyframe_interval {
+                    props.push(format!("key-int-max={}", interval));
+                }
+                if x265 {
+                    "x265enc"
+                } else {
+                    "x264enc"
+                }
+            }
+            VideoCodec::Vp9 | VideoCodec::Av1 => {
+                let av1 = self.codec == VideoCodec::Av1;
+                match self.rate_control {
+                    // vp9enc takes bit/s while av1enc takes kbit/s
+                    Some(RateControl::Bitrate(kbps)) => {
+                        let bitrate = if av1 { kbps } else { kbps * 1000 };
+                        props.push("end-usage=vbr".to_string());
+                        props.push(format!("target-bitrate={}", bitrate));
+                    }
+                    Some(RateControl::Crf(crf)) => {
+                        props.push(format!("end-usage={}", if av1 { "q" } else { "cq" }));
+                        props.push(format!("cq-level={}", crf));
+                    }
+                    None => (),
+                }
+                if let Some(preset) = self.preset {
+                    props.push(format!("cpu-used={}", preset.cpu_used()));
+                }
+                if let Some(interval) = self.keyframe_interval {
+                    props.push(format!("keyframe-max-dist={}", interval));
+                }
+                if av1 {
+                    "av1enc"
+                } else {
+                    "vp9enc"
+                }
+            }
+        };
+
+        std::iter::once(name.to_string())
+            .chain(props)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The hardware encoder element with its properties.
+    fn hw_encoder_description(&self, name: &str) -> String {
+        let vaapi = name.starts_with("vaapi");
+        let qsv = name.starts_with("qsv");
+
+        let mut props = Vec::new();
+        match self.rate_control {
+            // the hardware encoders all take kbit/s
+            Some(RateControl::Bitrate(kbps)) => {
+                if vaapi {
+                    props.push("rate-control=cbr".to_string());
+                }
+                props.push(format!("bitrate={}", kbps));
+            }
+            Some(RateControl::Crf(qp)) if vaapi => {
+                props.push("rate-control=cqp".to_string());
+                props.push(format!("init-qp={}", qp));
+            }
+            Some(RateControl::Crf(qp)) if qsv => {
+                props.push("rate-control=cqp".to_string());
+                props.push(format!("qp-i={} qp-p={} qp-b={}", qp, qp, qp));
+            }
+            Some(RateControl::Crf(qp)) => {
+                props.push("rc-mode=constqp".to_string());
+                props.push(format!("qp-const={}", qp));
+            }
+            None => (),
+        }
+        if let Some(interval) = self.keyframe_interval {
+            let gop = if vaapi { "keyframe-period" } else { "gop-size" };
+            props.push(format!("{}={}", gop, interval));
+        }
+
+        std::iter::once(name.to_string())
+            .chain(props)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The pipeline following the appsrc, from the color conversion to the file.
+    fn pipeline_description(&self, hw_encoder: Option<&str>) -> String {
+        let parser = match self.codec {
+            VideoCodec::H264 => Some("h264parse"),
+            VideoCodec::H265 => Some("h265parse"),
+            VideoCodec::Vp9 => None,
+            VideoCodec::Av1 => Some("av1parse"),
+        };
+        let muxer = match self.container {
+            VideoContainer::Mp4 => "mp4mux",
+            VideoContainer::Mkv => "matroskamux",
+            VideoContainer::Webm => "webmmux",
+        };
+
+        let mut stages = vec![
+            "videoconvert".to_string(),
+            self.encoder_description(hw_encoder),
+        ];
+        stages.extend(parser.map(str::to_string));
+        stages.push(muxer.to_string());
+        stages.push(format!(
+            "filesink location={}",
+            quote(&self.path.to_string_lossy())
+        ));
+
+        stages.join(" ! ")
+    }
+
+    /// Create the writer pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an option is invalid, e.g. an H.264 stream in a WebM
+    /// container, or if the encoder is not available.
+    pub fn build(self) -> Result<VideoWriter, KorniaError> {
+        self.validate()?;
+        gst::init()?;
+
+        let hw_encoder = if self.hw_encoder {
+            find_hw_encoder(self.codec)
+        } else {
+            None
+        };
+        // the name of the element, without its properties
+        let encoder = self
+            .encoder_description(hw_encoder)
+            .split(' ')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let pipeline_str = format!(
+            "appsrc name=src format=time ! {}",
+            self.pipeline_description(hw_encoder)
+        );
+        let pipeline = gst::parse::launch(&pipeline_str)?
+            .downcast::<gst::Pipeline>()
+            .map_err(|_| KorniaError::Pipeline("Failed to downcast pipeline".to_string()))?;
+
+        let appsrc = pipeline
+            .by_name("src")
+            .ok_or_else(|| KorniaError::Pipeline("Failed to get src".to_string()))?
+            .dynamic_cast::<gst_app::AppSrc>()
+            .map_err(|_| KorniaError::Pipeline("Failed to cast to AppSrc".to_string()))?;
+
+        let framerate = gst::Fraction::approximate_f64(self.fps).ok_or_else(|| {
+            KorniaError::InvalidArgument(format!("Invalid frame rate: {}", self.fps))
+        })?;
+        appsrc.set_caps(Some(
+            &gst::Caps::builder("video/x-raw")
+                .field("format", "RGB")
+                .field("width", self.size.width as i32)
+                .field("height", self.size.height as i32)
+                .field("framerate", framerate)
+                .build(),
+        ));
+
+        Ok(VideoWriter {
+            pipeline,
+            appsrc,
+            size: self.size,
+            fps: self.fps,
+            frame_count: 0,
+            last_pts: None,
+            encoder,
+        })
+    }
+}
+
+/// A writer encoding RGB frames to a video file.
+///
+/// Use [`VideoWriterBuilder`] to choose the codec, the container and the encoder settings.
+pub struct VideoWriter {
+    pipeline: gst::Pipeline,
+    appsrc: gst_app::AppSrc,
+    size: ImageSize,
+    fps: f64,
+    frame_count: u64,
+    last_pts: Option<Duration>,
+    encoder: String,
+}
+
+impl VideoWriter {
+    /// Creates a new VideoWriter object writing H.264 to an mp4 file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the output file
+    /// * `size` - The size of the frames
+    /// * `fps` - The frame rate in frames per second
+    pub fn new(path: impl AsRef<Path>, size: ImageSize, fps: f64) -> Result<Self, KorniaError> {
+        VideoWriterBuilder::new(path, size, fps).build()
+    }
+
+    /// Returns the name of the encoder element, e.g. "nvh264enc" or "x264enc".
+    pub fn encoder(&self) -> &str {
+        &self.encoder
+    }
+
+    /// Starts the encoding pipeline.
+    pub fn start(&mut self) -> Result<(), KorniaError> {
+        self.pipeline.set_state(gst::State::Playing)?;
+        self.started = true;
+        Ok(())
+    }
+
+    /// Writes a frame, timestamped from the frame rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to write, with the size given to the writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame size does not match or the pipeline is not running.
+    pub fn write(&mut self, frame: &Image<u8, 3>) -> Result<(), KorniaError> {
+        let pts = frame_timestamp(self.frame_count, self.fps);
+        let next = frame_timestamp(self.frame_count + 1, self.fps);
+        self.write_with_pts(frame, pts, Some(next - pts))
+    }
+
+    /// Writes a frame with an explicit presentation timestamp.
+    ///
+    /// Use this method for the streams with an irregular frame arrival, e.g. network
+    /// cameras, so that the video keeps the timing of the capture.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to write, with the size given to the writer
+    /// * `pts` - The presentation timestamp of the frame from the start of the video
+    /// * `duration` - The display duration of the frame, inferred by the muxer if `None`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame size does not match, the timestamp is not after the
+    /// one of the previous frame or the pipeline is not running.
+    pub fn write_with_pts(
+        &mut self,
+        frame: &Image<u8, 3>,
+        pts: Duration,
+        duration: Option<Duration>,
+    ) -> Result<(), KorniaError> {
+        if frame.size() != self.size {
+            return Err(KorniaError::SizeMismatch {
+                expected: self.size,
+                actual: frame.size(),
+            });
+        }
+        check_monotonic_pts(self.last_pts, pts)?;
+
+        let mut buffer = gst::Buffer::from_mut_slice(frame.as_contiguous_slice().into_owned());
+        {
+            let buffer = buffer
+                .get_mut()
+                .ok_or_else(|| KorniaError::Pipeline("Failed to get the buffer".to_string()))?;
+            buffer.set_pts(gst::ClockTime::from_nseconds(pts.as_nanos() as u64));
+            if let Some(duration) = duration {
+                buffer.set_duration(gst::ClockTime::from_nseconds(duration.as_nanos() as u64));
+            }
+        }
+
+        self.appsrc
+            .push_buffer(buffer)
+            .map_err(|err| KorniaError::Pipeline(format!("Failed to push the frame: {:?}", err)))?;
+        self.frame_count += 1;
+        self.last_pts = Some(pts);
+
+        Ok(())
+    }
+
+    /// Returns the timestamp following the last written frame, one frame interval after
+    /// it, or zero if no frame was written.
+    pub(crate) fn next_pts(&self) -> Duration {
+        self.last_pts.map_or(Duration::ZERO, |pts| {
+            pts + Duration::from_secs_f64(1.0 / self.fps)
+        })
+    }
+
+    /// Finishes the file and closes the writer.
+    ///
+    /// The end of stream is sent and awaited so that the container is finalized.
+    pub fn close(&mut self) -> Result<(), KorniaError> {
+        if self.pipeline.current_state() == gst::State::Playing {
+            self.appsrc.end_of_stream()?;
+            if let Some(bus) = self.pipeline.bus() {
+                let msg = bus.timed_pop_filtered(
+                    gst::ClockTime::from_seconds(10),
+                    &[gst::MessageType::Eos, gst::MessageType::Error],
+                );
+                if let Some(msg) = msg {
+                    if let gst::MessageView::Error(err) = msg.view() {
+                        self.pipeline.set_state(gst::State::Null)?;
+                        return Err(KorniaError::Pipeline(format!(
+                            "Failed to finish the video: {}",
+                            err.error()
+                        )));
+                    }
+                }
+            }
+        }
+        self.pipeline.set_state(gst::State::Null)?;
+        Ok(())
+    }
+}
+
+impl Drop for VideoWriter {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_monotonic_pts, estimate_frame_count, frame_timestamp, EncoderPreset, RateControl,
+        VideoCodec, VideoContainer, VideoReader, VideoWriterBuilder,
+    };
+    use crate::image::ImageSize;
+    use std::time::Duration;
+
+    #[test]
+    fn video_metadata() {
+        let duration = Some(Duration::from_millis(10_010));
+        assert_eq!(estimate_frame_count(duration, 30000.0 / 1001.0), Some(300));
+        assert_eq!(estimate_frame_count(duration, 0.0), None);
+        assert_eq!(estimate_frame_count(None, 30.0), None);
+
+        assert!(VideoReader::new("missing.mp4").is_err());
+
+        assert_eq!(frame_timestamp(30, 30.0), Duration::from_secs(1));
+        assert_eq!(frame_timestamp(0, 24.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn video_reader_corrupted() -> Result<(), crate::error::KorniaError> {
+        let tmp_dir = tempfile::tempdir()?;
+        let path = tmp_dir.path().join("corrupted.mp4");
+        std::fs::write(&path, [0u8; 1024])?;
+
+        // the decoding error is yielded instead of ending the iteration
+        let mut reader = VideoReader::new(&path)?;
+        assert!(matches!(reader.next(), Some(Err(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn video_writer_pts() {
+        let pts = Duration::from_millis(40);
+        assert!(check_monotonic_pts(None, Duration::ZERO).is_ok());
+        // irregular intervals are kept, but the timestamps must increase
+        assert!(check_monotonic_pts(Some(pts), Duration::from_millis(95)).is_ok());
+        assert!(check_monotonic_pts(Some(pts), pts).is_err());
+        assert!(check_monotonic_pts(Some(pts), Duration::from_millis(10)).is_err());
+    }
+
+    #[test]
+    fn video_writer_pipeline() {
+        let size = ImageSize {
+            width: 64,
+            height: 48,
+        };
+        let builder = VideoWriterBuilder::new("out.mp4", size, 30.0);
+        assert!(builder.validate().is_ok());
+        assert_eq!(
+            builder.pipeline_description(None),
+            "videoconvert ! x264enc ! h264parse ! mp4mux ! filesink location=\"out.mp4\""
+        );
+
+        let builder = VideoWriterBuilder::new("out.webm", size, 30.0)
+            .with_codec(VideoCodec::Vp9)
+            .with_container(VideoContainer::Webm)
+            .with_rate_control(RateControl::Bitrate(2000))
+            .with_preset(EncoderPreset::Fast)
+            .with_keyframe_interval(60);
+        assert_eq!(
+            builder.encoder_description(None),
+            "vp9enc end-usage=vbr target-bitrate=2000000 cpu-used=4 keyframe-max-dist=60"
+        );
+
+        let builder = VideoWriterBuilder::new("out.mkv", size, 30.0)
+            .with_codec(VideoCodec::H265)
+            .with_container(VideoContainer::Mkv)
+            .with_rate_control(RateControl::Crf(23));
+        assert_eq!(
+            builder.encoder_description(None),
+            "x265enc option-string=\"crf=23\""
+        );
+
+        let builder = VideoWriterBuilder::new("out.mp4", size, 30.0)
+            .with_hw_encoder(true)
+            .with_rate_control(RateControl::Crf(23))
+            .with_keyframe_interval(60);
+        assert_eq!(
+            builder.encoder_description(Some("nvh264enc")),
+            "nvh264enc rc-mode=constqp qp-const=23 gop-size=60"
+        );
+        assert_eq!(
+            builder.encoder_description(Some("vaapih264enc")),
+            "vaapih264enc rate-control=cqp init-qp=23 keyframe-period=60"
+        );
+        assert!(builder
+            .pipeline_description(Some("qsvh264enc"))
+            .starts_with("videoconvert ! qsvh264enc rate-control=cqp qp-i=23"));
+
+        // invalid combinations are rejected before creating the pipeline
+        let h264_webm =
+            VideoWriterBuilder::new("out.webm", size, 30.0).with_container(VideoContainer::Webm);
+        assert!(h264_webm.build().is_err());
+        let crf =
+            VideoWriterBuilder::new("out.mp4", size, 30.0).with_rate_control(RateControl::Crf(60));
+        assert!(crf.build().is_err());
+        assert!(VideoWriterBuilder::new("out.mp4", size, 0.0)
+            .build()
+            .is_err());
+
+        let builder = VideoWriterBuilder::new("my \"video\".mp4", size, 30.0);
+        assert!(builder
+            .pipeline_description(None)
+            .ends_with("filesink location=\"my \\\"video\\\".mp4\""));
+    }
+}
@@ -1,12 +1,61 @@
+use super::gst::{caps_frame_size, FrameDecimator};
 use crate::image::{Image, ImageSize};
 use anyhow::Result;
 use gst::prelude::*;
 
+/// A manual setting of the camera, mapped to the V4L2 controls of the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraControl {
+    /// The exposure time in units of 100 microseconds, disabling the auto exposure.
+    Exposure(i32),
+    /// The analog gain of the sensor.
+    Gain(i32),
+    /// The white balance temperature in Kelvin, disabling the auto white balance.
+    WhiteBalance(i32),
+    /// The focus distance in device units, disabling the autofocus.
+    Focus(i32),
+}
+
+impl CameraControl {
+    /// Returns the V4L2 controls and values to set.
+    fn v4l2_controls(&self) -> Vec<(&'static str, i32)> {
+        match *self {
+            // the manual exposure mode is 1 in the exposure_auto menu
+            CameraControl::Exposure(v) => vec![("exposure_auto", 1), ("exposure_absolute", v)],
+            CameraControl::Gain(v) => vec![("gain", v)],
+            CameraControl::WhiteBalance(v) => vec![
+                ("white_balance_temperature_auto", 0),
+                ("white_balance_temperature", v),
+            ],
+            CameraControl::Focus(v) => vec![("focus_auto", 0), ("focus_absolute", v)],
+        }
+    }
+}
+
+/// The format of the frames requested from the camera.
+///
+/// Most USB cameras only reach their full resolution and frame rate with compressed
+/// MJPEG frames, since raw frames saturate the bandwidth of the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptureFormat {
+    /// Raw frames in a format negotiated with the camera.
+    #[default]
+    Raw,
+    /// MJPEG frames decoded by the pipeline.
+    Mjpeg,
+    /// Raw YUYV 4:2:2 frames converted by the pipeline.
+    Yuyv,
+}
+
 /// A builder for creating a WebcamCapture object
 pub struct WebcamCaptureBuilder {
     camera_id: usize,
     size: Option<ImageSize>,
     fps: u32,
+    max_fps: Option<u32>,
+    frame_skip: usize,
+    format: CaptureFormat,
+    controls: Vec<CameraControl>,
 }
 
 impl WebcamCaptureBuilder {
@@ -22,6 +71,10 @@ impl WebcamCaptureBuilder {
             camera_id: 0,
             size: None,
             fps: 30,
+            max_fps: None,
+            frame_skip: 0,
+            format: CaptureFormat::Raw,
+            controls: Vec::new(),
         }
     }
 
@@ -55,9 +108,98 @@ impl WebcamCaptureBuilder {
         self
     }
 
+    /// Limits the rate of the grabbed frames by dropping frames on the sink side.
+    ///
+    /// Unlike [`WebcamCaptureBuilder::with_fps`], the camera keeps its own frame rate
+    /// and the frames are never duplicated.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_fps` - The maximum frames per second delivered to the application
+    pub fn with_max_fps(mut self, max_fps: u32) -> Self {
+        self.max_fps = Some(max_fps);
+        self
+    }
+
+    /// Keeps one frame out of every `frame_skip + 1` frames from the camera.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame_skip` - The number of frames dropped after each grabbed frame
+    pub fn with_frame_skip(mut self, frame_skip: usize) -> Self {
+        self.frame_skip = frame_skip;
+        self
+    }
+
+    /// Sets the format of the frames requested from the camera.
+    ///
+    /// With MJPEG or YUYV, the size and frames per second are requested from the camera
+    /// directly and must be supported by the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The desired capture format
+    pub fn with_format(mut self, format: CaptureFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets a manual exposure for the WebcamCaptureBuilder.
+    ///
+    /// # Arguments
+    ///
+    /// * `exposure` - The exposure time in units of 100 microseconds
+    pub fn with_exposure(mut self, exposure: i32) -> Self {
+        self.controls.push(CameraControl::Exposure(exposure));
+        self
+    }
+
+    /// Sets the gain for the WebcamCaptureBuilder.
+    ///
+    /// # Arguments
+    ///
+    /// * `gain` - The desired analog gain
+    pub fn with_gain(mut self, gain: i32) -> Self {
+        self.controls.push(CameraControl::Gain(gain));
+        self
+    }
+
+    /// Sets a manual white balance for the WebcamCaptureBuilder.
+    ///
+    /// # Arguments
+    ///
+    /// * `temperature` - The white balance temperature in Kelvin
+    pub fn with_white_balance(mut self, temperature: i32) -> Self {
+        self.controls.push(CameraControl::WhiteBalance(temperature));
+        self
+    }
+
+    /// Sets a manual focus for the WebcamCaptureBuilder.
+    ///
+    /// # Arguments
+    ///
+    /// * `focus` - The focus distance in device units
+    pub fn with_focus(mut self, focus: i32) -> Self {
+        self.controls.push(CameraControl::Focus(focus));
+        self
+    }
+
     /// Create a new [`WebcamCapture`] object.
     pub fn build(self) -> Result<WebcamCapture> {
-        WebcamCapture::new(self.camera_id, self.size, self.fps)
+        if self.max_fps == Some(0) {
+            return Err(anyhow::anyhow!(
+                "The maximum frames per second must be positive"
+            ));
+        }
+        let decimator = FrameDecimator::new(self.frame_skip, self.max_fps);
+        WebcamCapture::new(
+            self.camera_id,
+            self.size,
+            self.fps,
+            self.format,
+            &self.controls,
+            decimator,
+        )
     }
 }
 
@@ -110,15 +252,26 @@ impl WebcamCapture {
     ///
     /// * `camera_id` - The camera id used for capturing images
     /// * `size` - The image size used for resizing directly from the camera
+    /// * `fps` - The desired frames per second
+    /// * `format` - The format of the frames requested from the camera
+    /// * `controls` - The manual settings of the camera
+    /// * `decimator` - The frames dropped on the sink side
     ///
     /// # Returns
     ///
     /// A WebcamCapture object
-    fn new(camera_id: usize, size: Option<ImageSize>, fps: u32) -> Result<Self> {
+    fn new(
+        camera_id: usize,
+        size: Option<ImageSize>,
+        fps: u32,
+        format: CaptureFormat,
+        controls: &[CameraControl],
+        decimator: FrameDecimator,
+    ) -> Result<Self> {
         gst::init()?;
 
         // create a pipeline specified by the camera id and size
-        let pipeline_str = Self::gst_pipeline_string(camera_id, size, fps);
+        let pipeline_str = Self::gst_pipeline_string(camera_id, size, fps, format, controls);
         let pipeline = gst::parse::launch(&pipeline_str)?
             .downcast::<gst::Pipeline>()
             .map_err(|_| anyhow::anyhow!("Failed to downcast pipeline"))?;
@@ -130,18 +283,33 @@ impl WebcamCapture {
             .map_err(|_| anyhow::anyhow!("Failed to cast to AppSink"))?;
 
         let (tx, rx) = tokio::sync::mpsc::channel(50);
+        let decimator = std::sync::Mutex::new(decimator);
+        let start = std::time::Instant::now();
 
         appsink.set_callbacks(
             gst_app::AppSinkCallbacks::builder()
-                .new_sample(move |sink| match Self::extract_image_frame(sink) {
-                    Ok(frame) => {
-                        if tx.blocking_send(frame).is_err() {
-                            Err(gst::FlowError::Error)
-                        } else {
-                            Ok(gst::FlowSuccess::Ok)
+                .new_sample(move |sink| {
+                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Error)?;
+
+                    // drop the frame before copying it out of the buffer
+                    let keep = decimator
+                        .lock()
+                        .map(|mut d| d.keep(start.elapsed()))
+                        .unwrap_or(true);
+                    if !keep {
+                        return Ok(gst::FlowSuccess::Ok);
+                    }
+
+                    match Self::extract_image_frame(&sample) {
+                        Ok(frame) => {
+                            if tx.blocking_send(frame).is_err() {
+                                Err(gst::FlowError::Error)
+                            } else {
+                                Ok(gst::FlowSuccess::Ok)
+                            }
                         }
+                        Err(_) => Err(gst::FlowError::Error),
                     }
-                    Err(_) => Err(gst::FlowError::Error),
                 })
                 .build(),
         );
@@ -153,15 +321,8 @@ impl WebcamCapture {
         })
     }
 
-    /// Runs the webcam capture object and grabs frames from the camera
-    ///
-    /// # Arguments
-    ///
-    /// * `f` - A function that takes an image frame
-    pub async fn run<F>(&mut self, f: F) -> Result<()>
-    where
-        F: Fn(Image<u8, 3>) -> Result<()>,
-    {
+    /// Starts the pipeline and the thread handling the messages from the bus.
+    fn start(&mut self) -> Result<()> {
         // start the pipeline
         let pipeline = &self.pipeline;
         pipeline.set_state(gst::State::Playing)?;
@@ -177,6 +338,14 @@ impl WebcamCapture {
                 match msg.view() {
                     MessageView::Eos(..) => break,
                     MessageView::Error(err) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::error!(
+                            src = ?msg.src().map(|s| s.path_string()),
+                            error = %err.error(),
+                            debug = ?err.debug(),
+                            "webcam pipeline error"
+                        );
+                        #[cfg(not(feature = "tracing"))]
                         eprintln!(
                             "Error from {:?}: {} ({:?})",
                             msg.src().map(|s| s.path_string()),
@@ -191,6 +360,20 @@ impl WebcamCapture {
         });
         self.handle = Some(handle);
 
+        Ok(())
+    }
+
+    /// Runs the webcam capture object and grabs frames from the camera
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function that takes an image frame
+    pub async fn run<F>(&mut self, f: F) -> Result<()>
+    where
+        F: Fn(Image<u8, 3>) -> Result<()>,
+    {
+        self.start()?;
+
         // start grabbing frames from the camera
         while let Some(img) = self.receiver.recv().await {
             f(img)?;
@@ -199,6 +382,135 @@ impl WebcamCapture {
         Ok(())
     }
 
+    /// Starts the capture and returns the grabbed frames.
+    ///
+    /// The frames can be consumed asynchronously as a [`tokio_stream::Stream`], or
+    /// synchronously as an [`Iterator`] outside of an async context.
+    ///
+    /// # Returns
+    ///
+    /// The frames grabbed from the camera, ending when the capture is closed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kornia_rs::io::webcam::WebcamCaptureBuilder;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///   let mut webcam = WebcamCaptureBuilder::new().build()?;
+    ///
+    ///   let mut frames = webcam.frames()?;
+    ///   while let Some(img) = frames.next().await {
+    ///     println!("Image: {:?}", img.size());
+    ///   }
+    ///
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn frames(&mut self) -> Result<WebcamFrames<'_>> {
+        self.start()?;
+        Ok(WebcamFrames {
+            receiver: &mut self.receiver,
+        })
+    }
+
+    /// Pauses the capture, keeping the pipeline and the grabbed frames.
+    ///
+    /// The frames already grabbed can still be consumed while paused.
+    pub fn pause(&self) -> Result<()> {
+        self.control().pause()
+    }
+
+    /// Resumes a paused capture.
+    pub fn resume(&self) -> Result<()> {
+        self.control().resume()
+    }
+
+    /// Returns a handle to pause and resume the capture while frames are consumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kornia_rs::io::webcam::WebcamCaptureBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///   let mut webcam = WebcamCaptureBuilder::new().build()?;
+    ///   let control = webcam.control();
+    ///
+    ///   // pause the capture after the first frame
+    ///   webcam.run(move |img| {
+    ///     println!("Image: {:?}", img.size());
+    ///     control.pause()
+    ///   }).await?;
+    ///
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn control(&self) -> WebcamControl {
+        WebcamControl {
+            pipeline: self.pipeline.clone(),
+        }
+    }
+
+    /// Sets the exposure of the camera while capturing.
+    ///
+    /// # Arguments
+    ///
+    /// * `exposure` - The exposure time in units of 100 microseconds
+    pub fn set_exposure(&self, exposure: i32) -> Result<()> {
+        self.set_control(CameraControl::Exposure(exposure))
+    }
+
+    /// Sets the gain of the camera while capturing.
+    ///
+    /// # Arguments
+    ///
+    /// * `gain` - The desired analog gain
+    pub fn set_gain(&self, gain: i32) -> Result<()> {
+        self.set_control(CameraControl::Gain(gain))
+    }
+
+    /// Sets the white balance of the camera while capturing.
+    ///
+    /// # Arguments
+    ///
+    /// * `temperature` - The white balance temperature in Kelvin
+    pub fn set_white_balance(&self, temperature: i32) -> Result<()> {
+        self.set_control(CameraControl::WhiteBalance(temperature))
+    }
+
+    /// Sets the focus of the camera while capturing.
+    ///
+    /// # Arguments
+    ///
+    /// * `focus` - The focus distance in device units
+    pub fn set_focus(&self, focus: i32) -> Result<()> {
+        self.set_control(CameraControl::Focus(focus))
+    }
+
+    /// Applies a manual setting to the camera through the source element.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The setting to apply
+    pub fn set_control(&self, control: CameraControl) -> Result<()> {
+        let src = self
+            .pipeline
+            .by_name("src")
+            .ok_or_else(|| anyhow::anyhow!("Failed to get source"))?;
+
+        let mut structure = gst::Structure::builder("c");
+        for (name, value) in control.v4l2_controls() {
+            structure = structure.field(name, value);
+        }
+        src.set_property("extra-controls", structure.build());
+
+        Ok(())
+    }
+
     /// Closes the webcam capture object
     pub fn close(&mut self) -> Result<()> {
         self.pipeline.send_event(gst::event::Eos::new());
@@ -214,48 +526,136 @@ impl WebcamCapture {
     /// * `camera_id` - The camera id
     /// * `size` - The image size to capture
     /// * `fps` - The desired frames per second
+    /// * `format` - The format of the frames requested from the camera
+    /// * `controls` - The manual settings of the camera
     ///
     /// # Returns
     ///
     /// A GStreamer pipeline string
-    fn gst_pipeline_string(camera_id: usize, size: Option<ImageSize>, fps: u32) -> String {
-        let video_resize = if let Some(size) = size {
-            format!("! video/x-raw,width={},height={} ", size.width, size.height)
+    fn gst_pipeline_string(
+        camera_id: usize,
+        size: Option<ImageSize>,
+        fps: u32,
+        format: CaptureFormat,
+        controls: &[CameraControl],
+    ) -> String {
+        let size_fields = if let Some(size) = size {
+            format!(",width={},height={}", size.width, size.height)
         } else {
             "".to_string()
         };
 
+        let extra_controls = if controls.is_empty() {
+            "".to_string()
+        } else {
+            let fields = controls
+                .iter()
+                .flat_map(|c| c.v4l2_controls())
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>();
+            format!("extra-controls=\"c,{}\" ", fields.join(","))
+        };
+
+        // request the native format from the camera before converting it to rgb
+        let source_caps = match format {
+            CaptureFormat::Raw if size.is_some() => format!(
+                "! video/x-raw{} ! videorate ! video/x-raw,framerate={}/1",
+                size_fields, fps
+            ),
+            CaptureFormat::Raw => format!("! videorate ! video/x-raw,framerate={}/1", fps),
+            CaptureFormat::Mjpeg => {
+                format!("! image/jpeg{},framerate={}/1 ! jpegdec", size_fields, fps)
+            }
+            CaptureFormat::Yuyv => format!(
+                "! video/x-raw,format=YUY2{},framerate={}/1",
+                size_fields, fps
+            ),
+        };
+
         format!(
-            "v4l2src device=/dev/video{} {}! videorate ! video/x-raw,framerate={}/1 ! videoconvert ! video/x-raw,format=RGB ! appsink name=sink",
-            camera_id, video_resize, fps
+            "v4l2src name=src device=/dev/video{} {}{} ! videoconvert ! video/x-raw,format=RGB ! appsink name=sink",
+            camera_id, extra_controls, source_caps
         )
     }
 
-    /// Extracts an image frame from the appsink
+    /// Extracts an image frame from a sample of the appsink
     ///
     /// # Arguments
     ///
-    /// * `appsink` - The AppSink
+    /// * `sample` - The sample pulled from the AppSink
     ///
     /// # Returns
     ///
     /// An image frame
-    fn extract_image_frame(appsink: &gst_app::AppSink) -> Result<Image<u8, 3>> {
-        let sample = appsink.pull_sample()?;
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn extract_image_frame(sample: &gst::Sample) -> Result<Image<u8, 3>> {
         let caps = sample
             .caps()
             .ok_or_else(|| anyhow::anyhow!("Failed to get caps from sample"))?;
         let structure = caps
             .structure(0)
             .ok_or_else(|| anyhow::anyhow!("Failed to get structure"))?;
-        let height = structure.get::<i32>("height")? as usize;
-        let width = structure.get::<i32>("width")? as usize;
+        let size = caps_frame_size(structure)?;
 
         let buffer = sample
             .buffer()
             .ok_or_else(|| anyhow::anyhow!("Failed to get buffer from sample"))?;
         let map = buffer.map_readable()?;
-        Image::<u8, 3>::new(ImageSize { width, height }, map.as_slice().to_vec())
+        Image::<u8, 3>::new(size, map.as_slice().to_vec())
+    }
+}
+
+/// A handle to pause and resume a [`WebcamCapture`].
+#[derive(Clone)]
+pub struct WebcamControl {
+    pipeline: gst::Pipeline,
+}
+
+impl WebcamControl {
+    /// Pauses the capture by setting the pipeline to the Paused state.
+    pub fn pause(&self) -> Result<()> {
+        self.pipeline.set_state(gst::State::Paused)?;
+        Ok(())
+    }
+
+    /// Resumes the capture by setting the pipeline back to the Playing state.
+    pub fn resume(&self) -> Result<()> {
+        self.pipeline.set_state(gst::State::Playing)?;
+        Ok(())
+    }
+
+    /// Returns whether the capture is paused.
+    pub fn is_paused(&self) -> bool {
+        self.pipeline.current_state() == gst::State::Paused
+    }
+}
+
+/// The frames grabbed by a running [`WebcamCapture`].
+pub struct WebcamFrames<'a> {
+    receiver: &'a mut tokio::sync::mpsc::Receiver<Image<u8, 3>>,
+}
+
+impl tokio_stream::Stream for WebcamFrames<'_> {
+    type Item = Image<u8, 3>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Iterator for WebcamFrames<'_> {
+    type Item = Image<u8, 3>;
+
+    /// Blocks until the next frame is grabbed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async context, use the stream instead.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.blocking_recv()
     }
 }
 
@@ -264,3 +664,50 @@ impl Drop for WebcamCapture {
         self.close().expect("Failed to close webcam");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn pipeline_string_controls() {
+        use super::{CameraControl, CaptureFormat, WebcamCapture};
+
+        let pipeline = WebcamCapture::gst_pipeline_string(
+            1,
+            None,
+            30,
+            CaptureFormat::Raw,
+            &[CameraControl::Exposure(100), CameraControl::Gain(4)],
+        );
+        assert!(pipeline.starts_with(
+            "v4l2src name=src device=/dev/video1 \
+             extra-controls=\"c,exposure_auto=1,exposure_absolute=100,gain=4\" !"
+        ));
+
+        let pipeline = WebcamCapture::gst_pipeline_string(0, None, 30, CaptureFormat::Raw, &[]);
+        assert!(!pipeline.contains("extra-controls"));
+    }
+
+    #[test]
+    fn pipeline_string_formats() {
+        use super::{CaptureFormat, WebcamCapture};
+        use crate::image::ImageSize;
+
+        let size = Some(ImageSize {
+            width: 1920,
+            height: 1080,
+        });
+
+        let pipeline = WebcamCapture::gst_pipeline_string(0, size, 60, CaptureFormat::Mjpeg, &[]);
+        assert!(pipeline.contains("! image/jpeg,width=1920,height=1080,framerate=60/1 ! jpegdec !"));
+
+        let pipeline = WebcamCapture::gst_pipeline_string(0, size, 5, CaptureFormat::Yuyv, &[]);
+        assert!(
+            pipeline.contains("! video/x-raw,format=YUY2,width=1920,height=1080,framerate=5/1 !")
+        );
+
+        let pipeline = WebcamCapture::gst_pipeline_string(0, size, 30, CaptureFormat::Raw, &[]);
+        assert!(pipeline.contains(
+            "! video/x-raw,width=1920,height=1080 ! videorate ! video/x-raw,framerate=30/1 !"
+        ));
+    }
+}
@@ -0,0 +1,223 @@
+use anyhow::Result;
+use std::time::Duration;
+
+use super::frame::{decode_frame_payload, encode_frame, FrameHeader};
+use crate::image::Image;
+
+pub use super::frame::FrameEncoding;
+
+/// A frame received by a [`ZmqSubscriber`].
+pub struct ZmqFrame<const CHANNELS: usize> {
+    /// The topic the frame was published on.
+    pub topic: String,
+    /// The timestamp given by the publisher.
+    pub timestamp: Duration,
+    /// The decoded frame.
+    pub image: Image<u8, CHANNELS>,
+}
+
+/// A publisher sending frames on a ZeroMQ PUB socket.
+///
+/// Each frame is sent as a multipart message with the topic, a small header with the
+/// size, the encoding and the timestamp, and the payload.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::io::zmq::{FrameEncoding, ZmqPublisher};
+/// use std::time::Duration;
+///
+/// let publisher = ZmqPublisher::bind("tcp://*:5555", "camera/front")
+///     .unwrap()
+///     .with_encoding(FrameEncoding::Jpeg { quality: 85 });
+///
+/// let size = ImageSize {
+///     width: 640,
+///     height: 480,
+/// };
+/// let frame = Image::<u8, 3>::from_size_val(size, 0).unwrap();
+/// publisher.publish(&frame, Duration::from_millis(33)).unwrap();
+/// ```
+pub struct ZmqPublisher {
+    // the sockets are closed before the context
+    socket: zmq::Socket,
+    _context: zmq::Context,
+    topic: String,
+    encoding: FrameEncoding,
+}
+
+impl ZmqPublisher {
+    /// Creates a new ZmqPublisher object bound to an endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The endpoint to bind, e.g. `tcp://*:5555` or `ipc:///tmp/frames`
+    /// * `topic` - The topic of the frames
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be bound.
+    pub fn bind(endpoint: &str, topic: &str) -> Result<Self> {
+        let context = zmq::Context::new();
+        let socket = context.socket(zmq::PUB)?;
+        // keep a few frames for slow subscribers, dropping the newer ones after that
+        socket.set_sndhwm(4)?;
+        socket.bind(endpoint)?;
+
+        Ok(Self {
+            socket,
+            _context: context,
+            topic: topic.to_string(),
+            encoding: FrameEncoding::Raw,
+        })
+    }
+
+    /// Sets the encoding of the frames, raw by default.
+    pub fn with_encoding(mut self, encoding: FrameEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Publishes a frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The frame to publish
+    /// * `timestamp` - The timestamp of the frame, passed through to the subscribers
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame cannot be encoded or sent.
+    pub fn publish<const CHANNELS: usize>(
+        &self,
+        image: &Image<u8, CHANNELS>,
+        timestamp: Duration,
+    ) -> Result<()> {
+        let (header, payload) = encode_frame(image, self.encoding, timestamp)?;
+
+        self.socket.send_multipart(
+            [self.topic.as_bytes(), &header.to_bytes()[..], &payload[..]],
+            0,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// A subscriber receiving frames from a [`ZmqPublisher`] on a ZeroMQ SUB socket.
+///
+/// # Example
+///
+/// ```no_run
+/// use kornia_rs::io::zmq::ZmqSubscriber;
+/// use std::time::Duration;
+///
+/// let subscriber = ZmqSubscriber::connect("tcp://localhost:5555", "camera/")
+///     .unwrap()
+///     .with_timeout(Duration::from_secs(1))
+///     .unwrap();
+///
+/// while let Some(frame) = subscriber.recv::<3>().unwrap() {
+///     println!("{} at {:?}: {}", frame.topic, frame.timestamp, frame.image.size());
+/// }
+/// ```
+pub struct ZmqSubscriber {
+    socket: zmq::Socket,
+    _context: zmq::Context,
+}
+
+impl ZmqSubscriber {
+    /// Creates a new ZmqSubscriber object connected to an endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The endpoint of the publisher, e.g. `tcp://localhost:5555`
+    /// * `topic` - The prefix of the topics to receive, or an empty string for all
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be connected.
+    pub fn connect(endpoint: &str, topic: &str) -> Result<Self> {
+        let context = zmq::Context::new();
+        let socket = context.socket(zmq::SUB)?;
+        socket.connect(endpoint)?;
+        socket.set_subscribe(topic.as_bytes())?;
+
+        Ok(Self {
+            socket,
+            _context: context,
+        })
+    }
+
+    /// Sets the time waited for a frame by [`ZmqSubscriber::recv`], forever by default.
+    pub fn with_timeout(self, timeout: Duration) -> Result<Self> {
+        self.socket.set_rcvtimeo(timeout.as_millis() as i32)?;
+        Ok(self)
+    }
+
+    /// Receives the next frame, blocking until it arrives or the timeout expires.
+    ///
+    /// # Returns
+    ///
+    /// The next frame, or `None` if the timeout expired.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message is invalid or the frame has a number of channels
+    /// different from `CHANNELS`.
+    pub fn recv<const CHANNELS: usize>(&self) -> Result<Option<ZmqFrame<CHANNELS>>> {
+        let parts = match self.socket.recv_multipart(0) {
+            Ok(parts) => parts,
+            Err(zmq::Error::EAGAIN) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let [topic, header, payload]: [Vec<u8>; 3] =
+            parts.try_into().map_err(|parts: Vec<_>| {
+                anyhow::anyhow!("Invalid message with {} parts", parts.len())
+            })?;
+
+        decode_frame(&topic, &header, payload).map(Some)
+    }
+}
+
+/// Decode the parts of a frame message.
+fn decode_frame<const CHANNELS: usize>(
+    topic: &[u8],
+    header: &[u8],
+    payload: Vec<u8>,
+) -> Result<ZmqFrame<CHANNELS>> {
+    let header = FrameHeader::from_bytes(header)?;
+    Ok(ZmqFrame {
+        topic: String::from_utf8_lossy(topic).into_owned(),
+        timestamp: header.timestamp,
+        image: decode_frame_payload(&header, payload)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_frame;
+    use crate::image::{Image, ImageSize};
+    use crate::io::frame::{encode_frame, FrameEncoding};
+    use std::time::Duration;
+
+    #[test]
+    fn decode_frame_parts() -> anyhow::Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 1,
+        };
+        let image = Image::<u8, 3>::new(size, vec![1, 2, 3, 4, 5, 6])?;
+        let (header, payload) =
+            encode_frame(&image, FrameEncoding::Raw, Duration::from_micros(1500))?;
+
+        let frame = decode_frame::<3>(b"camera", &header.to_bytes(), payload.clone())?;
+        assert_eq!(frame.topic, "camera");
+        assert_eq!(frame.timestamp, Duration::from_micros(1500));
+        assert_eq!(frame.image.data[[0, 1, 2]], 6);
+        assert!(decode_frame::<3>(b"camera", &[0; 8], payload).is_err());
+
+        Ok(())
+    }
+}
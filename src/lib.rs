@@ -1,18 +1,47 @@
+pub mod augment;
+pub mod bench;
+pub mod blend;
 pub mod calibration;
 pub mod color;
+pub mod config;
+pub mod convert;
 pub mod core;
+pub mod dnn;
+pub mod draw;
+pub mod error;
+pub mod features;
+pub mod fft;
+pub mod filters;
 // NOTE: not ready yet
 // pub mod distance_transform;
 pub mod flip;
+pub mod geometry;
 pub mod histogram;
 pub mod image;
+pub mod inpaint;
 pub mod interpolation;
 pub mod io;
+pub mod mask;
 pub mod metrics;
+pub mod morphology;
 pub mod normalize;
+pub mod optim;
+pub mod pipeline;
+pub mod registration;
 pub mod resize;
+pub mod segmentation;
+pub mod shape;
+pub mod shared;
+pub mod stats;
 // NOTE: not ready yet
 pub mod enhance;
 pub mod tensor;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod threshold;
+pub mod tile;
+pub mod tracking;
+pub mod video;
+pub mod viz;
+pub mod vo;
 pub mod warp;
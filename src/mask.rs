@@ -0,0 +1,302 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Apply a bitwise operation element-wise to two images of the same size.
+fn bitwise_op<const CHANNELS: usize>(
+    src1: &Image<u8, CHANNELS>,
+    src2: &Image<u8, CHANNELS>,
+    op: impl Fn(u8, u8) -> u8 + Send + Sync,
+) -> Result<Image<u8, CHANNELS>> {
+    if src1.size() != src2.size() {
+        return Err(anyhow::anyhow!(
+            "The size of `src1` {} does not match the size of `src2` {}.",
+            src1.size(),
+            src2.size()
+        ));
+    }
+
+    let mut dst = Image::<u8, CHANNELS>::from_size_val(src1.size(), 0)?;
+
+    ndarray::Zip::from(&mut dst.data)
+        .and(&src1.data)
+        .and(&src2.data)
+        .par_for_each(|out, &a, &b| {
+            *out = op(a, b);
+        });
+
+    Ok(dst)
+}
+
+/// Compute the bitwise AND of two masks.
+///
+/// # Arguments
+///
+/// * `src1` - The first mask with shape (H, W, C).
+/// * `src2` - The second mask with shape (H, W, C).
+///
+/// # Returns
+///
+/// The bitwise AND of the masks.
+///
+/// # Errors
+///
+/// Returns an error if the masks have different sizes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::mask;
+///
+/// let size = ImageSize {
+///     width: 2,
+///     height: 1,
+/// };
+/// let a = Image::<u8, 1>::new(size, vec![255, 255]).unwrap();
+/// let b = Image::<u8, 1>::new(size, vec![0, 255]).unwrap();
+///
+/// let c = mask::and(&a, &b).unwrap();
+/// assert_eq!(c.data.as_slice().unwrap(), &[0, 255]);
+/// ```
+pub fn and<const CHANNELS: usize>(
+    src1: &Image<u8, CHANNELS>,
+    src2: &Image<u8, CHANNELS>,
+) -> Result<Image<u8, CHANNELS>> {
+    bitwise_op(src1, src2, |a, b| a & b)
+}
+
+/// Compute the bitwise OR of two masks.
+///
+/// # Arguments
+///
+/// * `src1` - The first mask with shape (H, W, C).
+/// * `src2` - The second mask with shape (H, W, C).
+///
+/// # Returns
+///
+/// The bitwise OR of the masks.
+///
+/// # Errors
+///
+/// Returns an error if the masks have different sizes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::mask;
+///
+/// let size = ImageSize {
+///     width: 2,
+///     height: 1,
+/// };
+/// let a = Image::<u8, 1>::new(size, vec![255, 0]).unwrap();
+/// let b = Image::<u8, 1>::new(size, vec![0, 0]).unwrap();
+///
+/// let c = mask::or(&a, &b).unwrap();
+/// assert_eq!(c.data.as_slice().unwrap(), &[255, 0]);
+/// ```
+pub fn or<const CHANNELS: usize>(
+    src1: &Image<u8, CHANNELS>,
+    src2: &Image<u8, CHANNELS>,
+) -> Result<Image<u8, CHANNELS>> {
+    bitwise_op(src1, src2, |a, b| a | b)
+}
+
+/// Compute the bitwise XOR of two masks.
+///
+/// # Arguments
+///
+/// * `src1` - The first mask with shape (H, W, C).
+/// * `src2` - The second mask with shape (H, W, C).
+///
+/// # Returns
+///
+/// The bitwise XOR of the masks.
+///
+/// # Errors
+///
+/// Returns an error if the masks have different sizes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::mask;
+///
+/// let size = ImageSize {
+///     width: 2,
+///     height: 1,
+/// };
+/// let a = Image::<u8, 1>::new(size, vec![255, 255]).unwrap();
+/// let b = Image::<u8, 1>::new(size, vec![0, 255]).unwrap();
+///
+/// let c = mask::xor(&a, &b).unwrap();
+/// assert_eq!(c.data.as_slice().unwrap(), &[255, 0]);
+/// ```
+pub fn xor<const CHANNELS: usize>(
+    src1: &Image<u8, CHANNELS>,
+    src2: &Image<u8, CHANNELS>,
+) -> Result<Image<u8, CHANNELS>> {
+    bitwise_op(src1, src2, |a, b| a ^ b)
+}
+
+/// Compute the bitwise NOT of a mask.
+///
+/// # Arguments
+///
+/// * `src` - The input mask with shape (H, W, C).
+///
+/// # Returns
+///
+/// The bitwise NOT of the mask.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::mask;
+///
+/// let a = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 1,
+///     },
+///     vec![255, 0],
+/// )
+/// .unwrap();
+///
+/// let b = mask::not(&a).unwrap();
+/// assert_eq!(b.data.as_slice().unwrap(), &[0, 255]);
+/// ```
+pub fn not<const CHANNELS: usize>(src: &Image<u8, CHANNELS>) -> Result<Image<u8, CHANNELS>> {
+    let mut dst = Image::<u8, CHANNELS>::from_size_val(src.size(), 0)?;
+
+    ndarray::Zip::from(&mut dst.data)
+        .and(&src.data)
+        .par_for_each(|out, &a| {
+            *out = !a;
+        });
+
+    Ok(dst)
+}
+
+/// Keep the pixels of an image where the mask is set.
+///
+/// The single channel mask is broadcast over all the channels of the image and
+/// the pixels where the mask is zero are set to the default value of the type.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `mask` - The mask with shape (H, W, 1).
+///
+/// # Returns
+///
+/// The masked image.
+///
+/// # Errors
+///
+/// Returns an error if the mask and the image have different sizes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::mask::apply_mask;
+///
+/// let size = ImageSize {
+///     width: 2,
+///     height: 1,
+/// };
+/// let image = Image::<f32, 3>::from_size_val(size, 0.5).unwrap();
+/// let mask = Image::<u8, 1>::new(size, vec![0, 255]).unwrap();
+///
+/// let masked = apply_mask(&image, &mask).unwrap();
+/// assert_eq!(masked.data.as_slice().unwrap(), &[0.0, 0.0, 0.0, 0.5, 0.5, 0.5]);
+/// ```
+pub fn apply_mask<T, const CHANNELS: usize>(
+    src: &Image<T, CHANNELS>,
+    mask: &Image<u8, 1>,
+) -> Result<Image<T, CHANNELS>>
+where
+    T: Copy + Clone + Default + Send + Sync,
+{
+    if src.size() != mask.size() {
+        return Err(anyhow::anyhow!(
+            "The mask size {} does not match the image size {}.",
+            mask.size(),
+            src.size()
+        ));
+    }
+
+    let mut dst = Image::<T, CHANNELS>::from_size_val(src.size(), T::default())?;
+
+    ndarray::Zip::from(dst.data.rows_mut())
+        .and(src.data.rows())
+        .and(mask.data.rows())
+        .par_for_each(|mut out, inp, m| {
+            if m[0] != 0 {
+                out.assign(&inp);
+            }
+        });
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn bitwise_ops() -> Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+        let a = Image::<u8, 1>::new(size, vec![0, 0, 255, 255])?;
+        let b = Image::<u8, 1>::new(size, vec![0, 255, 0, 255])?;
+
+        let and = super::and(&a, &b)?;
+        assert_eq!(and.data.as_slice().unwrap(), &[0, 0, 0, 255]);
+
+        let or = super::or(&a, &b)?;
+        assert_eq!(or.data.as_slice().unwrap(), &[0, 255, 255, 255]);
+
+        let xor = super::xor(&a, &b)?;
+        assert_eq!(xor.data.as_slice().unwrap(), &[0, 255, 255, 0]);
+
+        let not = super::not(&a)?;
+        assert_eq!(not.data.as_slice().unwrap(), &[255, 255, 0, 0]);
+
+        let c = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 3,
+                height: 2,
+            },
+            0,
+        )?;
+        assert!(super::and(&a, &c).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_mask_broadcast() -> Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+        let image = Image::<u8, 3>::new(size, (1..=12).collect())?;
+        let mask = Image::<u8, 1>::new(size, vec![255, 0, 0, 1])?;
+
+        let masked = super::apply_mask(&image, &mask)?;
+        assert_eq!(
+            masked.data.as_slice().unwrap(),
+            &[1, 2, 3, 0, 0, 0, 0, 0, 0, 10, 11, 12]
+        );
+
+        Ok(())
+    }
+}
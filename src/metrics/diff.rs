@@ -0,0 +1,177 @@
+use crate::image::{Image, ImageDtype};
+use anyhow::Result;
+
+/// Summary statistics of the per-pixel differences between two images.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiffStats {
+    /// The maximum absolute difference over all the pixels and channels.
+    pub max: f32,
+    /// The mean absolute difference over all the pixels and channels.
+    pub mean: f32,
+    /// The number of pixels with a difference in any channel.
+    pub num_different: usize,
+}
+
+/// Map a value in the range [0, 1] to a black-red-yellow-white color.
+fn heat_color(t: f32) -> [u8; 3] {
+    let t = t.clamp(0.0, 1.0) * 3.0;
+    let r = t.min(1.0);
+    let g = (t - 1.0).clamp(0.0, 1.0);
+    let b = (t - 2.0).clamp(0.0, 1.0);
+    [r, g, b].map(|v| (v * 255.0).round() as u8)
+}
+
+/// Compute a heatmap of the per-pixel differences between two images.
+///
+/// The difference of a pixel is the maximum absolute difference over its channels.
+/// The heatmap is normalized by the maximum difference of the images, going from
+/// black for identical pixels to white for the most different ones.
+///
+/// # Arguments
+///
+/// * `image1` - The first input image with shape (H, W, C).
+/// * `image2` - The second input image with shape (H, W, C).
+///
+/// # Returns
+///
+/// The RGB heatmap with shape (H, W, 3) and the summary statistics of the differences.
+///
+/// # Errors
+///
+/// Returns an error if the images have different sizes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::metrics::diff_image;
+///
+/// let size = ImageSize {
+///     width: 2,
+///     height: 1,
+/// };
+/// let image1 = Image::<u8, 1>::new(size, vec![10, 20]).unwrap();
+/// let image2 = Image::<u8, 1>::new(size, vec![10, 30]).unwrap();
+///
+/// let (heatmap, stats) = diff_image(&image1, &image2).unwrap();
+/// assert_eq!(heatmap.get_pixel(0, 0, 0).unwrap(), 0);
+/// assert_eq!(heatmap.get_pixel(1, 0, 2).unwrap(), 255);
+/// assert_eq!(stats.max, 10.0);
+/// assert_eq!(stats.mean, 5.0);
+/// assert_eq!(stats.num_different, 1);
+/// ```
+pub fn diff_image<T, const CHANNELS: usize>(
+    image1: &Image<T, CHANNELS>,
+    image2: &Image<T, CHANNELS>,
+) -> Result<(Image<u8, 3>, DiffStats)>
+where
+    T: ImageDtype,
+{
+    if image1.size() != image2.size() {
+        return Err(anyhow::anyhow!(
+            "The size of `image1` {} does not match the size of `image2` {}.",
+            image1.size(),
+            image2.size()
+        ));
+    }
+
+    // maximum absolute difference over the channels of each pixel
+    let mut pixel_diff = ndarray::Array2::<f32>::zeros((image1.height(), image1.width()));
+    ndarray::Zip::from(&mut pixel_diff)
+        .and(image1.data.rows())
+        .and(image2.data.rows())
+        .par_for_each(|out, a, b| {
+            *out = a
+                .iter()
+                .zip(b.iter())
+                .map(|(&x, &y)| (x.into() - y.into()).abs())
+                .fold(0.0, f32::max);
+        });
+
+    let sum = image1
+        .data
+        .iter()
+        .zip(image2.data.iter())
+        .map(|(&x, &y)| (x.into() - y.into()).abs())
+        .sum::<f32>();
+
+    let max = pixel_diff.iter().cloned().fold(0.0, f32::max);
+    let num_elements = image1.data.len();
+
+    let stats = DiffStats {
+        max,
+        mean: if num_elements > 0 {
+            sum / num_elements as f32
+        } else {
+            0.0
+        },
+        num_different: pixel_diff.iter().filter(|&&d| d > 0.0).count(),
+    };
+
+    let mut heatmap = Image::<u8, 3>::from_size_val(image1.size(), 0)?;
+    if max > 0.0 {
+        ndarray::Zip::from(heatmap.data.rows_mut())
+            .and(&pixel_diff)
+            .par_for_each(|mut out, &d| {
+                for (o, v) in out.iter_mut().zip(heat_color(d / max)) {
+                    *o = v;
+                }
+            });
+    }
+
+    Ok((heatmap, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn diff_image_stats() -> Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+        let image1 = Image::<f32, 3>::from_size_val(size, 0.5)?;
+        let mut image2 = image1.clone();
+        image2.set_pixel(1, 0, 0, 0.7)?;
+        image2.set_pixel(0, 1, 2, 0.4)?;
+
+        let (heatmap, stats) = super::diff_image(&image1, &image2)?;
+        assert_eq!(heatmap.size(), size);
+        assert!((stats.max - 0.2).abs() < 1e-6);
+        assert!((stats.mean - 0.3 / 12.0).abs() < 1e-6);
+        assert_eq!(stats.num_different, 2);
+
+        // the largest difference is white and the identical pixels are black
+        for c in 0..3 {
+            assert_eq!(heatmap.get_pixel(1, 0, c)?, 255);
+            assert_eq!(heatmap.get_pixel(0, 0, c)?, 0);
+        }
+        assert_eq!(heatmap.get_pixel(0, 1, 0)?, 255);
+        assert_eq!(heatmap.get_pixel(0, 1, 2)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff_image_size_mismatch() -> Result<()> {
+        let image1 = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 2,
+                height: 2,
+            },
+            0,
+        )?;
+        let image2 = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 3,
+                height: 2,
+            },
+            0,
+        )?;
+        assert!(super::diff_image(&image1, &image2).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// The level from which a channel is considered clipped in the highlights.
+const HIGHLIGHT_LEVEL: u8 = 250;
+
+/// The level up to which a channel is considered clipped in the shadows.
+const SHADOW_LEVEL: u8 = 5;
+
+/// The number of tonal zones of the histogram summary.
+const NUM_ZONES: usize = 5;
+
+/// Summary of the exposure of an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExposureReport {
+    /// The fraction of pixels with a channel clipped in the highlights.
+    pub clipped_highlights: f32,
+    /// The fraction of pixels with all the channels clipped in the shadows.
+    pub clipped_shadows: f32,
+    /// The mean brightness of the pixels in the range [0, 255].
+    pub mean: f32,
+    /// The fraction of pixels in each tonal zone, from the blacks to the whites.
+    pub zones: [f32; NUM_ZONES],
+}
+
+impl ExposureReport {
+    /// Check if the clipped highlights and shadows are both below a fraction of the pixels.
+    pub fn is_well_exposed(&self, max_clipped: f32) -> bool {
+        self.clipped_highlights <= max_clipped && self.clipped_shadows <= max_clipped
+    }
+}
+
+/// Compute a report of the exposure of an image.
+///
+/// The brightness of a pixel is the mean of its channels. A pixel is clipped in the
+/// highlights if any of its channels is saturated, and in the shadows if all of them are
+/// close to zero. The histogram of the brightness is summarized in five tonal zones of
+/// equal width: blacks, shadows, midtones, highlights and whites.
+///
+/// # Arguments
+///
+/// * `image` - The input image with shape (H, W, C).
+///
+/// # Returns
+///
+/// The exposure report of the image.
+///
+/// # Errors
+///
+/// Returns an error if the image is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::metrics::exposure_report;
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 4,
+///         height: 1,
+///     },
+///     vec![0, 128, 128, 255],
+/// )
+/// .unwrap();
+///
+/// let report = exposure_report(&image).unwrap();
+/// assert_eq!(report.clipped_highlights, 0.25);
+/// assert_eq!(report.clipped_shadows, 0.25);
+/// assert_eq!(report.zones, [0.25, 0.0, 0.5, 0.0, 0.25]);
+/// assert!(!report.is_well_exposed(0.1));
+/// ```
+pub fn exposure_report<const CHANNELS: usize>(
+    image: &Image<u8, CHANNELS>,
+) -> Result<ExposureReport> {
+    let num_pixels = image.width() * image.height();
+    if num_pixels == 0 {
+        return Err(anyhow::anyhow!("Empty image"));
+    }
+
+    let (mut highlights, mut shadows, mut sum) = (0usize, 0usize, 0u64);
+    let mut zones = [0usize; NUM_ZONES];
+    for pixel in image.as_contiguous_slice().chunks_exact(CHANNELS) {
+        if pixel.iter().any(|&v| v >= HIGHLIGHT_LEVEL) {
+            highlights += 1;
+        }
+        if pixel.iter().all(|&v| v <= SHADOW_LEVEL) {
+            shadows += 1;
+        }
+        let brightness = pixel.iter().map(|&v| v as usize).sum::<usize>() / CHANNELS;
+        zones[brightness * NUM_ZONES / 256] += 1;
+        sum += brightness as u64;
+    }
+
+    let n = num_pixels as f32;
+    Ok(ExposureReport {
+        clipped_highlights: highlights as f32 / n,
+        clipped_shadows: shadows as f32 / n,
+        mean: sum as f32 / n,
+        zones: zones.map(|z| z as f32 / n),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn exposure_report() -> Result<()> {
+        let size = ImageSize {
+            width: 10,
+            height: 10,
+        };
+
+        // an overexposed image with a blown out sky in the top rows
+        let mut image = Image::<u8, 3>::from_size_val(size, 150)?;
+        image.data.slice_mut(ndarray::s![..3, .., 2]).fill(255);
+        let report = super::exposure_report(&image)?;
+        assert_eq!(report.clipped_highlights, 0.3);
+        assert_eq!(report.clipped_shadows, 0.0);
+        assert_eq!(report.zones, [0.0, 0.0, 0.7, 0.3, 0.0]);
+        assert!(!report.is_well_exposed(0.05));
+
+        let gray = Image::<u8, 3>::from_size_val(size, 100)?;
+        let report = super::exposure_report(&gray)?;
+        assert_eq!(report.mean, 100.0);
+        assert!(report.is_well_exposed(0.0));
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+use crate::image::{Image, ImageSize};
+use anyhow::Result;
+
+/// The focus measures to score the sharpness of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusMetric {
+    /// The variance of the Laplacian.
+    VarianceOfLaplacian,
+    /// The mean squared magnitude of the Sobel gradient.
+    Tenengrad,
+    /// The mean squared difference of the pixels two columns apart.
+    Brenner,
+}
+
+/// A rectangular region of an image.
+#[derive(Clone, Copy)]
+struct Region {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+/// Compute a focus measure over a region of an image, or None if it is too small.
+fn region_score(image: &Image<f32, 1>, region: Region, metric: FocusMetric) -> Option<f32> {
+    let at = |x: usize, y: usize| image.data[[region.y + y, region.x + x, 0]] as f64;
+    let (w, h) = (region.width, region.height);
+
+    let (sum, sum_sq, count) = match metric {
+        FocusMetric::VarianceOfLaplacian | FocusMetric::Tenengrad => {
+            if w < 3 || h < 3 {
+                return None;
+            }
+            let (mut sum, mut sum_sq) = (0f64, 0f64);
+            for y in 1..h - 1 {
+                for x in 1..w - 1 {
+                    if metric == FocusMetric::VarianceOfLaplacian {
+                        let lap = at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1)
+                            - 4.0 * at(x, y);
+                        sum += lap;
+                        sum_sq += lap * lap;
+                    } else {
+                        let gx = at(x + 1, y - 1) + 2.0 * at(x + 1, y) + at(x + 1, y + 1)
+                            - at(x - 1, y - 1)
+                            - 2.0 * at(x - 1, y)
+                            - at(x - 1, y + 1);
+                        let gy = at(x - 1, y + 1) + 2.0 * at(x, y + 1) + at(x + 1, y + 1)
+                            - at(x - 1, y - 1)
+                            - 2.0 * at(x, y - 1)
+                            - at(x + 1, y - 1);
+                        sum_sq += gx * gx + gy * gy;
+                    }
+                }
+            }
+            (sum, sum_sq, (w - 2) * (h - 2))
+        }
+        FocusMetric::Brenner => {
+            if w < 3 || h < 1 {
+                return None;
+            }
+            let mut sum_sq = 0f64;
+            for y in 0..h {
+                for x in 0..w - 2 {
+                    let d = at(x + 2, y) - at(x, y);
+                    sum_sq += d * d;
+                }
+            }
+            (0.0, sum_sq, (w - 2) * h)
+        }
+    };
+
+    let n = count as f64;
+    let mean = sum / n;
+    Some((sum_sq / n - mean * mean).max(0.0) as f32)
+}
+
+/// Compute a focus measure over a whole image.
+fn image_score(image: &Image<f32, 1>, metric: FocusMetric) -> Result<f32> {
+    let region = Region {
+        x: 0,
+        y: 0,
+        width: image.width(),
+        height: image.height(),
+    };
+    region_score(image, region, metric).ok_or_else(|| {
+        anyhow::anyhow!(
+            "The image of size {} is too small for the focus measure",
+            image.size()
+        )
+    })
+}
+
+/// Compute the variance of the Laplacian of a grayscale image.
+///
+/// A sharp image has strong edges and thus a high variance of its second derivatives.
+/// The 4-neighbour Laplacian is evaluated on the interior pixels.
+///
+/// # Arguments
+///
+/// * `image` - The input grayscale image with shape (H, W, 1).
+///
+/// # Returns
+///
+/// The sharpness score of the image, higher is sharper.
+///
+/// # Errors
+///
+/// Returns an error if the image is smaller than 3x3.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::metrics::variance_of_laplacian;
+///
+/// let flat = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 4,
+///         height: 4,
+///     },
+///     0.5,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(variance_of_laplacian(&flat).unwrap(), 0.0);
+/// ```
+pub fn variance_of_laplacian(image: &Image<f32, 1>) -> Result<f32> {
+    image_score(image, FocusMetric::VarianceOfLaplacian)
+}
+
+/// Compute the Tenengrad focus measure of a grayscale image.
+///
+/// The Tenengrad is the mean squared magnitude of the Sobel gradient on the interior pixels.
+///
+/// # Arguments
+///
+/// * `image` - The input grayscale image with shape (H, W, 1).
+///
+/// # Returns
+///
+/// The sharpness score of the image, higher is sharper.
+///
+/// # Errors
+///
+/// Returns an error if the image is smaller than 3x3.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::metrics::tenengrad;
+///
+/// // a horizontal ramp has a constant gradient of 8
+/// let ramp = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 3,
+///     },
+///     vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0, 0.0, 1.0, 2.0],
+/// )
+/// .unwrap();
+///
+/// assert_eq!(tenengrad(&ramp).unwrap(), 64.0);
+/// ```
+pub fn tenengrad(image: &Image<f32, 1>) -> Result<f32> {
+    image_score(image, FocusMetric::Tenengrad)
+}
+
+/// Compute the Brenner focus measure of a grayscale image.
+///
+/// The Brenner measure is the mean squared difference of the pixels two columns apart.
+///
+/// # Arguments
+///
+/// * `image` - The input grayscale image with shape (H, W, 1).
+///
+/// # Returns
+///
+/// The sharpness score of the image, higher is sharper.
+///
+/// # Errors
+///
+/// Returns an error if the image is less than 3 pixels wide.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::metrics::brenner;
+///
+/// let image = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 4,
+///         height: 1,
+///     },
+///     vec![0.0, 0.0, 1.0, 3.0],
+/// )
+/// .unwrap();
+///
+/// assert_eq!(brenner(&image).unwrap(), 5.0);
+/// ```
+pub fn brenner(image: &Image<f32, 1>) -> Result<f32> {
+    image_score(image, FocusMetric::Brenner)
+}
+
+/// Compute a focus measure on each tile of a grayscale image.
+///
+/// The image is split into a grid of tiles, the tiles on the right and bottom borders
+/// being smaller if the size of the image is not a multiple of the tile size. This allows
+/// to find the sharp regions of an image, e.g. with a shallow depth of field.
+///
+/// # Arguments
+///
+/// * `image` - The input grayscale image with shape (H, W, 1).
+/// * `tile_size` - The size of the tiles.
+/// * `metric` - The focus measure to compute.
+///
+/// # Returns
+///
+/// The score of each tile, as an image with one pixel per tile. The tiles too small for
+/// the measure have a score of zero.
+///
+/// # Errors
+///
+/// Returns an error if the tile size is zero.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::metrics::{focus_tiles, FocusMetric};
+///
+/// let image = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 10,
+///         height: 8,
+///     },
+///     0.0,
+/// )
+/// .unwrap();
+///
+/// let tile_size = ImageSize {
+///     width: 4,
+///     height: 4,
+/// };
+/// let scores = focus_tiles(&image, tile_size, FocusMetric::Tenengrad).unwrap();
+/// assert_eq!((scores.width(), scores.height()), (3, 2));
+/// ```
+pub fn focus_tiles(
+    image: &Image<f32, 1>,
+    tile_size: ImageSize,
+    metric: FocusMetric,
+) -> Result<Image<f32, 1>> {
+    if tile_size.width == 0 || tile_size.height == 0 {
+        return Err(anyhow::anyhow!(
+            "The tile size {} must be non zero",
+            tile_size
+        ));
+    }
+
+    let grid = ImageSize {
+        width: image.width().div_ceil(tile_size.width),
+        height: image.height().div_ceil(tile_size.height),
+    };
+
+    let mut scores = Image::<f32, 1>::from_size_val(grid, 0.0)?;
+    ndarray::Zip::indexed(scores.data.rows_mut()).par_for_each(|(j, i), mut score| {
+        let (x, y) = (i * tile_size.width, j * tile_size.height);
+        let region = Region {
+            x,
+            y,
+            width: tile_size.width.min(image.width() - x),
+            height: tile_size.height.min(image.height() - y),
+        };
+        score[0] = region_score(image, region, metric).unwrap_or(0.0);
+    });
+
+    Ok(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FocusMetric;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn focus_blurred() -> Result<()> {
+        // a checkerboard and its box blurred version
+        let size = ImageSize {
+            width: 16,
+            height: 12,
+        };
+        let sharp = Image::<f32, 1>::new(
+            size,
+            (0..16 * 12)
+                .map(|i| (((i % 16) / 2 + (i / 16) / 2) % 2) as f32)
+                .collect(),
+        )?;
+        let mut blurred = sharp.clone();
+        for y in 1..11 {
+            for x in 1..15 {
+                let mut sum = 0.0;
+                for dy in 0..3 {
+                    for dx in 0..3 {
+                        sum += sharp.data[[y + dy - 1, x + dx - 1, 0]];
+                    }
+                }
+                blurred.data[[y, x, 0]] = sum / 9.0;
+            }
+        }
+
+        assert!(super::variance_of_laplacian(&sharp)? > super::variance_of_laplacian(&blurred)?);
+        assert!(super::tenengrad(&sharp)? > super::tenengrad(&blurred)?);
+        assert!(super::brenner(&sharp)? > super::brenner(&blurred)?);
+
+        // only the left half of the image is textured
+        let mut half = sharp.clone();
+        half.data.slice_mut(ndarray::s![.., 8.., ..]).fill(0.5);
+        let tile_size = ImageSize {
+            width: 8,
+            height: 6,
+        };
+        let scores = super::focus_tiles(&half, tile_size, FocusMetric::Brenner)?;
+        assert_eq!(
+            scores.size(),
+            ImageSize {
+                width: 2,
+                height: 2
+            }
+        );
+        assert!(scores.data[[0, 0, 0]] > 0.0 && scores.data[[1, 0, 0]] > 0.0);
+        assert_eq!(scores.data[[0, 1, 0]], 0.0);
+
+        assert!(super::tenengrad(&Image::<f32, 1>::from_size_val(
+            ImageSize {
+                width: 2,
+                height: 5,
+            },
+            0.0,
+        )?)
+        .is_err());
+        Ok(())
+    }
+}
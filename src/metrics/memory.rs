@@ -0,0 +1,119 @@
+use crate::tensor::{TensorAllocator, TrackingAllocator};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A snapshot of the memory allocated through a [`TrackingAllocator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The bytes currently allocated.
+    pub live_bytes: usize,
+    /// The largest number of bytes allocated at the same time.
+    pub peak_bytes: usize,
+    /// The number of allocations.
+    pub allocations: usize,
+    /// The number of deallocations.
+    pub deallocations: usize,
+}
+
+/// The counters of a tracking allocator, updated from any thread.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryCounters {
+    live_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    allocations: AtomicUsize,
+    deallocations: AtomicUsize,
+}
+
+impl MemoryCounters {
+    pub(crate) fn record_alloc(&self, bytes: usize) {
+        let live = self.live_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak_bytes.fetch_max(live, Ordering::Relaxed);
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_dealloc(&self, bytes: usize) {
+        self.live_bytes.fetch_sub(bytes, Ordering::Relaxed);
+        self.deallocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            live_bytes: self.live_bytes.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed),
+            allocations: self.allocations.load(Ordering::Relaxed),
+            deallocations: self.deallocations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A registry of the memory usage of the stages of a pipeline.
+///
+/// Each stage allocates its tensors with its own [`TrackingAllocator`], so that the
+/// live bytes, the peak usage and the allocation counts are reported per stage.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::metrics::MemoryTracker;
+/// use kornia_rs::tensor::{CpuAllocator, Tensor};
+///
+/// let tracker = MemoryTracker::new();
+/// let alloc = tracker.allocator("preprocess", CpuAllocator);
+///
+/// let t = Tensor::<f32, 2>::new_uninitialized([4, 4], alloc).unwrap();
+/// assert_eq!(tracker.usage("preprocess").unwrap().live_bytes, 64);
+///
+/// drop(t);
+/// let usage = tracker.usage("preprocess").unwrap();
+/// assert_eq!((usage.live_bytes, usage.peak_bytes), (0, 64));
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryTracker {
+    stages: Arc<Mutex<BTreeMap<String, Arc<MemoryCounters>>>>,
+}
+
+impl MemoryTracker {
+    /// Create a new tracker without stages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get an allocator recording its usage for a stage.
+    ///
+    /// The allocators of the same stage share their counters.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The name of the stage.
+    /// * `inner` - The allocator doing the allocations.
+    pub fn allocator<A: TensorAllocator>(&self, stage: &str, inner: A) -> TrackingAllocator<A> {
+        let counters = self
+            .stages
+            .lock()
+            .expect("The memory tracker is poisoned")
+            .entry(stage.to_string())
+            .or_default()
+            .clone();
+        TrackingAllocator::with_counters(inner, counters)
+    }
+
+    /// Get the memory usage of a stage, or `None` if the stage is unknown.
+    pub fn usage(&self, stage: &str) -> Option<MemoryUsage> {
+        self.stages
+            .lock()
+            .expect("The memory tracker is poisoned")
+            .get(stage)
+            .map(|counters| counters.usage())
+    }
+
+    /// Get the memory usage of all the stages, sorted by name.
+    pub fn report(&self) -> Vec<(String, MemoryUsage)> {
+        self.stages
+            .lock()
+            .expect("The memory tracker is poisoned")
+            .iter()
+            .map(|(stage, counters)| (stage.clone(), counters.usage()))
+            .collect()
+    }
+}
@@ -1,7 +1,15 @@
+mod diff;
+mod exposure;
+mod focus;
 mod huber;
 mod l1;
+pub(crate) mod memory;
 mod mse;
 
+pub use diff::{diff_image, DiffStats};
+pub use exposure::{exposure_report, ExposureReport};
+pub use focus::{brenner, focus_tiles, tenengrad, variance_of_laplacian, FocusMetric};
 pub use huber::huber;
 pub use l1::l1_loss;
+pub use memory::{MemoryTracker, MemoryUsage};
 pub use mse::{mse, psnr};
@@ -0,0 +1,198 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Thinning algorithm used to compute the skeleton of a binary mask.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThinningMethod {
+    /// Zhang-Suen two sub-iteration parallel thinning.
+    ZhangSuen,
+}
+
+/// Collect the 8-neighbourhood of a pixel in the order P2, P3, ..., P9.
+///
+/// P2 is the pixel above and the sequence goes clockwise. Pixels outside
+/// the image are considered as background.
+fn neighbours(mask: &[u8], width: usize, height: usize, x: usize, y: usize) -> [u8; 8] {
+    let at = |dx: isize, dy: isize| -> u8 {
+        let nx = x as isize + dx;
+        let ny = y as isize + dy;
+        if nx < 0 || ny < 0 || nx >= width as isize || ny >= height as isize {
+            return 0;
+        }
+        mask[ny as usize * width + nx as usize]
+    };
+
+    [
+        at(0, -1),
+        at(1, -1),
+        at(1, 0),
+        at(1, 1),
+        at(0, 1),
+        at(-1, 1),
+        at(-1, 0),
+        at(-1, -1),
+    ]
+}
+
+/// Run one Zhang-Suen sub-iteration and return whether any pixel was removed.
+fn zhang_suen_step(mask: &mut [u8], width: usize, height: usize, first_pass: bool) -> bool {
+    let mut to_remove = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            if mask[y * width + x] == 0 {
+                continue;
+            }
+
+            let p = neighbours(mask, width, height, x, y);
+            let (p2, p4, p6, p8) = (p[0], p[2], p[4], p[6]);
+
+            // number of foreground neighbours
+            let b = p.iter().map(|&v| v as u32).sum::<u32>();
+            if !(2..=6).contains(&b) {
+                continue;
+            }
+
+            // number of 0 -> 1 transitions in the sequence P2, P3, ..., P9, P2
+            let a = (0..8).filter(|&i| p[i] == 0 && p[(i + 1) % 8] == 1).count();
+            if a != 1 {
+                continue;
+            }
+
+            let removable = if first_pass {
+                p2 * p4 * p6 == 0 && p4 * p6 * p8 == 0
+            } else {
+                p2 * p4 * p8 == 0 && p2 * p6 * p8 == 0
+            };
+
+            if removable {
+                to_remove.push(y * width + x);
+            }
+        }
+    }
+
+    for &idx in to_remove.iter() {
+        mask[idx] = 0;
+    }
+
+    !to_remove.is_empty()
+}
+
+/// Compute the skeleton of a binary mask by morphological thinning.
+///
+/// The input mask is considered as foreground where the value is different from zero.
+/// The output is a 1-pixel-wide skeleton with the foreground set to 255.
+///
+/// # Arguments
+///
+/// * `src` - The input binary mask with shape (H, W, 1).
+/// * `method` - The thinning algorithm to use.
+///
+/// # Returns
+///
+/// The skeleton of the mask with the same size as the input.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::morphology::{thinning, ThinningMethod};
+///
+/// let image = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 5,
+///         height: 5,
+///     },
+///     255,
+/// )
+/// .unwrap();
+///
+/// let skeleton = thinning(&image, ThinningMethod::ZhangSuen).unwrap();
+/// assert_eq!(skeleton.size().width, 5);
+/// assert_eq!(skeleton.size().height, 5);
+/// ```
+pub fn thinning(src: &Image<u8, 1>, method: ThinningMethod) -> Result<Image<u8, 1>> {
+    let (width, height) = (src.width(), src.height());
+
+    // binarize the input into a working buffer of 0s and 1s
+    let mut mask = src
+        .data
+        .iter()
+        .map(|&v| if v != 0 { 1u8 } else { 0u8 })
+        .collect::<Vec<_>>();
+
+    match method {
+        ThinningMethod::ZhangSuen => loop {
+            let changed_first = zhang_suen_step(&mut mask, width, height, true);
+            let changed_second = zhang_suen_step(&mut mask, width, height, false);
+            if !changed_first && !changed_second {
+                break;
+            }
+        },
+    }
+
+    let data = mask.into_iter().map(|v| v * 255).collect();
+
+    Image::new(src.size(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn thinning_horizontal_bar() -> Result<()> {
+        // a 3-pixel thick horizontal bar
+        #[rustfmt::skip]
+        let data = vec![
+            0u8, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 255, 255, 255, 255, 255, 255, 255, 0,
+            0, 255, 255, 255, 255, 255, 255, 255, 0,
+            0, 255, 255, 255, 255, 255, 255, 255, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let image = Image::<u8, 1>::new(
+            ImageSize {
+                width: 9,
+                height: 5,
+            },
+            data,
+        )?;
+
+        let skeleton = super::thinning(&image, super::ThinningMethod::ZhangSuen)?;
+        assert_eq!(skeleton.size(), image.size());
+
+        // the skeleton must be a subset of the input mask
+        for (s, i) in skeleton.data.iter().zip(image.data.iter()) {
+            assert!(*s == 0 || *i != 0);
+        }
+
+        // the skeleton must be at most 1 pixel wide on each column
+        for x in 0..skeleton.width() {
+            let count = (0..skeleton.height())
+                .filter(|&y| skeleton.data[[y, x, 0]] != 0)
+                .count();
+            assert!(count <= 1);
+        }
+
+        // and must not be empty
+        assert!(skeleton.data.iter().any(|&v| v == 255));
+
+        Ok(())
+    }
+
+    #[test]
+    fn thinning_empty() -> Result<()> {
+        let image = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 4,
+                height: 3,
+            },
+            0,
+        )?;
+        let skeleton = super::thinning(&image, super::ThinningMethod::ZhangSuen)?;
+        assert!(skeleton.data.iter().all(|&v| v == 0));
+        Ok(())
+    }
+}
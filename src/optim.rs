@@ -0,0 +1,520 @@
+use crate::calibration::{CameraExtrinsic, CameraIntrinsic};
+use crate::geometry::linalg::solve;
+use crate::geometry::Point2;
+use anyhow::Result;
+
+type Matrix3 = [[f64; 3]; 3];
+
+/// The increments of the cameras and of the points.
+type Step = (Vec<[f64; 6]>, Vec<[f64; 3]>);
+
+/// An observation of a 3D point in the image of a camera.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Observation {
+    /// The index of the camera
+    pub camera: usize,
+    /// The index of the 3D point
+    pub point: usize,
+    /// The measured pixel coordinates of the point
+    pub pixel: Point2,
+}
+
+/// The parameters of the bundle adjustment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BundleAdjustParams {
+    /// The maximum number of Levenberg-Marquardt iterations
+    pub max_iterations: usize,
+    /// The initial damping of the normal equations
+    pub initial_damping: f64,
+    /// The relative decrease of the cost below which the optimization stops
+    pub tolerance: f64,
+}
+
+impl Default for BundleAdjustParams {
+    fn default() -> Self {
+        Self {
+            max_iterations: 50,
+            initial_damping: 1e-3,
+            tolerance: 1e-12,
+        }
+    }
+}
+
+/// The summary of a bundle adjustment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BundleAdjustReport {
+    /// The root mean square reprojection error in pixels before the optimization
+    pub initial_rmse: f64,
+    /// The root mean square reprojection error in pixels after the optimization
+    pub final_rmse: f64,
+    /// The number of iterations run
+    pub iterations: usize,
+}
+
+fn mat_vec(m: &Matrix3, v: &[f64; 3]) -> [f64; 3] {
+    [0, 1, 2].map(|i| (0..3).map(|k| m[i][k] * v[k]).sum())
+}
+
+fn matmul(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+fn skew(v: &[f64; 3]) -> Matrix3 {
+    [[0.0, -v[2], v[1]], [v[2], 0.0, -v[0]], [-v[1], v[0], 0.0]]
+}
+
+/// Compute the rotation matrix of an axis-angle vector with the Rodrigues formula.
+fn exp_so3(w: &[f64; 3]) -> Matrix3 {
+    let theta = (w[0] * w[0] + w[1] * w[1] + w[2] * w[2]).sqrt();
+    let k = skew(w);
+    let k2 = matmul(&k, &k);
+    let (a, b) = if theta < 1e-12 {
+        (1.0, 0.5)
+    } else {
+        (theta.sin() / theta, (1.0 - theta.cos()) / (theta * theta))
+    };
+    [0, 1, 2].map(|i| {
+        [0, 1, 2].map(|j| {
+            let identity = if i == j { 1.0 } else { 0.0 };
+            identity + a * k[i][j] + b * k2[i][j]
+        })
+    })
+}
+
+/// Invert a 3x3 matrix with its adjugate.
+fn invert3(m: &Matrix3) -> Option<Matrix3> {
+    let adj = [
+        [
+            m[1][1] * m[2][2] - m[1][2] * m[2][1],
+            m[0][2] * m[2][1] - m[0][1] * m[2][2],
+            m[0][1] * m[1][2] - m[0][2] * m[1][1],
+        ],
+        [
+            m[1][2] * m[2][0] - m[1][0] * m[2][2],
+            m[0][0] * m[2][2] - m[0][2] * m[2][0],
+            m[0][2] * m[1][0] - m[0][0] * m[1][2],
+        ],
+        [
+            m[1][0] * m[2][1] - m[1][1] * m[2][0],
+            m[0][1] * m[2][0] - m[0][0] * m[2][1],
+            m[0][0] * m[1][1] - m[0][1] * m[1][0],
+        ],
+    ];
+    let det = m[0][0] * adj[0][0] + m[0][1] * adj[1][0] + m[0][2] * adj[2][0];
+    if det.abs() < 1e-300 {
+        return None;
+    }
+    Some(adj.map(|row| row.map(|x| x / det)))
+}
+
+/// The residual of an observation and its Jacobians with respect to the camera, as a
+/// left rotation increment followed by a translation increment, and to the point.
+struct Linearized {
+    residual: [f64; 2],
+    d_camera: [[f64; 6]; 2],
+    d_point: [[f64; 3]; 2],
+}
+
+fn linearize(
+    camera: &CameraExtrinsic,
+    point: &[f64; 3],
+    pixel: &Point2,
+    intrinsic: &CameraIntrinsic,
+) -> Option<Linearized> {
+    let rx = mat_vec(&camera.rotation, point);
+    let [x, y, z] = [0, 1, 2].map(|i| rx[i] + camera.translation[i]);
+
+    // the points behind the camera do not constrain the problem
+    if z <= 1e-9 {
+        return None;
+    }
+
+    let residual = [
+        intrinsic.fx * x / z + intrinsic.cx - pixel.x as f64,
+        intrinsic.fy * y / z + intrinsic.cy - pixel.y as f64,
+    ];
+    let d_proj = [
+        [intrinsic.fx / z, 0.0, -intrinsic.fx * x / (z * z)],
+        [0.0, intrinsic.fy / z, -intrinsic.fy * y / (z * z)],
+    ];
+
+    // d(exp(w) * R * X) / dw = -[R * X]x
+    let d_rotation = skew(&rx).map(|row| row.map(|v| -v));
+    let mut d_camera = [[0.0; 6]; 2];
+    let mut d_point = [[0.0; 3]; 2];
+    for i in 0..2 {
+        for j in 0..3 {
+            d_camera[i][j] = (0..3).map(|k| d_proj[i][k] * d_rotation[k][j]).sum();
+            d_camera[i][3 + j] = d_proj[i][j];
+            d_point[i][j] = (0..3).map(|k| d_proj[i][k] * camera.rotation[k][j]).sum();
+        }
+    }
+
+    Some(Linearized {
+        residual,
+        d_camera,
+        d_point,
+    })
+}
+
+/// Compute the sum of the squared reprojection errors and the number of valid observations.
+fn reprojection_cost(
+    cameras: &[CameraExtrinsic],
+    points3d: &[[f64; 3]],
+    observations: &[Observation],
+    intrinsic: &CameraIntrinsic,
+) -> (f64, usize) {
+    observations
+        .iter()
+        .filter_map(|obs| {
+            linearize(
+                &cameras[obs.camera],
+                &points3d[obs.point],
+                &obs.pixel,
+                intrinsic,
+            )
+        })
+        .fold((0.0, 0), |(cost, n), lin| {
+            (
+                cost + lin.residual[0].powi(2) + lin.residual[1].powi(2),
+                n + 1,
+            )
+        })
+}
+
+/// The normal equations of the problem, split into camera and point blocks.
+struct NormalEquations {
+    u: Vec<[[f64; 6]; 6]>,
+    g_camera: Vec<[f64; 6]>,
+    v: Vec<Matrix3>,
+    g_point: Vec<[f64; 3]>,
+    /// The camera-point blocks grouped by point.
+    w: Vec<Vec<(usize, [[f64; 3]; 6])>>,
+}
+
+impl NormalEquations {
+    /// Accumulate the normal equations, the first camera being fixed.
+    fn new(
+        cameras: &[CameraExtrinsic],
+        points3d: &[[f64; 3]],
+        observations: &[Observation],
+        intrinsic: &CameraIntrinsic,
+    ) -> Self {
+        let num_cameras = cameras.len() - 1;
+        let mut eqs = Self {
+            u: vec![[[0.0; 6]; 6]; num_cameras],
+            g_camera: vec![[0.0; 6]; num_cameras],
+            v: vec![[[0.0; 3]; 3]; points3d.len()],
+            g_point: vec![[0.0; 3]; points3d.len()],
+            w: vec![Vec::new(); points3d.len()],
+        };
+
+        for obs in observations {
+            let Some(lin) = linearize(
+                &cameras[obs.camera],
+                &points3d[obs.point],
+                &obs.pixel,
+                intrinsic,
+            ) else {
+                continue;
+            };
+            let (r, jc, jp) = (&lin.residual, &lin.d_camera, &lin.d_point);
+
+            for a in 0..3 {
+                eqs.g_point[obs.point][a] -= jp[0][a] * r[0] + jp[1][a] * r[1];
+                for b in 0..3 {
+                    eqs.v[obs.point][a][b] += jp[0][a] * jp[0][b] + jp[1][a] * jp[1][b];
+                }
+            }
+
+            if obs.camera == 0 {
+                continue;
+            }
+            let c = obs.camera - 1;
+            let mut w = [[0.0; 3]; 6];
+            for a in 0..6 {
+                eqs.g_camera[c][a] -= jc[0][a] * r[0] + jc[1][a] * r[1];
+                for b in 0..6 {
+                    eqs.u[c][a][b] += jc[0][a] * jc[0][b] + jc[1][a] * jc[1][b];
+                }
+                for b in 0..3 {
+                    w[a][b] = jc[0][a] * jp[0][b] + jc[1][a] * jp[1][b];
+                }
+            }
+            eqs.w[obs.point].push((c, w));
+        }
+
+        eqs
+    }
+
+    /// Solve the damped normal equations with the Schur complement of the point blocks.
+    ///
+    fn solve(&self, damping: f64) -> Option<Step> {
+        let num_cameras = self.u.len();
+        let n = 6 * num_cameras;
+
+        let v_inv = self
+            .v
+            .iter()
+            .map(|v| {
+                let mut v = *v;
+                for (i, row) in v.iter_mut().enumerate() {
+                    row[i] *= 1.0 + damping;
+                }
+                // the points without observations are not updated
+                invert3(&v).unwrap_or([[0.0; 3]; 3])
+            })
+            .collect::<Vec<_>>();
+
+        // the reduced camera system S * dc = rhs
+        let mut s = vec![0.0; n * n];
+        let mut rhs = vec![0.0; n];
+        for (c, u) in self.u.iter().enumerate() {
+            for a in 0..6 {
+                rhs[6 * c + a] = self.g_camera[c][a];
+                for b in 0..6 {
+                    let scale = if a == b { 1.0 + damping } else { 1.0 };
+                    s[(6 * c + a) * n + 6 * c + b] = u[a][b] * scale;
+                }
+            }
+        }
+        for (p, blocks) in self.w.iter().enumerate() {
+            for (c1, w1) in blocks {
+                // W1 * V^-1
+                let y = w1.map(|row| {
+                    [0, 1, 2].map(|j| (0..3).map(|k| row[k] * v_inv[p][k][j]).sum::<f64>())
+                });
+                for a in 0..6 {
+                    rhs[6 * c1 + a] -= (0..3).map(|k| y[a][k] * self.g_point[p][k]).sum::<f64>();
+                    for (c2, w2) in blocks {
+                        for b in 0..6 {
+                            s[(6 * c1 + a) * n + 6 * c2 + b] -=
+                                (0..3).map(|k| y[a][k] * w2[b][k]).sum::<f64>();
+                        }
+                    }
+                }
+            }
+        }
+
+        let dc = if n > 0 { solve(s, rhs, n)? } else { Vec::new() };
+        let dc = dc
+            .chunks(6)
+            .map(|x| [x[0], x[1], x[2], x[3], x[4], x[5]])
+            .collect::<Vec<_>>();
+
+        // back substitute the point increments
+        let dp = self
+            .w
+            .iter()
+            .enumerate()
+            .map(|(p, blocks)| {
+                let mut g = self.g_point[p];
+                for (c, w) in blocks {
+                    for (k, gk) in g.iter_mut().enumerate() {
+                        *gk -= (0..6).map(|a| w[a][k] * dc[*c][a]).sum::<f64>();
+                    }
+                }
+                mat_vec(&v_inv[p], &g)
+            })
+            .collect();
+
+        Some((dc, dp))
+    }
+}
+
+/// Refines camera poses and 3D points by minimizing the reprojection errors.
+///
+/// The problem is solved with Levenberg-Marquardt, eliminating the points with the
+/// Schur complement so that only a dense system of the size of the cameras is solved at
+/// each iteration. The first camera is kept fixed to remove the gauge freedom of the
+/// reference frame.
+///
+/// # Arguments
+///
+/// * `cameras` - The poses of the cameras, mapping world points to the camera frames.
+/// * `points3d` - The 3D points in the world frame.
+/// * `observations` - The measured projections of the points in the cameras.
+/// * `intrinsic` - The intrinsic parameters shared by the cameras.
+/// * `params` - The parameters of the optimization.
+///
+/// # Returns
+///
+/// A report with the reprojection errors before and after the optimization.
+///
+/// # Errors
+///
+/// Returns an error if there are no cameras or an observation refers to a missing
+/// camera or point.
+pub fn bundle_adjust(
+    cameras: &mut [CameraExtrinsic],
+    points3d: &mut [[f64; 3]],
+    observations: &[Observation],
+    intrinsic: &CameraIntrinsic,
+    params: &BundleAdjustParams,
+) -> Result<BundleAdjustReport> {
+    if cameras.is_empty() {
+        return Err(anyhow::anyhow!("At least one camera is needed"));
+    }
+    if let Some(obs) = observations
+        .iter()
+        .find(|obs| obs.camera >= cameras.len() || obs.point >= points3d.len())
+    {
+        return Err(anyhow::anyhow!(
+            "The observation of the point {} in the camera {} is out of range",
+            obs.point,
+            obs.camera
+        ));
+    }
+
+    let rmse = |(cost, n): (f64, usize)| {
+        if n == 0 {
+            0.0
+        } else {
+            (cost / n as f64).sqrt()
+        }
+    };
+    let (mut cost, num_valid) = reprojection_cost(cameras, points3d, observations, intrinsic);
+    let initial_rmse = rmse((cost, num_valid));
+
+    let mut damping = params.initial_damping;
+    let mut iterations = 0;
+    while iterations < params.max_iterations && cost > 0.0 {
+        iterations += 1;
+        let eqs = NormalEquations::new(cameras, points3d, observations, intrinsic);
+
+        // increase the damping until the step decreases the cost
+        let mut decrease = None;
+        for _ in 0..10 {
+            if let Some((dc, dp)) = eqs.solve(damping) {
+                let mut new_cameras = cameras.to_vec();
+                for (camera, d) in new_cameras.iter_mut().skip(1).zip(dc.iter()) {
+                    camera.rotation = matmul(&exp_so3(&[d[0], d[1], d[2]]), &camera.rotation);
+                    for k in 0..3 {
+                        camera.translation[k] += d[3 + k];
+                    }
+                }
+                let mut new_points = points3d.to_vec();
+                for (point, d) in new_points.iter_mut().zip(dp.iter()) {
+                    for k in 0..3 {
+                        point[k] += d[k];
+                    }
+                }
+
+                let (new_cost, _) =
+                    reprojection_cost(&new_cameras, &new_points, observations, intrinsic);
+                if new_cost < cost {
+                    decrease = Some((cost - new_cost) / cost);
+                    cameras.clone_from_slice(&new_cameras);
+                    points3d.copy_from_slice(&new_points);
+                    cost = new_cost;
+                    damping = (damping / 10.0).max(1e-12);
+                    break;
+                }
+            }
+            damping *= 10.0;
+        }
+
+        if decrease.map(|d| d < params.tolerance).unwrap_or(true) {
+            break;
+        }
+    }
+
+    Ok(BundleAdjustReport {
+        initial_rmse,
+        final_rmse: rmse(reprojection_cost(
+            cameras,
+            points3d,
+            observations,
+            intrinsic,
+        )),
+        iterations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BundleAdjustParams, Observation};
+    use crate::calibration::{CameraExtrinsic, CameraIntrinsic};
+    use crate::geometry::Point2;
+    use anyhow::Result;
+
+    #[test]
+    fn bundle_adjust() -> Result<()> {
+        let intrinsic = CameraIntrinsic {
+            fx: 600.0,
+            fy: 600.0,
+            cx: 320.0,
+            cy: 240.0,
+        };
+
+        // three cameras looking at the points along the z axis
+        let cameras = (0..3)
+            .map(|i| CameraExtrinsic {
+                rotation: super::exp_so3(&[0.0, 0.05 * i as f64, 0.0]),
+                translation: [-0.5 * i as f64, 0.0, 0.0],
+            })
+            .collect::<Vec<_>>();
+        let points3d = (0..30)
+            .map(|i| {
+                [
+                    ((i * 37) % 101) as f64 / 101.0 * 4.0 - 2.0,
+                    ((i * 53) % 89) as f64 / 89.0 * 3.0 - 1.5,
+                    4.0 + ((i * 29) % 97) as f64 / 97.0 * 4.0,
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut observations = Vec::new();
+        for (c, camera) in cameras.iter().enumerate() {
+            for (p, point) in points3d.iter().enumerate() {
+                let x = super::mat_vec(&camera.rotation, point);
+                let x = [0, 1, 2].map(|k| x[k] + camera.translation[k]);
+                observations.push(Observation {
+                    camera: c,
+                    point: p,
+                    pixel: Point2::new(
+                        (intrinsic.fx * x[0] / x[2] + intrinsic.cx) as f32,
+                        (intrinsic.fy * x[1] / x[2] + intrinsic.cy) as f32,
+                    ),
+                });
+            }
+        }
+
+        // perturb all but the first camera and the points
+        let mut noisy_cameras = cameras.clone();
+        for (i, camera) in noisy_cameras.iter_mut().enumerate().skip(1) {
+            let d = super::exp_so3(&[0.01, -0.02 * i as f64, 0.015]);
+            camera.rotation = super::matmul(&d, &camera.rotation);
+            camera.translation[1] += 0.03;
+        }
+        let mut noisy_points = points3d.clone();
+        for (i, point) in noisy_points.iter_mut().enumerate() {
+            point[i % 3] += 0.05;
+        }
+
+        let report = super::bundle_adjust(
+            &mut noisy_cameras,
+            &mut noisy_points,
+            &observations,
+            &intrinsic,
+            &BundleAdjustParams::default(),
+        )?;
+        assert!(report.initial_rmse > 1.0);
+        assert!(report.final_rmse < 1e-3);
+
+        let bad = [Observation {
+            camera: 3,
+            point: 0,
+            pixel: Point2::new(0.0, 0.0),
+        }];
+        assert!(super::bundle_adjust(
+            &mut noisy_cameras,
+            &mut noisy_points,
+            &bad,
+            &intrinsic,
+            &BundleAdjustParams::default(),
+        )
+        .is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use crate::image::{Image, ImageSize};
+use anyhow::Result;
+
+/// A batch of images of the same size.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::pipeline::ImageBatch;
+///
+/// let size = ImageSize {
+///     width: 4,
+///     height: 2,
+/// };
+/// let images = vec![Image::<u8, 3>::from_size_val(size, 0).unwrap(); 3];
+///
+/// let batch = ImageBatch::new(images).unwrap();
+/// assert_eq!(batch.len(), 3);
+/// assert_eq!(batch.image_size(), size);
+/// ```
+#[derive(Clone)]
+pub struct ImageBatch<const CHANNELS: usize> {
+    images: Vec<Image<u8, CHANNELS>>,
+    image_size: ImageSize,
+}
+
+impl<const CHANNELS: usize> ImageBatch<CHANNELS> {
+    /// Create a new batch of images.
+    ///
+    /// # Arguments
+    ///
+    /// * `images` - The images of the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch is empty or the images have different sizes.
+    pub fn new(images: Vec<Image<u8, CHANNELS>>) -> Result<Self> {
+        let image_size = images
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("The batch should contain at least one image"))?
+            .size();
+        if let Some(image) = images.iter().find(|image| image.size() != image_size) {
+            return Err(anyhow::anyhow!(
+                "The images of a batch should have the same size: {} != {}",
+                image.size(),
+                image_size
+            ));
+        }
+
+        Ok(Self { images, image_size })
+    }
+
+    /// Get the number of images of the batch.
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Check if the batch is empty, which never happens for a valid batch.
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// Get the size of the images of the batch.
+    pub fn image_size(&self) -> ImageSize {
+        self.image_size
+    }
+
+    /// Get the images of the batch.
+    pub fn images(&self) -> &[Image<u8, CHANNELS>] {
+        &self.images
+    }
+
+    /// Get the shape of the batch as (N, H, W, C).
+    pub fn shape(&self) -> [usize; 4] {
+        [
+            self.images.len(),
+            self.image_size.height,
+            self.image_size.width,
+            CHANNELS,
+        ]
+    }
+
+    /// Copy the pixels of the batch into a buffer in the (N, H, W, C) layout.
+    ///
+    /// Used by the uploader to stage the batches, only built with the `candle` feature.
+    #[cfg(any(test, feature = "candle"))]
+    pub(crate) fn write_to(&self, buffer: &mut Vec<u8>) {
+        buffer.clear();
+        for image in &self.images {
+            buffer.extend(image.data.iter());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImageBatch;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn image_batch() -> Result<()> {
+        let size = ImageSize {
+            width: 2,
+            height: 1,
+        };
+        let a = Image::<u8, 1>::new(size, vec![1, 2])?;
+        let b = Image::<u8, 1>::new(size, vec![3, 4])?;
+
+        let batch = ImageBatch::new(vec![a, b])?;
+        assert_eq!(batch.shape(), [2, 1, 2, 1]);
+
+        let mut buffer = vec![9];
+        batch.write_to(&mut buffer);
+        assert_eq!(buffer, vec![1, 2, 3, 4]);
+
+        let other = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 1,
+                height: 1,
+            },
+            0,
+        )?;
+        assert!(ImageBatch::new(vec![other, batch.images()[0].clone()]).is_err());
+        assert!(ImageBatch::<1>::new(vec![]).is_err());
+        Ok(())
+    }
+}
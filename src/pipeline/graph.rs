@@ -0,0 +1,256 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// A node of a pipeline running on its own thread.
+struct Node {
+    name: String,
+    handle: JoinHandle<Result<()>>,
+}
+
+/// A pipeline under construction, whose last node outputs values of type `T`.
+///
+/// Each node runs on its own thread and is connected to the next one by a bounded
+/// queue, so that a slow node blocks the nodes before it instead of letting the
+/// values pile up. The types of the values flowing between the nodes are checked at
+/// compile time.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::interpolation::InterpolationMode;
+/// use kornia_rs::pipeline::PipelineBuilder;
+/// use std::sync::{Arc, Mutex};
+///
+/// let frames = Arc::new(Mutex::new(Vec::new()));
+/// let sink_frames = frames.clone();
+///
+/// let mut count = 0;
+/// let pipeline = PipelineBuilder::source("capture", 4, move || {
+///     count += 1;
+///     let size = ImageSize {
+///         width: 32,
+///         height: 16,
+///     };
+///     Ok((count <= 5).then(|| Image::<u8, 3>::from_size_val(size, 128).unwrap()))
+/// })
+/// .map("resize", |image| {
+///     let size = ImageSize {
+///         width: 16,
+///         height: 8,
+///     };
+///     Ok(kornia_rs::resize::resize_fast(&image, size, InterpolationMode::Bilinear)?)
+/// })
+/// .map("gray", |image| {
+///     kornia_rs::color::gray_from_rgb(&image.cast_and_scale::<f32>(1.0 / 255.0)?)
+/// })
+/// .sink("collect", move |gray| {
+///     sink_frames.lock().unwrap().push(gray);
+///     Ok(())
+/// });
+///
+/// pipeline.join().unwrap();
+/// assert_eq!(frames.lock().unwrap().len(), 5);
+/// ```
+pub struct PipelineBuilder<T> {
+    nodes: Vec<Node>,
+    output: Receiver<T>,
+    capacity: usize,
+    stop: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> PipelineBuilder<T> {
+    /// Start a pipeline with a source node.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the node, to report its errors.
+    /// * `capacity` - The capacity of the queues between the nodes.
+    /// * `source` - The function producing the values, returning `None` at the end of
+    ///   the stream.
+    pub fn source<F>(name: &str, capacity: usize, mut source: F) -> Self
+    where
+        F: FnMut() -> Result<Option<T>> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::sync_channel(capacity);
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let node_stop = stop.clone();
+        let handle = std::thread::spawn(move || {
+            while !node_stop.load(Ordering::Relaxed) {
+                let Some(value) = source()? else {
+                    break;
+                };
+                if tx.send(value).is_err() {
+                    // the downstream node has stopped
+                    break;
+                }
+            }
+            Ok(())
+        });
+
+        Self {
+            nodes: vec![Node {
+                name: name.to_string(),
+                handle,
+            }],
+            output: rx,
+            capacity,
+            stop,
+        }
+    }
+
+    /// Add a node transforming the values.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the node, to report its errors.
+    /// * `f` - The function transforming each value.
+    pub fn map<U, F>(mut self, name: &str, mut f: F) -> PipelineBuilder<U>
+    where
+        U: Send + 'static,
+        F: FnMut(T) -> Result<U> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::sync_channel(self.capacity);
+        let input = self.output;
+
+        let handle = std::thread::spawn(move || {
+            for value in input {
+                if tx.send(f(value)?).is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+        self.nodes.push(Node {
+            name: name.to_string(),
+            handle,
+        });
+
+        PipelineBuilder {
+            nodes: self.nodes,
+            output: rx,
+            capacity: self.capacity,
+            stop: self.stop,
+        }
+    }
+
+    /// End the pipeline with a sink node consuming the values.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the node, to report its errors.
+    /// * `f` - The function consuming each value.
+    ///
+    /// # Returns
+    ///
+    /// The running pipeline.
+    pub fn sink<F>(mut self, name: &str, mut f: F) -> Pipeline
+    where
+        F: FnMut(T) -> Result<()> + Send + 'static,
+    {
+        let input = self.output;
+        let handle = std::thread::spawn(move || {
+            for value in input {
+                f(value)?;
+            }
+            Ok(())
+        });
+        self.nodes.push(Node {
+            name: name.to_string(),
+            handle,
+        });
+
+        Pipeline {
+            nodes: self.nodes,
+            stop: self.stop,
+        }
+    }
+}
+
+/// A running pipeline, built with a [`PipelineBuilder`].
+///
+/// When a node fails, its queues are closed: the nodes before it stop at their next
+/// value and the nodes after it finish the values already queued.
+pub struct Pipeline {
+    nodes: Vec<Node>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Pipeline {
+    /// Ask the source to stop producing values, letting the queued values through.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if all the nodes have finished.
+    pub fn is_finished(&self) -> bool {
+        self.nodes.iter().all(|node| node.handle.is_finished())
+    }
+
+    /// Wait for all the nodes to finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failed node, in the order of the pipeline.
+    pub fn join(self) -> Result<()> {
+        let mut result = Ok(());
+        for node in self.nodes {
+            let node_result = match node.handle.join() {
+                Ok(node_result) => node_result,
+                Err(_) => Err(anyhow::anyhow!("The node panicked")),
+            };
+            if let (Ok(()), Err(e)) = (&result, node_result) {
+                result = Err(e.context(format!("The pipeline node `{}` failed", node.name)));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PipelineBuilder;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn pipeline_order() -> anyhow::Result<()> {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let sink_output = output.clone();
+
+        let mut values = 0..100;
+        PipelineBuilder::source("count", 2, move || Ok(values.next()))
+            .map("double", |x| Ok(2 * x))
+            .map("format", |x| Ok(x.to_string()))
+            .sink("collect", move |x| {
+                sink_output.lock().unwrap().push(x);
+                Ok(())
+            })
+            .join()?;
+
+        let output = output.lock().unwrap();
+        assert_eq!(output.len(), 100);
+        assert_eq!(output[21], "42");
+        Ok(())
+    }
+
+    #[test]
+    fn pipeline_error() {
+        let mut values = 0..;
+        let result = PipelineBuilder::source("count", 2, move || Ok(values.next()))
+            .map("check", |x: u32| {
+                if x == 10 {
+                    return Err(anyhow::anyhow!("unexpected value"));
+                }
+                Ok(x)
+            })
+            .sink("drop", |_| Ok(()))
+            .join();
+
+        let error = result.unwrap_err();
+        assert_eq!(error.to_string(), "The pipeline node `check` failed");
+    }
+}
@@ -0,0 +1,9 @@
+mod batch;
+mod graph;
+#[cfg(feature = "candle")]
+mod uploader;
+
+pub use batch::ImageBatch;
+pub use graph::{Pipeline, PipelineBuilder};
+#[cfg(feature = "candle")]
+pub use uploader::Uploader;
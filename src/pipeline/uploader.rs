@@ -0,0 +1,142 @@
+use super::ImageBatch;
+use anyhow::Result;
+use candle_core::{Device, Tensor};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread::JoinHandle;
+
+/// The number of gathered batches waiting for the worker before [`Uploader::submit`] blocks.
+const MAX_QUEUED_BATCHES: usize = 2;
+
+/// A gathered batch waiting to be copied to the device.
+struct Staged {
+    data: Vec<u8>,
+    shape: [usize; 4],
+}
+
+/// An upload queue copying batches of images to device tensors on a worker thread.
+///
+/// The pixels of a batch are gathered into one contiguous host buffer, which is moved to
+/// the worker and handed to the device, so that the caller captures the next batch while
+/// the previous one is copied. The tensors are returned in the order of the batches with
+/// shape (N, H, W, C) and the `u8` type, leaving the conversion to the device.
+///
+/// Note: candle has no pinned memory allocation nor copy streams, so the host buffers are
+/// pageable and each copy is synchronous on the worker thread. The transfers overlap with
+/// the caller, not with the kernels running on the device. On the CPU device the buffer
+/// becomes the tensor without a copy.
+///
+/// # Example
+///
+/// ```
+/// use candle_core::Device;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::pipeline::{ImageBatch, Uploader};
+///
+/// let size = ImageSize {
+///     width: 4,
+///     height: 2,
+/// };
+/// let mut uploader = Uploader::new(Device::Cpu);
+///
+/// for _ in 0..3 {
+///     let image = Image::<u8, 3>::from_size_val(size, 1).unwrap();
+///     let batch = ImageBatch::new(vec![image; 2]).unwrap();
+///     uploader.submit(&batch).unwrap();
+///
+///     let tensor = uploader.next().unwrap().unwrap();
+///     assert_eq!(tensor.dims(), &[2, 2, 4, 3]);
+/// }
+/// ```
+pub struct Uploader {
+    staged_tx: Option<SyncSender<Staged>>,
+    uploaded_rx: Receiver<Result<Tensor>>,
+    worker: Option<JoinHandle<()>>,
+    in_flight: usize,
+}
+
+impl Uploader {
+    /// Create a new uploader and start its worker.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device of the tensors.
+    pub fn new(device: Device) -> Self {
+        let (staged_tx, staged_rx) = mpsc::sync_channel::<Staged>(MAX_QUEUED_BATCHES);
+        let (uploaded_tx, uploaded_rx) = mpsc::channel();
+
+        let worker = std::thread::spawn(move || {
+            for staged in staged_rx {
+                let [n, h, w, c] = staged.shape;
+                let tensor = Tensor::from_vec(staged.data, (n, h, w, c), &device)
+                    .map_err(anyhow::Error::from);
+                if uploaded_tx.send(tensor).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            staged_tx: Some(staged_tx),
+            uploaded_rx,
+            worker: Some(worker),
+            in_flight: 0,
+        }
+    }
+
+    /// Get the number of submitted batches not yet returned by [`Uploader::next`].
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// Gather the pixels of a batch and queue their copy to the device.
+    ///
+    /// Blocks while two batches are already waiting for the worker.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - The batch of images to upload.
+    pub fn submit<const CHANNELS: usize>(&mut self, batch: &ImageBatch<CHANNELS>) -> Result<()> {
+        let shape = batch.shape();
+        let mut data = Vec::with_capacity(shape.iter().product());
+        batch.write_to(&mut data);
+
+        self.staged_tx
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("The uploader is closed"))?
+            .send(Staged { data, shape })
+            .map_err(|_| anyhow::anyhow!("The upload worker has stopped"))?;
+        self.in_flight += 1;
+
+        Ok(())
+    }
+
+    /// Wait for the oldest batch in flight to be on the device.
+    ///
+    /// # Returns
+    ///
+    /// The tensor of the batch, or `None` if no batch is in flight.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<Tensor>> {
+        if self.in_flight == 0 {
+            return Ok(None);
+        }
+
+        let tensor = self
+            .uploaded_rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("The upload worker has stopped"))??;
+        self.in_flight -= 1;
+
+        Ok(Some(tensor))
+    }
+}
+
+impl Drop for Uploader {
+    fn drop(&mut self) {
+        // close the queue so that the worker finishes the staged batches and exits
+        self.staged_tx.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,523 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// Geometric transformation estimated by [`find_transform_ecc`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MotionModel {
+    /// Translation with 2 parameters.
+    Translation,
+    /// Rotation and translation with 3 parameters.
+    Euclidean,
+    /// Affine transformation with 6 parameters.
+    Affine,
+    /// Perspective transformation with 8 parameters.
+    Homography,
+}
+
+impl MotionModel {
+    /// Get the number of parameters of the motion model.
+    fn num_params(&self) -> usize {
+        match self {
+            MotionModel::Translation => 2,
+            MotionModel::Euclidean => 3,
+            MotionModel::Affine => 6,
+            MotionModel::Homography => 8,
+        }
+    }
+}
+
+/// A single channel image stored as a row-major buffer.
+pub(crate) struct Plane {
+    pub(crate) data: Vec<f32>,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+}
+
+impl Plane {
+    pub(crate) fn from_image(image: &Image<f32, 1>) -> Self {
+        Self {
+            data: image.data.iter().cloned().collect(),
+            width: image.width(),
+            height: image.height(),
+        }
+    }
+
+    pub(crate) fn at(&self, x: usize, y: usize) -> f32 {
+        self.data[y * self.width + x]
+    }
+
+    /// Sample the plane with bilinear interpolation, or None outside of it.
+    pub(crate) fn sample(&self, u: f32, v: f32) -> Option<f32> {
+        if !(u >= 0.0 && v >= 0.0 && u <= (self.width - 1) as f32 && v <= (self.height - 1) as f32)
+        {
+            return None;
+        }
+        let (x0, y0) = (u.floor() as usize, v.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+        let (fx, fy) = (u - x0 as f32, v - y0 as f32);
+        Some(
+            self.at(x0, y0) * (1.0 - fx) * (1.0 - fy)
+                + self.at(x1, y0) * fx * (1.0 - fy)
+                + self.at(x0, y1) * (1.0 - fx) * fy
+                + self.at(x1, y1) * fx * fy,
+        )
+    }
+
+    /// Halve the resolution by averaging blocks of 2x2 pixels.
+    pub(crate) fn downsample(&self) -> Self {
+        let (width, height) = (self.width / 2, self.height / 2);
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let sum = self.at(2 * x, 2 * y)
+                    + self.at(2 * x + 1, 2 * y)
+                    + self.at(2 * x, 2 * y + 1)
+                    + self.at(2 * x + 1, 2 * y + 1);
+                data.push(sum / 4.0);
+            }
+        }
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Compute the horizontal and vertical gradients with centered differences.
+    pub(crate) fn gradients(&self) -> (Self, Self) {
+        let (w, h) = (self.width, self.height);
+        let mut gx = Vec::with_capacity(w * h);
+        let mut gy = Vec::with_capacity(w * h);
+        for y in 0..h {
+            for x in 0..w {
+                let (xl, xr) = (x.saturating_sub(1), (x + 1).min(w - 1));
+                let (yt, yb) = (y.saturating_sub(1), (y + 1).min(h - 1));
+                gx.push((self.at(xr, y) - self.at(xl, y)) / (xr - xl).max(1) as f32);
+                gy.push((self.at(x, yb) - self.at(x, yt)) / (yb - yt).max(1) as f32);
+            }
+        }
+        (
+            Self {
+                data: gx,
+                width: w,
+                height: h,
+            },
+            Self {
+                data: gy,
+                width: w,
+                height: h,
+            },
+        )
+    }
+}
+
+/// Map a point with a 3x3 row-major warp matrix.
+fn warp_point(w: &[f32; 9], x: f32, y: f32) -> (f32, f32) {
+    let d = w[6] * x + w[7] * y + w[8];
+    (
+        (w[0] * x + w[1] * y + w[2]) / d,
+        (w[3] * x + w[4] * y + w[5]) / d,
+    )
+}
+
+/// Change the scale of the coordinates of a warp matrix by a factor.
+fn scale_warp(w: &[f32; 9], factor: f32) -> [f32; 9] {
+    [
+        w[0],
+        w[1],
+        w[2] * factor,
+        w[3],
+        w[4],
+        w[5] * factor,
+        w[6] / factor,
+        w[7] / factor,
+        w[8],
+    ]
+}
+
+/// Invert a square matrix with Gauss-Jordan elimination and partial pivoting.
+fn invert(matrix: &[f64], n: usize) -> Option<Vec<f64>> {
+    let mut a = matrix.to_vec();
+    let mut inv = (0..n * n)
+        .map(|i| if i / n == i % n { 1.0 } else { 0.0 })
+        .collect::<Vec<f64>>();
+
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))?;
+        if a[pivot * n + col].abs() < 1e-12 {
+            return None;
+        }
+        for k in 0..n {
+            a.swap(col * n + k, pivot * n + k);
+            inv.swap(col * n + k, pivot * n + k);
+        }
+
+        let p = a[col * n + col];
+        for k in 0..n {
+            a[col * n + k] /= p;
+            inv[col * n + k] /= p;
+        }
+
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let f = a[row * n + col];
+            for k in 0..n {
+                a[row * n + k] -= f * a[col * n + k];
+                inv[row * n + k] -= f * inv[col * n + k];
+            }
+        }
+    }
+
+    Some(inv)
+}
+
+/// Run the ECC iterations at a single pyramid level.
+fn ecc_level(
+    template: &Plane,
+    image: &Plane,
+    motion: MotionModel,
+    warp: &mut [f32; 9],
+    max_iterations: usize,
+    epsilon: f32,
+) -> Result<f32> {
+    let n = motion.num_params();
+    let (grad_x, grad_y) = image.gradients();
+
+    let mut rho = -1.0f64;
+    let mut last_rho = f64::MIN;
+
+    for _ in 0..max_iterations {
+        let theta = warp[3].atan2(warp[0]);
+        let (sin, cos) = (theta.sin(), theta.cos());
+
+        // jacobians and values of the pixels of the template inside the warped image
+        let mut jacobians: Vec<f64> = Vec::with_capacity(template.data.len() * n);
+        let mut t_values = Vec::with_capacity(template.data.len());
+        let mut i_values = Vec::with_capacity(template.data.len());
+
+        for y in 0..template.height {
+            for x in 0..template.width {
+                let (xf, yf) = (x as f32, y as f32);
+                let (u, v) = warp_point(warp, xf, yf);
+                let (Some(value), Some(gx), Some(gy)) =
+                    (image.sample(u, v), grad_x.sample(u, v), grad_y.sample(u, v))
+                else {
+                    continue;
+                };
+
+                let (gx, gy) = (gx as f64, gy as f64);
+                let (xd, yd) = (xf as f64, yf as f64);
+                match motion {
+                    MotionModel::Translation => jacobians.extend([gx, gy]),
+                    MotionModel::Euclidean => {
+                        let (s, c) = (sin as f64, cos as f64);
+                        let d_theta = gx * (-s * xd - c * yd) + gy * (c * xd - s * yd);
+                        jacobians.extend([d_theta, gx, gy]);
+                    }
+                    MotionModel::Affine => {
+                        jacobians.extend([gx * xd, gx * yd, gx, gy * xd, gy * yd, gy])
+                    }
+                    MotionModel::Homography => {
+                        let d = (warp[6] * xf + warp[7] * yf + warp[8]) as f64;
+                        let (ud, vd) = (u as f64, v as f64);
+                        let (gxd, gyd) = (gx / d, gy / d);
+                        jacobians.extend([
+                            gxd * xd,
+                            gxd * yd,
+                            gxd,
+                            gyd * xd,
+                            gyd * yd,
+                            gyd,
+                            -(gxd * ud + gyd * vd) * xd,
+                            -(gxd * ud + gyd * vd) * yd,
+                        ]);
+                    }
+                }
+
+                t_values.push(template.at(x, y) as f64);
+                i_values.push(value as f64);
+            }
+        }
+
+        let count = t_values.len();
+        if count <= n {
+            return Err(anyhow::anyhow!(
+                "The warped image does not overlap the template."
+            ));
+        }
+
+        // zero-mean the values over the overlapping region
+        let t_mean = t_values.iter().sum::<f64>() / count as f64;
+        let i_mean = i_values.iter().sum::<f64>() / count as f64;
+        t_values.iter_mut().for_each(|v| *v -= t_mean);
+        i_values.iter_mut().for_each(|v| *v -= i_mean);
+
+        // hessian and projections onto the jacobian
+        let mut hessian = vec![0f64; n * n];
+        let mut t_proj = vec![0f64; n];
+        let mut i_proj = vec![0f64; n];
+        for (k, jac) in jacobians.chunks_exact(n).enumerate() {
+            for a in 0..n {
+                t_proj[a] += jac[a] * t_values[k];
+                i_proj[a] += jac[a] * i_values[k];
+                for b in 0..n {
+                    hessian[a * n + b] += jac[a] * jac[b];
+                }
+            }
+        }
+
+        let t_norm = t_values.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let i_norm = i_values.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let correlation = t_values
+            .iter()
+            .zip(i_values.iter())
+            .map(|(t, i)| t * i)
+            .sum::<f64>();
+
+        rho = correlation / (t_norm * i_norm).max(f64::EPSILON);
+        if (rho - last_rho).abs() < epsilon as f64 {
+            break;
+        }
+        last_rho = rho;
+
+        let hessian_inv = invert(&hessian, n)
+            .ok_or_else(|| anyhow::anyhow!("The hessian of the ECC criterion is singular."))?;
+
+        let quad = |x: &[f64], y: &[f64]| {
+            (0..n)
+                .map(|a| {
+                    (0..n)
+                        .map(|b| x[a] * hessian_inv[a * n + b] * y[b])
+                        .sum::<f64>()
+                })
+                .sum::<f64>()
+        };
+
+        let lambda_n = i_norm * i_norm - quad(&i_proj, &i_proj);
+        let lambda_d = correlation - quad(&t_proj, &i_proj);
+        if lambda_d <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "The ECC algorithm diverged. The images may be uncorrelated or not overlapping."
+            ));
+        }
+        let lambda = lambda_n / lambda_d;
+
+        // parameter update
+        let error = (0..n)
+            .map(|a| lambda * t_proj[a] - i_proj[a])
+            .collect::<Vec<_>>();
+        let delta = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| hessian_inv[a * n + b] * error[b])
+                    .sum::<f64>() as f32
+            })
+            .collect::<Vec<_>>();
+
+        match motion {
+            MotionModel::Translation => {
+                warp[2] += delta[0];
+                warp[5] += delta[1];
+            }
+            MotionModel::Euclidean => {
+                let (s, c) = (theta + delta[0]).sin_cos();
+                warp[0] = c;
+                warp[1] = -s;
+                warp[3] = s;
+                warp[4] = c;
+                warp[2] += delta[1];
+                warp[5] += delta[2];
+            }
+            MotionModel::Affine | MotionModel::Homography => {
+                for (w, d) in warp.iter_mut().zip(delta.iter()) {
+                    *w += d;
+                }
+            }
+        }
+    }
+
+    Ok(rho as f32)
+}
+
+/// Find the geometric transformation between two images with the ECC criterion.
+///
+/// The enhanced correlation coefficient (ECC) maximization by Evangelidis and Psarakis (2008)
+/// iteratively refines the warp that aligns the image to the template. The estimation runs
+/// coarse to fine over an image pyramid to handle larger displacements.
+///
+/// # Arguments
+///
+/// * `template` - The template image with shape (H, W, 1).
+/// * `image` - The image to align with shape (H', W', 1).
+/// * `motion` - The motion model to estimate.
+/// * `max_iterations` - The maximum number of iterations per pyramid level.
+/// * `epsilon` - The minimum change of the correlation coefficient to keep iterating.
+/// * `levels` - The number of pyramid levels, with 1 for the full resolution only.
+///
+/// # Returns
+///
+/// The 3x3 row-major warp matrix mapping the template coordinates to the image coordinates,
+/// such that `image(warp(x)) ~ template(x)`, and the final correlation coefficient.
+///
+/// # Errors
+///
+/// Returns an error if the images do not overlap, are uncorrelated or too small for the
+/// number of pyramid levels.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::registration::{find_transform_ecc, MotionModel};
+///
+/// let size = ImageSize {
+///     width: 32,
+///     height: 32,
+/// };
+/// let blob = |dx: f32| -> Vec<f32> {
+///     (0..32 * 32)
+///         .map(|i| {
+///             let (x, y) = ((i % 32) as f32 - 16.0 - dx, (i / 32) as f32 - 16.0);
+///             (-(x * x + y * y) / 50.0).exp()
+///         })
+///         .collect()
+/// };
+/// let template = Image::<f32, 1>::new(size, blob(0.0)).unwrap();
+/// let image = Image::<f32, 1>::new(size, blob(1.5)).unwrap();
+///
+/// let (warp, rho) = find_transform_ecc(&template, &image, MotionModel::Translation, 50, 1e-6, 1).unwrap();
+/// assert!((warp[2] - 1.5).abs() < 0.05);
+/// assert!(rho > 0.99);
+/// ```
+pub fn find_transform_ecc(
+    template: &Image<f32, 1>,
+    image: &Image<f32, 1>,
+    motion: MotionModel,
+    max_iterations: usize,
+    epsilon: f32,
+    levels: usize,
+) -> Result<([f32; 9], f32)> {
+    let levels = levels.max(1);
+
+    let mut templates = vec![Plane::from_image(template)];
+    let mut images = vec![Plane::from_image(image)];
+    for _ in 1..levels {
+        let (t, i) = (templates.last().unwrap(), images.last().unwrap());
+        if t.width < 4 || t.height < 4 || i.width < 4 || i.height < 4 {
+            return Err(anyhow::anyhow!(
+                "The images are too small for {} pyramid levels.",
+                levels
+            ));
+        }
+        templates.push(t.downsample());
+        images.push(i.downsample());
+    }
+
+    // start from the identity at the coarsest level
+    let mut warp = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+    let mut rho = 0.0;
+
+    for level in (0..levels).rev() {
+        rho = ecc_level(
+            &templates[level],
+            &images[level],
+            motion,
+            &mut warp,
+            max_iterations,
+            epsilon,
+        )?;
+        if level > 0 {
+            warp = scale_warp(&warp, 2.0);
+        }
+    }
+
+    Ok((warp, rho))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    /// A smooth textured image made of gaussian blobs sampled at warped coordinates.
+    fn textured_image(
+        size: ImageSize,
+        warp: impl Fn(f32, f32) -> (f32, f32),
+    ) -> Result<Image<f32, 1>> {
+        let blobs = [
+            (20.0, 18.0, 6.0),
+            (42.0, 25.0, 8.0),
+            (30.0, 45.0, 7.0),
+            (50.0, 50.0, 5.0),
+        ];
+        let data = (0..size.width * size.height)
+            .map(|i| {
+                let (x, y) = warp((i % size.width) as f32, (i / size.width) as f32);
+                blobs
+                    .iter()
+                    .map(|&(cx, cy, s)| {
+                        (-((x - cx).powi(2) + (y - cy).powi(2)) / (2.0 * s * s)).exp()
+                    })
+                    .sum()
+            })
+            .collect();
+        Image::new(size, data)
+    }
+
+    #[test]
+    fn ecc_translation_pyramid() -> Result<()> {
+        let size = ImageSize {
+            width: 64,
+            height: 64,
+        };
+        let template = textured_image(size, |x, y| (x, y))?;
+        // the image content is shifted by (4.0, -3.0) with respect to the template
+        let image = textured_image(size, |x, y| (x - 4.0, y + 3.0))?;
+
+        let (warp, rho) = super::find_transform_ecc(
+            &template,
+            &image,
+            super::MotionModel::Translation,
+            100,
+            1e-7,
+            3,
+        )?;
+        assert!((warp[2] - 4.0).abs() < 0.05, "{warp:?}");
+        assert!((warp[5] + 3.0).abs() < 0.05, "{warp:?}");
+        assert!(rho > 0.99);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ecc_euclidean_and_affine() -> Result<()> {
+        let size = ImageSize {
+            width: 64,
+            height: 64,
+        };
+        let (s, c) = 3f32.to_radians().sin_cos();
+        let template = textured_image(size, |x, y| (x, y))?;
+        // image(R x + t) = template(x)
+        let image = textured_image(size, |u, v| {
+            let (u, v) = (u - 1.0, v - 0.5);
+            (c * u + s * v, -s * u + c * v)
+        })?;
+
+        for motion in [
+            super::MotionModel::Euclidean,
+            super::MotionModel::Affine,
+            super::MotionModel::Homography,
+        ] {
+            let (warp, _) = super::find_transform_ecc(&template, &image, motion, 200, 1e-8, 1)?;
+            let expected = [c, -s, 1.0, s, c, 0.5];
+            for (w, e) in warp.iter().zip(expected.iter()) {
+                assert!((w - e).abs() < 0.02, "{motion:?}: {warp:?}");
+            }
+        }
+
+        Ok(())
+    }
+}
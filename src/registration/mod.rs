@@ -0,0 +1,3 @@
+mod ecc;
+pub(crate) use ecc::Plane;
+pub use ecc::{find_transform_ecc, MotionModel};
@@ -1,6 +1,6 @@
+use crate::error::KorniaError;
 use crate::image::{Image, ImageDtype, ImageSize};
 use crate::interpolation::{interpolate_pixel, meshgrid, InterpolationMode};
-use anyhow::Result;
 use fast_image_resize as fr;
 use ndarray::stack;
 use std::num::NonZeroU32;
@@ -45,13 +45,18 @@ use std::num::NonZeroU32;
 /// assert_eq!(image_resized.size().width, 2);
 /// assert_eq!(image_resized.size().height, 3);
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(src = %image.size(), dst = %new_size))
+)]
 pub fn resize_native<T: ImageDtype, const CHANNELS: usize>(
     image: &Image<T, CHANNELS>,
     new_size: ImageSize,
     interpolation: InterpolationMode,
-) -> Result<Image<T, CHANNELS>> {
+) -> Result<Image<T, CHANNELS>, KorniaError> {
     // create the output image
-    let mut output = Image::from_size_val(new_size, T::default())?;
+    let mut output = Image::from_size_val(new_size, T::default())
+        .map_err(|_| KorniaError::InvalidImageSize(new_size))?;
 
     // create a grid of x and y coordinates for the output image
     // and interpolate the values from the input image.
@@ -91,7 +96,9 @@ pub fn resize_native<T: ImageDtype, const CHANNELS: usize>(
 /// Resize an image to a new size using the [fast_image_resize](https://crates.io/crates/fast_image_resize) crate.
 ///
 /// The function resizes an image to a new size using the specified interpolation mode.
-/// It supports only 3-channel images and u8 data type.
+/// It supports only 3-channel images and u8 data type. The pixels are copied to the
+/// buffer of the resizer, gathering the strided images, e.g. regions of interest, in the
+/// same pass.
 ///
 /// # Arguments
 ///
@@ -131,37 +138,31 @@ pub fn resize_native<T: ImageDtype, const CHANNELS: usize>(
 ///
 /// # Errors
 ///
-/// The function returns an error if the image cannot be resized.
+/// Returns [`KorniaError::InvalidImageSize`] if the input or output size is empty, or
+/// [`KorniaError::Resize`] if the resizer fails.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(src = %image.size(), dst = %new_size))
+)]
 pub fn resize_fast(
     image: &Image<u8, 3>,
     new_size: ImageSize,
     interpolation: InterpolationMode,
-) -> Result<Image<u8, 3>> {
-    let src_width = NonZeroU32::new(image.width() as u32).ok_or(anyhow::anyhow!(
-        "The width of the input image must be greater than zero."
-    ))?;
-    let src_height = NonZeroU32::new(image.height() as u32).ok_or(anyhow::anyhow!(
-        "The height of the input image must be greater than zero."
-    ))?;
-
-    // get the image data as a contiguous slice
-    let image_data = image.data.as_slice().ok_or(anyhow::anyhow!(
-        "The image data must be contiguous and not empty."
-    ))?;
-
-    let src_image = fr::Image::from_vec_u8(
-        src_width,
-        src_height,
-        image_data.to_vec(),
-        fr::PixelType::U8x3,
-    )?;
-
-    let dst_width = NonZeroU32::new(new_size.width as u32).ok_or(anyhow::anyhow!(
-        "The width of the output image must be greater than zero."
-    ))?;
-    let dst_height = NonZeroU32::new(new_size.height as u32).ok_or(anyhow::anyhow!(
-        "The height of the output image must be greater than zero."
-    ))?;
+) -> Result<Image<u8, 3>, KorniaError> {
+    let invalid_src = || KorniaError::InvalidImageSize(image.size());
+    let src_width = NonZeroU32::new(image.width() as u32).ok_or_else(invalid_src)?;
+    let src_height = NonZeroU32::new(image.height() as u32).ok_or_else(invalid_src)?;
+
+    // the resizer takes an owned buffer without a row pitch, so the data is copied once
+    // for all the images, gathering the pixels of the strided ones
+    let image_data = image.as_contiguous_slice().into_owned();
+
+    let src_image = fr::Image::from_vec_u8(src_width, src_height, image_data, fr::PixelType::U8x3)
+        .map_err(|e| KorniaError::Resize(e.to_string()))?;
+
+    let invalid_dst = || KorniaError::InvalidImageSize(new_size);
+    let dst_width = NonZeroU32::new(new_size.width as u32).ok_or_else(invalid_dst)?;
+    let dst_height = NonZeroU32::new(new_size.height as u32).ok_or_else(invalid_dst)?;
 
     let mut dst_image = fr::Image::new(dst_width, dst_height, src_image.pixel_type());
     let mut dst_view = dst_image.view_mut();
@@ -174,10 +175,353 @@ pub fn resize_fast(
             InterpolationMode::Nearest => fr::Resizer::new(fr::ResizeAlg::Nearest),
         }
     };
-    resizer.resize(&src_image.view(), &mut dst_view)?;
+    resizer
+        .resize(&src_image.view(), &mut dst_view)
+        .map_err(|e| KorniaError::Resize(e.to_string()))?;
 
     // TODO: create a new image from the buffer directly from a slice
     Image::new(new_size, dst_image.buffer().to_vec())
+        .map_err(|_| KorniaError::InvalidImageSize(new_size))
+}
+
+/// Compute the gradient energy of an interleaved image buffer.
+fn seam_energy(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let at = |x: usize, y: usize, c: usize| data[(y * width + x) * channels + c];
+
+    let mut energy = vec![0f32; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            let (x0, x1) = (x.saturating_sub(1), (x + 1).min(width - 1));
+            let (y0, y1) = (y.saturating_sub(1), (y + 1).min(height - 1));
+            energy[y * width + x] = (0..channels)
+                .map(|c| (at(x1, y, c) - at(x0, y, c)).abs() + (at(x, y1, c) - at(x, y0, c)).abs())
+                .sum();
+        }
+    }
+
+    energy
+}
+
+/// Remove the vertical seams with the lowest energy until the buffer has `new_width` columns.
+fn carve_columns(
+    mut data: Vec<f32>,
+    mut width: usize,
+    height: usize,
+    channels: usize,
+    new_width: usize,
+) -> Vec<f32> {
+    while width > new_width {
+        let energy = seam_energy(&data, width, height, channels);
+
+        // accumulate the minimum energy of the seams ending at each pixel
+        let mut cost = energy.clone();
+        for y in 1..height {
+            for x in 0..width {
+                let (x0, x1) = (x.saturating_sub(1), (x + 1).min(width - 1));
+                let min_prev = (x0..=x1)
+                    .map(|px| cost[(y - 1) * width + px])
+                    .fold(f32::INFINITY, f32::min);
+                cost[y * width + x] += min_prev;
+            }
+        }
+
+        // backtrack the seam from the bottom row
+        let mut seam = vec![0; height];
+        let last_row = &cost[(height - 1) * width..];
+        seam[height - 1] = (0..width)
+            .min_by(|&a, &b| last_row[a].total_cmp(&last_row[b]))
+            .unwrap_or(0);
+        for y in (0..height - 1).rev() {
+            let x = seam[y + 1];
+            let (x0, x1) = (x.saturating_sub(1), (x + 1).min(width - 1));
+            seam[y] = (x0..=x1)
+                .min_by(|&a, &b| cost[y * width + a].total_cmp(&cost[y * width + b]))
+                .unwrap_or(x);
+        }
+
+        // remove the seam pixels
+        let mut carved = Vec::with_capacity((width - 1) * height * channels);
+        for (y, &seam_x) in seam.iter().enumerate() {
+            let row = &data[y * width * channels..(y + 1) * width * channels];
+            carved.extend_from_slice(&row[..seam_x * channels]);
+            carved.extend_from_slice(&row[(seam_x + 1) * channels..]);
+        }
+
+        data = carved;
+        width -= 1;
+    }
+
+    data
+}
+
+/// Transpose an interleaved image buffer of shape (height, width, channels).
+fn transpose_buffer(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let mut transposed = vec![0f32; data.len()];
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..channels {
+                transposed[(x * height + y) * channels + c] = data[(y * width + x) * channels + c];
+            }
+        }
+    }
+    transposed
+}
+
+/// Resize an image with content-aware seam carving.
+///
+/// The function iteratively removes the connected vertical and horizontal paths of pixels
+/// (seams) with the lowest gradient energy, so that the salient content of the image is
+/// preserved better than with uniform scaling.
+///
+/// NOTE: only reducing the size of the image is supported.
+///
+/// # Arguments
+///
+/// * `image` - The input image container.
+/// * `new_size` - The new size of the image. Must not be larger than the input size.
+///
+/// # Returns
+///
+/// The resized image with the new size.
+///
+/// # Errors
+///
+/// Returns [`KorniaError::Resize`] if the new size is larger than the input size, or
+/// [`KorniaError::InvalidImageSize`] if it is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+///
+/// let image = Image::<u8, 3>::from_size_val(
+///     ImageSize {
+///         width: 8,
+///         height: 6,
+///     },
+///     0,
+/// )
+/// .unwrap();
+///
+/// let carved = kornia_rs::resize::seam_carve(
+///     &image,
+///     ImageSize {
+///         width: 5,
+///         height: 6,
+///     },
+/// )
+/// .unwrap();
+/// assert_eq!(carved.size().width, 5);
+/// assert_eq!(carved.size().height, 6);
+/// ```
+pub fn seam_carve<T: ImageDtype, const CHANNELS: usize>(
+    image: &Image<T, CHANNELS>,
+    new_size: ImageSize,
+) -> Result<Image<T, CHANNELS>, KorniaError> {
+    if new_size.width > image.width() || new_size.height > image.height() {
+        return Err(KorniaError::Resize(format!(
+            "seam carving can only reduce the image size: {} -> {}.",
+            image.size(),
+            new_size
+        )));
+    }
+
+    if new_size.width == 0 || new_size.height == 0 {
+        return Err(KorniaError::InvalidImageSize(new_size));
+    }
+
+    let data = image.data.iter().map(|&v| v.into()).collect::<Vec<f32>>();
+
+    // remove the vertical seams
+    let data = carve_columns(
+        data,
+        image.width(),
+        image.height(),
+        CHANNELS,
+        new_size.width,
+    );
+
+    // remove the horizontal seams by carving the columns of the transposed image
+    let data = transpose_buffer(&data, new_size.width, image.height(), CHANNELS);
+    let data = carve_columns(
+        data,
+        image.height(),
+        new_size.width,
+        CHANNELS,
+        new_size.height,
+    );
+    let data = transpose_buffer(&data, new_size.height, new_size.width, CHANNELS);
+
+    Image::new(new_size, data.into_iter().map(T::from_f32).collect())
+        .map_err(|_| KorniaError::InvalidImageSize(new_size))
+}
+
+/// How [`resize_keep_aspect`] fits an image into a size with a different aspect ratio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fit<T> {
+    /// Scale the whole image into the size and pad the borders with a value (letterbox).
+    Pad(T),
+    /// Scale the image to cover the size and crop the borders in excess.
+    Crop,
+}
+
+/// The transformation applied by [`resize_keep_aspect`].
+///
+/// A point of the original image maps to `p * scale + offset` in the resized image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResizeTransform {
+    /// The scale factor applied to the image.
+    pub scale: f32,
+    /// The horizontal position of the scaled image in the output, negative when cropped.
+    pub offset_x: f32,
+    /// The vertical position of the scaled image in the output, negative when cropped.
+    pub offset_y: f32,
+}
+
+impl ResizeTransform {
+    /// Map a point from the original image to the resized image.
+    pub fn forward(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            x * self.scale + self.offset_x,
+            y * self.scale + self.offset_y,
+        )
+    }
+
+    /// Map a point from the resized image back to the original image.
+    pub fn inverse(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.offset_x) / self.scale,
+            (y - self.offset_y) / self.scale,
+        )
+    }
+}
+
+/// Resize an image to a new size preserving its aspect ratio.
+///
+/// The image is scaled uniformly and centered in the new size, either padding or cropping
+/// the borders. The returned transformation maps the coordinates between the original and
+/// the resized image, e.g. to bring the detections of a model back to the original frame.
+///
+/// # Arguments
+///
+/// * `image` - The input image container.
+/// * `max_size` - The size of the output image.
+/// * `fit` - Whether to pad or crop the borders.
+/// * `interpolation` - The interpolation mode to use.
+///
+/// # Returns
+///
+/// The resized image with the new size and the applied transformation.
+///
+/// # Errors
+///
+/// Returns [`KorniaError::InvalidImageSize`] if the input or output size is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::interpolation::InterpolationMode;
+/// use kornia_rs::resize::{resize_keep_aspect, Fit};
+///
+/// let image = Image::<u8, 3>::from_size_val(
+///     ImageSize {
+///         width: 8,
+///         height: 4,
+///     },
+///     255,
+/// )
+/// .unwrap();
+///
+/// let (letterbox, transform) = resize_keep_aspect(
+///     &image,
+///     ImageSize {
+///         width: 4,
+///         height: 4,
+///     },
+///     Fit::Pad(0),
+///     InterpolationMode::Nearest,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(letterbox.size().width, 4);
+/// assert_eq!(transform.scale, 0.5);
+/// assert_eq!(transform.offset_y, 1.0);
+/// assert_eq!(transform.inverse(2.0, 1.0), (4.0, 0.0));
+/// ```
+pub fn resize_keep_aspect<T: ImageDtype, const CHANNELS: usize>(
+    image: &Image<T, CHANNELS>,
+    max_size: ImageSize,
+    fit: Fit<T>,
+    interpolation: InterpolationMode,
+) -> Result<(Image<T, CHANNELS>, ResizeTransform), KorniaError> {
+    if image.width() == 0 || image.height() == 0 {
+        return Err(KorniaError::InvalidImageSize(image.size()));
+    }
+    if max_size.width == 0 || max_size.height == 0 {
+        return Err(KorniaError::InvalidImageSize(max_size));
+    }
+
+    let scale_x = max_size.width as f32 / image.width() as f32;
+    let scale_y = max_size.height as f32 / image.height() as f32;
+    let scale = match fit {
+        Fit::Pad(_) => scale_x.min(scale_y),
+        Fit::Crop => scale_x.max(scale_y),
+    };
+
+    // the size of the scaled image, rounded to cover the output on the fitted axis
+    let scaled_size = ImageSize {
+        width: ((image.width() as f32 * scale).round() as usize).max(1),
+        height: ((image.height() as f32 * scale).round() as usize).max(1),
+    };
+    let scaled = resize_native(image, scaled_size, interpolation)?;
+
+    let (output, offset_x, offset_y) = match fit {
+        Fit::Pad(value) => {
+            let (width, height) = (
+                scaled_size.width.min(max_size.width),
+                scaled_size.height.min(max_size.height),
+            );
+            let (x0, y0) = ((max_size.width - width) / 2, (max_size.height - height) / 2);
+            let mut output = Image::from_size_val(max_size, value)
+                .map_err(|_| KorniaError::InvalidImageSize(max_size))?;
+            output
+                .data
+                .slice_mut(ndarray::s![y0..y0 + height, x0..x0 + width, ..])
+                .assign(&scaled.data.slice(ndarray::s![..height, ..width, ..]));
+            (output, x0 as f32, y0 as f32)
+        }
+        Fit::Crop => {
+            let (width, height) = (
+                max_size.width.min(scaled_size.width),
+                max_size.height.min(scaled_size.height),
+            );
+            let (x0, y0) = (
+                (scaled_size.width - width) / 2,
+                (scaled_size.height - height) / 2,
+            );
+            let mut output = Image::from_size_val(max_size, T::default())
+                .map_err(|_| KorniaError::InvalidImageSize(max_size))?;
+            output
+                .data
+                .slice_mut(ndarray::s![..height, ..width, ..])
+                .assign(
+                    &scaled
+                        .data
+                        .slice(ndarray::s![y0..y0 + height, x0..x0 + width, ..]),
+                );
+            (output, -(x0 as f32), -(y0 as f32))
+        }
+    };
+
+    Ok((
+        output,
+        ResizeTransform {
+            scale,
+            offset_x,
+            offset_y,
+        },
+    ))
 }
 
 #[cfg(test)]
@@ -269,4 +613,131 @@ mod tests {
         assert_eq!(image_resized.size().height, 3);
         Ok(())
     }
+
+    #[test]
+    fn resize_strided() -> Result<()> {
+        use crate::image::{Image, ImageSize};
+        let size = ImageSize {
+            width: 4,
+            height: 4,
+        };
+        let data = (0..4 * 4 * 3).map(|i| (i * 5) as u8).collect::<Vec<_>>();
+        let planar = Image::<u8, 3>::from_planar(size, data)?;
+        let contiguous = planar.clone().into_contiguous();
+
+        let new_size = ImageSize {
+            width: 2,
+            height: 3,
+        };
+        let interpolation = super::InterpolationMode::Bilinear;
+        assert_eq!(
+            super::resize_fast(&planar, new_size, interpolation)?.data,
+            super::resize_fast(&contiguous, new_size, interpolation)?.data
+        );
+        assert_eq!(
+            super::resize_native(&planar, new_size, interpolation)?.data,
+            super::resize_native(&contiguous, new_size, interpolation)?.data
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn seam_carve_keeps_salient_column() -> Result<()> {
+        use crate::image::{Image, ImageSize};
+        let size = ImageSize {
+            width: 8,
+            height: 4,
+        };
+        let mut image = Image::<u8, 1>::from_size_val(size, 10)?;
+        for y in 0..size.height {
+            image.set_pixel(4, y, 0, 200)?;
+        }
+
+        let carved = super::seam_carve(
+            &image,
+            ImageSize {
+                width: 5,
+                height: 3,
+            },
+        )?;
+        assert_eq!(carved.size().width, 5);
+        assert_eq!(carved.size().height, 3);
+
+        // the bright column must survive in every row
+        for y in 0..carved.height() {
+            assert!((0..carved.width()).any(|x| carved.data[[y, x, 0]] == 200));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn seam_carve_enlarge_fails() -> Result<()> {
+        use crate::error::KorniaError;
+        use crate::image::{Image, ImageSize};
+        let image = Image::<f32, 3>::from_size_val(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            0.0,
+        )?;
+        let res = super::seam_carve(
+            &image,
+            ImageSize {
+                width: 5,
+                height: 4,
+            },
+        );
+        assert!(matches!(res, Err(KorniaError::Resize(_))));
+
+        let empty = ImageSize {
+            width: 0,
+            height: 4,
+        };
+        let res = super::seam_carve(&image, empty);
+        assert!(matches!(res, Err(KorniaError::InvalidImageSize(size)) if size == empty));
+        Ok(())
+    }
+
+    #[test]
+    fn resize_keep_aspect() -> Result<()> {
+        use crate::image::{Image, ImageSize};
+        let image = Image::<f32, 1>::new(
+            ImageSize {
+                width: 4,
+                height: 2,
+            },
+            vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0],
+        )?;
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+
+        // letterbox: scaled to 2x1 and padded vertically
+        let (padded, transform) = super::resize_keep_aspect(
+            &image,
+            size,
+            super::Fit::Pad(-1.0),
+            super::InterpolationMode::Nearest,
+        )?;
+        assert_eq!(padded.data.as_slice().unwrap(), &[0.0, 3.0, -1.0, -1.0]);
+        assert_eq!(transform.scale, 0.5);
+        assert_eq!((transform.offset_x, transform.offset_y), (0.0, 0.0));
+
+        // crop: scaled to 4x2 and cropped horizontally
+        let (cropped, transform) = super::resize_keep_aspect(
+            &image,
+            size,
+            super::Fit::Crop,
+            super::InterpolationMode::Nearest,
+        )?;
+        assert_eq!(cropped.data.as_slice().unwrap(), &[1.0, 2.0, 5.0, 6.0]);
+        assert_eq!(transform.scale, 1.0);
+        assert_eq!(transform.offset_x, -1.0);
+        assert_eq!(transform.inverse(0.0, 1.0), (1.0, 1.0));
+        assert_eq!(transform.forward(1.0, 1.0), (0.0, 1.0));
+        Ok(())
+    }
 }
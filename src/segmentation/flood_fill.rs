@@ -0,0 +1,239 @@
+use crate::geometry::Rect;
+use crate::image::{Image, ImageDtype};
+use anyhow::Result;
+
+/// Pixel connectivity used to grow regions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Connectivity {
+    /// Only the horizontal and vertical neighbours are connected.
+    Four,
+    /// The horizontal, vertical and diagonal neighbours are connected.
+    Eight,
+}
+
+impl Connectivity {
+    /// Get the neighbour offsets (dx, dy) for the connectivity.
+    pub(crate) fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            Connectivity::Eight => &[
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (1, -1),
+                (-1, 1),
+                (-1, -1),
+            ],
+        }
+    }
+}
+
+/// The region filled by [`flood_fill`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloodFillRegion {
+    /// The bounding box of the filled pixels.
+    pub bounding_box: Rect,
+    /// The number of filled pixels.
+    pub area: usize,
+}
+
+/// Fill a connected region of an image with a new value.
+///
+/// The region grows from the seed pixel to all the connected pixels whose value
+/// differs from the seed value by at most `tolerance` in every channel.
+///
+/// # Arguments
+///
+/// * `image` - The image to fill in place.
+/// * `seed` - The (x, y) coordinates of the seed pixel.
+/// * `new_value` - The value to write in the filled pixels.
+/// * `tolerance` - The maximum absolute difference to the seed value per channel.
+/// * `connectivity` - The pixel connectivity used to grow the region.
+///
+/// # Returns
+///
+/// The bounding box and the area of the filled region.
+///
+/// # Errors
+///
+/// Returns an error if the seed is out of the image bounds.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::segmentation::{flood_fill, Connectivity};
+///
+/// let mut image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 3,
+///     },
+///     vec![0, 0, 9, 0, 9, 9, 9, 9, 0],
+/// )
+/// .unwrap();
+///
+/// let region = flood_fill(&mut image, (0, 0), [5], 0.0, Connectivity::Four).unwrap();
+/// assert_eq!(region.area, 3);
+/// assert_eq!(image.data.as_slice().unwrap(), &[5, 5, 9, 5, 9, 9, 9, 9, 0]);
+/// ```
+pub fn flood_fill<T, const CHANNELS: usize>(
+    image: &mut Image<T, CHANNELS>,
+    seed: (usize, usize),
+    new_value: [T; CHANNELS],
+    tolerance: f32,
+    connectivity: Connectivity,
+) -> Result<FloodFillRegion>
+where
+    T: ImageDtype,
+{
+    let (width, height) = (image.width(), image.height());
+    let (seed_x, seed_y) = seed;
+
+    if seed_x >= width || seed_y >= height {
+        return Err(anyhow::anyhow!(
+            "Seed coordinates ({}, {}) out of bounds ({}, {}).",
+            seed_x,
+            seed_y,
+            width,
+            height
+        ));
+    }
+
+    let mut seed_value = [0f32; CHANNELS];
+    for (c, v) in seed_value.iter_mut().enumerate() {
+        *v = image.data[[seed_y, seed_x, c]].into();
+    }
+
+    let is_similar = |image: &Image<T, CHANNELS>, x: usize, y: usize| {
+        (0..CHANNELS).all(|c| {
+            let v: f32 = image.data[[y, x, c]].into();
+            (v - seed_value[c]).abs() <= tolerance
+        })
+    };
+
+    // collect the region first so that the new value does not affect the growth
+    let mut visited = vec![false; width * height];
+    let mut stack = vec![(seed_x, seed_y)];
+    let mut region = Vec::new();
+    visited[seed_y * width + seed_x] = true;
+
+    while let Some((x, y)) = stack.pop() {
+        region.push((x, y));
+
+        for &(dx, dy) in connectivity.offsets() {
+            let nx = x as isize + dx;
+            let ny = y as isize + dy;
+            if nx < 0 || ny < 0 || nx >= width as isize || ny >= height as isize {
+                continue;
+            }
+            let (nx, ny) = (nx as usize, ny as usize);
+            if !visited[ny * width + nx] && is_similar(image, nx, ny) {
+                visited[ny * width + nx] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (seed_x, seed_y, seed_x, seed_y);
+    for &(x, y) in region.iter() {
+        for (c, &v) in new_value.iter().enumerate() {
+            image.data[[y, x, c]] = v;
+        }
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+
+    Ok(FloodFillRegion {
+        bounding_box: Rect {
+            x: min_x,
+            y: min_y,
+            width: max_x - min_x + 1,
+            height: max_y - min_y + 1,
+        },
+        area: region.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Rect;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn flood_fill_connectivity() -> Result<()> {
+        #[rustfmt::skip]
+        let data = vec![
+            0u8, 9, 9, 9,
+            9, 0, 9, 0,
+            0, 9, 9, 0,
+        ];
+        let size = ImageSize {
+            width: 4,
+            height: 3,
+        };
+
+        // with 4-connectivity the seed pixel is isolated
+        let mut image = Image::<u8, 1>::new(size, data.clone())?;
+        let region = super::flood_fill(&mut image, (1, 1), [1], 0.0, super::Connectivity::Four)?;
+        assert_eq!(region.area, 1);
+        assert_eq!(image.get_pixel(1, 1, 0)?, 1);
+
+        // with 8-connectivity it reaches the top-left and bottom-left pixels
+        let mut image = Image::<u8, 1>::new(size, data)?;
+        let region = super::flood_fill(&mut image, (1, 1), [1], 0.0, super::Connectivity::Eight)?;
+        assert_eq!(region.area, 3);
+        assert_eq!(
+            region.bounding_box,
+            Rect {
+                x: 0,
+                y: 0,
+                width: 2,
+                height: 3
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn flood_fill_tolerance() -> Result<()> {
+        let mut image = Image::<f32, 3>::new(
+            ImageSize {
+                width: 3,
+                height: 1,
+            },
+            vec![0.5, 0.5, 0.5, 0.55, 0.5, 0.45, 0.9, 0.5, 0.5],
+        )?;
+        let region = super::flood_fill(
+            &mut image,
+            (0, 0),
+            [0.0, 0.0, 0.0],
+            0.1,
+            super::Connectivity::Four,
+        )?;
+        assert_eq!(region.area, 2);
+        assert_eq!(image.get_pixel(2, 0, 0)?, 0.9);
+        Ok(())
+    }
+
+    #[test]
+    fn flood_fill_out_of_bounds() -> Result<()> {
+        let mut image = Image::<u8, 1>::from_size_val(
+            ImageSize {
+                width: 2,
+                height: 2,
+            },
+            0,
+        )?;
+        assert!(
+            super::flood_fill(&mut image, (2, 0), [1], 0.0, super::Connectivity::Four).is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,625 @@
+use crate::geometry::Rect;
+use crate::image::Image;
+use anyhow::Result;
+
+/// Mask label for a pixel that is definitely background.
+pub const GC_BGD: u8 = 0;
+/// Mask label for a pixel that is definitely foreground.
+pub const GC_FGD: u8 = 1;
+/// Mask label for a pixel that is probably background.
+pub const GC_PR_BGD: u8 = 2;
+/// Mask label for a pixel that is probably foreground.
+pub const GC_PR_FGD: u8 = 3;
+
+// number of gaussian components per color model
+const NUM_COMPONENTS: usize = 5;
+
+// smoothness weight of the pairwise term, following the original paper
+const GAMMA: f64 = 50.0;
+
+// minimum residual capacity considered as non saturated
+const FLOW_EPS: f64 = 1e-9;
+
+/// The initialization of the GrabCut algorithm.
+pub enum GrabCutInit {
+    /// Pixels outside the rectangle are background, pixels inside are probably foreground.
+    Rect(Rect),
+    /// A mask with the labels `GC_BGD`, `GC_FGD`, `GC_PR_BGD` and `GC_PR_FGD`.
+    Mask(Image<u8, 1>),
+}
+
+type Color = [f64; 3];
+
+#[derive(Clone, Default)]
+struct Gaussian {
+    weight: f64,
+    mean: Color,
+    inv_cov: [[f64; 3]; 3],
+    det: f64,
+}
+
+/// A gaussian mixture model over RGB colors.
+struct Gmm {
+    components: Vec<Gaussian>,
+}
+
+impl Gmm {
+    /// Fit the mixture from the samples given the component assignment of each sample.
+    fn fit(samples: &[Color], assignment: &[usize]) -> Self {
+        let mut components = vec![Gaussian::default(); NUM_COMPONENTS];
+
+        for (k, component) in components.iter_mut().enumerate() {
+            let members = samples
+                .iter()
+                .zip(assignment.iter())
+                .filter(|(_, &a)| a == k)
+                .map(|(s, _)| s)
+                .collect::<Vec<_>>();
+
+            if members.is_empty() {
+                continue;
+            }
+
+            let n = members.len() as f64;
+
+            let mut mean = [0.0; 3];
+            for s in members.iter() {
+                for i in 0..3 {
+                    mean[i] += s[i] / n;
+                }
+            }
+
+            // covariance with a small regularization to avoid singular matrices
+            let mut cov = [[0.0; 3]; 3];
+            for s in members.iter() {
+                for i in 0..3 {
+                    for j in 0..3 {
+                        cov[i][j] += (s[i] - mean[i]) * (s[j] - mean[j]) / n;
+                    }
+                }
+            }
+            for (i, row) in cov.iter_mut().enumerate() {
+                row[i] += 0.01;
+            }
+
+            let det = cov[0][0] * (cov[1][1] * cov[2][2] - cov[1][2] * cov[2][1])
+                - cov[0][1] * (cov[1][0] * cov[2][2] - cov[1][2] * cov[2][0])
+                + cov[0][2] * (cov[1][0] * cov[2][1] - cov[1][1] * cov[2][0]);
+
+            let mut inv_cov = [[0.0; 3]; 3];
+            for (i, row) in inv_cov.iter_mut().enumerate() {
+                for (j, val) in row.iter_mut().enumerate() {
+                    // cofactor of (j, i) divided by the determinant
+                    let (r0, r1) = ((j + 1) % 3, (j + 2) % 3);
+                    let (c0, c1) = ((i + 1) % 3, (i + 2) % 3);
+                    *val = (cov[r0][c0] * cov[r1][c1] - cov[r0][c1] * cov[r1][c0]) / det;
+                }
+            }
+
+            *component = Gaussian {
+                weight: n / samples.len() as f64,
+                mean,
+                inv_cov,
+                det,
+            };
+        }
+
+        Self { components }
+    }
+
+    fn component_likelihood(&self, k: usize, color: &Color) -> f64 {
+        let g = &self.components[k];
+        if g.weight <= 0.0 {
+            return 0.0;
+        }
+
+        let d = [
+            color[0] - g.mean[0],
+            color[1] - g.mean[1],
+            color[2] - g.mean[2],
+        ];
+
+        let mut mahalanobis = 0.0;
+        for i in 0..3 {
+            for j in 0..3 {
+                mahalanobis += d[i] * g.inv_cov[i][j] * d[j];
+            }
+        }
+
+        (-0.5 * mahalanobis).exp() / g.det.sqrt()
+    }
+
+    fn likelihood(&self, color: &Color) -> f64 {
+        (0..NUM_COMPONENTS)
+            .map(|k| self.components[k].weight * self.component_likelihood(k, color))
+            .sum()
+    }
+
+    fn most_likely_component(&self, color: &Color) -> usize {
+        (0..NUM_COMPONENTS)
+            .map(|k| (k, self.component_likelihood(k, color)))
+            .fold(
+                (0, f64::MIN),
+                |best, cur| if cur.1 > best.1 { cur } else { best },
+            )
+            .0
+    }
+}
+
+fn squared_distance(a: &Color, b: &Color) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+/// Cluster the samples into `NUM_COMPONENTS` groups with k-means.
+fn kmeans(samples: &[Color]) -> Vec<usize> {
+    // deterministic initialization spreading the centers over the samples
+    let mut centers = (0..NUM_COMPONENTS)
+        .map(|k| samples[k * samples.len() / NUM_COMPONENTS])
+        .collect::<Vec<_>>();
+
+    let mut assignment = vec![0; samples.len()];
+
+    for _ in 0..10 {
+        for (a, s) in assignment.iter_mut().zip(samples.iter()) {
+            *a = (0..NUM_COMPONENTS)
+                .map(|k| (k, squared_distance(s, &centers[k])))
+                .fold(
+                    (0, f64::MAX),
+                    |best, cur| if cur.1 < best.1 { cur } else { best },
+                )
+                .0;
+        }
+
+        for (k, center) in centers.iter_mut().enumerate() {
+            let mut sum = [0.0; 3];
+            let mut count = 0;
+            for (s, _) in samples
+                .iter()
+                .zip(assignment.iter())
+                .filter(|(_, &a)| a == k)
+            {
+                for i in 0..3 {
+                    sum[i] += s[i];
+                }
+                count += 1;
+            }
+            if count > 0 {
+                *center = [
+                    sum[0] / count as f64,
+                    sum[1] / count as f64,
+                    sum[2] / count as f64,
+                ];
+            }
+        }
+    }
+
+    assignment
+}
+
+/// A flow network solved with Dinic's algorithm.
+struct FlowGraph {
+    adj: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    cap: Vec<f64>,
+}
+
+impl FlowGraph {
+    fn new(num_nodes: usize) -> Self {
+        Self {
+            adj: vec![Vec::new(); num_nodes],
+            to: Vec::new(),
+            cap: Vec::new(),
+        }
+    }
+
+    /// Add an edge `u -> v` with capacity `cap` and its reverse with capacity `rev_cap`.
+    fn add_edge(&mut self, u: usize, v: usize, cap: f64, rev_cap: f64) {
+        self.adj[u].push(self.to.len());
+        self.to.push(v);
+        self.cap.push(cap);
+        self.adj[v].push(self.to.len());
+        self.to.push(u);
+        self.cap.push(rev_cap);
+    }
+
+    fn max_flow(&mut self, s: usize, t: usize) -> f64 {
+        let n = self.adj.len();
+        let mut flow = 0.0;
+
+        loop {
+            // build the level graph
+            let mut level = vec![usize::MAX; n];
+            let mut queue = std::collections::VecDeque::new();
+            level[s] = 0;
+            queue.push_back(s);
+            while let Some(u) = queue.pop_front() {
+                for &e in self.adj[u].iter() {
+                    let v = self.to[e];
+                    if self.cap[e] > FLOW_EPS && level[v] == usize::MAX {
+                        level[v] = level[u] + 1;
+                        queue.push_back(v);
+                    }
+                }
+            }
+
+            if level[t] == usize::MAX {
+                break;
+            }
+
+            // find blocking flow with an iterative depth first search
+            let mut next_edge = vec![0; n];
+            loop {
+                let mut path = Vec::new();
+                let mut u = s;
+
+                let found = loop {
+                    if u == t {
+                        break true;
+                    }
+
+                    let mut advanced = false;
+                    while next_edge[u] < self.adj[u].len() {
+                        let e = self.adj[u][next_edge[u]];
+                        let v = self.to[e];
+                        if self.cap[e] > FLOW_EPS && level[v] == level[u] + 1 {
+                            path.push(e);
+                            u = v;
+                            advanced = true;
+                            break;
+                        }
+                        next_edge[u] += 1;
+                    }
+
+                    if !advanced {
+                        // dead end: retreat to the previous node
+                        if u == s {
+                            break false;
+                        }
+                        level[u] = usize::MAX;
+                        let e = path.pop().expect("path must not be empty");
+                        u = self.to[e ^ 1];
+                        next_edge[u] += 1;
+                    }
+                };
+
+                if !found {
+                    break;
+                }
+
+                let bottleneck = path
+                    .iter()
+                    .map(|&e| self.cap[e])
+                    .fold(f64::INFINITY, f64::min);
+
+                for &e in path.iter() {
+                    self.cap[e] -= bottleneck;
+                    self.cap[e ^ 1] += bottleneck;
+                }
+
+                flow += bottleneck;
+            }
+        }
+
+        flow
+    }
+
+    /// Nodes reachable from the source in the residual graph.
+    fn source_side(&self, s: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.adj.len()];
+        let mut stack = vec![s];
+        visited[s] = true;
+        while let Some(u) = stack.pop() {
+            for &e in self.adj[u].iter() {
+                let v = self.to[e];
+                if self.cap[e] > FLOW_EPS && !visited[v] {
+                    visited[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        visited
+    }
+}
+
+/// Run the GrabCut iterations over a flat buffer of colors and update the mask in place.
+fn grabcut_impl(
+    pixels: &[Color],
+    width: usize,
+    height: usize,
+    mask: &mut [u8],
+    iterations: usize,
+) -> Result<()> {
+    let is_fgd = |label: u8| label == GC_FGD || label == GC_PR_FGD;
+
+    // neighbour offsets (dx, dy) to the left, upper-left, up and upper-right pixels
+    let offsets: [(isize, isize); 4] = [(-1, 0), (-1, -1), (0, -1), (1, -1)];
+    let neighbour = |x: usize, y: usize, (dx, dy): (isize, isize)| -> Option<usize> {
+        let nx = x as isize + dx;
+        let ny = y as isize + dy;
+        if nx < 0 || ny < 0 || nx >= width as isize || ny >= height as isize {
+            None
+        } else {
+            Some(ny as usize * width + nx as usize)
+        }
+    };
+
+    // compute beta as the inverse of the expected color difference between neighbours
+    let (mut sum_diff, mut num_diff) = (0.0, 0usize);
+    for y in 0..height {
+        for x in 0..width {
+            for &off in offsets.iter() {
+                if let Some(q) = neighbour(x, y, off) {
+                    sum_diff += squared_distance(&pixels[y * width + x], &pixels[q]);
+                    num_diff += 1;
+                }
+            }
+        }
+    }
+    let beta = if sum_diff <= f64::EPSILON {
+        0.0
+    } else {
+        1.0 / (2.0 * sum_diff / num_diff as f64)
+    };
+
+    // the weight used for the hard constraints is larger than any pairwise sum
+    let lambda = 9.0 * GAMMA;
+
+    // initialize the color models with k-means
+    let split = |mask: &[u8], assignment: &[usize]| {
+        let mut fgd = (Vec::new(), Vec::new());
+        let mut bgd = (Vec::new(), Vec::new());
+        for ((p, &label), &a) in pixels.iter().zip(mask.iter()).zip(assignment.iter()) {
+            let target = if is_fgd(label) { &mut fgd } else { &mut bgd };
+            target.0.push(*p);
+            target.1.push(a);
+        }
+        (fgd, bgd)
+    };
+
+    let (fgd, bgd) = split(mask, &vec![0; pixels.len()]);
+    if fgd.0.is_empty() || bgd.0.is_empty() {
+        return Err(anyhow::anyhow!(
+            "The initial mask must contain both foreground and background pixels."
+        ));
+    }
+
+    let mut fgd_gmm = Gmm::fit(&fgd.0, &kmeans(&fgd.0));
+    let mut bgd_gmm = Gmm::fit(&bgd.0, &kmeans(&bgd.0));
+
+    for iter in 0..iterations {
+        if iter > 0 {
+            // re-assign each pixel to its most likely component and re-learn the models
+            let assignment = pixels
+                .iter()
+                .zip(mask.iter())
+                .map(|(p, &label)| {
+                    if is_fgd(label) {
+                        fgd_gmm.most_likely_component(p)
+                    } else {
+                        bgd_gmm.most_likely_component(p)
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let (fgd, bgd) = split(mask, &assignment);
+            if fgd.0.is_empty() || bgd.0.is_empty() {
+                break;
+            }
+            fgd_gmm = Gmm::fit(&fgd.0, &fgd.1);
+            bgd_gmm = Gmm::fit(&bgd.0, &bgd.1);
+        }
+
+        // build the graph: the source is the foreground and the sink the background
+        let num_pixels = width * height;
+        let (source, sink) = (num_pixels, num_pixels + 1);
+        let mut graph = FlowGraph::new(num_pixels + 2);
+
+        for y in 0..height {
+            for x in 0..width {
+                let p = y * width + x;
+                let color = &pixels[p];
+
+                let (from_source, to_sink) = match mask[p] {
+                    GC_BGD => (0.0, lambda),
+                    GC_FGD => (lambda, 0.0),
+                    _ => (
+                        -(bgd_gmm.likelihood(color).max(f64::MIN_POSITIVE)).ln(),
+                        -(fgd_gmm.likelihood(color).max(f64::MIN_POSITIVE)).ln(),
+                    ),
+                };
+                graph.add_edge(source, p, from_source, 0.0);
+                graph.add_edge(p, sink, to_sink, 0.0);
+
+                for &(dx, dy) in offsets.iter() {
+                    if let Some(q) = neighbour(x, y, (dx, dy)) {
+                        let dist = if dx != 0 && dy != 0 {
+                            std::f64::consts::SQRT_2
+                        } else {
+                            1.0
+                        };
+                        let w = GAMMA / dist * (-beta * squared_distance(color, &pixels[q])).exp();
+                        graph.add_edge(p, q, w, w);
+                    }
+                }
+            }
+        }
+
+        graph.max_flow(source, sink);
+
+        // update only the uncertain pixels
+        let is_source = graph.source_side(source);
+        for (p, label) in mask.iter_mut().enumerate() {
+            if *label == GC_PR_BGD || *label == GC_PR_FGD {
+                *label = if is_source[p] { GC_PR_FGD } else { GC_PR_BGD };
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Segment the foreground of an image with the GrabCut algorithm.
+///
+/// The foreground and background colors are modelled with gaussian mixture models
+/// and the segmentation is refined with graph cuts for the given number of iterations.
+///
+/// # Arguments
+///
+/// * `image` - The input RGB image.
+/// * `init` - A rectangle enclosing the object or a mask with the initial labels.
+/// * `iterations` - The number of refinement iterations.
+///
+/// # Returns
+///
+/// A mask with the labels `GC_BGD`, `GC_FGD`, `GC_PR_BGD` and `GC_PR_FGD`.
+///
+/// # Errors
+///
+/// Returns an error if the initialization does not match the image or if it
+/// does not contain both foreground and background pixels.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Rect;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::segmentation::{grabcut, GrabCutInit};
+///
+/// let image = Image::<u8, 3>::from_size_val(
+///     ImageSize {
+///         width: 8,
+///         height: 8,
+///     },
+///     128,
+/// )
+/// .unwrap();
+///
+/// let rect = Rect {
+///     x: 2,
+///     y: 2,
+///     width: 4,
+///     height: 4,
+/// };
+///
+/// let mask = grabcut(&image, GrabCutInit::Rect(rect), 1).unwrap();
+/// assert_eq!(mask.size(), image.size());
+/// ```
+pub fn grabcut(image: &Image<u8, 3>, init: GrabCutInit, iterations: usize) -> Result<Image<u8, 1>> {
+    let (width, height) = (image.width(), image.height());
+
+    let mut mask = match init {
+        GrabCutInit::Rect(rect) => {
+            if rect.x + rect.width > width || rect.y + rect.height > height {
+                return Err(anyhow::anyhow!(
+                    "The rectangle {:?} is out of the image bounds ({}, {}).",
+                    rect,
+                    width,
+                    height
+                ));
+            }
+            let mut mask = vec![GC_BGD; width * height];
+            for y in rect.y..rect.y + rect.height {
+                for x in rect.x..rect.x + rect.width {
+                    mask[y * width + x] = GC_PR_FGD;
+                }
+            }
+            mask
+        }
+        GrabCutInit::Mask(mask) => {
+            if mask.size() != image.size() {
+                return Err(anyhow::anyhow!(
+                    "The mask size {} does not match the image size {}.",
+                    mask.size(),
+                    image.size()
+                ));
+            }
+            if mask.data.iter().any(|&v| v > GC_PR_FGD) {
+                return Err(anyhow::anyhow!("The mask contains invalid labels."));
+            }
+            mask.data.iter().copied().collect()
+        }
+    };
+
+    let pixels = image
+        .data
+        .rows()
+        .into_iter()
+        .map(|p| [p[0] as f64, p[1] as f64, p[2] as f64])
+        .collect::<Vec<_>>();
+
+    grabcut_impl(&pixels, width, height, &mut mask, iterations)?;
+
+    Image::new(image.size(), mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Rect;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn grabcut_square() -> Result<()> {
+        // a red square over a blue background
+        let size = ImageSize {
+            width: 20,
+            height: 20,
+        };
+        let mut image = Image::<u8, 3>::from_size_val(size, 0)?;
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let color = if (7..13).contains(&x) && (7..13).contains(&y) {
+                    [220, 20, 20]
+                } else {
+                    [20, 20, 220]
+                };
+                for (c, v) in color.iter().enumerate() {
+                    image.set_pixel(x, y, c, *v)?;
+                }
+            }
+        }
+
+        let rect = Rect {
+            x: 4,
+            y: 4,
+            width: 12,
+            height: 12,
+        };
+
+        let mask = super::grabcut(&image, super::GrabCutInit::Rect(rect), 2)?;
+
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let label = mask.get_pixel(x, y, 0)?;
+                if !rect.contains(x, y) {
+                    assert_eq!(label, super::GC_BGD);
+                } else if (7..13).contains(&x) && (7..13).contains(&y) {
+                    assert_eq!(label, super::GC_PR_FGD);
+                } else {
+                    assert_eq!(label, super::GC_PR_BGD);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn grabcut_invalid_rect() -> Result<()> {
+        let image = Image::<u8, 3>::from_size_val(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            0,
+        )?;
+        let rect = Rect {
+            x: 2,
+            y: 2,
+            width: 4,
+            height: 4,
+        };
+        assert!(super::grabcut(&image, super::GrabCutInit::Rect(rect), 1).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+mod flood_fill;
+mod grabcut;
+
+pub use flood_fill::{flood_fill, Connectivity, FloodFillRegion};
+pub use grabcut::{grabcut, GrabCutInit, GC_BGD, GC_FGD, GC_PR_BGD, GC_PR_FGD};
@@ -0,0 +1,352 @@
+use crate::geometry::{Point2, RotatedBox};
+use anyhow::Result;
+
+/// Cross product of the vectors `o -> a` and `o -> b`.
+fn cross(o: &Point2, a: &Point2, b: &Point2) -> f32 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+/// Compute the convex hull of a set of points.
+///
+/// The hull is computed with the monotone chain algorithm and collinear points
+/// on the hull edges are discarded.
+///
+/// # Arguments
+///
+/// * `points` - The input points.
+///
+/// # Returns
+///
+/// The vertices of the convex hull in counter-clockwise order (in a y-up frame),
+/// starting from the point with the lowest x coordinate.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Point2;
+/// use kornia_rs::shape::convex_hull;
+///
+/// let points = vec![
+///     Point2::new(0.0, 0.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(1.0, 1.0),
+///     Point2::new(2.0, 2.0),
+///     Point2::new(0.0, 2.0),
+/// ];
+///
+/// let hull = convex_hull(&points);
+/// assert_eq!(hull.len(), 4);
+/// ```
+pub fn convex_hull(points: &[Point2]) -> Vec<Point2> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut hull: Vec<Point2> = Vec::with_capacity(2 * sorted.len());
+
+    // lower hull
+    for p in sorted.iter() {
+        while hull.len() >= 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    // upper hull
+    let lower_len = hull.len() + 1;
+    for p in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower_len
+            && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    // the last point is the same as the first one
+    hull.pop();
+
+    hull
+}
+
+/// Compute the rotated rectangle of minimum area enclosing a set of points.
+///
+/// The rectangle is found with the rotating calipers method: one of its sides
+/// is collinear with an edge of the convex hull of the points.
+///
+/// # Arguments
+///
+/// * `points` - The input points.
+///
+/// # Returns
+///
+/// The rotated box with the angle in degrees in the range (-90, 90].
+///
+/// # Errors
+///
+/// Returns an error if the set of points is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Point2;
+/// use kornia_rs::shape::min_area_rect;
+///
+/// let points = vec![
+///     Point2::new(1.0, 1.0),
+///     Point2::new(5.0, 1.0),
+///     Point2::new(5.0, 3.0),
+///     Point2::new(1.0, 3.0),
+/// ];
+///
+/// let rbox = min_area_rect(&points).unwrap();
+/// assert_eq!(rbox.cx, 3.0);
+/// assert_eq!(rbox.cy, 2.0);
+/// assert_eq!(rbox.area(), 8.0);
+/// ```
+pub fn min_area_rect(points: &[Point2]) -> Result<RotatedBox> {
+    let hull = convex_hull(points);
+
+    match hull.len() {
+        0 => return Err(anyhow::anyhow!("The set of points is empty.")),
+        1 => {
+            return Ok(RotatedBox {
+                cx: hull[0].x,
+                cy: hull[0].y,
+                ..Default::default()
+            })
+        }
+        _ => {}
+    }
+
+    let mut best: Option<(f32, RotatedBox)> = None;
+
+    for i in 0..hull.len() {
+        let p0 = hull[i];
+        let p1 = hull[(i + 1) % hull.len()];
+
+        // unit vectors along the edge and its normal
+        let len = p0.distance(&p1);
+        if len == 0.0 {
+            continue;
+        }
+        let (ux, uy) = ((p1.x - p0.x) / len, (p1.y - p0.y) / len);
+        let (vx, vy) = (-uy, ux);
+
+        // extent of the hull projected onto both axes
+        let (mut min_u, mut max_u, mut min_v, mut max_v) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+        for p in hull.iter() {
+            let u = (p.x - p0.x) * ux + (p.y - p0.y) * uy;
+            let v = (p.x - p0.x) * vx + (p.y - p0.y) * vy;
+            min_u = min_u.min(u);
+            max_u = max_u.max(u);
+            min_v = min_v.min(v);
+            max_v = max_v.max(v);
+        }
+
+        let (width, height) = (max_u - min_u, max_v - min_v);
+        let area = width * height;
+
+        let is_better = match &best {
+            Some((best_area, _)) => area < *best_area,
+            None => true,
+        };
+
+        if is_better {
+            let (mu, mv) = ((min_u + max_u) / 2.0, (min_v + max_v) / 2.0);
+
+            // keep the angle in the range (-90, 90]
+            let mut angle = uy.atan2(ux).to_degrees();
+            if angle <= -90.0 {
+                angle += 180.0;
+            } else if angle > 90.0 {
+                angle -= 180.0;
+            }
+
+            best = Some((
+                area,
+                RotatedBox {
+                    cx: p0.x + mu * ux + mv * vx,
+                    cy: p0.y + mu * uy + mv * vy,
+                    width,
+                    height,
+                    angle,
+                },
+            ));
+        }
+    }
+
+    best.map(|(_, rbox)| rbox)
+        .ok_or_else(|| anyhow::anyhow!("Failed to compute the minimum area rectangle."))
+}
+
+/// Smallest circle passing through two points.
+fn circle_from_two(a: &Point2, b: &Point2) -> (Point2, f32) {
+    let center = Point2::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+    (center, a.distance(b) / 2.0)
+}
+
+/// Circle passing through three points, or the widest two-point circle if they are collinear.
+fn circle_from_three(a: &Point2, b: &Point2, c: &Point2) -> (Point2, f32) {
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    if d.abs() < f32::EPSILON {
+        return [
+            circle_from_two(a, b),
+            circle_from_two(a, c),
+            circle_from_two(b, c),
+        ]
+        .into_iter()
+        .fold((Point2::default(), f32::MIN), |best, cur| {
+            if cur.1 > best.1 {
+                cur
+            } else {
+                best
+            }
+        });
+    }
+
+    let (a2, b2, c2) = (
+        a.x * a.x + a.y * a.y,
+        b.x * b.x + b.y * b.y,
+        c.x * c.x + c.y * c.y,
+    );
+    let center = Point2::new(
+        (a2 * (b.y - c.y) + b2 * (c.y - a.y) + c2 * (a.y - b.y)) / d,
+        (a2 * (c.x - b.x) + b2 * (a.x - c.x) + c2 * (b.x - a.x)) / d,
+    );
+    (center, center.distance(a))
+}
+
+/// Compute the circle of minimum radius enclosing a set of points.
+///
+/// The circle is computed with the incremental Welzl algorithm.
+///
+/// # Arguments
+///
+/// * `points` - The input points.
+///
+/// # Returns
+///
+/// The center and the radius of the circle.
+///
+/// # Errors
+///
+/// Returns an error if the set of points is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Point2;
+/// use kornia_rs::shape::min_enclosing_circle;
+///
+/// let points = vec![
+///     Point2::new(0.0, 0.0),
+///     Point2::new(4.0, 0.0),
+///     Point2::new(2.0, 1.0),
+/// ];
+///
+/// let (center, radius) = min_enclosing_circle(&points).unwrap();
+/// assert_eq!(center, Point2::new(2.0, 0.0));
+/// assert_eq!(radius, 2.0);
+/// ```
+pub fn min_enclosing_circle(points: &[Point2]) -> Result<(Point2, f32)> {
+    if points.is_empty() {
+        return Err(anyhow::anyhow!("The set of points is empty."));
+    }
+
+    // only the hull vertices can lie on the circle
+    let hull = convex_hull(points);
+
+    // small tolerance to absorb rounding errors
+    let inside = |circle: &(Point2, f32), p: &Point2| circle.0.distance(p) <= circle.1 + 1e-4;
+
+    let mut circle = (hull[0], 0.0);
+    for i in 1..hull.len() {
+        if inside(&circle, &hull[i]) {
+            continue;
+        }
+        circle = (hull[i], 0.0);
+        for j in 0..i {
+            if inside(&circle, &hull[j]) {
+                continue;
+            }
+            circle = circle_from_two(&hull[i], &hull[j]);
+            for p in hull[..j].iter() {
+                if !inside(&circle, p) {
+                    circle = circle_from_three(&hull[i], &hull[j], p);
+                }
+            }
+        }
+    }
+
+    Ok(circle)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point2;
+    use anyhow::Result;
+
+    #[test]
+    fn convex_hull_square() {
+        let points = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 0.5),
+            Point2::new(2.0, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(0.0, 2.0),
+            Point2::new(1.0, 1.0),
+        ];
+        let hull = super::convex_hull(&points);
+        assert_eq!(
+            hull,
+            vec![
+                Point2::new(0.0, 0.0),
+                Point2::new(2.0, 0.0),
+                Point2::new(2.0, 2.0),
+                Point2::new(0.0, 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn min_area_rect_rotated() -> Result<()> {
+        // a square of side sqrt(2) rotated by 45 degrees
+        let points = vec![
+            Point2::new(1.0, 0.0),
+            Point2::new(2.0, 1.0),
+            Point2::new(1.0, 2.0),
+            Point2::new(0.0, 1.0),
+            Point2::new(1.0, 1.0),
+        ];
+        let rbox = super::min_area_rect(&points)?;
+        assert!((rbox.cx - 1.0).abs() < 1e-5);
+        assert!((rbox.cy - 1.0).abs() < 1e-5);
+        assert!((rbox.width - std::f32::consts::SQRT_2).abs() < 1e-5);
+        assert!((rbox.height - std::f32::consts::SQRT_2).abs() < 1e-5);
+        assert!((rbox.angle.abs() - 45.0).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn min_enclosing_circle_square() -> Result<()> {
+        let points = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(0.0, 2.0),
+            Point2::new(1.0, 1.5),
+        ];
+        let (center, radius) = super::min_enclosing_circle(&points)?;
+        assert!((center.x - 1.0).abs() < 1e-5);
+        assert!((center.y - 1.0).abs() < 1e-5);
+        assert!((radius - std::f32::consts::SQRT_2).abs() < 1e-5);
+        assert!(super::min_enclosing_circle(&[]).is_err());
+        Ok(())
+    }
+}
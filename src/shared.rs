@@ -0,0 +1,109 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A value shared among several owners with copy-on-write semantics.
+///
+/// Cloning a shared value only increments a reference count, so that a frame can be
+/// handed to several consumers, e.g. a display, a writer and an analytics thread,
+/// without copying its data. The value is read through [`Deref`] and copied by
+/// [`Shared::make_mut`] only when it is modified while still shared.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize, SharedImage};
+///
+/// let image = Image::<u8, 1>::from_size_val(
+///     ImageSize {
+///         width: 2,
+///         height: 1,
+///     },
+///     0,
+/// )
+/// .unwrap();
+///
+/// let frame = SharedImage::new(image);
+/// let mut consumer = frame.clone();
+/// assert!(SharedImage::ptr_eq(&frame, &consumer));
+///
+/// // the first write copies the image for the consumer
+/// consumer.make_mut().data[[0, 0, 0]] = 255;
+/// assert_eq!(frame.data[[0, 0, 0]], 0);
+/// assert_eq!(consumer.data[[0, 0, 0]], 255);
+/// ```
+pub struct Shared<T>(Arc<T>);
+
+impl<T> Shared<T> {
+    /// Create a new shared value.
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Get the number of owners of the value.
+    pub fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+
+    /// Check if two shared values point to the same data.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T: Clone> Shared<T> {
+    /// Get a mutable reference to the value, copying it first if it is shared.
+    pub fn make_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Take the value out, copying it only if it is shared.
+    pub fn into_inner(self) -> T {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Shared<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Shared;
+    use crate::tensor::{CpuAllocator, SharedTensor, Tensor};
+
+    #[test]
+    fn copy_on_write() -> anyhow::Result<()> {
+        let tensor = Tensor::<u8, 1>::from_shape_vec([3], vec![1, 2, 3], CpuAllocator)?;
+        let mut a = SharedTensor::new(tensor);
+        let b = a.clone();
+        assert_eq!(Shared::strong_count(&a), 2);
+
+        // a unique value is modified in place
+        let mut c = Shared::new(vec![1, 2]);
+        let ptr = c.as_ptr();
+        c.make_mut()[0] = 3;
+        assert_eq!(c.as_ptr(), ptr);
+
+        a.make_mut().as_slice_mut()[0] = 9;
+        assert!(!Shared::ptr_eq(&a, &b));
+        assert_eq!(a.as_slice(), &[9, 2, 3]);
+        assert_eq!(b.into_inner().as_slice(), &[1, 2, 3]);
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+use crate::image::{Image, ImageDtype};
+use anyhow::Result;
+use rayon::prelude::*;
+
+/// Get the contiguous pixel data of an image, or an error if it is empty.
+fn pixels<T: ImageDtype, const CHANNELS: usize>(
+    image: &Image<T, CHANNELS>,
+) -> Result<std::borrow::Cow<'_, [T]>> {
+    if image.width() == 0 || image.height() == 0 {
+        return Err(anyhow::anyhow!("Empty image"));
+    }
+    Ok(image.as_contiguous_slice())
+}
+
+/// Compute the mean and the standard deviation of each channel of an image.
+///
+/// The standard deviation is the population one, i.e. normalized by the number of pixels.
+/// The sums are accumulated in parallel over the rows of the image.
+///
+/// # Arguments
+///
+/// * `image` - The input image with shape (H, W, C).
+///
+/// # Returns
+///
+/// The mean and the standard deviation of each channel.
+///
+/// # Errors
+///
+/// Returns an error if the image is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::stats::mean_std;
+///
+/// let image = Image::<u8, 2>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 1,
+///     },
+///     vec![0, 10, 4, 10],
+/// )
+/// .unwrap();
+///
+/// let (mean, std) = mean_std(&image).unwrap();
+/// assert_eq!(mean, [2.0, 10.0]);
+/// assert_eq!(std, [2.0, 0.0]);
+/// ```
+pub fn mean_std<T: ImageDtype, const CHANNELS: usize>(
+    image: &Image<T, CHANNELS>,
+) -> Result<([f32; CHANNELS], [f32; CHANNELS])> {
+    let data = pixels(image)?;
+
+    let (sum, sum_sq) = data
+        .par_chunks(image.width() * CHANNELS)
+        .map(|row| {
+            let (mut sum, mut sum_sq) = ([0f64; CHANNELS], [0f64; CHANNELS]);
+            for pixel in row.chunks_exact(CHANNELS) {
+                for (c, &value) in pixel.iter().enumerate() {
+                    let value = value.into() as f64;
+                    sum[c] += value;
+                    sum_sq[c] += value * value;
+                }
+            }
+            (sum, sum_sq)
+        })
+        .reduce(
+            || ([0f64; CHANNELS], [0f64; CHANNELS]),
+            |(mut sum, mut sum_sq), (row_sum, row_sum_sq)| {
+                for c in 0..CHANNELS {
+                    sum[c] += row_sum[c];
+                    sum_sq[c] += row_sum_sq[c];
+                }
+                (sum, sum_sq)
+            },
+        );
+
+    let n = (image.width() * image.height()) as f64;
+    let mean = sum.map(|s| s / n);
+    let mut std = [0f32; CHANNELS];
+    for (c, s) in std.iter_mut().enumerate() {
+        *s = (sum_sq[c] / n - mean[c] * mean[c]).max(0.0).sqrt() as f32;
+    }
+
+    Ok((mean.map(|m| m as f32), std))
+}
+
+/// Find the minimum and maximum values of each channel of an image.
+///
+/// # Arguments
+///
+/// * `image` - The input image with shape (H, W, C).
+///
+/// # Returns
+///
+/// The minimum and the maximum of each channel.
+///
+/// # Errors
+///
+/// Returns an error if the image is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::stats::min_max;
+///
+/// let image = Image::<u8, 2>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 1,
+///     },
+///     vec![5, 1, 2, 7, 9, 3],
+/// )
+/// .unwrap();
+///
+/// let (min, max) = min_max(&image).unwrap();
+/// assert_eq!(min, [2, 1]);
+/// assert_eq!(max, [9, 7]);
+/// ```
+pub fn min_max<T, const CHANNELS: usize>(
+    image: &Image<T, CHANNELS>,
+) -> Result<([T; CHANNELS], [T; CHANNELS])>
+where
+    T: ImageDtype + PartialOrd,
+{
+    let data = pixels(image)?;
+
+    let mut first = [T::default(); CHANNELS];
+    first.copy_from_slice(&data[..CHANNELS]);
+
+    let bounds = data
+        .par_chunks(image.width() * CHANNELS)
+        .map(|row| {
+            let (mut min, mut max) = (first, first);
+            for pixel in row.chunks_exact(CHANNELS) {
+                for (c, &value) in pixel.iter().enumerate() {
+                    if value < min[c] {
+                        min[c] = value;
+                    }
+                    if value > max[c] {
+                        max[c] = value;
+                    }
+                }
+            }
+            (min, max)
+        })
+        .reduce(
+            || (first, first),
+            |(mut min, mut max), (row_min, row_max)| {
+                for c in 0..CHANNELS {
+                    if row_min[c] < min[c] {
+                        min[c] = row_min[c];
+                    }
+                    if row_max[c] > max[c] {
+                        max[c] = row_max[c];
+                    }
+                }
+                (min, max)
+            },
+        );
+
+    Ok(bounds)
+}
+
+/// Compute a percentile of each channel of an image.
+///
+/// The percentile is linearly interpolated between the two closest ranks, so that the
+/// 0th, 50th and 100th percentiles are the minimum, the median and the maximum.
+///
+/// # Arguments
+///
+/// * `image` - The input image with shape (H, W, C).
+/// * `q` - The percentile to compute in the range [0, 100].
+///
+/// # Returns
+///
+/// The percentile of each channel.
+///
+/// # Errors
+///
+/// Returns an error if the image is empty or the percentile is out of range.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::stats::percentile;
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 5,
+///         height: 1,
+///     },
+///     vec![50, 10, 40, 20, 30],
+/// )
+/// .unwrap();
+///
+/// assert_eq!(percentile(&image, 50.0).unwrap(), [30.0]);
+/// assert_eq!(percentile(&image, 10.0).unwrap(), [14.0]);
+/// ```
+pub fn percentile<T: ImageDtype, const CHANNELS: usize>(
+    image: &Image<T, CHANNELS>,
+    q: f32,
+) -> Result<[f32; CHANNELS]> {
+    if !(0.0..=100.0).contains(&q) {
+        return Err(anyhow::anyhow!(
+            "The percentile {} must be in the range [0, 100].",
+            q
+        ));
+    }
+    let data = pixels(image)?;
+
+    // sort the values of each channel in parallel
+    let channels = (0..CHANNELS)
+        .into_par_iter()
+        .map(|c| {
+            let mut values = data
+                .iter()
+                .skip(c)
+                .step_by(CHANNELS)
+                .map(|&v| v.into())
+                .collect::<Vec<f32>>();
+            values.sort_unstable_by(|a, b| a.total_cmp(b));
+            values
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = [0f32; CHANNELS];
+    for (out, values) in result.iter_mut().zip(channels.iter()) {
+        let rank = q / 100.0 * (values.len() - 1) as f32;
+        let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+        let frac = rank - lo as f32;
+        *out = values[lo] + (values[hi] - values[lo]) * frac;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn stats_channels() -> Result<()> {
+        let image = Image::<f32, 3>::new(
+            ImageSize {
+                width: 2,
+                height: 2,
+            },
+            vec![
+                1.0, -2.0, 0.5, 3.0, -2.0, 0.5, 5.0, -2.0, 0.5, 7.0, -2.0, 4.5,
+            ],
+        )?;
+
+        let (mean, std) = super::mean_std(&image)?;
+        assert_eq!(mean, [4.0, -2.0, 1.5]);
+        assert!((std[0] - 5f32.sqrt()).abs() < 1e-6);
+        assert_eq!(std[1], 0.0);
+        assert!((std[2] - 3f32.sqrt()).abs() < 1e-6);
+
+        let (min, max) = super::min_max(&image)?;
+        assert_eq!(min, [1.0, -2.0, 0.5]);
+        assert_eq!(max, [7.0, -2.0, 4.5]);
+
+        assert_eq!(super::percentile(&image, 0.0)?, [1.0, -2.0, 0.5]);
+        assert_eq!(super::percentile(&image, 100.0)?, [7.0, -2.0, 4.5]);
+        assert_eq!(super::percentile(&image, 50.0)?, [4.0, -2.0, 0.5]);
+        assert!(super::percentile(&image, 101.0).is_err());
+
+        let empty = Image::<f32, 3>::new(
+            ImageSize {
+                width: 0,
+                height: 0,
+            },
+            vec![],
+        )?;
+        assert!(super::mean_std(&empty).is_err());
+        Ok(())
+    }
+}
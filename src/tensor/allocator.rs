@@ -9,19 +9,27 @@ pub enum TensorAllocatorError {
 
     #[error("Null pointer")]
     NullPointer,
+
+    #[error("The buffer is not aligned for the tensor data type")]
+    Misaligned,
 }
 
 /// A trait for allocating and deallocating memory for tensors.
 ///
 /// # Safety
 ///
-/// The tensor allocator must be thread-safe.
+/// The tensor allocator must be thread-safe, since the tensor storage returns its
+/// memory to the allocator from the thread dropping the last reference.
+///
+/// The storage keeps a clone of the allocator in the deallocation of its arrow buffer,
+/// which arrow requires to be `Send + Sync + RefUnwindSafe` and `'static`, hence the
+/// bounds of the trait.
 ///
 /// # Methods
 ///
 /// * `alloc` - Allocates memory for a tensor with the given layout.
 /// * `dealloc` - Deallocates memory for a tensor with the given layout.
-pub trait TensorAllocator: Clone {
+pub trait TensorAllocator: Clone + Send + Sync + std::panic::RefUnwindSafe + 'static {
     fn alloc(&self, layout: Layout) -> Result<*mut u8, TensorAllocatorError>;
     fn dealloc(&self, ptr: *mut u8, layout: Layout);
 }
@@ -2,6 +2,7 @@ use thiserror::Error;
 
 use super::{
     allocator::{CpuAllocator, TensorAllocator, TensorAllocatorError},
+    layout::{LayoutError, TensorLayout},
     storage::TensorStorage,
 };
 
@@ -15,25 +16,9 @@ pub enum TensorError {
 
     #[error("Error with the tensor storage: {0}")]
     StorageError(#[from] TensorAllocatorError),
-}
 
-/// Compute the strides from the shape of a tensor.
-///
-/// # Arguments
-///
-/// * `shape` - The shape of the tensor.
-///
-/// # Returns
-///
-/// * `strides` - The strides of the tensor.
-fn get_strides_from_shape<const N: usize>(shape: [usize; N]) -> [usize; N] {
-    let mut strides: [usize; N] = [0; N];
-    let mut stride = 1;
-    for i in (0..shape.len()).rev() {
-        strides[i] = stride;
-        stride *= shape[i];
-    }
-    strides
+    #[error("Invalid tensor layout: {0}")]
+    InvalidLayout(#[from] LayoutError),
 }
 
 /// A data structure to represent a multi-dimensional tensor.
@@ -81,13 +66,15 @@ where
     ///
     /// A new `Tensor` instance with uninitialized data.
     pub fn new_uninitialized(shape: [usize; N], alloc: A) -> Result<Self, TensorError> {
-        let numel = shape.iter().product::<usize>();
-        let strides = get_strides_from_shape(shape);
+        let layout = TensorLayout::contiguous(shape);
+        let numel = layout
+            .numel()
+            .ok_or_else(|| LayoutError::Overflow(shape.to_vec()))?;
         let storage = TensorStorage::new(numel, alloc)?;
         Ok(Tensor {
             storage,
             shape,
-            strides,
+            strides: layout.strides,
         })
     }
 
@@ -140,16 +127,56 @@ where
     /// assert_eq!(t.shape, [2, 2]);
     ///```
     pub fn from_shape_vec(shape: [usize; N], data: Vec<T>, alloc: A) -> Result<Self, TensorError> {
-        let numel = shape.iter().product::<usize>();
-        if numel != data.len() {
-            Err(TensorError::InvalidShape(numel))?;
-        }
+        let layout = TensorLayout::contiguous(shape);
+        layout.validate(data.len())?;
         let storage = TensorStorage::from_vec(data, alloc)?;
-        let strides = get_strides_from_shape(shape);
         Ok(Tensor {
             storage,
             shape,
-            strides,
+            strides: layout.strides,
+        })
+    }
+
+    /// Creates a new `Tensor` with the given shape sharing an arrow buffer.
+    ///
+    /// The data is not copied, e.g. to wrap the buffers received through Arrow IPC.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - An array containing the shape of the tensor.
+    /// * `buffer` - The buffer containing the data of the tensor.
+    /// * `alloc` - The allocator to use.
+    ///
+    /// # Returns
+    ///
+    /// A new `Tensor` instance.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is misaligned for `T` or does not match the shape of the tensor,
+    /// an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kornia_rs::tensor::{Tensor, CpuAllocator};
+    ///
+    /// let buffer = arrow_buffer::Buffer::from_vec(vec![1u8, 2, 3, 4]);
+    /// let t = Tensor::<u8, 2>::from_shape_buffer([2, 2], buffer, CpuAllocator).unwrap();
+    /// assert_eq!(t.as_slice(), &[1, 2, 3, 4]);
+    ///```
+    pub fn from_shape_buffer(
+        shape: [usize; N],
+        buffer: arrow_buffer::Buffer,
+        alloc: A,
+    ) -> Result<Self, TensorError> {
+        let storage = TensorStorage::from_buffer(buffer, alloc)?;
+        let layout = TensorLayout::contiguous(shape);
+        layout.validate(storage.data.len() / std::mem::size_of::<T>())?;
+        Ok(Tensor {
+            storage,
+            shape,
+            strides: layout.strides,
         })
     }
 
@@ -233,7 +260,7 @@ where
             })
             .collect();
         let storage = TensorStorage::from_vec(data, alloc).unwrap();
-        let strides = get_strides_from_shape(shape);
+        let strides = TensorLayout::contiguous(shape).strides;
         Tensor {
             storage,
             shape,
@@ -243,7 +270,27 @@ where
 
     /// Returns the number of elements in the tensor.
     pub fn numel(&self) -> usize {
-        self.storage.data.len()
+        self.as_slice().len()
+    }
+
+    /// Get the layout of the tensor.
+    pub fn layout(&self) -> TensorLayout<N> {
+        TensorLayout {
+            shape: self.shape,
+            strides: self.strides,
+        }
+    }
+
+    /// Check that the shape and the strides of the tensor are consistent with its data.
+    ///
+    /// The fields of the tensor are public, so the check should be done after modifying
+    /// them by hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout does not match the data, see [`TensorLayout::validate`].
+    pub fn validate(&self) -> Result<(), TensorError> {
+        Ok(self.layout().validate(self.numel())?)
     }
 
     // TODO: find a better name
@@ -356,17 +403,13 @@ where
         self,
         shape: [usize; M],
     ) -> Result<Tensor<T, M, A>, TensorError> {
-        let numel = shape.iter().product::<usize>();
-        if numel != self.storage.data.len() {
-            Err(TensorError::InvalidShape(numel))?;
-        }
-
-        let strides = get_strides_from_shape(shape);
+        let layout = TensorLayout::contiguous(shape);
+        layout.validate(self.numel())?;
 
         Ok(Tensor {
             storage: self.storage,
             shape,
-            strides,
+            strides: layout.strides,
         })
     }
 
@@ -0,0 +1,190 @@
+use thiserror::Error;
+
+/// An error describing an inconsistent tensor layout.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    #[error("The number of elements of the shape {0:?} overflows")]
+    Overflow(Vec<usize>),
+
+    #[error("The shape {shape:?} has {numel} elements but the data has {len}")]
+    LengthMismatch {
+        shape: Vec<usize>,
+        numel: usize,
+        len: usize,
+    },
+
+    #[error(
+        "The strides {strides:?} reach the offset {max_offset} out of the data of length {len}"
+    )]
+    StridesOutOfBounds {
+        strides: Vec<usize>,
+        max_offset: usize,
+        len: usize,
+    },
+}
+
+/// The shape and the strides of a tensor, in elements.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::tensor::TensorLayout;
+///
+/// let layout = TensorLayout::contiguous([2, 3]);
+/// assert_eq!(layout.strides, [3, 1]);
+/// assert!(layout.validate(6).is_ok());
+/// assert!(layout.validate(5).is_err());
+///
+/// // the strides of a transposed view address the same data
+/// let transposed = TensorLayout {
+///     shape: [3, 2],
+///     strides: [1, 3],
+/// };
+/// assert!(transposed.validate(6).is_ok());
+/// assert!(!transposed.is_contiguous());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TensorLayout<const N: usize> {
+    /// The size of each dimension.
+    pub shape: [usize; N],
+    /// The distance in elements between two consecutive indices of each dimension.
+    pub strides: [usize; N],
+}
+
+impl<const N: usize> TensorLayout<N> {
+    /// Create the row-major contiguous layout of a shape.
+    pub fn contiguous(shape: [usize; N]) -> Self {
+        let mut strides = [0; N];
+        let mut stride = 1usize;
+        for i in (0..N).rev() {
+            strides[i] = stride;
+            stride = stride.saturating_mul(shape[i]);
+        }
+        Self { shape, strides }
+    }
+
+    /// Get the number of elements of the shape, or `None` if it overflows.
+    pub fn numel(&self) -> Option<usize> {
+        self.shape
+            .iter()
+            .try_fold(1usize, |acc, &dim| acc.checked_mul(dim))
+    }
+
+    /// Check if the layout is row-major contiguous.
+    pub fn is_contiguous(&self) -> bool {
+        *self == Self::contiguous(self.shape)
+    }
+
+    /// Check that the layout describes exactly the given number of elements, and that
+    /// all its indices address them.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - The number of elements of the data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of elements overflows or differs from `len`, or a
+    /// stride reaches out of the data.
+    pub fn validate(&self, len: usize) -> Result<(), LayoutError> {
+        let numel = self
+            .numel()
+            .ok_or_else(|| LayoutError::Overflow(self.shape.to_vec()))?;
+        if numel != len {
+            return Err(LayoutError::LengthMismatch {
+                shape: self.shape.to_vec(),
+                numel,
+                len,
+            });
+        }
+        if numel == 0 {
+            return Ok(());
+        }
+
+        // the largest offset, reached by the last index of each dimension
+        let out_of_bounds = |max_offset| LayoutError::StridesOutOfBounds {
+            strides: self.strides.to_vec(),
+            max_offset,
+            len,
+        };
+        let max_offset = self
+            .shape
+            .iter()
+            .zip(self.strides.iter())
+            .try_fold(0usize, |acc, (&dim, &stride)| {
+                (dim - 1)
+                    .checked_mul(stride)
+                    .and_then(|offset| acc.checked_add(offset))
+            })
+            .ok_or_else(|| out_of_bounds(usize::MAX))?;
+        if max_offset >= len {
+            return Err(out_of_bounds(max_offset));
+        }
+
+        Ok(())
+    }
+
+    /// Get the offset of an index, or `None` if the index is out of the shape.
+    pub fn offset(&self, index: [usize; N]) -> Option<usize> {
+        let mut offset = 0;
+        for ((&idx, &dim), &stride) in index.iter().zip(&self.shape).zip(&self.strides) {
+            if idx >= dim {
+                return None;
+            }
+            offset += idx * stride;
+        }
+        Some(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LayoutError, TensorLayout};
+    use crate::geometry::SplitMix64;
+
+    #[test]
+    fn validate_layout() {
+        assert_eq!(
+            TensorLayout::contiguous([usize::MAX, 2]).validate(4),
+            Err(LayoutError::Overflow(vec![usize::MAX, 2]))
+        );
+        let layout = TensorLayout {
+            shape: [2, 2],
+            strides: [2, 2],
+        };
+        assert_eq!(
+            layout.validate(4),
+            Err(LayoutError::StridesOutOfBounds {
+                strides: vec![2, 2],
+                max_offset: 4,
+                len: 4
+            })
+        );
+        assert!(TensorLayout::contiguous([0, 3]).validate(0).is_ok());
+    }
+
+    #[test]
+    fn validated_offsets_in_bounds() {
+        // any validated layout only addresses offsets inside the data
+        let mut rng = SplitMix64::new(0);
+        for _ in 0..1000 {
+            let shape = [0; 3].map(|_| (rng.next_u64() % 4) as usize);
+            let strides = [0; 3].map(|_| (rng.next_u64() % 8) as usize);
+            let layout = TensorLayout { shape, strides };
+            let len = shape.iter().product::<usize>();
+
+            let valid = layout.validate(len).is_ok();
+            let mut max_offset = None;
+            for i in 0..shape[0] {
+                for j in 0..shape[1] {
+                    for k in 0..shape[2] {
+                        let offset = layout.offset([i, j, k]).unwrap();
+                        max_offset = max_offset.max(Some(offset));
+                    }
+                }
+            }
+            assert_eq!(valid, max_offset.unwrap_or(0) < len.max(1));
+            assert!(TensorLayout::contiguous(shape).validate(len).is_ok());
+        }
+    }
+}
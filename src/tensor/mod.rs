@@ -1,14 +1,21 @@
 pub mod allocator;
 mod base;
+mod layout;
 // TODO: mod ops;
 mod serde;
 mod storage;
+mod tracking;
 
 pub use allocator::{CpuAllocator, TensorAllocator};
 pub use base::{Tensor, TensorError};
+pub use layout::{LayoutError, TensorLayout};
+pub use tracking::TrackingAllocator;
 
 // aliases
 pub type Tensor1<T> = Tensor<T, 1>;
 pub type Tensor2<T> = Tensor<T, 2>;
 pub type Tensor3<T> = Tensor<T, 3>;
 pub type Tensor4<T> = Tensor<T, 4>;
+
+/// A tensor shared with copy-on-write semantics, see [`Shared`](crate::shared::Shared).
+pub type SharedTensor<T, const N: usize, A = CpuAllocator> = crate::shared::Shared<Tensor<T, N, A>>;
@@ -1,7 +1,7 @@
 use crate::tensor::storage::TensorStorage;
 
 use super::allocator::TensorAllocator;
-use super::Tensor;
+use super::{Tensor, TensorLayout};
 use serde::ser::SerializeStruct;
 use serde::Deserialize;
 
@@ -43,9 +43,6 @@ where
             strides,
         } = TensorData::deserialize(deserializer)?;
 
-        let storage_array = TensorStorage::from_vec(data, A::default())
-            .map_err(|_| serde::de::Error::custom("Invalid storage"))?;
-
         let shape_array: [usize; N] = shape
             .try_into()
             .map_err(|_| serde::de::Error::custom("Invalid shape"))?;
@@ -54,6 +51,17 @@ where
             .try_into()
             .map_err(|_| serde::de::Error::custom("Invalid strides"))?;
 
+        // reject the data inconsistent with the shape and the strides
+        TensorLayout {
+            shape: shape_array,
+            strides: strides_array,
+        }
+        .validate(data.len())
+        .map_err(serde::de::Error::custom)?;
+
+        let storage_array = TensorStorage::from_vec(data, A::default())
+            .map_err(|_| serde::de::Error::custom("Invalid storage"))?;
+
         Ok(Tensor {
             storage: storage_array,
             shape: shape_array,
@@ -61,3 +69,26 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::tensor::{CpuAllocator, Tensor};
+
+    #[test]
+    fn serde_roundtrip_validated() -> anyhow::Result<()> {
+        let t = Tensor::<u8, 2>::from_shape_vec([2, 2], vec![1, 2, 3, 4], CpuAllocator)?;
+        let json = serde_json::to_string(&t)?;
+        let t2: Tensor<u8, 2> = serde_json::from_str(&json)?;
+        assert_eq!(t2.as_slice(), t.as_slice());
+        assert_eq!(t2.strides, [2, 1]);
+
+        // the shape does not match the data
+        let json = r#"{"data":[1,2,3],"shape":[2,2],"strides":[2,1]}"#;
+        assert!(serde_json::from_str::<Tensor<u8, 2>>(json).is_err());
+
+        // the strides reach out of the data
+        let json = r#"{"data":[1,2,3,4],"shape":[2,2],"strides":[4,1]}"#;
+        assert!(serde_json::from_str::<Tensor<u8, 2>>(json).is_err());
+        Ok(())
+    }
+}
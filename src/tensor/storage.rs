@@ -23,6 +23,24 @@ pub struct TensorStorage<T: ArrowNativeType, A: TensorAllocator> {
     marker: PhantomData<T>,
 }
 
+/// The memory of a tensor storage allocated by a tensor allocator.
+struct Allocation<A: TensorAllocator> {
+    ptr: *mut u8,
+    layout: Layout,
+    alloc: A,
+}
+
+// SAFETY: the allocation is only accessed through the immutable buffer and freed once,
+// and the allocators are thread-safe.
+unsafe impl<A: TensorAllocator> Send for Allocation<A> {}
+unsafe impl<A: TensorAllocator> Sync for Allocation<A> {}
+
+impl<A: TensorAllocator> Drop for Allocation<A> {
+    fn drop(&mut self) {
+        self.alloc.dealloc(self.ptr, self.layout);
+    }
+}
+
 /// Implement the `TensorStorage` struct.
 impl<T, A: TensorAllocator> TensorStorage<T, A>
 where
@@ -40,15 +58,19 @@ where
     /// A new tensor storage if successful, otherwise an error.
     pub fn new(len: usize, alloc: A) -> Result<Self, TensorAllocatorError> {
         // allocate memory for tensor storage
-        let ptr =
-            alloc.alloc(Layout::array::<T>(len).map_err(TensorAllocatorError::LayoutError)?)?;
+        let layout = Layout::array::<T>(len).map_err(TensorAllocatorError::LayoutError)?;
+        let ptr = alloc.alloc(layout)?;
 
-        // create the buffer
+        // create the buffer, returning the memory to the allocator when dropped
         let buffer = unsafe {
             Buffer::from_custom_allocation(
                 NonNull::new_unchecked(ptr),
                 len * std::mem::size_of::<T>(),
-                Arc::new(Vec::<T>::with_capacity(len)),
+                Arc::new(Allocation {
+                    ptr,
+                    layout,
+                    alloc: alloc.clone(),
+                }),
             )
         };
 
@@ -89,6 +111,34 @@ where
         Ok(storage)
     }
 
+    /// Creates a new tensor storage sharing an existing buffer without copying the data.
+    ///
+    /// The buffer keeps its own deallocation, the allocator is only used for the tensors
+    /// created from this storage.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer holding the tensor storage.
+    /// * `alloc` - The allocator associated with the tensor storage.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is not aligned for `T` or its length is not a multiple of the size
+    /// of `T`, an error is returned.
+    pub fn from_buffer(buffer: Buffer, alloc: A) -> Result<Self, TensorAllocatorError> {
+        if buffer.as_ptr().align_offset(std::mem::align_of::<T>()) != 0
+            || buffer.len() % std::mem::size_of::<T>() != 0
+        {
+            return Err(TensorAllocatorError::Misaligned);
+        }
+
+        Ok(Self {
+            data: buffer,
+            alloc,
+            marker: PhantomData,
+        })
+    }
+
     /// Returns the allocator used to allocate the tensor storage.
     pub fn alloc(&self) -> &A {
         &self.alloc
@@ -126,4 +176,34 @@ mod tests {
         assert_eq!(storage.data.len(), 6);
         Ok(())
     }
+
+    #[derive(Clone, Default)]
+    struct CountingAllocator(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl TensorAllocator for CountingAllocator {
+        fn alloc(&self, layout: Layout) -> Result<*mut u8, TensorAllocatorError> {
+            CpuAllocator.alloc(layout)
+        }
+
+        fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            CpuAllocator.dealloc(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn test_tensor_storage_dealloc() -> Result<(), TensorAllocatorError> {
+        let allocator = CountingAllocator::default();
+        let deallocs = || allocator.0.load(std::sync::atomic::Ordering::SeqCst);
+
+        let storage = TensorStorage::<u8, _>::new(1024, allocator.clone())?;
+        let buffer = storage.data.clone();
+        drop(storage);
+        assert_eq!(deallocs(), 0);
+
+        // the memory is returned to the allocator with the last reference
+        drop(buffer);
+        assert_eq!(deallocs(), 1);
+        Ok(())
+    }
 }
@@ -0,0 +1,108 @@
+use std::alloc::Layout;
+use std::sync::Arc;
+
+use super::allocator::{CpuAllocator, TensorAllocator, TensorAllocatorError};
+use crate::metrics::memory::MemoryCounters;
+use crate::metrics::MemoryUsage;
+
+/// A tensor allocator recording the memory allocated through another allocator.
+///
+/// Only the storage allocated by the tensors is recorded, e.g. by
+/// [`Tensor::new_uninitialized`](super::Tensor::new_uninitialized) or when cloning a
+/// tensor, while the tensors built from a vector keep its memory. The clones of an
+/// allocator share their counters. Use a
+/// [`MemoryTracker`](crate::metrics::MemoryTracker) to get an allocator per stage of a
+/// pipeline.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::tensor::{CpuAllocator, Tensor, TrackingAllocator};
+///
+/// let alloc = TrackingAllocator::new(CpuAllocator);
+/// let t = Tensor::<u8, 1>::from_shape_val([100], 0, alloc.clone()).unwrap();
+///
+/// let usage = alloc.usage();
+/// assert_eq!(usage.live_bytes, 100);
+/// assert_eq!(usage.allocations, 1);
+/// ```
+#[derive(Clone)]
+pub struct TrackingAllocator<A: TensorAllocator = CpuAllocator> {
+    inner: A,
+    counters: Arc<MemoryCounters>,
+}
+
+impl<A: TensorAllocator> TrackingAllocator<A> {
+    /// Create a new tracking allocator with its own counters.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The allocator doing the allocations.
+    pub fn new(inner: A) -> Self {
+        Self::with_counters(inner, Arc::default())
+    }
+
+    pub(crate) fn with_counters(inner: A, counters: Arc<MemoryCounters>) -> Self {
+        Self { inner, counters }
+    }
+
+    /// Get the memory usage recorded by the allocator.
+    pub fn usage(&self) -> MemoryUsage {
+        self.counters.usage()
+    }
+}
+
+impl<A: TensorAllocator + Default> Default for TrackingAllocator<A> {
+    fn default() -> Self {
+        Self::new(A::default())
+    }
+}
+
+impl<A: TensorAllocator> TensorAllocator for TrackingAllocator<A> {
+    fn alloc(&self, layout: Layout) -> Result<*mut u8, TensorAllocatorError> {
+        let ptr = self.inner.alloc(layout)?;
+        self.counters.record_alloc(layout.size());
+        Ok(ptr)
+    }
+
+    fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.counters.record_dealloc(layout.size());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrackingAllocator;
+    use crate::metrics::MemoryTracker;
+    use crate::tensor::{CpuAllocator, Tensor, TensorError};
+
+    #[test]
+    fn tracking_allocator() -> Result<(), TensorError> {
+        let tracker = MemoryTracker::new();
+        let decode = tracker.allocator("decode", CpuAllocator);
+        let infer = tracker.allocator("infer", TrackingAllocator::new(CpuAllocator));
+
+        let a = Tensor::<u8, 2, _>::new_uninitialized([10, 10], decode.clone())?;
+        let b = Tensor::<u16, 1, _>::new_uninitialized([8], decode)?;
+        drop(a);
+        let c = Tensor::<f32, 1, _>::new_uninitialized([4], infer.clone())?;
+
+        let report = tracker.report();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].0, "decode");
+        assert_eq!(report[0].1.live_bytes, 16);
+        assert_eq!(report[0].1.peak_bytes, 116);
+        assert_eq!(report[0].1.allocations, 2);
+        assert_eq!(report[0].1.deallocations, 1);
+        assert_eq!(tracker.usage("infer").map(|u| u.live_bytes), Some(16));
+        assert_eq!(tracker.usage("missing"), None);
+
+        // the clones of a tensor are allocated with the same allocator
+        let d = c.clone();
+        assert_eq!(infer.usage().allocations, 2);
+        drop((b, c, d));
+        assert_eq!(tracker.usage("decode").unwrap().live_bytes, 0);
+        Ok(())
+    }
+}
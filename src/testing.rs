@@ -0,0 +1,307 @@
+//! Utilities to write image regression tests.
+
+use crate::geometry::SplitMix64;
+use crate::image::{Image, ImageDtype, ImageSize};
+use crate::io::bmp::{read_image_bmp, write_image_bmp};
+use crate::io::functional::read_image_any;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// The environment variable overriding the directory of the fixtures.
+pub const FIXTURES_DIR_ENV: &str = "KORNIA_FIXTURES_DIR";
+
+/// The environment variable to (re)write the golden images instead of comparing them.
+pub const UPDATE_GOLDEN_ENV: &str = "KORNIA_UPDATE_GOLDEN";
+
+/// Asserts that two images have the same size and pixels within a tolerance.
+///
+/// # Arguments
+///
+/// * `a` - The first image.
+/// * `b` - The second image.
+/// * `tol` - The maximum absolute difference between two pixel values.
+///
+/// # Panics
+///
+/// Panics with the location of the largest difference if the images differ.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::testing::assert_images_close;
+///
+/// let size = ImageSize {
+///     width: 2,
+///     height: 1,
+/// };
+/// let a = Image::<f32, 1>::new(size, vec![0.0, 1.0]).unwrap();
+/// let b = Image::<f32, 1>::new(size, vec![0.0, 1.001]).unwrap();
+/// assert_images_close(&a, &b, 1e-2);
+/// ```
+#[track_caller]
+pub fn assert_images_close<T: ImageDtype, const CHANNELS: usize>(
+    a: &Image<T, CHANNELS>,
+    b: &Image<T, CHANNELS>,
+    tol: f32,
+) {
+    assert_eq!(a.size(), b.size(), "The images have different sizes");
+
+    let mut worst: Option<((usize, usize, usize), f32)> = None;
+    for (((y, x, c), &va), &vb) in a.data.indexed_iter().zip(b.data.iter()) {
+        let diff = (va.into() - vb.into()).abs();
+        // a NaN difference is always reported
+        let exceeds = diff > tol || diff.is_nan();
+        let is_worst = match worst {
+            Some((_, worst_diff)) => diff > worst_diff,
+            None => true,
+        };
+        if exceeds && is_worst {
+            worst = Some(((y, x, c), diff));
+        }
+    }
+
+    if let Some(((y, x, c), diff)) = worst {
+        panic!(
+            "The images differ by {} > {} at (y={}, x={}, c={}): {} != {}",
+            diff,
+            tol,
+            y,
+            x,
+            c,
+            a.data[[y, x, c]].into(),
+            b.data[[y, x, c]].into()
+        );
+    }
+}
+
+/// The direction of a gradient image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// Increasing from left to right.
+    Horizontal,
+    /// Increasing from top to bottom.
+    Vertical,
+    /// Increasing from the top left to the bottom right corner.
+    Diagonal,
+}
+
+/// Generates a linear gradient from 0 to 255 over the image.
+///
+/// # Arguments
+///
+/// * `size` - The size of the image.
+/// * `direction` - The direction of the gradient.
+///
+/// # Returns
+///
+/// The gradient image, with the same values in all the channels.
+pub fn gradient_image<const CHANNELS: usize>(
+    size: ImageSize,
+    direction: GradientDirection,
+) -> Result<Image<u8, CHANNELS>> {
+    let ramp = |i: usize, n: usize| i as f32 / (n.max(2) - 1) as f32;
+    let mut image = Image::from_size_val(size, 0)?;
+    for ((y, x, _), value) in image.data.indexed_iter_mut() {
+        let t = match direction {
+            GradientDirection::Horizontal => ramp(x, size.width),
+            GradientDirection::Vertical => ramp(y, size.height),
+            GradientDirection::Diagonal => (ramp(x, size.width) + ramp(y, size.height)) / 2.0,
+        };
+        *value = (255.0 * t).round() as u8;
+    }
+    Ok(image)
+}
+
+/// Generates a checkerboard of black and white squares, starting with black at the top left.
+///
+/// # Arguments
+///
+/// * `size` - The size of the image.
+/// * `square_size` - The side of the squares in pixels.
+///
+/// # Returns
+///
+/// The checkerboard image, with the same values in all the channels.
+pub fn checkerboard_image<const CHANNELS: usize>(
+    size: ImageSize,
+    square_size: usize,
+) -> Result<Image<u8, CHANNELS>> {
+    if square_size == 0 {
+        return Err(anyhow::anyhow!("The square size should be positive"));
+    }
+
+    let mut image = Image::from_size_val(size, 0)?;
+    for ((y, x, _), value) in image.data.indexed_iter_mut() {
+        if (x / square_size + y / square_size) % 2 == 1 {
+            *value = 255;
+        }
+    }
+    Ok(image)
+}
+
+/// Generates an image of uniform random values in [0, 255].
+///
+/// # Arguments
+///
+/// * `size` - The size of the image.
+/// * `seed` - The seed of the generator, the same seed always producing the same image.
+///
+/// # Returns
+///
+/// The noise image, with independent values in each channel.
+pub fn noise_image<const CHANNELS: usize>(
+    size: ImageSize,
+    seed: u64,
+) -> Result<Image<u8, CHANNELS>> {
+    let mut rng = SplitMix64::new(seed);
+    let data = (0..size.width * size.height * CHANNELS)
+        .map(|_| (rng.next_u64() >> 56) as u8)
+        .collect();
+    Image::new(size, data)
+}
+
+/// Gets the path of a fixture.
+///
+/// The fixtures are looked up in the directory of the [`FIXTURES_DIR_ENV`] variable if
+/// set, or else in the `tests/data` directory of the crate under test.
+///
+/// # Arguments
+///
+/// * `name` - The name of the fixture, relative to the fixtures directory.
+pub fn fixture_path(name: &str) -> PathBuf {
+    let dir = match std::env::var_os(FIXTURES_DIR_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join("tests")
+            .join("data"),
+    };
+    dir.join(name)
+}
+
+/// Loads an image fixture in any supported format.
+///
+/// # Arguments
+///
+/// * `name` - The name of the fixture, relative to the fixtures directory.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::testing::load_fixture;
+///
+/// let image = load_fixture("dog.jpeg").unwrap();
+/// assert_eq!(image.size().width, 258);
+/// ```
+pub fn load_fixture(name: &str) -> Result<Image<u8, 3>> {
+    Ok(read_image_any(&fixture_path(name))?)
+}
+
+/// Asserts that an image matches a golden image within a tolerance.
+///
+/// The golden images are stored as lossless BMP fixtures. When the [`UPDATE_GOLDEN_ENV`]
+/// variable is set, the image is written as the new golden image instead, so that the
+/// golden images are only created or updated on purpose.
+///
+/// # Arguments
+///
+/// * `image` - The image produced by the test.
+/// * `name` - The name of the golden image fixture, e.g. `golden/blur.bmp`.
+/// * `tol` - The maximum absolute difference between two pixel values.
+///
+/// # Panics
+///
+/// Panics if the image differs from the golden image, or the golden image does not exist
+/// or cannot be read or written.
+#[track_caller]
+pub fn assert_golden(image: &Image<u8, 3>, name: &str, tol: f32) {
+    let update = std::env::var_os(UPDATE_GOLDEN_ENV).is_some();
+    check_golden(image, &fixture_path(name), tol, update);
+}
+
+/// Compares an image with the golden image at a path, or writes it when updating.
+#[track_caller]
+fn check_golden(image: &Image<u8, 3>, path: &Path, tol: f32, update: bool) {
+    if update {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).expect("Failed to create the golden image directory");
+        }
+        write_image_bmp(path, image).expect("Failed to write the golden image");
+        return;
+    }
+
+    if !path.exists() {
+        panic!(
+            "The golden image {} does not exist, run the test with {}=1 to create it",
+            path.display(),
+            UPDATE_GOLDEN_ENV
+        );
+    }
+
+    let golden = read_image_bmp(path)
+        .unwrap_or_else(|e| panic!("Failed to read the golden image {}: {}", path.display(), e));
+    assert_images_close(image, &golden, tol);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        assert_images_close, checkerboard_image, gradient_image, noise_image, GradientDirection,
+    };
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+    use std::path::Path;
+
+    #[test]
+    fn synthetic_images() -> Result<()> {
+        let size = ImageSize {
+            width: 4,
+            height: 2,
+        };
+
+        let gradient = gradient_image::<1>(size, GradientDirection::Horizontal)?;
+        assert_eq!(
+            gradient.data.as_slice().unwrap(),
+            &[0, 85, 170, 255, 0, 85, 170, 255]
+        );
+
+        let checkerboard = checkerboard_image::<3>(size, 1)?;
+        assert_eq!(checkerboard.data[[0, 0, 2]], 0);
+        assert_eq!(checkerboard.data[[0, 1, 2]], 255);
+        assert_eq!(checkerboard.data[[1, 1, 0]], 0);
+
+        let noise = noise_image::<3>(size, 7)?;
+        assert_images_close(&noise, &noise_image::<3>(size, 7)?, 0.0);
+        assert_ne!(noise.data, noise_image::<3>(size, 8)?.data);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "does not exist")]
+    fn golden_missing() {
+        let image = Image::<u8, 3>::from_size_val(
+            ImageSize {
+                width: 2,
+                height: 2,
+            },
+            0,
+        )
+        .unwrap();
+        let path = Path::new("tests/data/golden/missing.bmp");
+        super::check_golden(&image, path, 0.0, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "at (y=1, x=0, c=0)")]
+    fn images_not_close() {
+        let size = ImageSize {
+            width: 2,
+            height: 2,
+        };
+        let a = Image::<u8, 1>::new(size, vec![0, 0, 0, 0]).unwrap();
+        let b = Image::<u8, 1>::new(size, vec![1, 0, 9, 0]).unwrap();
+        assert_images_close(&a, &b, 2.0);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::image::{Image, ImageSize};
+use anyhow::Result;
+use ndarray::parallel::prelude::*;
+
+/// A processor running an operation tile by tile on large images.
+///
+/// The image is split in a grid of tiles, each one extended by an overlap on every side
+/// so that operations looking at neighboring pixels see the same context as on the
+/// whole image. The tiles are processed in parallel and only their inner region is
+/// stitched into the output, which makes the result seamless when the overlap is at
+/// least the radius of the operation.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::filters::filter2d;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::tile::TileProcessor;
+///
+/// let image = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 1000,
+///         height: 800,
+///     },
+///     1.0,
+/// )
+/// .unwrap();
+///
+/// let tile_size = ImageSize {
+///     width: 256,
+///     height: 256,
+/// };
+/// let kernel = ndarray::Array2::from_elem((3, 3), 1.0 / 9.0);
+///
+/// let blurred = TileProcessor::new(tile_size, 1)
+///     .unwrap()
+///     .process(&image, |tile| filter2d(tile, &kernel))
+///     .unwrap();
+/// assert_eq!(blurred.size(), image.size());
+/// ```
+pub struct TileProcessor {
+    tile_size: ImageSize,
+    overlap: usize,
+}
+
+/// The placement of a tile in the image.
+struct Tile {
+    /// The region extracted from the image, including the overlap.
+    outer: (usize, usize, usize, usize),
+    /// The region written to the output.
+    inner: (usize, usize, usize, usize),
+}
+
+impl TileProcessor {
+    /// Creates a new TileProcessor object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_size` - The size of the region written by each tile
+    /// * `overlap` - The number of context pixels added on each side of the tiles
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tile size is zero.
+    pub fn new(tile_size: ImageSize, overlap: usize) -> Result<Self> {
+        if tile_size.width == 0 || tile_size.height == 0 {
+            return Err(anyhow::anyhow!("Invalid tile size: {}", tile_size));
+        }
+        Ok(Self { tile_size, overlap })
+    }
+
+    /// Compute the tiles covering an image of the given size.
+    fn tiles(&self, size: ImageSize) -> Vec<Tile> {
+        let (tw, th) = (self.tile_size.width, self.tile_size.height);
+        let mut tiles = Vec::new();
+        for y in (0..size.height).step_by(th) {
+            for x in (0..size.width).step_by(tw) {
+                let (w, h) = (tw.min(size.width - x), th.min(size.height - y));
+                let x0 = x.saturating_sub(self.overlap);
+                let y0 = y.saturating_sub(self.overlap);
+                let x1 = (x + w + self.overlap).min(size.width);
+                let y1 = (y + h + self.overlap).min(size.height);
+                tiles.push(Tile {
+                    outer: (x0, y0, x1 - x0, y1 - y0),
+                    inner: (x, y, w, h),
+                });
+            }
+        }
+        tiles
+    }
+
+    /// Run an operation on each tile of an image and stitch the results.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The input image with shape (H, W, C).
+    /// * `op` - The operation applied to each tile, returning an image of the same size.
+    ///
+    /// # Returns
+    ///
+    /// The stitched output with the size of the input image.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the operation, or an error if it changes the size
+    /// of a tile.
+    pub fn process<T, U, F, const C: usize, const D: usize>(
+        &self,
+        image: &Image<T, C>,
+        op: F,
+    ) -> Result<Image<U, D>>
+    where
+        T: Clone + Send + Sync,
+        U: Clone + Default + Send + Sync,
+        F: Fn(&Image<T, C>) -> Result<Image<U, D>> + Send + Sync,
+    {
+        let tiles = self.tiles(image.size());
+
+        let results = tiles
+            .into_par_iter()
+            .map(|tile| {
+                let (x0, y0, w, h) = tile.outer;
+                let input = Image {
+                    data: image
+                        .data
+                        .slice(ndarray::s![y0..y0 + h, x0..x0 + w, ..])
+                        .to_owned(),
+                };
+
+                let output = op(&input)?;
+                if output.size() != input.size() {
+                    return Err(anyhow::anyhow!(
+                        "The operation changed the tile size from {} to {}.",
+                        input.size(),
+                        output.size()
+                    ));
+                }
+                Ok((tile, output))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut stitched = Image::<U, D>::from_size_val(image.size(), U::default())?;
+        for (tile, output) in results {
+            let (x0, y0, _, _) = tile.outer;
+            let (x, y, w, h) = tile.inner;
+            let (ox, oy) = (x - x0, y - y0);
+            stitched
+                .data
+                .slice_mut(ndarray::s![y..y + h, x..x + w, ..])
+                .assign(&output.data.slice(ndarray::s![oy..oy + h, ox..ox + w, ..]));
+        }
+
+        Ok(stitched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn tile_processor_seamless() -> Result<()> {
+        let image = Image::<f32, 2>::new(
+            ImageSize {
+                width: 20,
+                height: 13,
+            },
+            (0..20 * 13 * 2).map(|i| ((i * 37) % 101) as f32).collect(),
+        )?;
+
+        // a 5x5 filter needs an overlap of 2 pixels
+        let kernel = ndarray::Array2::from_elem((5, 5), 1.0 / 25.0);
+        let expected = crate::filters::filter2d(&image, &kernel)?;
+
+        let tile_size = ImageSize {
+            width: 7,
+            height: 5,
+        };
+        let processor = super::TileProcessor::new(tile_size, 2)?;
+        assert_eq!(processor.tiles(image.size()).len(), 3 * 3);
+
+        let tiled = processor.process(&image, |tile| crate::filters::filter2d(tile, &kernel))?;
+        assert_eq!(tiled.data, expected.data);
+
+        // the operation must keep the size of the tiles
+        let shrink = |_: &Image<f32, 2>| {
+            Image::<f32, 2>::from_size_val(
+                ImageSize {
+                    width: 1,
+                    height: 1,
+                },
+                0.0,
+            )
+        };
+        assert!(processor.process(&image, shrink).is_err());
+
+        let empty = ImageSize {
+            width: 0,
+            height: 5,
+        };
+        assert!(super::TileProcessor::new(empty, 2).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,437 @@
+use crate::features::good_features_to_track;
+use crate::geometry::Point2;
+use crate::image::Image;
+use crate::registration::Plane;
+use anyhow::Result;
+
+/// Parameters of the [`KltTracker`].
+#[derive(Debug, Clone)]
+pub struct KltParams {
+    /// The half size of the square window tracked around each feature.
+    pub window_radius: usize,
+    /// The number of pyramid levels, with 1 for the full resolution only.
+    pub levels: usize,
+    /// The maximum number of Lucas-Kanade iterations per pyramid level.
+    pub max_iterations: usize,
+    /// The displacement update in pixels under which the iterations stop.
+    pub epsilon: f32,
+    /// The maximum mean absolute intensity difference over the window to keep a track.
+    pub max_error: f32,
+    /// The number of active tracks under which new features are detected.
+    pub min_tracks: usize,
+    /// The maximum number of active tracks.
+    pub max_tracks: usize,
+    /// The quality of the detected features, see [`good_features_to_track`].
+    pub quality: f32,
+    /// The minimum distance in pixels between two tracks.
+    pub min_distance: f32,
+}
+
+impl Default for KltParams {
+    fn default() -> Self {
+        Self {
+            window_radius: 7,
+            levels: 3,
+            max_iterations: 20,
+            epsilon: 0.01,
+            max_error: 0.1,
+            min_tracks: 50,
+            max_tracks: 200,
+            quality: 0.01,
+            min_distance: 8.0,
+        }
+    }
+}
+
+/// The state of a track after a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackState {
+    /// The feature was detected in the frame.
+    New,
+    /// The feature was tracked from the previous frame.
+    Tracked,
+    /// The feature was lost in the frame, the track is removed at the next frame.
+    Lost,
+}
+
+/// A feature tracked over consecutive frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track {
+    /// The unique identifier of the track.
+    pub id: u64,
+    /// The position of the feature in the last frame.
+    pub position: Point2,
+    /// The state of the track after the last frame.
+    pub state: TrackState,
+    /// The number of frames the feature was tracked for.
+    pub age: usize,
+}
+
+/// Build the image pyramid with its gradients, from the finest level.
+fn build_pyramid(image: &Image<f32, 1>, levels: usize) -> Vec<(Plane, Plane, Plane)> {
+    let mut pyramid = Vec::with_capacity(levels);
+    let mut plane = Plane::from_image(image);
+    for level in 0..levels {
+        let next = (level + 1 < levels && plane.width >= 2 && plane.height >= 2)
+            .then(|| plane.downsample());
+        let (gx, gy) = plane.gradients();
+        pyramid.push((plane, gx, gy));
+        match next {
+            Some(next) => plane = next,
+            None => break,
+        }
+    }
+    pyramid
+}
+
+/// Track a point between two pyramids with the iterative Lucas-Kanade method.
+///
+/// The pixels of the window outside of the images are ignored.
+///
+/// # Returns
+///
+/// The position in the next frame and the mean absolute error over the window, or None
+/// if the window has no texture or the point leaves the image.
+fn track_point(
+    prev: &[(Plane, Plane, Plane)],
+    next: &[(Plane, Plane, Plane)],
+    point: Point2,
+    params: &KltParams,
+) -> Option<(Point2, f32)> {
+    let r = params.window_radius as i32;
+    let levels = prev.len().min(next.len());
+
+    // the displacement guess propagated from the coarsest level
+    let (mut gx, mut gy) = (0.0f32, 0.0f32);
+    let mut error = 0.0;
+
+    for level in (0..levels).rev() {
+        let (prev_plane, prev_dx, prev_dy) = &prev[level];
+        let (next_plane, _, _) = &next[level];
+        let scale = (1 << level) as f32;
+        let (px, py) = (point.x / scale, point.y / scale);
+
+        // the window of the previous frame and its gradient matrix
+        let mut window = Vec::with_capacity(((2 * r + 1) * (2 * r + 1)) as usize);
+        let (mut gxx, mut gxy, mut gyy) = (0.0f32, 0.0f32, 0.0f32);
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let (u, v) = (px + dx as f32, py + dy as f32);
+                let (Some(value), Some(ix), Some(iy)) = (
+                    prev_plane.sample(u, v),
+                    prev_dx.sample(u, v),
+                    prev_dy.sample(u, v),
+                ) else {
+                    continue;
+                };
+                gxx += ix * ix;
+                gxy += ix * iy;
+                gyy += iy * iy;
+                window.push((dx as f32, dy as f32, value, ix, iy));
+            }
+        }
+        if window.is_empty() {
+            return None;
+        }
+
+        // the smallest eigenvalue of the gradient matrix measures the texture
+        let det = gxx * gyy - gxy * gxy;
+        let min_eigen = (gxx + gyy) / 2.0 - (((gxx - gyy) / 2.0).powi(2) + gxy * gxy).sqrt();
+        if min_eigen / (window.len() as f32) < 1e-6 {
+            return None;
+        }
+
+        let (mut vx, mut vy) = (0.0f32, 0.0f32);
+        for _ in 0..params.max_iterations {
+            let (mut bx, mut by) = (0.0f32, 0.0f32);
+            let (mut sum, mut count) = (0.0f32, 0);
+            for &(dx, dy, value, ix, iy) in window.iter() {
+                let Some(moved) = next_plane.sample(px + gx + vx + dx, py + gy + vy + dy) else {
+                    continue;
+                };
+                let diff = value - moved;
+                bx += diff * ix;
+                by += diff * iy;
+                sum += diff.abs();
+                count += 1;
+            }
+            if count == 0 {
+                return None;
+            }
+            error = sum / count as f32;
+
+            let (ux, uy) = ((gyy * bx - gxy * by) / det, (gxx * by - gxy * bx) / det);
+            vx += ux;
+            vy += uy;
+            if ux * ux + uy * uy < params.epsilon * params.epsilon {
+                break;
+            }
+        }
+
+        if level > 0 {
+            gx = 2.0 * (gx + vx);
+            gy = 2.0 * (gy + vy);
+        } else {
+            gx += vx;
+            gy += vy;
+        }
+    }
+
+    let (x, y) = (point.x + gx, point.y + gy);
+    let (width, height) = (prev[0].0.width as f32, prev[0].0.height as f32);
+    if !(x >= 0.0 && y >= 0.0 && x <= width - 1.0 && y <= height - 1.0) {
+        return None;
+    }
+
+    Some((Point2::new(x, y), error))
+}
+
+/// Tracks a persistent set of features over a video stream with the KLT method.
+///
+/// The features are tracked between consecutive frames with the pyramidal Lucas-Kanade
+/// method. The tracks that leave the image or whose appearance changes too much are marked
+/// as lost, and new features are detected with [`good_features_to_track`] when the number
+/// of active tracks drops under the minimum.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::tracking::{KltParams, KltTracker, TrackState};
+///
+/// let frame = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 64,
+///         height: 64,
+///     },
+///     (0..64 * 64)
+///         .map(|i| (((i % 64) as f32 / 3.0).sin() * ((i / 64) as f32 / 4.0).cos()))
+///         .collect(),
+/// )
+/// .unwrap();
+///
+/// let mut tracker = KltTracker::new(KltParams {
+///     min_tracks: 1,
+///     ..Default::default()
+/// });
+/// let tracks = tracker.update(&frame).unwrap();
+/// assert!(tracks.iter().all(|t| t.state == TrackState::New));
+///
+/// // the same frame again keeps all the tracks
+/// let tracks = tracker.update(&frame).unwrap();
+/// assert!(tracks.iter().all(|t| t.state == TrackState::Tracked));
+/// ```
+pub struct KltTracker {
+    params: KltParams,
+    pyramid: Option<Vec<(Plane, Plane, Plane)>>,
+    tracks: Vec<Track>,
+    next_id: u64,
+}
+
+impl KltTracker {
+    /// Create a new tracker without tracks.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters of the tracker.
+    pub fn new(params: KltParams) -> Self {
+        Self {
+            params,
+            pyramid: None,
+            tracks: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Get the tracks after the last frame, including the ones lost in it.
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    /// Remove all the tracks and forget the last frame.
+    pub fn reset(&mut self) {
+        self.pyramid = None;
+        self.tracks.clear();
+    }
+
+    /// Track the features in a new frame.
+    ///
+    /// The tracks lost in the previous frame are removed, the active ones are tracked into
+    /// the new frame and new features are detected if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The new grayscale frame with shape (H, W, 1).
+    ///
+    /// # Returns
+    ///
+    /// The tracks after the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame size changes or the feature detection fails.
+    pub fn update(&mut self, frame: &Image<f32, 1>) -> Result<&[Track]> {
+        let pyramid = build_pyramid(frame, self.params.levels.max(1));
+
+        self.tracks.retain(|t| t.state != TrackState::Lost);
+
+        if let Some(prev) = &self.pyramid {
+            let (prev_plane, next_plane) = (&prev[0].0, &pyramid[0].0);
+            if (prev_plane.width, prev_plane.height) != (next_plane.width, next_plane.height) {
+                return Err(anyhow::anyhow!(
+                    "The frame size changed from {}x{} to {}x{}.",
+                    prev_plane.width,
+                    prev_plane.height,
+                    next_plane.width,
+                    next_plane.height
+                ));
+            }
+
+            for track in self.tracks.iter_mut() {
+                match track_point(prev, &pyramid, track.position, &self.params) {
+                    Some((position, error)) if error <= self.params.max_error => {
+                        track.position = position;
+                        track.state = TrackState::Tracked;
+                        track.age += 1;
+                    }
+                    _ => track.state = TrackState::Lost,
+                }
+            }
+        }
+
+        let active = self
+            .tracks
+            .iter()
+            .filter(|t| t.state != TrackState::Lost)
+            .count();
+
+        if active < self.params.min_tracks.max(1) {
+            let mut active_positions = self
+                .tracks
+                .iter()
+                .filter(|t| t.state != TrackState::Lost)
+                .map(|t| t.position)
+                .collect::<Vec<_>>();
+
+            let corners = good_features_to_track(
+                frame,
+                self.params.max_tracks,
+                self.params.quality,
+                self.params.min_distance,
+            )?;
+
+            for corner in corners {
+                if active_positions.len() >= self.params.max_tracks {
+                    break;
+                }
+                if active_positions
+                    .iter()
+                    .all(|p| p.distance(&corner) >= self.params.min_distance)
+                {
+                    active_positions.push(corner);
+                    self.tracks.push(Track {
+                        id: self.next_id,
+                        position: corner,
+                        state: TrackState::New,
+                        age: 0,
+                    });
+                    self.next_id += 1;
+                }
+            }
+        }
+
+        self.pyramid = Some(pyramid);
+
+        Ok(&self.tracks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KltParams, KltTracker, TrackState};
+    use crate::geometry::Point2;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    /// A smooth textured frame made of gaussian blobs shifted by an offset.
+    fn frame(dx: f32, dy: f32) -> Result<Image<f32, 1>> {
+        let blobs = [
+            (20.0, 18.0, 4.0),
+            (42.0, 25.0, 5.0),
+            (30.0, 45.0, 4.5),
+            (50.0, 50.0, 3.5),
+        ];
+        let data = (0..64 * 64)
+            .map(|i| {
+                let (x, y) = ((i % 64) as f32 - dx, (i / 64) as f32 - dy);
+                blobs
+                    .iter()
+                    .map(|&(cx, cy, s)| {
+                        (-((x - cx).powi(2) + (y - cy).powi(2)) / (2.0 * s * s)).exp()
+                    })
+                    .sum()
+            })
+            .collect();
+        Image::new(
+            ImageSize {
+                width: 64,
+                height: 64,
+            },
+            data,
+        )
+    }
+
+    #[test]
+    fn track_point() -> Result<()> {
+        let params = KltParams::default();
+        let prev = super::build_pyramid(&frame(0.0, 0.0)?, params.levels);
+        let next = super::build_pyramid(&frame(2.5, -1.5)?, params.levels);
+
+        let (position, error) =
+            super::track_point(&prev, &next, Point2::new(24.0, 20.0), &params).unwrap();
+        assert!((position.x - 26.5).abs() < 0.05);
+        assert!((position.y - 18.5).abs() < 0.05);
+        assert!(error < 0.01);
+
+        // a flat window can not be tracked
+        assert!(super::track_point(&prev, &next, Point2::new(8.0, 60.0), &params).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn klt_tracker() -> Result<()> {
+        let params = KltParams {
+            min_tracks: 2,
+            max_tracks: 4,
+            ..Default::default()
+        };
+        let mut tracker = KltTracker::new(params);
+
+        let tracks = tracker.update(&frame(0.0, 0.0)?)?.to_vec();
+        assert_eq!(tracks.len(), 4);
+        assert!(tracks.iter().all(|t| t.state == TrackState::New));
+
+        let moved = tracker.update(&frame(1.5, 1.0)?)?;
+        assert_eq!(moved.len(), 4);
+        for (before, after) in tracks.iter().zip(moved.iter()) {
+            assert_eq!(before.id, after.id);
+            assert_eq!(after.state, TrackState::Tracked);
+            assert_eq!(after.age, 1);
+            assert!((after.position.x - before.position.x - 1.5).abs() < 0.1);
+            assert!((after.position.y - before.position.y - 1.0).abs() < 0.1);
+        }
+
+        // the content disappears, the tracks are lost and removed at the next frame
+        let blank = Image::from_size_val(
+            ImageSize {
+                width: 64,
+                height: 64,
+            },
+            0.0,
+        )?;
+        let lost = tracker.update(&blank)?;
+        assert!(lost.iter().all(|t| t.state == TrackState::Lost));
+        assert!(tracker.update(&blank)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,415 @@
+use crate::geometry::{Point2, Rect, RotatedBox};
+use crate::image::Image;
+use anyhow::Result;
+
+/// The margin in pixels added around the window to measure the object size in CAMShift.
+const CAMSHIFT_MARGIN: usize = 10;
+
+/// The raw moments of the likelihood inside a window, up to the second order.
+#[derive(Default)]
+struct Moments {
+    m00: f64,
+    m10: f64,
+    m01: f64,
+    m20: f64,
+    m11: f64,
+    m02: f64,
+}
+
+impl Moments {
+    fn compute(probability: &Image<f32, 1>, window: Rect) -> Self {
+        let mut m = Moments::default();
+        for y in window.y..window.y + window.height {
+            for x in window.x..window.x + window.width {
+                let p = probability.data[[y, x, 0]] as f64;
+                let (xf, yf) = (x as f64, y as f64);
+                m.m00 += p;
+                m.m10 += p * xf;
+                m.m01 += p * yf;
+                m.m20 += p * xf * xf;
+                m.m11 += p * xf * yf;
+                m.m02 += p * yf * yf;
+            }
+        }
+        m
+    }
+}
+
+/// Check that the window is not empty and fits inside the image.
+fn check_window(probability: &Image<f32, 1>, window: Rect) -> Result<()> {
+    if window.width == 0
+        || window.height == 0
+        || window.x + window.width > probability.width()
+        || window.y + window.height > probability.height()
+    {
+        return Err(anyhow::anyhow!(
+            "The window {:?} is empty or does not fit in the image {}.",
+            window,
+            probability.size()
+        ));
+    }
+    Ok(())
+}
+
+/// Move the window to the centroid of the likelihood until it converges.
+fn mean_shift(
+    probability: &Image<f32, 1>,
+    mut window: Rect,
+    max_iterations: usize,
+    epsilon: f32,
+) -> Rect {
+    let (width, height) = (probability.width(), probability.height());
+
+    for _ in 0..max_iterations {
+        let m = Moments::compute(probability, window);
+        if m.m00 <= f64::EPSILON {
+            break;
+        }
+
+        // center the window on the centroid, inside the image
+        let (cx, cy) = (m.m10 / m.m00, m.m01 / m.m00);
+        let x = (cx - (window.width as f64 - 1.0) / 2.0).round().max(0.0) as usize;
+        let y = (cy - (window.height as f64 - 1.0) / 2.0).round().max(0.0) as usize;
+        let x = x.min(width - window.width);
+        let y = y.min(height - window.height);
+
+        let shift = (x as f32 - window.x as f32).hypot(y as f32 - window.y as f32);
+        window.x = x;
+        window.y = y;
+        if shift < epsilon {
+            break;
+        }
+    }
+
+    window
+}
+
+/// Tracks an object by moving a fixed size window to the mode of a likelihood image.
+///
+/// The likelihood image is usually the back projection of the histogram of the object,
+/// see [`crate::histogram::back_project`].
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Rect;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::tracking::MeanShift;
+///
+/// let mut probability = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 40,
+///         height: 40,
+///     },
+///     0.0,
+/// )
+/// .unwrap();
+/// probability.data.slice_mut(ndarray::s![20..29, 22..31, ..]).fill(1.0);
+///
+/// let start = Rect {
+///     x: 16,
+///     y: 15,
+///     width: 9,
+///     height: 9,
+/// };
+/// let mut tracker = MeanShift::new(start);
+/// let window = tracker.update(&probability).unwrap();
+/// assert_eq!((window.x, window.y), (22, 20));
+/// ```
+pub struct MeanShift {
+    window: Rect,
+    max_iterations: usize,
+    epsilon: f32,
+}
+
+impl MeanShift {
+    /// Create a new tracker from the initial window of the object.
+    ///
+    /// By default the window moves at most 10 times per frame, until it moves by less
+    /// than 1 pixel.
+    pub fn new(window: Rect) -> Self {
+        Self {
+            window,
+            max_iterations: 10,
+            epsilon: 1.0,
+        }
+    }
+
+    /// Set the maximum number of mean-shift iterations per frame.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the displacement in pixels under which the iterations stop.
+    pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Get the current window of the object.
+    pub fn window(&self) -> Rect {
+        self.window
+    }
+
+    /// Track the object in a new frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `probability` - The likelihood of the object with shape (H, W, 1).
+    ///
+    /// # Returns
+    ///
+    /// The new window of the object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the window does not fit in the image.
+    pub fn update(&mut self, probability: &Image<f32, 1>) -> Result<Rect> {
+        check_window(probability, self.window)?;
+        self.window = mean_shift(probability, self.window, self.max_iterations, self.epsilon);
+        Ok(self.window)
+    }
+}
+
+/// Tracks an object with the continuously adaptive mean-shift (CAMShift) algorithm.
+///
+/// After the mean-shift iterations, the size and orientation of the object are estimated
+/// from the second order moments of the likelihood, and the window adapts to the object
+/// for the next frame.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Rect;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::tracking::CamShift;
+///
+/// let mut probability = Image::<f32, 1>::from_size_val(
+///     ImageSize {
+///         width: 60,
+///         height: 40,
+///     },
+///     0.0,
+/// )
+/// .unwrap();
+/// probability.data.slice_mut(ndarray::s![15..25, 20..40, ..]).fill(1.0);
+///
+/// let start = Rect {
+///     x: 16,
+///     y: 12,
+///     width: 10,
+///     height: 10,
+/// };
+/// let mut tracker = CamShift::new(start);
+/// let object = tracker.update(&probability).unwrap();
+/// assert!((object.cx - 29.5).abs() < 0.5 && (object.cy - 19.5).abs() < 0.5);
+/// assert!((object.width - 20.0).abs() < 1.0 && (object.height - 10.0).abs() < 1.0);
+/// ```
+pub struct CamShift {
+    window: Rect,
+    max_iterations: usize,
+    epsilon: f32,
+}
+
+impl CamShift {
+    /// Create a new tracker from the initial window of the object.
+    ///
+    /// By default the window moves at most 10 times per frame, until it moves by less
+    /// than 1 pixel.
+    pub fn new(window: Rect) -> Self {
+        Self {
+            window,
+            max_iterations: 10,
+            epsilon: 1.0,
+        }
+    }
+
+    /// Set the maximum number of mean-shift iterations per frame.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the displacement in pixels under which the iterations stop.
+    pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Get the search window for the next frame.
+    pub fn window(&self) -> Rect {
+        self.window
+    }
+
+    /// Track the object in a new frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `probability` - The likelihood of the object with shape (H, W, 1).
+    ///
+    /// # Returns
+    ///
+    /// The oriented box of the object, with its width along the major axis.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the window does not fit in the image or the object is lost,
+    /// i.e. the likelihood is null around the window.
+    pub fn update(&mut self, probability: &Image<f32, 1>) -> Result<RotatedBox> {
+        check_window(probability, self.window)?;
+        let window = mean_shift(probability, self.window, self.max_iterations, self.epsilon);
+
+        // measure the object in the window grown by a margin to let it expand
+        let (width, height) = (probability.width(), probability.height());
+        let (x0, y0) = (
+            window.x.saturating_sub(CAMSHIFT_MARGIN),
+            window.y.saturating_sub(CAMSHIFT_MARGIN),
+        );
+        let x1 = (window.x + window.width + CAMSHIFT_MARGIN).min(width);
+        let y1 = (window.y + window.height + CAMSHIFT_MARGIN).min(height);
+        let m = Moments::compute(
+            probability,
+            Rect {
+                x: x0,
+                y: y0,
+                width: x1 - x0,
+                height: y1 - y0,
+            },
+        );
+        if m.m00 <= f64::EPSILON {
+            return Err(anyhow::anyhow!(
+                "The object is lost, the likelihood is null."
+            ));
+        }
+
+        // the covariance of the likelihood and its principal axes
+        let (cx, cy) = (m.m10 / m.m00, m.m01 / m.m00);
+        let a = m.m20 / m.m00 - cx * cx;
+        let b = m.m11 / m.m00 - cx * cy;
+        let c = m.m02 / m.m00 - cy * cy;
+        let root = (4.0 * b * b + (a - c).powi(2)).sqrt();
+        let (major, minor) = (((a + c + root) / 2.0), ((a + c - root) / 2.0).max(0.0));
+
+        // the sides of a uniform rectangle with the same variances
+        let object = RotatedBox {
+            cx: cx as f32,
+            cy: cy as f32,
+            width: (12.0 * major).sqrt() as f32,
+            height: (12.0 * minor).sqrt() as f32,
+            angle: (0.5 * (2.0 * b).atan2(a - c)).to_degrees() as f32,
+        };
+
+        // the next search window bounds the object
+        let corners = object.corners();
+        let bound = |f: fn(&Point2) -> f32, max: usize| {
+            let lo = corners.iter().map(f).fold(f32::MAX, f32::min);
+            let hi = corners.iter().map(f).fold(f32::MIN, f32::max);
+            let lo = lo.floor().clamp(0.0, (max - 1) as f32) as usize;
+            let hi = hi.ceil().clamp(0.0, (max - 1) as f32) as usize;
+            (lo, hi.max(lo) - lo + 1)
+        };
+        let (x, w) = bound(|p| p.x, width);
+        let (y, h) = bound(|p| p.y, height);
+        self.window = Rect {
+            x,
+            y,
+            width: w,
+            height: h,
+        };
+
+        Ok(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CamShift, MeanShift};
+    use crate::geometry::Rect;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn mean_shift() -> Result<()> {
+        let mut probability = Image::<f32, 1>::from_size_val(
+            ImageSize {
+                width: 50,
+                height: 40,
+            },
+            0.0,
+        )?;
+        // a cone of likelihood centered at (34, 15)
+        for y in 0..40 {
+            for x in 0..50 {
+                let d = (x as f32 - 34.0).hypot(y as f32 - 15.0);
+                probability.data[[y, x, 0]] = (1.0 - d / 4.0).max(0.0);
+            }
+        }
+
+        let mut tracker = MeanShift::new(Rect {
+            x: 27,
+            y: 14,
+            width: 9,
+            height: 9,
+        })
+        .with_max_iterations(30);
+        assert_eq!(
+            tracker.update(&probability)?,
+            Rect {
+                x: 30,
+                y: 11,
+                width: 9,
+                height: 9,
+            }
+        );
+
+        // the window stays inside the image
+        let mut tracker = MeanShift::new(Rect {
+            x: 45,
+            y: 0,
+            width: 10,
+            height: 10,
+        });
+        assert!(tracker.update(&probability).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn cam_shift() -> Result<()> {
+        // a bar of 24x6 pixels rotated by 30 degrees
+        let (cx, cy, angle) = (40.0f32, 30.0f32, 30f32.to_radians());
+        let mut probability = Image::<f32, 1>::from_size_val(
+            ImageSize {
+                width: 80,
+                height: 60,
+            },
+            0.0,
+        )?;
+        for y in 0..60 {
+            for x in 0..80 {
+                let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+                let u = dx * angle.cos() + dy * angle.sin();
+                let v = -dx * angle.sin() + dy * angle.cos();
+                if u.abs() < 12.0 && v.abs() < 3.0 {
+                    probability.data[[y, x, 0]] = 1.0;
+                }
+            }
+        }
+
+        let mut tracker = CamShift::new(Rect {
+            x: 30,
+            y: 24,
+            width: 10,
+            height: 10,
+        });
+        let object = tracker.update(&probability)?;
+        assert!((object.cx - cx).abs() < 0.5 && (object.cy - cy).abs() < 0.5);
+        assert!((object.angle - 30.0).abs() < 2.0);
+        assert!((object.width - 24.0).abs() < 1.5 && (object.height - 6.0).abs() < 1.5);
+
+        // the search window grows to the object
+        let window = tracker.window();
+        assert!(window.width > 20 && window.height > 12);
+        Ok(())
+    }
+}
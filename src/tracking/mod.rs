@@ -0,0 +1,4 @@
+mod klt;
+mod meanshift;
+pub use klt::{KltParams, KltTracker, Track, TrackState};
+pub use meanshift::{CamShift, MeanShift};
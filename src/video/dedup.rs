@@ -0,0 +1,316 @@
+use crate::image::Image;
+use anyhow::Result;
+
+/// The size of the thumbnail for the perceptual hash.
+const HASH_THUMBNAIL_SIZE: usize = 32;
+
+/// The number of low frequencies in each direction kept in the perceptual hash.
+const HASH_FREQUENCIES: usize = 8;
+
+/// The size of the thumbnail for the thumbnail difference.
+const THUMBNAIL_SIZE: usize = 16;
+
+/// Downscale an image to a square grayscale thumbnail by averaging the pixels in each cell.
+///
+/// The values are normalized to the range [0, 1].
+fn thumbnail<const CHANNELS: usize>(image: &Image<u8, CHANNELS>, size: usize) -> Vec<f32> {
+    let (width, height) = (image.width(), image.height());
+    let cell = |i: usize, len: usize| {
+        let start = (i * len / size).min(len - 1);
+        start..((i + 1) * len / size).max(start + 1)
+    };
+
+    let mut thumbnail = Vec::with_capacity(size * size);
+    for j in 0..size {
+        let rows = cell(j, height);
+        for i in 0..size {
+            let cols = cell(i, width);
+            let count = (rows.len() * cols.len() * CHANNELS) as f32;
+            let sum = image
+                .data
+                .slice(ndarray::s![rows.clone(), cols, ..])
+                .iter()
+                .map(|&v| v as f32)
+                .sum::<f32>();
+            thumbnail.push(sum / count / 255.0);
+        }
+    }
+    thumbnail
+}
+
+/// Compute the perceptual hash of an image.
+///
+/// The image is downscaled to a 32x32 grayscale thumbnail, and each bit of the hash tells
+/// if one of the 8x8 lowest frequencies of its discrete cosine transform is above their
+/// median. Similar images have hashes with a small Hamming distance.
+///
+/// # Arguments
+///
+/// * `image` - The input image with shape (H, W, C).
+///
+/// # Returns
+///
+/// The 64 bits hash of the image.
+///
+/// # Errors
+///
+/// Returns an error if the image is empty.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::video::perceptual_hash;
+///
+/// let image = Image::<u8, 1>::new(
+///     ImageSize {
+///         width: 64,
+///         height: 48,
+///     },
+///     (0..64 * 48)
+///         .map(|i| {
+///             let (x, y) = ((i % 64) as f32, (i / 64) as f32);
+///             (128.0 + 100.0 * (x * 0.2).sin() * (y * 0.15).cos()) as u8
+///         })
+///         .collect(),
+/// )
+/// .unwrap();
+///
+/// // a small change of brightness keeps the hash
+/// let mut brighter = image.clone();
+/// brighter.data.mapv_inplace(|v| v.saturating_add(2));
+/// let (a, b) = (perceptual_hash(&image).unwrap(), perceptual_hash(&brighter).unwrap());
+/// assert!((a ^ b).count_ones() <= 2);
+/// ```
+pub fn perceptual_hash<const CHANNELS: usize>(image: &Image<u8, CHANNELS>) -> Result<u64> {
+    if image.width() == 0 || image.height() == 0 {
+        return Err(anyhow::anyhow!("Empty image"));
+    }
+
+    let n = HASH_THUMBNAIL_SIZE;
+    let pixels = thumbnail(image, n);
+
+    // the 1D cosine basis of the lowest frequencies
+    let basis = (0..HASH_FREQUENCIES)
+        .map(|k| {
+            (0..n)
+                .map(|x| {
+                    (std::f32::consts::PI * (2 * x + 1) as f32 * k as f32 / (2 * n) as f32).cos()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // the separable 2D transform, first along the rows then along the columns
+    let mut rows = vec![0f32; n * HASH_FREQUENCIES];
+    for (y, row) in pixels.chunks_exact(n).enumerate() {
+        for (u, b) in basis.iter().enumerate() {
+            rows[y * HASH_FREQUENCIES + u] = row.iter().zip(b).map(|(p, c)| p * c).sum();
+        }
+    }
+    let mut coeffs = Vec::with_capacity(HASH_FREQUENCIES * HASH_FREQUENCIES);
+    for b in basis.iter() {
+        for u in 0..HASH_FREQUENCIES {
+            coeffs.push(
+                (0..n)
+                    .map(|y| rows[y * HASH_FREQUENCIES + u] * b[y])
+                    .sum::<f32>(),
+            );
+        }
+    }
+
+    // the median excludes the mean of the image, i.e. the first coefficient
+    let mut sorted = coeffs[1..].to_vec();
+    sorted.sort_unstable_by(|a, b| a.total_cmp(b));
+    let median = sorted[sorted.len() / 2];
+
+    Ok(coeffs
+        .iter()
+        .enumerate()
+        .filter(|(_, &c)| c > median)
+        .fold(0u64, |hash, (i, _)| hash | (1 << i)))
+}
+
+/// The methods to compare consecutive frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DedupMethod {
+    /// Compare the perceptual hashes, duplicates differing by at most `max_distance` bits.
+    PerceptualHash {
+        /// The maximum Hamming distance between the hashes of duplicates.
+        max_distance: u32,
+    },
+    /// Compare 16x16 grayscale thumbnails, duplicates having a mean absolute difference
+    /// of at most `max_difference` in the range [0, 1].
+    Thumbnail {
+        /// The maximum mean absolute difference between the thumbnails of duplicates.
+        max_difference: f32,
+    },
+}
+
+/// The signature of a frame for the deduplication.
+enum Signature {
+    Hash(u64),
+    Thumbnail(Vec<f32>),
+}
+
+/// Detects the near-identical frames of a video stream.
+///
+/// Each frame is compared to the last frame that was kept, so that a slow drift of the
+/// scene is eventually kept even if every pair of consecutive frames is similar.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::video::{DedupMethod, FrameDeduplicator};
+///
+/// let size = ImageSize {
+///     width: 32,
+///     height: 32,
+/// };
+/// let frames = [0, 1, 0, 200].map(|v| Image::<u8, 1>::from_size_val(size, v).unwrap());
+///
+/// let mut dedup = FrameDeduplicator::new(DedupMethod::Thumbnail {
+///     max_difference: 0.05,
+/// });
+/// let kept = frames
+///     .iter()
+///     .filter(|frame| !dedup.is_duplicate(frame).unwrap())
+///     .count();
+/// assert_eq!(kept, 2);
+/// assert_eq!(dedup.num_duplicates(), 2);
+/// ```
+pub struct FrameDeduplicator {
+    method: DedupMethod,
+    reference: Option<Signature>,
+    num_duplicates: usize,
+}
+
+impl FrameDeduplicator {
+    /// Create a new frame deduplicator.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method to compare the frames.
+    pub fn new(method: DedupMethod) -> Self {
+        Self {
+            method,
+            reference: None,
+            num_duplicates: 0,
+        }
+    }
+
+    /// Get the number of duplicates found so far.
+    pub fn num_duplicates(&self) -> usize {
+        self.num_duplicates
+    }
+
+    /// Forget the last kept frame and the number of duplicates.
+    pub fn reset(&mut self) {
+        self.reference = None;
+        self.num_duplicates = 0;
+    }
+
+    /// Check if a frame is a duplicate of the last kept frame.
+    ///
+    /// The frame becomes the new reference if it is not a duplicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The next frame of the stream with shape (H, W, C).
+    ///
+    /// # Returns
+    ///
+    /// True if the frame is a duplicate and can be dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is empty.
+    pub fn is_duplicate<const CHANNELS: usize>(
+        &mut self,
+        frame: &Image<u8, CHANNELS>,
+    ) -> Result<bool> {
+        if frame.width() == 0 || frame.height() == 0 {
+            return Err(anyhow::anyhow!("Empty frame"));
+        }
+
+        let signature = match self.method {
+            DedupMethod::PerceptualHash { .. } => Signature::Hash(perceptual_hash(frame)?),
+            DedupMethod::Thumbnail { .. } => Signature::Thumbnail(thumbnail(frame, THUMBNAIL_SIZE)),
+        };
+
+        let duplicate = match (&self.reference, &signature, self.method) {
+            (
+                Some(Signature::Hash(a)),
+                Signature::Hash(b),
+                DedupMethod::PerceptualHash { max_distance },
+            ) => (a ^ b).count_ones() <= max_distance,
+            (
+                Some(Signature::Thumbnail(a)),
+                Signature::Thumbnail(b),
+                DedupMethod::Thumbnail { max_difference },
+            ) => {
+                let diff = a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum::<f32>();
+                diff / a.len() as f32 <= max_difference
+            }
+            _ => false,
+        };
+
+        if duplicate {
+            self.num_duplicates += 1;
+        } else {
+            self.reference = Some(signature);
+        }
+        Ok(duplicate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DedupMethod, FrameDeduplicator};
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn frame_deduplicator() -> Result<()> {
+        let size = ImageSize {
+            width: 40,
+            height: 30,
+        };
+        let frame = |shift: usize| {
+            Image::<u8, 3>::new(
+                size,
+                (0..40 * 30 * 3)
+                    .map(|i| {
+                        let (x, y) = ((i / 3 % 40 + shift) as f32, (i / 3 / 40) as f32);
+                        (128.0 + 90.0 * (x * 0.3).sin() * (y * 0.25).cos()) as u8
+                    })
+                    .collect(),
+            )
+        };
+
+        // a static scene with a little noise, then a moving pattern
+        let mut noisy = frame(0)?;
+        noisy.data[[5, 5, 0]] = 255;
+        let frames = [frame(0)?, noisy, frame(0)?, frame(7)?, frame(7)?];
+
+        for method in [
+            DedupMethod::PerceptualHash { max_distance: 4 },
+            DedupMethod::Thumbnail {
+                max_difference: 0.02,
+            },
+        ] {
+            let mut dedup = FrameDeduplicator::new(method);
+            let flags = frames
+                .iter()
+                .map(|f| dedup.is_duplicate(f))
+                .collect::<Result<Vec<_>>>()?;
+            assert_eq!(flags, vec![false, true, true, false, true], "{:?}", method);
+            assert_eq!(dedup.num_duplicates(), 3);
+
+            dedup.reset();
+            assert!(!dedup.is_duplicate(&frames[0])?);
+        }
+        Ok(())
+    }
+}
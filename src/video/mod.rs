@@ -0,0 +1,6 @@
+mod dedup;
+mod rolling_shutter;
+mod scene;
+pub use dedup::{perceptual_hash, DedupMethod, FrameDeduplicator};
+pub use rolling_shutter::{rolling_shutter_correct, row_motion_from_velocity};
+pub use scene::{SceneCut, SceneDetector, SceneMethod};
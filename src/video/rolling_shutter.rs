@@ -0,0 +1,153 @@
+use crate::image::Image;
+use crate::interpolation::{remap, BorderMode, InterpolationMode};
+use anyhow::Result;
+
+/// Compute the motion of each row of a frame captured with a rolling shutter.
+///
+/// Assumes a constant image velocity during the frame, the rows being read from top to
+/// bottom. The motion is relative to the middle row, which is kept in place.
+///
+/// # Arguments
+///
+/// * `height` - The number of rows of the frame.
+/// * `inter_frame_motion` - The (x, y) image motion in pixels between two consecutive frames.
+/// * `readout_ratio` - The time from the first to the last row over the frame period, in [0, 1].
+///
+/// # Returns
+///
+/// The (x, y) motion of each row.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::video::row_motion_from_velocity;
+///
+/// let motion = row_motion_from_velocity(3, (4.0, 0.0), 0.5);
+/// assert_eq!(motion, vec![(-1.0, 0.0), (0.0, 0.0), (1.0, 0.0)]);
+/// ```
+pub fn row_motion_from_velocity(
+    height: usize,
+    inter_frame_motion: (f32, f32),
+    readout_ratio: f32,
+) -> Vec<(f32, f32)> {
+    let (vx, vy) = inter_frame_motion;
+    let middle = (height as f32 - 1.0) / 2.0;
+    let row_time = readout_ratio / (height.max(2) - 1) as f32;
+    (0..height)
+        .map(|y| {
+            let t = (y as f32 - middle) * row_time;
+            (vx * t, vy * t)
+        })
+        .collect()
+}
+
+/// Compensates the skew of a frame captured with a rolling shutter.
+///
+/// Each row of a rolling shutter frame is exposed at a different time, so that a moving
+/// camera skews and wobbles the image. Each row is shifted back by its motion relative to
+/// the reference time, e.g. as computed with [`row_motion_from_velocity`].
+///
+/// # Arguments
+///
+/// * `frame` - The input frame with shape (H, W, C).
+/// * `per_row_motion` - The (x, y) image motion of each row relative to the reference time.
+/// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the pixels mapped outside of the frame.
+///
+/// # Returns
+///
+/// The corrected frame with the same shape as the input.
+///
+/// # Errors
+///
+/// Returns an error if the number of motions does not match the height of the frame.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::interpolation::{BorderMode, InterpolationMode};
+/// use kornia_rs::video::rolling_shutter_correct;
+///
+/// let frame = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 2,
+///     },
+///     vec![0.0, 1.0, 2.0, 0.0, 0.0, 1.0],
+/// )
+/// .unwrap();
+///
+/// // the second row moved one pixel to the right during the readout
+/// let corrected = rolling_shutter_correct(
+///     &frame,
+///     &[(0.0, 0.0), (1.0, 0.0)],
+///     InterpolationMode::Nearest,
+///     BorderMode::Replicate,
+/// )
+/// .unwrap();
+/// assert_eq!(corrected.data.as_slice().unwrap(), &[0.0, 1.0, 2.0, 0.0, 1.0, 1.0]);
+/// ```
+pub fn rolling_shutter_correct<const CHANNELS: usize>(
+    frame: &Image<f32, CHANNELS>,
+    per_row_motion: &[(f32, f32)],
+    interpolation: InterpolationMode,
+    border: BorderMode,
+) -> Result<Image<f32, CHANNELS>> {
+    if per_row_motion.len() != frame.height() {
+        return Err(anyhow::anyhow!(
+            "The number of row motions {} does not match the frame height {}",
+            per_row_motion.len(),
+            frame.height()
+        ));
+    }
+
+    let mut map_x = Image::<f32, 1>::from_size_val(frame.size(), 0.0)?;
+    let mut map_y = Image::<f32, 1>::from_size_val(frame.size(), 0.0)?;
+    ndarray::Zip::indexed(map_x.data.rows_mut())
+        .and(map_y.data.rows_mut())
+        .par_for_each(|(y, x), mut u, mut v| {
+            let (dx, dy) = per_row_motion[y];
+            u[0] = x as f32 + dx;
+            v[0] = y as f32 + dy;
+        });
+
+    remap(frame, &map_x, &map_y, interpolation, border)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use crate::interpolation::{BorderMode, InterpolationMode};
+    use anyhow::Result;
+
+    #[test]
+    fn rolling_shutter_correct() -> Result<()> {
+        // a vertical line skewed by a horizontal motion of 8 pixels per frame
+        let (width, height) = (16, 9);
+        let motion = super::row_motion_from_velocity(height, (8.0, 0.0), 1.0);
+        let mut frame = Image::<f32, 1>::from_size_val(ImageSize { width, height }, 0.0)?;
+        for (y, (dx, _)) in motion.iter().enumerate() {
+            frame.data[[y, (8.0 + dx) as usize, 0]] = 1.0;
+        }
+
+        let corrected = super::rolling_shutter_correct(
+            &frame,
+            &motion,
+            InterpolationMode::Nearest,
+            BorderMode::Constant(0.0),
+        )?;
+        for y in 0..height {
+            assert_eq!(corrected.data[[y, 8, 0]], 1.0, "row {}", y);
+        }
+
+        assert!(super::rolling_shutter_correct(
+            &frame,
+            &motion[1..],
+            InterpolationMode::Nearest,
+            BorderMode::Constant(0.0),
+        )
+        .is_err());
+        Ok(())
+    }
+}
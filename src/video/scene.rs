@@ -0,0 +1,335 @@
+use crate::image::{Image, ImageSize};
+use anyhow::Result;
+
+/// The number of bins of the histogram of each channel.
+const HISTOGRAM_BINS: usize = 16;
+
+/// The minimum gradient magnitude of an edge pixel.
+const EDGE_THRESHOLD: f32 = 40.0;
+
+/// The distance in pixels under which two edges are considered the same.
+const EDGE_RADIUS: usize = 2;
+
+/// The methods to detect the shot boundaries of a video.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SceneMethod {
+    /// Compare the color histograms of consecutive frames.
+    ///
+    /// The score is the mean over the channels of the total variation distance between the
+    /// normalized histograms, in the range [0, 1].
+    HistogramDifference {
+        /// The minimum score of a cut.
+        threshold: f32,
+    },
+    /// Compare the edges of consecutive frames.
+    ///
+    /// The score is the edge change ratio of Zabih et al. (1995), the largest fraction of
+    /// entering or exiting edges, in the range [0, 1]. It is robust to the changes of
+    /// lighting and the motion of the camera.
+    EdgeChangeRatio {
+        /// The minimum score of a cut.
+        threshold: f32,
+    },
+}
+
+/// A shot boundary detected in a video.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneCut {
+    /// The index of the first frame of the new scene.
+    pub frame_index: usize,
+    /// The score of the cut, in the range [0, 1].
+    pub score: f32,
+}
+
+/// The features of a frame compared by the scene detector.
+enum FrameFeatures {
+    Histogram(Vec<f32>),
+    Edges {
+        size: ImageSize,
+        count: usize,
+        edges: Vec<bool>,
+        dilated: Vec<bool>,
+    },
+}
+
+impl FrameFeatures {
+    /// Compute the normalized histograms of the channels of a frame.
+    fn histogram(frame: &Image<u8, 3>) -> Self {
+        let mut histogram = vec![0f32; 3 * HISTOGRAM_BINS];
+        for pixel in frame.data.rows() {
+            for c in 0..3 {
+                histogram[c * HISTOGRAM_BINS + pixel[c] as usize * HISTOGRAM_BINS / 256] += 1.0;
+            }
+        }
+        let n = (frame.width() * frame.height()).max(1) as f32;
+        histogram.iter_mut().for_each(|h| *h /= n);
+        Self::Histogram(histogram)
+    }
+
+    /// Compute the edge map of a frame and its dilation.
+    fn edges(frame: &Image<u8, 3>) -> Self {
+        let size = frame.size();
+        let (width, height) = (size.width, size.height);
+        let gray = frame
+            .data
+            .rows()
+            .into_iter()
+            .map(|p| (p[0] as f32 + p[1] as f32 + p[2] as f32) / 3.0)
+            .collect::<Vec<_>>();
+
+        let mut edges = vec![false; width * height];
+        for y in 1..height.saturating_sub(1) {
+            for x in 1..width.saturating_sub(1) {
+                let i = y * width + x;
+                let gx = gray[i + 1] - gray[i - 1];
+                let gy = gray[i + width] - gray[i - width];
+                edges[i] = gx.abs() + gy.abs() > EDGE_THRESHOLD;
+            }
+        }
+
+        let mut dilated = vec![false; width * height];
+        for (i, _) in edges.iter().enumerate().filter(|(_, &e)| e) {
+            let (x, y) = (i % width, i / width);
+            for v in y.saturating_sub(EDGE_RADIUS)..(y + EDGE_RADIUS + 1).min(height) {
+                for u in x.saturating_sub(EDGE_RADIUS)..(x + EDGE_RADIUS + 1).min(width) {
+                    dilated[v * width + u] = true;
+                }
+            }
+        }
+
+        Self::Edges {
+            size,
+            count: edges.iter().filter(|&&e| e).count(),
+            edges,
+            dilated,
+        }
+    }
+
+    /// Compute the score of the change between two frames.
+    fn score(&self, other: &Self) -> f32 {
+        match (self, other) {
+            (Self::Histogram(a), Self::Histogram(b)) => {
+                let l1 = a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum::<f32>();
+                0.5 * l1 / 3.0
+            }
+            (
+                Self::Edges {
+                    size: size_a,
+                    count: count_a,
+                    edges: edges_a,
+                    dilated: dilated_a,
+                },
+                Self::Edges {
+                    size: size_b,
+                    count: count_b,
+                    edges: edges_b,
+                    dilated: dilated_b,
+                },
+            ) => {
+                if size_a != size_b {
+                    return 1.0;
+                }
+                let ratio = |edges: &[bool], dilated: &[bool], count: usize| {
+                    if count == 0 {
+                        return 0.0;
+                    }
+                    let changed = edges.iter().zip(dilated).filter(|(&e, &d)| e && !d).count();
+                    changed as f32 / count as f32
+                };
+                // the edges of the previous frame exiting and of the next frame entering
+                let exiting = ratio(edges_a, dilated_b, *count_a);
+                let entering = ratio(edges_b, dilated_a, *count_b);
+                exiting.max(entering)
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+/// Detects the shot boundaries of a video stream.
+///
+/// The frames are fed one by one, e.g. while decoding a video or reading a camera, and
+/// the detector reports a cut when a frame differs enough from the previous one.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::video::{SceneDetector, SceneMethod};
+///
+/// let size = ImageSize {
+///     width: 8,
+///     height: 8,
+/// };
+/// let frames = [10, 12, 11, 200, 201].map(|v| Image::<u8, 3>::from_size_val(size, v).unwrap());
+///
+/// let mut detector = SceneDetector::new(SceneMethod::HistogramDifference { threshold: 0.5 });
+/// let cuts = frames
+///     .iter()
+///     .filter_map(|frame| detector.process(frame).unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(cuts.len(), 1);
+/// assert_eq!(cuts[0].frame_index, 3);
+/// ```
+pub struct SceneDetector {
+    method: SceneMethod,
+    min_scene_length: usize,
+    previous: Option<FrameFeatures>,
+    frame_index: usize,
+    scene_start: usize,
+}
+
+impl SceneDetector {
+    /// Create a new scene detector.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method to compare the frames.
+    pub fn new(method: SceneMethod) -> Self {
+        Self {
+            method,
+            min_scene_length: 1,
+            previous: None,
+            frame_index: 0,
+            scene_start: 0,
+        }
+    }
+
+    /// Set the minimum number of frames of a scene, to ignore the cuts following too closely
+    /// a previous one, e.g. on flashes. Defaults to 1.
+    pub fn with_min_scene_length(mut self, min_scene_length: usize) -> Self {
+        self.min_scene_length = min_scene_length;
+        self
+    }
+
+    /// Get the number of frames processed so far.
+    pub fn num_frames(&self) -> usize {
+        self.frame_index
+    }
+
+    /// Restart the detection on a new stream.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.frame_index = 0;
+        self.scene_start = 0;
+    }
+
+    /// Process the next frame of the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The next RGB frame with shape (H, W, 3).
+    ///
+    /// # Returns
+    ///
+    /// The cut starting a new scene at this frame, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is empty.
+    pub fn process(&mut self, frame: &Image<u8, 3>) -> Result<Option<SceneCut>> {
+        if frame.width() == 0 || frame.height() == 0 {
+            return Err(anyhow::anyhow!("Empty frame"));
+        }
+
+        let (features, threshold) = match self.method {
+            SceneMethod::HistogramDifference { threshold } => {
+                (FrameFeatures::histogram(frame), threshold)
+            }
+            SceneMethod::EdgeChangeRatio { threshold } => (FrameFeatures::edges(frame), threshold),
+        };
+
+        let index = self.frame_index;
+        self.frame_index += 1;
+
+        let score = self
+            .previous
+            .as_ref()
+            .map(|previous| previous.score(&features));
+        self.previous = Some(features);
+        let Some(score) = score else {
+            return Ok(None);
+        };
+
+        if score < threshold || index - self.scene_start < self.min_scene_length {
+            return Ok(None);
+        }
+
+        self.scene_start = index;
+        Ok(Some(SceneCut {
+            frame_index: index,
+            score,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SceneDetector, SceneMethod};
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    #[test]
+    fn scene_detector() -> Result<()> {
+        let size = ImageSize {
+            width: 32,
+            height: 24,
+        };
+
+        // vertical bars panning slowly, a brightness change, then horizontal bars
+        let bars = |offset: usize, vertical: bool, gain: u32| {
+            Image::<u8, 3>::new(
+                size,
+                (0..32 * 24 * 3)
+                    .map(|i| {
+                        let (x, y) = (i / 3 % 32, i / 3 / 32);
+                        let t = if vertical { x + offset } else { y };
+                        let v = if t / 12 % 2 == 0 { 40 } else { 160 };
+                        (v * gain / 4) as u8
+                    })
+                    .collect(),
+            )
+        };
+        let frames = [
+            bars(0, true, 4)?,
+            bars(1, true, 4)?,
+            bars(2, true, 5)?,
+            bars(2, false, 5)?,
+            bars(2, false, 5)?,
+        ];
+
+        let mut edge_detector = SceneDetector::new(SceneMethod::EdgeChangeRatio { threshold: 0.5 });
+        let mut histogram_detector =
+            SceneDetector::new(SceneMethod::HistogramDifference { threshold: 0.5 });
+        let (mut edge_cuts, mut histogram_cuts) = (vec![], vec![]);
+        for frame in frames.iter() {
+            edge_cuts.extend(edge_detector.process(frame)?);
+            histogram_cuts.extend(histogram_detector.process(frame)?);
+        }
+
+        // the edges ignore the brightness change, unlike the histograms
+        assert_eq!(
+            edge_cuts.iter().map(|c| c.frame_index).collect::<Vec<_>>(),
+            [3]
+        );
+        assert_eq!(
+            histogram_cuts
+                .iter()
+                .map(|c| c.frame_index)
+                .collect::<Vec<_>>(),
+            [2]
+        );
+
+        // a minimum scene length drops the cuts too close to each other
+        let mut detector = SceneDetector::new(SceneMethod::HistogramDifference { threshold: 0.5 })
+            .with_min_scene_length(3);
+        let mut cuts = vec![];
+        for frame in frames.iter().chain(frames[..1].iter()) {
+            cuts.extend(detector.process(frame)?);
+        }
+        assert_eq!(cuts.iter().map(|c| c.frame_index).collect::<Vec<_>>(), [5]);
+        assert_eq!(detector.num_frames(), 6);
+        Ok(())
+    }
+}
@@ -0,0 +1,512 @@
+use crate::image::{Image, ImageSize};
+use crate::interpolation::InterpolationMode;
+use anyhow::Result;
+use std::time::Duration;
+
+/// The labels drawn on the tiles of [`make_grid`].
+#[derive(Debug, Clone, Copy)]
+pub struct GridLabels<'a, T> {
+    /// The text of each tile, in the order of the images.
+    pub texts: &'a [&'a str],
+    /// The value of the pixels of the text.
+    pub color: T,
+}
+
+/// Tile a batch of images into a single canvas.
+///
+/// The images are placed row by row in cells of the size of the largest image and
+/// separated by `padding` pixels, including a border around the canvas. Smaller
+/// images are placed at the top-left corner of their cell.
+///
+/// The labels are drawn in the top-left corner of the tiles with a 3x5 pixel font,
+/// scaled with the height of the cells. Only the digits, the letters, ignoring the
+/// case, and `-._:/` are drawn, the other characters are left blank.
+///
+/// # Arguments
+///
+/// * `images` - The images to tile with shape (H, W, C).
+/// * `cols` - The number of images per row.
+/// * `padding` - The number of pixels between the images.
+/// * `pad_value` - The value of the padding pixels.
+/// * `labels` - The optional labels of the tiles.
+///
+/// # Returns
+///
+/// The canvas with all the images.
+///
+/// # Errors
+///
+/// Returns an error if there are no images, the number of columns is zero or the
+/// number of labels does not match the number of images.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::viz::{make_grid, GridLabels};
+///
+/// let size = ImageSize {
+///     width: 4,
+///     height: 3,
+/// };
+/// let images = vec![Image::<u8, 3>::from_size_val(size, 255).unwrap(); 5];
+///
+/// let grid = make_grid(&images, 3, 2, 0, None).unwrap();
+/// assert_eq!(grid.width(), 3 * 4 + 4 * 2);
+/// assert_eq!(grid.height(), 2 * 3 + 3 * 2);
+///
+/// let labels = GridLabels {
+///     texts: &["cat", "dog", "cat", "cat", "dog"],
+///     color: 0,
+/// };
+/// let grid = make_grid(&images, 3, 2, 0, Some(&labels)).unwrap();
+/// ```
+pub fn make_grid<T, const CHANNELS: usize>(
+    images: &[Image<T, CHANNELS>],
+    cols: usize,
+    padding: usize,
+    pad_value: T,
+    labels: Option<&GridLabels<T>>,
+) -> Result<Image<T, CHANNELS>>
+where
+    T: Copy + Clone + Default + Send + Sync,
+{
+    if images.is_empty() {
+        return Err(anyhow::anyhow!("No images to tile."));
+    }
+
+    if cols == 0 {
+        return Err(anyhow::anyhow!("The number of columns must be positive."));
+    }
+
+    if let Some(labels) = labels {
+        if labels.texts.len() != images.len() {
+            return Err(anyhow::anyhow!(
+                "The number of labels ({}) does not match the number of images ({}).",
+                labels.texts.len(),
+                images.len()
+            ));
+        }
+    }
+
+    let cols = cols.min(images.len());
+    let rows = images.len().div_ceil(cols);
+
+    // size of the cells
+    let cell_width = images.iter().map(|img| img.width()).max().unwrap_or(0);
+    let cell_height = images.iter().map(|img| img.height()).max().unwrap_or(0);
+
+    let mut grid = Image::<T, CHANNELS>::from_size_val(
+        ImageSize {
+            width: cols * cell_width + (cols + 1) * padding,
+            height: rows * cell_height + (rows + 1) * padding,
+        },
+        pad_value,
+    )?;
+
+    // keep the labels readable on the large tiles
+    let scale = (cell_height / 48).max(1);
+
+    for (i, image) in images.iter().enumerate() {
+        let x0 = padding + (i % cols) * (cell_width + padding);
+        let y0 = padding + (i / cols) * (cell_height + padding);
+
+        grid.data
+            .slice_mut(ndarray::s![
+                y0..y0 + image.height(),
+                x0..x0 + image.width(),
+                ..
+            ])
+            .assign(&image.data);
+
+        if let Some(labels) = labels {
+            let cell = (x0, y0, x0 + cell_width, y0 + cell_height);
+            draw_label(&mut grid, labels.texts[i], cell, scale, labels.color);
+        }
+    }
+
+    Ok(grid)
+}
+
+/// Get the rows of the 3x5 glyph of a character, with the left pixel in the third bit.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        _ => [0; 5],
+    }
+}
+
+/// Draw a text one pixel inside the top-left corner of a cell, clipped to the cell.
+///
+/// # Arguments
+///
+/// * `image` - The image to draw on.
+/// * `text` - The text to draw.
+/// * `cell` - The cell as (x0, y0, x1, y1), the end excluded.
+/// * `scale` - The size of the pixels of the font.
+/// * `color` - The value of the pixels of the text.
+fn draw_label<T: Copy, const CHANNELS: usize>(
+    image: &mut Image<T, CHANNELS>,
+    text: &str,
+    cell: (usize, usize, usize, usize),
+    scale: usize,
+    color: T,
+) {
+    let (x0, y0, x1, y1) = cell;
+    for (i, c) in text.chars().enumerate() {
+        // the glyphs are separated by one pixel
+        let gx = x0 + scale * (1 + 4 * i);
+        if gx >= x1 {
+            break;
+        }
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                let (px, py) = (gx + col * scale, y0 + scale * (1 + row));
+                image
+                    .data
+                    .slice_mut(ndarray::s![
+                        py.min(y1)..(py + scale).min(y1),
+                        px.min(x1)..(px + scale).min(x1),
+                        ..
+                    ])
+                    .fill(color);
+            }
+        }
+    }
+}
+
+/// A compositor tiling the latest frames of several streams into one frame.
+///
+/// Each stream updates its own tile at its own rate, while the composite frames are
+/// produced on a fixed clock with the most recent frame of every stream. The tiles of
+/// the streams without frames yet are black.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::viz::Compositor;
+/// use std::time::Duration;
+///
+/// let tile_size = ImageSize {
+///     width: 32,
+///     height: 24,
+/// };
+/// let mut compositor = Compositor::new(2, 2, tile_size).unwrap().with_fps(10);
+///
+/// // the first stream runs at 30 fps and the second one at 5 fps
+/// let mut composites = 0;
+/// for i in 0..30u64 {
+///     let frame = Image::<u8, 3>::from_size_val(tile_size, 255).unwrap();
+///     compositor.update(0, &frame).unwrap();
+///     if i % 6 == 0 {
+///         compositor.update(1, &frame).unwrap();
+///     }
+///
+///     if let Some(composite) = compositor.tick(Duration::from_millis(i * 1000 / 30)).unwrap() {
+///         assert_eq!(composite.width(), 64);
+///         composites += 1;
+///     }
+/// }
+/// assert_eq!(composites, 10);
+/// ```
+pub struct Compositor {
+    tiles: Vec<Image<u8, 3>>,
+    tile_size: ImageSize,
+    cols: usize,
+    padding: usize,
+    period: Duration,
+    next_tick: Option<Duration>,
+}
+
+impl Compositor {
+    /// Creates a new Compositor object at 30 frames per second without padding.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_streams` - The number of streams to tile
+    /// * `cols` - The number of tiles per row
+    /// * `tile_size` - The size of each tile, the frames are resized to fit
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of streams, the number of columns or the tile
+    /// size is zero.
+    pub fn new(num_streams: usize, cols: usize, tile_size: ImageSize) -> Result<Self> {
+        if num_streams == 0 || cols == 0 || tile_size.width == 0 || tile_size.height == 0 {
+            return Err(anyhow::anyhow!(
+                "Invalid compositor of {} streams, {} columns and tiles of {}.",
+                num_streams,
+                cols,
+                tile_size
+            ));
+        }
+
+        Ok(Self {
+            tiles: vec![Image::from_size_val(tile_size, 0)?; num_streams],
+            tile_size,
+            cols,
+            padding: 0,
+            period: Duration::from_secs(1) / 30,
+            next_tick: None,
+        })
+    }
+
+    /// Sets the number of composite frames per second.
+    ///
+    /// # Arguments
+    ///
+    /// * `fps` - The desired frames per second, must be positive
+    pub fn with_fps(mut self, fps: u32) -> Self {
+        self.period = Duration::from_secs(1) / fps.max(1);
+        self
+    }
+
+    /// Sets the number of black pixels between the tiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The desired padding
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the latest frame of a stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the stream
+    /// * `frame` - The new frame of the stream
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream index is out of range or the frame cannot be resized.
+    pub fn update(&mut self, index: usize, frame: &Image<u8, 3>) -> Result<()> {
+        let num_streams = self.tiles.len();
+        let tile = self.tiles.get_mut(index).ok_or_else(|| {
+            anyhow::anyhow!(
+                "The stream {} is out of range for {} streams.",
+                index,
+                num_streams
+            )
+        })?;
+
+        *tile = if frame.size() == self.tile_size {
+            frame.clone()
+        } else {
+            crate::resize::resize_fast(frame, self.tile_size, InterpolationMode::Bilinear)?
+        };
+
+        Ok(())
+    }
+
+    /// Tiles the latest frames of all the streams.
+    pub fn compose(&self) -> Result<Image<u8, 3>> {
+        make_grid(&self.tiles, self.cols, self.padding, 0, None)
+    }
+
+    /// Advances the clock and composes a frame when a new one is due.
+    ///
+    /// The ticks missed while no frame arrived are skipped rather than produced late.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The current time of the streams
+    ///
+    /// # Returns
+    ///
+    /// The composite frame if one is due at the given time.
+    pub fn tick(&mut self, timestamp: Duration) -> Result<Option<Image<u8, 3>>> {
+        let next_tick = self.next_tick.unwrap_or(timestamp);
+        if timestamp < next_tick {
+            return Ok(None);
+        }
+
+        // schedule the first tick after the current time
+        let missed = (timestamp - next_tick).as_nanos() / self.period.as_nanos();
+        self.next_tick = Some(next_tick + self.period * (missed as u32 + 1));
+
+        Ok(Some(self.compose()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+    use std::time::Duration;
+
+    #[test]
+    fn make_grid_layout() -> Result<()> {
+        let images = vec![
+            Image::<u8, 1>::from_size_val(
+                ImageSize {
+                    width: 2,
+                    height: 2,
+                },
+                1,
+            )?,
+            Image::<u8, 1>::from_size_val(
+                ImageSize {
+                    width: 1,
+                    height: 2,
+                },
+                2,
+            )?,
+            Image::<u8, 1>::from_size_val(
+                ImageSize {
+                    width: 2,
+                    height: 1,
+                },
+                3,
+            )?,
+        ];
+
+        let grid = super::make_grid(&images, 2, 1, 9, None)?;
+        assert_eq!(grid.width(), 7);
+        assert_eq!(grid.height(), 7);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            9, 9, 9, 9, 9, 9, 9,
+            9, 1, 1, 9, 2, 9, 9,
+            9, 1, 1, 9, 2, 9, 9,
+            9, 9, 9, 9, 9, 9, 9,
+            9, 3, 3, 9, 9, 9, 9,
+            9, 9, 9, 9, 9, 9, 9,
+            9, 9, 9, 9, 9, 9, 9,
+        ];
+        assert_eq!(grid.data.iter().copied().collect::<Vec<_>>(), expected);
+
+        assert!(super::make_grid::<u8, 1>(&[], 2, 1, 0, None).is_err());
+        assert!(super::make_grid(&images, 0, 1, 0, None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn make_grid_labels() -> Result<()> {
+        let size = ImageSize {
+            width: 6,
+            height: 7,
+        };
+        let images = vec![Image::<u8, 1>::from_size_val(size, 0)?; 2];
+
+        let labels = super::GridLabels {
+            texts: &["1", "t"],
+            color: 255,
+        };
+        let grid = super::make_grid(&images, 2, 0, 9, Some(&labels))?;
+
+        // the glyphs start one pixel inside the cells
+        let rows = |x0: usize| {
+            (1..6)
+                .map(|y| {
+                    (0..3)
+                        .map(|x| grid.data[[y, x0 + 1 + x, 0]])
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+        #[rustfmt::skip]
+        let one = vec![
+            vec![0, 255, 0],
+            vec![255, 255, 0],
+            vec![0, 255, 0],
+            vec![0, 255, 0],
+            vec![255, 255, 255],
+        ];
+        assert_eq!(rows(0), one);
+        assert_eq!(rows(6)[0], vec![255, 255, 255]);
+        assert_eq!(rows(6)[1], vec![0, 255, 0]);
+        // the text does not overflow the first row and column of the cells
+        assert!((0..12).all(|x| grid.data[[0, x, 0]] == 0));
+        assert!((0..7).all(|y| grid.data[[y, 0, 0]] == 0));
+
+        let labels = super::GridLabels {
+            texts: &["1"],
+            color: 255,
+        };
+        assert!(super::make_grid(&images, 2, 0, 9, Some(&labels)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn compositor_clock() -> Result<()> {
+        let tile_size = ImageSize {
+            width: 2,
+            height: 1,
+        };
+        let mut compositor = super::Compositor::new(3, 2, tile_size)?
+            .with_fps(10)
+            .with_padding(1);
+
+        let frame = Image::<u8, 3>::from_size_val(tile_size, 7)?;
+        compositor.update(1, &frame)?;
+        assert!(compositor.update(3, &frame).is_err());
+
+        // the composite frames follow the clock and skip the missed ticks
+        let ms = Duration::from_millis;
+        assert!(compositor.tick(ms(0))?.is_some());
+        assert!(compositor.tick(ms(50))?.is_none());
+        assert!(compositor.tick(ms(100))?.is_some());
+        assert!(compositor.tick(ms(450))?.is_some());
+        assert!(compositor.tick(ms(480))?.is_none());
+        assert!(compositor.tick(ms(500))?.is_some());
+
+        let composite = compositor.compose()?;
+        assert_eq!(composite.width(), 7);
+        assert_eq!(composite.height(), 5);
+        assert_eq!(composite.get_pixel(4, 1, 0)?, 7);
+        assert_eq!(composite.get_pixel(1, 1, 0)?, 0);
+        assert_eq!(composite.get_pixel(1, 3, 0)?, 0);
+
+        assert!(super::Compositor::new(0, 2, tile_size).is_err());
+        Ok(())
+    }
+}
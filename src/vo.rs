@@ -0,0 +1,448 @@
+use crate::calibration::{CameraExtrinsic, CameraIntrinsic};
+use crate::geometry::epipolar::{decompose_essential, find_essential};
+use crate::geometry::{Point2, RansacParams};
+use crate::image::Image;
+use crate::optim::{bundle_adjust, BundleAdjustParams, Observation};
+use crate::tracking::{KltParams, KltTracker, TrackState};
+use anyhow::Result;
+use std::collections::HashMap;
+
+type Matrix3 = [[f64; 3]; 3];
+
+const IDENTITY: Matrix3 = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+fn mat_vec(m: &Matrix3, v: &[f64; 3]) -> [f64; 3] {
+    [0, 1, 2].map(|i| (0..3).map(|k| m[i][k] * v[k]).sum())
+}
+
+fn matmul(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+fn transpose(m: &Matrix3) -> Matrix3 {
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| m[j][i]))
+}
+
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|k| a[k] * b[k]).sum()
+}
+
+fn identity_pose() -> CameraExtrinsic {
+    CameraExtrinsic {
+        rotation: IDENTITY,
+        translation: [0.0; 3],
+    }
+}
+
+/// The parameters of the [`VisualOdometry`].
+#[derive(Debug, Clone)]
+pub struct VoParams {
+    /// The parameters of the feature tracker.
+    pub klt: KltParams,
+    /// The parameters of the essential matrix estimation, with the threshold in pixels.
+    pub ransac: RansacParams,
+    /// The median displacement in pixels of the tracks since the keyframe above which
+    /// the motion is estimated and a new keyframe is created.
+    pub min_parallax: f32,
+    /// The minimum number of inlier tracks to accept a motion.
+    pub min_inliers: usize,
+    /// The parameters of the two-view bundle adjustment refining each motion, or `None`
+    /// to keep the motion of the essential matrix.
+    pub bundle_adjust: Option<BundleAdjustParams>,
+}
+
+impl Default for VoParams {
+    fn default() -> Self {
+        Self {
+            klt: KltParams::default(),
+            ransac: RansacParams {
+                threshold: 1.0,
+                ..Default::default()
+            },
+            min_parallax: 10.0,
+            min_inliers: 20,
+            bundle_adjust: Some(BundleAdjustParams {
+                max_iterations: 10,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// The frame the motion is estimated from.
+struct Keyframe {
+    /// The positions of the tracks in the keyframe by their identifier.
+    positions: HashMap<u64, Point2>,
+    /// The pose of the keyframe.
+    pose: CameraExtrinsic,
+}
+
+/// A monocular visual odometry estimating the camera poses over a stream of frames.
+///
+/// The features are detected and tracked with a [`KltTracker`]. When the tracks moved
+/// enough since the last keyframe, the relative motion is estimated with the essential
+/// matrix, the candidate placing the most points in front of both cameras is kept and
+/// refined with a two-view bundle adjustment, and the frame becomes the new keyframe.
+/// The frames in between keep the pose of the keyframe.
+///
+/// The poses map the points of the first camera frame to the camera frames. As the
+/// scale is not observable by a single camera, each motion between two keyframes has a
+/// unit translation.
+///
+/// Note: the features are the Shi-Tomasi corners of the tracker, there are no FAST or
+/// ORB detectors in the crate.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::calibration::CameraIntrinsic;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::vo::{VisualOdometry, VoParams};
+///
+/// let intrinsic = CameraIntrinsic {
+///     fx: 500.0,
+///     fy: 500.0,
+///     cx: 32.0,
+///     cy: 32.0,
+/// };
+/// let mut vo = VisualOdometry::new(intrinsic, VoParams::default());
+///
+/// let size = ImageSize {
+///     width: 64,
+///     height: 64,
+/// };
+/// let frame = Image::<f32, 1>::from_size_val(size, 0.5).unwrap();
+/// let pose = vo.process(&frame).unwrap();
+/// assert_eq!(pose.translation, [0.0, 0.0, 0.0]);
+/// assert_eq!(vo.trajectory().len(), 1);
+/// ```
+pub struct VisualOdometry {
+    intrinsic: CameraIntrinsic,
+    params: VoParams,
+    tracker: KltTracker,
+    keyframe: Option<Keyframe>,
+    trajectory: Vec<CameraExtrinsic>,
+}
+
+impl VisualOdometry {
+    /// Create a new visual odometry starting at the identity pose.
+    ///
+    /// # Arguments
+    ///
+    /// * `intrinsic` - The intrinsic parameters of the camera.
+    /// * `params` - The parameters of the odometry.
+    pub fn new(intrinsic: CameraIntrinsic, params: VoParams) -> Self {
+        Self {
+            intrinsic,
+            tracker: KltTracker::new(params.klt.clone()),
+            params,
+            keyframe: None,
+            trajectory: Vec::new(),
+        }
+    }
+
+    /// Get the poses of all the processed frames.
+    pub fn trajectory(&self) -> &[CameraExtrinsic] {
+        &self.trajectory
+    }
+
+    /// Process a new frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The new grayscale frame with shape (H, W, 1).
+    ///
+    /// # Returns
+    ///
+    /// The pose of the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame size changes or the feature detection fails.
+    pub fn process(&mut self, frame: &Image<f32, 1>) -> Result<CameraExtrinsic> {
+        let tracks = self.tracker.update(frame)?;
+        let positions = tracks
+            .iter()
+            .filter(|t| t.state != TrackState::Lost)
+            .map(|t| (t.id, t.position))
+            .collect::<HashMap<_, _>>();
+
+        let keyframe = match &self.keyframe {
+            Some(keyframe) => keyframe,
+            None => {
+                // the first frame is the origin of the trajectory
+                let pose = identity_pose();
+                self.keyframe = Some(Keyframe {
+                    positions,
+                    pose: pose.clone(),
+                });
+                self.trajectory.push(pose.clone());
+                return Ok(pose);
+            }
+        };
+
+        // the tracks followed since the keyframe
+        let (points1, points2): (Vec<_>, Vec<_>) = tracks
+            .iter()
+            .filter(|t| t.state == TrackState::Tracked)
+            .filter_map(|t| keyframe.positions.get(&t.id).map(|&p| (p, t.position)))
+            .unzip();
+
+        let mut pose = keyframe.pose.clone();
+        if points1.len() < self.params.min_inliers.max(8) {
+            // too few tracks survived, restart from this frame at the same pose
+            self.keyframe = Some(Keyframe {
+                positions,
+                pose: pose.clone(),
+            });
+        } else if median_parallax(&points1, &points2) >= self.params.min_parallax {
+            if let Some(motion) =
+                estimate_motion(&points1, &points2, &self.intrinsic, &self.params)?
+            {
+                // chain the motion to the pose of the keyframe
+                pose = CameraExtrinsic {
+                    rotation: matmul(&motion.rotation, &keyframe.pose.rotation),
+                    translation: {
+                        let t = mat_vec(&motion.rotation, &keyframe.pose.translation);
+                        [0, 1, 2].map(|k| t[k] + motion.translation[k])
+                    },
+                };
+                self.keyframe = Some(Keyframe {
+                    positions,
+                    pose: pose.clone(),
+                });
+            }
+        }
+
+        self.trajectory.push(pose.clone());
+        Ok(pose)
+    }
+}
+
+/// The median displacement of the point pairs.
+fn median_parallax(points1: &[Point2], points2: &[Point2]) -> f32 {
+    let mut distances = points1
+        .iter()
+        .zip(points2)
+        .map(|(p1, p2)| p1.distance(p2))
+        .collect::<Vec<_>>();
+    distances.sort_by(|a, b| a.total_cmp(b));
+    distances[distances.len() / 2]
+}
+
+/// Triangulate a point from its normalized coordinates in two cameras with the midpoint
+/// of the closest points of the two rays.
+///
+/// # Returns
+///
+/// The point in the first camera frame, or `None` if the rays are parallel.
+fn triangulate(motion: &CameraExtrinsic, x1: &[f64; 3], x2: &[f64; 3]) -> Option<[f64; 3]> {
+    // the second ray in the first camera frame, from the center of the second camera
+    let rt = transpose(&motion.rotation);
+    let c2 = mat_vec(&rt, &motion.translation).map(|x| -x);
+    let d2 = mat_vec(&rt, x2);
+
+    // solve l1 * x1 - l2 * d2 = c2 in the least squares sense
+    let (a, b, c) = (dot(x1, x1), dot(x1, &d2), dot(&d2, &d2));
+    let (d, e) = (dot(x1, &c2), dot(&d2, &c2));
+    let det = a * c - b * b;
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let l1 = (d * c - b * e) / det;
+    let l2 = (b * d - a * e) / det;
+
+    Some([0, 1, 2].map(|k| (l1 * x1[k] + c2[k] + l2 * d2[k]) / 2.0))
+}
+
+/// Estimate the motion between two views from the point pairs.
+///
+/// # Returns
+///
+/// The motion mapping the points of the first camera frame to the second one, with a
+/// unit translation, or `None` if too few pairs are consistent with a motion.
+fn estimate_motion(
+    points1: &[Point2],
+    points2: &[Point2],
+    intrinsic: &CameraIntrinsic,
+    params: &VoParams,
+) -> Result<Option<CameraExtrinsic>> {
+    let (e, inliers) = match find_essential(points1, points2, intrinsic, &params.ransac) {
+        Ok(result) => result,
+        // the tracks are degenerate, e.g. without texture or motion
+        Err(_) => return Ok(None),
+    };
+
+    let normalize = |p: &Point2| {
+        [
+            (p.x as f64 - intrinsic.cx) / intrinsic.fx,
+            (p.y as f64 - intrinsic.cy) / intrinsic.fy,
+            1.0,
+        ]
+    };
+    let pairs = points1
+        .iter()
+        .zip(points2)
+        .zip(&inliers)
+        .filter(|(_, inlier)| **inlier)
+        .map(|((p1, p2), _)| ((*p1, *p2), (normalize(p1), normalize(p2))))
+        .collect::<Vec<_>>();
+
+    // keep the candidate placing the most points in front of both cameras
+    let mut best: Option<(CameraExtrinsic, Vec<usize>, Vec<[f64; 3]>)> = None;
+    for candidate in decompose_essential(&e) {
+        let mut indices = Vec::new();
+        let mut points3d = Vec::new();
+        for (i, (_, (x1, x2))) in pairs.iter().enumerate() {
+            if let Some(x) = triangulate(&candidate, x1, x2) {
+                let z2 = mat_vec(&candidate.rotation, &x)[2] + candidate.translation[2];
+                if x[2] > 0.0 && z2 > 0.0 {
+                    indices.push(i);
+                    points3d.push(x);
+                }
+            }
+        }
+        let better = match &best {
+            Some((_, b, _)) => indices.len() > b.len(),
+            None => true,
+        };
+        if better {
+            best = Some((candidate, indices, points3d));
+        }
+    }
+
+    let (motion, indices, mut points3d) = match best {
+        Some(best) if best.1.len() >= params.min_inliers => best,
+        _ => return Ok(None),
+    };
+
+    let mut cameras = [identity_pose(), motion];
+    if let Some(ba_params) = &params.bundle_adjust {
+        let observations = indices
+            .iter()
+            .enumerate()
+            .flat_map(|(point, &i)| {
+                let (p1, p2) = pairs[i].0;
+                [
+                    Observation {
+                        camera: 0,
+                        point,
+                        pixel: p1,
+                    },
+                    Observation {
+                        camera: 1,
+                        point,
+                        pixel: p2,
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+        bundle_adjust(
+            &mut cameras,
+            &mut points3d,
+            &observations,
+            intrinsic,
+            ba_params,
+        )?;
+    }
+
+    // the scale is not observable, fix the translation to a unit norm
+    let [_, mut motion] = cameras;
+    let norm = dot(&motion.translation, &motion.translation).sqrt();
+    if norm <= f64::EPSILON {
+        return Ok(None);
+    }
+    motion.translation = motion.translation.map(|x| x / norm);
+
+    Ok(Some(motion))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VisualOdometry, VoParams};
+    use crate::calibration::CameraIntrinsic;
+    use crate::geometry::Point2;
+    use crate::image::{Image, ImageSize};
+    use anyhow::Result;
+
+    fn intrinsic() -> CameraIntrinsic {
+        CameraIntrinsic {
+            fx: 500.0,
+            fy: 520.0,
+            cx: 320.0,
+            cy: 240.0,
+        }
+    }
+
+    #[test]
+    fn estimate_motion() -> Result<()> {
+        let intrinsic = intrinsic();
+        let (s, c) = 5f64.to_radians().sin_cos();
+        let rotation = [[c, 0.0, s], [0.0, 1.0, 0.0], [-s, 0.0, c]];
+        let translation = [0.5, 0.1, 0.1];
+
+        let project = |x: [f64; 3]| {
+            Point2::new(
+                (intrinsic.fx * x[0] / x[2] + intrinsic.cx) as f32,
+                (intrinsic.fy * x[1] / x[2] + intrinsic.cy) as f32,
+            )
+        };
+        let (mut points1, mut points2) = (Vec::new(), Vec::new());
+        for i in 0..60 {
+            let x = [
+                ((i * 37) % 101) as f64 / 101.0 * 4.0 - 2.0,
+                ((i * 53) % 89) as f64 / 89.0 * 3.0 - 1.5,
+                4.0 + ((i * 29) % 97) as f64 / 97.0 * 4.0,
+            ];
+            let x2 = super::mat_vec(&rotation, &x);
+            points1.push(project(x));
+            points2.push(project([0, 1, 2].map(|k| x2[k] + translation[k])));
+        }
+
+        let motion =
+            super::estimate_motion(&points1, &points2, &intrinsic, &VoParams::default())?.unwrap();
+
+        // the motion in front of the cameras, with a unit translation
+        let norm = super::dot(&translation, &translation).sqrt();
+        for i in 0..3 {
+            assert!((motion.translation[i] - translation[i] / norm).abs() < 1e-3);
+            for j in 0..3 {
+                assert!((motion.rotation[i][j] - rotation[i][j]).abs() < 1e-3);
+            }
+        }
+
+        // too few pairs to accept a motion
+        assert!(super::estimate_motion(
+            &points1[..10],
+            &points2[..10],
+            &intrinsic,
+            &VoParams::default()
+        )?
+        .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn visual_odometry_static() -> Result<()> {
+        let size = ImageSize {
+            width: 64,
+            height: 64,
+        };
+        let data = (0..64 * 64)
+            .map(|i| {
+                let (x, y) = ((i % 64) as f32, (i / 64) as f32);
+                ((x / 5.0).sin() * (y / 7.0).cos() + 1.0) / 2.0
+            })
+            .collect();
+        let frame = Image::<f32, 1>::new(size, data)?;
+
+        let mut vo = VisualOdometry::new(intrinsic(), VoParams::default());
+        for _ in 0..3 {
+            let pose = vo.process(&frame)?;
+            assert_eq!(pose.rotation, super::IDENTITY);
+            assert_eq!(pose.translation, [0.0; 3]);
+        }
+        assert_eq!(vo.trajectory().len(), 3);
+        Ok(())
+    }
+}
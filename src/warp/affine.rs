@@ -1,8 +1,9 @@
 use std::f32::consts::PI;
 
+use crate::geometry::Point2;
 use crate::image::{Image, ImageSize};
 use crate::interpolation::meshgrid;
-use crate::interpolation::{interpolate_pixel, InterpolationMode};
+use crate::interpolation::{interpolate_pixel_border, BorderMode, InterpolationMode};
 use anyhow::Result;
 use ndarray::stack;
 
@@ -79,6 +80,71 @@ pub fn get_rotation_matrix2d(center: (f32, f32), angle: f32, scale: f32) -> Affi
     (alpha, beta, tx, -beta, alpha, ty)
 }
 
+/// Computes the affine transformation mapping three points to three other points.
+///
+/// The transformation is the exact solution of the six equations given by the point
+/// pairs. Use [`invert_affine_transform`] to get the mapping from `dst` to `src`.
+///
+/// # Arguments
+///
+/// * `src` - The three points in the source image.
+/// * `dst` - The three corresponding points in the destination image.
+///
+/// # Returns
+///
+/// The 2x3 affine transformation matrix src -> dst.
+///
+/// # Errors
+///
+/// Returns an error if the source points are collinear.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Point2;
+/// use kornia_rs::warp::get_affine_transform;
+///
+/// let src = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(1.0, 0.0),
+///     Point2::new(0.0, 1.0),
+/// ];
+/// let dst = [
+///     Point2::new(2.0, 3.0),
+///     Point2::new(4.0, 3.0),
+///     Point2::new(2.0, 5.0),
+/// ];
+///
+/// let m = get_affine_transform(&src, &dst).unwrap();
+/// assert_eq!(m, (2.0, 0.0, 2.0, 0.0, 2.0, 3.0));
+/// ```
+pub fn get_affine_transform(src: &[Point2; 3], dst: &[Point2; 3]) -> Result<AffineMatrix> {
+    // solve the two 3x3 systems [x y 1] * [a b c]^T = u and [x y 1] * [d e f]^T = v
+    // with the Cramer's rule, in double precision
+    let [(x0, y0), (x1, y1), (x2, y2)] = src.map(|p| (p.x as f64, p.y as f64));
+
+    let determinant = x0 * (y1 - y2) - y0 * (x1 - x2) + (x1 * y2 - x2 * y1);
+    if determinant.abs() < f64::EPSILON {
+        return Err(anyhow::anyhow!(
+            "The source points are collinear, the affine transform is undefined"
+        ));
+    }
+
+    let solve = |r0: f64, r1: f64, r2: f64| {
+        let a = (r0 * (y1 - y2) - y0 * (r1 - r2) + (r1 * y2 - r2 * y1)) / determinant;
+        let b = (x0 * (r1 - r2) - r0 * (x1 - x2) + (x1 * r2 - x2 * r1)) / determinant;
+        let c = (x0 * (y1 * r2 - y2 * r1) - y0 * (x1 * r2 - x2 * r1) + r0 * (x1 * y2 - x2 * y1))
+            / determinant;
+        (a as f32, b as f32, c as f32)
+    };
+
+    let [(u0, v0), (u1, v1), (u2, v2)] = dst.map(|p| (p.x as f64, p.y as f64));
+    let (a, b, c) = solve(u0, u1, u2);
+    let (d, e, f) = solve(v0, v1, v2);
+
+    Ok((a, b, c, d, e, f))
+}
+
 /// Applies an affine transformation to an image.
 ///
 /// # Arguments
@@ -87,6 +153,7 @@ pub fn get_rotation_matrix2d(center: (f32, f32), angle: f32, scale: f32) -> Affi
 /// * `m` - The 2x3 affine transformation matrix.
 /// * `new_size` - The size of the output image.
 /// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the samples outside of the input image.
 ///
 /// # Returns
 ///
@@ -111,7 +178,14 @@ pub fn get_rotation_matrix2d(center: (f32, f32), angle: f32, scale: f32) -> Affi
 ///   height: 5,
 /// };
 ///
-/// let output = warp_affine(&src, m, new_size, kornia_rs::interpolation::InterpolationMode::Nearest).unwrap();
+/// let output = warp_affine(
+///     &src,
+///     m,
+///     new_size,
+///     kornia_rs::interpolation::InterpolationMode::Nearest,
+///     kornia_rs::interpolation::BorderMode::Replicate,
+/// )
+/// .unwrap();
 ///
 /// assert_eq!(output.size().width, 4);
 /// assert_eq!(output.size().height, 5);
@@ -121,6 +195,7 @@ pub fn warp_affine<const CHANNELS: usize>(
     m: AffineMatrix,
     new_size: ImageSize,
     interpolation: InterpolationMode,
+    border: BorderMode,
 ) -> Result<Image<f32, CHANNELS>> {
     // invert affine transform matrix to find corresponding positions in src from dst
     let m_inv = invert_affine_transform(m);
@@ -152,18 +227,10 @@ pub fn warp_affine<const CHANNELS: usize>(
             let u_src = m_inv.0 * u + m_inv.1 * v + m_inv.2;
             let v_src = m_inv.3 * u + m_inv.4 * v + m_inv.5;
 
-            // TODO: remove -- this is already done in interpolate_pixel
-            if u_src < 0.0
-                || u_src > (src.width() - 1) as f32
-                || v_src < 0.0
-                || v_src > (src.height() - 1) as f32
-            {
-                return;
-            }
-
             // compute the pixel values for each channel
-            let pixels = (0..src.num_channels())
-                .map(|k| interpolate_pixel(&src.data, u_src, v_src, k, interpolation));
+            let pixels = (0..src.num_channels()).map(|k| {
+                interpolate_pixel_border(&src.data, u_src, v_src, k, interpolation, border)
+            });
 
             // write the pixel values to the output image
             for (k, pixel) in pixels.enumerate() {
@@ -174,10 +241,148 @@ pub fn warp_affine<const CHANNELS: usize>(
     Ok(output)
 }
 
+/// Rotates an image by an angle around a center.
+///
+/// The rotation matrix is computed with [`get_rotation_matrix2d`], so that a positive angle
+/// rotates the image counter-clockwise. With `expand` the output image grows to contain
+/// the whole rotated image, centered, otherwise it keeps the size of the input image.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (height, width, channels).
+/// * `angle` - The rotation angle in degrees.
+/// * `expand` - Whether to enlarge the output image to contain the whole rotated image.
+/// * `center` - The center of the rotation, or the center of the image if None.
+/// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the samples outside of the input image.
+///
+/// # Returns
+///
+/// The rotated image.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::interpolation::{BorderMode, InterpolationMode};
+/// use kornia_rs::warp::rotate;
+///
+/// let src = Image::<f32, 3>::from_size_val(
+///     ImageSize {
+///         width: 4,
+///         height: 2,
+///     },
+///     1.0,
+/// )
+/// .unwrap();
+///
+/// let rotated = rotate(
+///     &src,
+///     90.0,
+///     true,
+///     None,
+///     InterpolationMode::Bilinear,
+///     BorderMode::Constant(0.0),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(rotated.size().width, 2);
+/// assert_eq!(rotated.size().height, 4);
+/// ```
+pub fn rotate<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    angle: f32,
+    expand: bool,
+    center: Option<(f32, f32)>,
+    interpolation: InterpolationMode,
+    border: BorderMode,
+) -> Result<Image<f32, CHANNELS>> {
+    let (width, height) = (src.width() as f32, src.height() as f32);
+    let center = center.unwrap_or(((width - 1.0) / 2.0, (height - 1.0) / 2.0));
+    let (a, b, tx, d, e, ty) = get_rotation_matrix2d(center, angle, 1.0);
+
+    if !expand {
+        return warp_affine(src, (a, b, tx, d, e, ty), src.size(), interpolation, border);
+    }
+
+    // the extent of the rotated pixel area, from the outer edges of the corner pixels
+    let corners = [
+        (-0.5, -0.5),
+        (width - 0.5, -0.5),
+        (-0.5, height - 0.5),
+        (width - 0.5, height - 0.5),
+    ]
+    .map(|(x, y)| (a * x + b * y + tx, d * x + e * y + ty));
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+    for (x, y) in corners {
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    }
+
+    // tolerate the rounding errors of the rotation, e.g. for multiples of 90 degrees
+    let new_size = ImageSize {
+        width: ((max_x - min_x - 1e-3).ceil() as usize).max(1),
+        height: ((max_y - min_y - 1e-3).ceil() as usize).max(1),
+    };
+
+    // center the rotated area in the new image
+    let shift_x = (new_size.width as f32 - (max_x - min_x)) / 2.0 - 0.5 - min_x;
+    let shift_y = (new_size.height as f32 - (max_y - min_y)) / 2.0 - 0.5 - min_y;
+
+    warp_affine(
+        src,
+        (a, b, tx + shift_x, d, e, ty + shift_y),
+        new_size,
+        interpolation,
+        border,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
 
+    #[test]
+    fn get_affine_transform() -> Result<()> {
+        use crate::geometry::Point2;
+
+        let m = super::get_rotation_matrix2d((3.0, 2.0), 30.0, 1.5);
+        let apply =
+            |p: Point2| Point2::new(m.0 * p.x + m.1 * p.y + m.2, m.3 * p.x + m.4 * p.y + m.5);
+
+        let src = [
+            Point2::new(1.0, 2.0),
+            Point2::new(10.0, -3.0),
+            Point2::new(-4.0, 7.0),
+        ];
+        let dst = src.map(apply);
+
+        let m_est = super::get_affine_transform(&src, &dst)?;
+        let (a, b) = (
+            [m.0, m.1, m.2, m.3, m.4, m.5],
+            [m_est.0, m_est.1, m_est.2, m_est.3, m_est.4, m_est.5],
+        );
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-4);
+        }
+
+        // the inverse maps the destination points back
+        let m_inv = super::get_affine_transform(&dst, &src)?;
+        let expected = super::invert_affine_transform(m);
+        assert!((m_inv.2 - expected.2).abs() < 1e-3);
+        assert!((m_inv.5 - expected.5).abs() < 1e-3);
+
+        let collinear = [
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(2.0, 2.0),
+        ];
+        assert!(super::get_affine_transform(&collinear, &dst).is_err());
+        Ok(())
+    }
+
     #[test]
     fn warp_affine_smoke_ch3() -> Result<()> {
         use crate::image::{Image, ImageSize};
@@ -196,6 +401,7 @@ mod tests {
                 height: 3,
             },
             super::InterpolationMode::Bilinear,
+            super::BorderMode::Constant(0.0),
         )?;
         assert_eq!(image_transformed.num_channels(), 3);
         assert_eq!(image_transformed.size().width, 2);
@@ -221,6 +427,7 @@ mod tests {
                 height: 3,
             },
             super::InterpolationMode::Nearest,
+            super::BorderMode::Constant(0.0),
         )?;
         assert_eq!(image_transformed.num_channels(), 1);
         assert_eq!(image_transformed.size().width, 2);
@@ -246,6 +453,7 @@ mod tests {
                 height: 5,
             },
             super::InterpolationMode::Nearest,
+            super::BorderMode::Constant(0.0),
         )?;
         assert_eq!(image_transformed.data, image.data);
         assert_eq!(image_transformed.size(), image.size());
@@ -270,6 +478,7 @@ mod tests {
                 height: 2,
             },
             super::InterpolationMode::Nearest,
+            super::BorderMode::Constant(0.0),
         )?;
         assert_eq!(
             image_transformed.data,
@@ -277,4 +486,50 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn rotate() -> Result<()> {
+        use crate::image::{Image, ImageSize};
+        use crate::interpolation::{BorderMode, InterpolationMode};
+
+        let image = Image::<f32, 1>::new(
+            ImageSize {
+                width: 3,
+                height: 2,
+            },
+            vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+        )?;
+
+        // a quarter turn counter-clockwise moves the top-right corner to the top-left
+        let rotated = super::rotate(
+            &image,
+            90.0,
+            true,
+            None,
+            InterpolationMode::Nearest,
+            BorderMode::Replicate,
+        )?;
+        assert_eq!(rotated.size().width, 2);
+        assert_eq!(rotated.size().height, 3);
+        assert_eq!(
+            rotated.data.as_slice().unwrap(),
+            &[2.0, 5.0, 1.0, 4.0, 0.0, 3.0]
+        );
+
+        // without expansion the size is kept, and a half turn flips both axes
+        let rotated = super::rotate(
+            &image,
+            180.0,
+            false,
+            None,
+            InterpolationMode::Nearest,
+            BorderMode::Replicate,
+        )?;
+        assert_eq!(rotated.size(), image.size());
+        assert_eq!(
+            rotated.data.as_slice().unwrap(),
+            &[5.0, 4.0, 3.0, 2.0, 1.0, 0.0]
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,158 @@
+use crate::image::{Image, ImageSize};
+use crate::interpolation::{remap, BorderMode, InterpolationMode};
+use anyhow::Result;
+
+/// Applies a piecewise bilinear deformation defined by a mesh of control points.
+///
+/// The mesh nodes are spread regularly over the output image, from its top-left to its
+/// bottom-right pixel, and each node holds the position in the input image sampled at that
+/// node. The positions between the nodes are interpolated bilinearly.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `mesh` - The mesh with shape (rows, cols, 2) holding the (x, y) input position of each node.
+/// * `new_size` - The size of the output image.
+/// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the pixels mapped outside of the input image.
+///
+/// # Returns
+///
+/// The deformed image with the new size.
+///
+/// # Errors
+///
+/// Returns an error if the mesh has less than 2x2 nodes.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::interpolation::{BorderMode, InterpolationMode};
+/// use kornia_rs::warp::warp_mesh;
+///
+/// let image = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 3,
+///         height: 3,
+///     },
+///     vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0],
+/// )
+/// .unwrap();
+///
+/// // a mesh of 2x2 nodes flipping the image horizontally
+/// let mesh = Image::<f32, 2>::new(
+///     ImageSize {
+///         width: 2,
+///         height: 2,
+///     },
+///     vec![2.0, 0.0, 0.0, 0.0, 2.0, 2.0, 0.0, 2.0],
+/// )
+/// .unwrap();
+///
+/// let dst = warp_mesh(
+///     &image,
+///     &mesh,
+///     image.size(),
+///     InterpolationMode::Bilinear,
+///     BorderMode::Replicate,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     dst.data.as_slice().unwrap(),
+///     &[2.0, 1.0, 0.0, 5.0, 4.0, 3.0, 8.0, 7.0, 6.0]
+/// );
+/// ```
+pub fn warp_mesh<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    mesh: &Image<f32, 2>,
+    new_size: ImageSize,
+    interpolation: InterpolationMode,
+    border: BorderMode,
+) -> Result<Image<f32, CHANNELS>> {
+    let (cols, rows) = (mesh.width(), mesh.height());
+    if cols < 2 || rows < 2 {
+        return Err(anyhow::anyhow!(
+            "The mesh must have at least 2x2 nodes, got {}",
+            mesh.size()
+        ));
+    }
+
+    // the spacing of the nodes in the output image
+    let step_x = (new_size.width.max(2) - 1) as f32 / (cols - 1) as f32;
+    let step_y = (new_size.height.max(2) - 1) as f32 / (rows - 1) as f32;
+
+    let mut map_x = Image::<f32, 1>::from_size_val(new_size, 0.0)?;
+    let mut map_y = Image::<f32, 1>::from_size_val(new_size, 0.0)?;
+    ndarray::Zip::indexed(map_x.data.rows_mut())
+        .and(map_y.data.rows_mut())
+        .par_for_each(|(y, x), mut u, mut v| {
+            // the cell of the mesh containing the pixel and the position inside it
+            let (gx, gy) = (x as f32 / step_x, y as f32 / step_y);
+            let (i, j) = ((gx as usize).min(cols - 2), (gy as usize).min(rows - 2));
+            let (fx, fy) = (gx - i as f32, gy - j as f32);
+
+            for (c, out) in [&mut u[0], &mut v[0]].into_iter().enumerate() {
+                let node = |i: usize, j: usize| mesh.data[[j, i, c]];
+                *out = node(i, j) * (1.0 - fx) * (1.0 - fy)
+                    + node(i + 1, j) * fx * (1.0 - fy)
+                    + node(i, j + 1) * (1.0 - fx) * fy
+                    + node(i + 1, j + 1) * fx * fy;
+            }
+        });
+
+    remap(src, &map_x, &map_y, interpolation, border)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{Image, ImageSize};
+    use crate::interpolation::{BorderMode, InterpolationMode};
+    use anyhow::Result;
+
+    #[test]
+    fn warp_mesh_identity() -> Result<()> {
+        let size = ImageSize {
+            width: 5,
+            height: 4,
+        };
+        let image = Image::<f32, 2>::new(size, (0..40).map(|x| x as f32).collect())?;
+
+        // a 3x2 mesh with the identity mapping
+        let mesh = Image::<f32, 2>::new(
+            ImageSize {
+                width: 3,
+                height: 2,
+            },
+            vec![0.0, 0.0, 2.0, 0.0, 4.0, 0.0, 0.0, 3.0, 2.0, 3.0, 4.0, 3.0],
+        )?;
+
+        let dst = super::warp_mesh(
+            &image,
+            &mesh,
+            size,
+            InterpolationMode::Bilinear,
+            BorderMode::Constant(0.0),
+        )?;
+        for (a, b) in dst.data.iter().zip(image.data.iter()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+
+        assert!(super::warp_mesh(
+            &image,
+            &Image::<f32, 2>::from_size_val(
+                ImageSize {
+                    width: 1,
+                    height: 2,
+                },
+                0.0,
+            )?,
+            size,
+            InterpolationMode::Bilinear,
+            BorderMode::Constant(0.0),
+        )
+        .is_err());
+        Ok(())
+    }
+}
@@ -1,5 +1,11 @@
 mod affine;
+mod mesh;
 mod perspective;
+mod tps;
 
-pub use affine::{get_rotation_matrix2d, invert_affine_transform, warp_affine};
-pub use perspective::{warp_perspective, PerspectiveMatrix};
+pub use affine::{
+    get_affine_transform, get_rotation_matrix2d, invert_affine_transform, rotate, warp_affine,
+};
+pub use mesh::warp_mesh;
+pub use perspective::{get_perspective_transform, warp_perspective, PerspectiveMatrix};
+pub use tps::warp_tps;
@@ -1,4 +1,5 @@
-use crate::interpolation::{interpolate_pixel, InterpolationMode};
+use crate::geometry::Point2;
+use crate::interpolation::{interpolate_pixel_border, BorderMode, InterpolationMode};
 use crate::{
     image::{Image, ImageSize},
     interpolation::meshgrid,
@@ -57,12 +58,118 @@ fn transform_point(x: f32, y: f32, m: PerspectiveMatrix) -> (f32, f32) {
     (x, y)
 }
 
+/// Solve a square linear system with the Gaussian elimination and partial pivoting.
+///
+/// # Returns
+///
+/// The solution of the system, or `None` if the matrix is singular.
+fn solve_linear<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    for col in 0..N {
+        // move the largest pivot to the diagonal
+        let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let pivot_row = a[col];
+        for row in col + 1..N {
+            let factor = a[row][col] / pivot_row[col];
+            for (x, p) in a[row].iter_mut().zip(pivot_row.iter()).skip(col) {
+                *x -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    // back substitution
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let sum = (row + 1..N).map(|k| a[row][k] * x[k]).sum::<f64>();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+
+    Some(x)
+}
+
+/// Computes the perspective transformation mapping four points to four other points.
+///
+/// The homography is the exact solution of the eight equations given by the point
+/// pairs, normalized so that its last element is one.
+///
+/// # Arguments
+///
+/// * `src` - The four points in the source image.
+/// * `dst` - The four corresponding points in the destination image.
+///
+/// # Returns
+///
+/// The 3x3 perspective transformation matrix src -> dst.
+///
+/// # Errors
+///
+/// Returns an error if three of the points are collinear.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Point2;
+/// use kornia_rs::warp::get_perspective_transform;
+///
+/// let src = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(2.0, 2.0),
+///     Point2::new(0.0, 2.0),
+/// ];
+/// let dst = [
+///     Point2::new(1.0, 1.0),
+///     Point2::new(5.0, 1.0),
+///     Point2::new(5.0, 5.0),
+///     Point2::new(1.0, 5.0),
+/// ];
+///
+/// let m = get_perspective_transform(&src, &dst).unwrap();
+/// assert_eq!(m, [2.0, 0.0, 1.0, 0.0, 2.0, 1.0, 0.0, 0.0, 1.0]);
+/// ```
+pub fn get_perspective_transform(
+    src: &[Point2; 4],
+    dst: &[Point2; 4],
+) -> Result<PerspectiveMatrix> {
+    // each pair gives two equations in the first eight elements of the matrix
+    //   u = (m0 * x + m1 * y + m2) / (m6 * x + m7 * y + 1)
+    //   v = (m3 * x + m4 * y + m5) / (m6 * x + m7 * y + 1)
+    let mut a = [[0.0f64; 8]; 8];
+    let mut b = [0.0f64; 8];
+    for (i, (p, q)) in src.iter().zip(dst.iter()).enumerate() {
+        let (x, y) = (p.x as f64, p.y as f64);
+        let (u, v) = (q.x as f64, q.y as f64);
+        a[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y];
+        a[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y];
+        b[2 * i] = u;
+        b[2 * i + 1] = v;
+    }
+
+    let h = solve_linear(a, b).ok_or_else(|| {
+        anyhow::anyhow!("The points are degenerate, the perspective transform is undefined")
+    })?;
+
+    let mut m = [1.0; 9];
+    for (m, h) in m.iter_mut().zip(h.iter()) {
+        *m = *h as f32;
+    }
+
+    Ok(m)
+}
+
 /// Applies a perspective transformation to an image.
 ///
 /// * `src` - The input image with shape (height, width, channels).
 /// * `m` - The 3x3 perspective transformation matrix src -> dst.
 /// * `new_size` - The size of the output image.
 /// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the samples outside of the input image.
 ///
 /// # Returns
 ///
@@ -88,7 +195,8 @@ fn transform_point(x: f32, y: f32, m: PerspectiveMatrix) -> (f32, f32) {
 ///     width: 2,
 ///     height: 3,
 ///   },
-///   kornia_rs::interpolation::InterpolationMode::Bilinear
+///   kornia_rs::interpolation::InterpolationMode::Bilinear,
+///   kornia_rs::interpolation::BorderMode::Constant(0.0),
 /// ).unwrap();
 ///
 /// assert_eq!(dst.size().width, 2);
@@ -99,6 +207,7 @@ pub fn warp_perspective<const CHANNELS: usize>(
     m: PerspectiveMatrix,
     new_size: ImageSize,
     interpolation: InterpolationMode,
+    border: BorderMode,
 ) -> Result<Image<f32, CHANNELS>> {
     // inverse perspective matrix
     // TODO: allow later to skip the inverse calculation if user provides it
@@ -131,8 +240,9 @@ pub fn warp_perspective<const CHANNELS: usize>(
 
             // TODO: allow for multi-channel images
             // interpolate the pixel value
-            let pixels = (0..src.num_channels())
-                .map(|c| interpolate_pixel(&src.data, u_src, v_src, c, interpolation));
+            let pixels = (0..src.num_channels()).map(|c| {
+                interpolate_pixel_border(&src.data, u_src, v_src, c, interpolation, border)
+            });
 
             for (c, pixel) in pixels.enumerate() {
                 out[c] = pixel;
@@ -164,6 +274,52 @@ mod tests {
         assert_eq!(y, y_expected);
     }
 
+    #[test]
+    fn get_perspective_transform() -> Result<()> {
+        use crate::geometry::Point2;
+
+        let corners = |w: f32, h: f32| {
+            [
+                Point2::new(0.0, 0.0),
+                Point2::new(w, 0.0),
+                Point2::new(w, h),
+                Point2::new(0.0, h),
+            ]
+        };
+
+        // the matrix resizing a 4x4 image to 2x2
+        let m = super::get_perspective_transform(&corners(3.0, 3.0), &corners(1.0, 1.0))?;
+        let expected = [1.0 / 3.0, 0.0, 0.0, 0.0, 1.0 / 3.0, 0.0, 0.0, 0.0, 1.0];
+        for (a, b) in m.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        // a document seen in perspective maps its corners exactly
+        let src = [
+            Point2::new(12.0, 20.0),
+            Point2::new(95.0, 8.0),
+            Point2::new(110.0, 140.0),
+            Point2::new(4.0, 120.0),
+        ];
+        let dst = corners(210.0, 297.0);
+        let m = super::get_perspective_transform(&src, &dst)?;
+        for (p, q) in src.iter().zip(dst.iter()) {
+            let w = m[6] * p.x + m[7] * p.y + m[8];
+            let u = (m[0] * p.x + m[1] * p.y + m[2]) / w;
+            let v = (m[3] * p.x + m[4] * p.y + m[5]) / w;
+            assert!((u - q.x).abs() < 1e-3 && (v - q.y).abs() < 1e-3);
+        }
+
+        let collinear = [
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(0.0, 5.0),
+        ];
+        assert!(super::get_perspective_transform(&collinear, &dst).is_err());
+        Ok(())
+    }
+
     #[test]
     fn warp_perspective_identity() -> Result<()> {
         use crate::image::{Image, ImageSize};
@@ -186,6 +342,7 @@ mod tests {
                 height: 3,
             },
             super::InterpolationMode::Bilinear,
+            super::BorderMode::Constant(0.0),
         )?;
 
         assert_eq!(image_transformed.num_channels(), 3);
@@ -219,6 +376,7 @@ mod tests {
                 height: 3,
             },
             super::InterpolationMode::Bilinear,
+            super::BorderMode::Constant(0.0),
         )?;
 
         assert_eq!(image_transformed.num_channels(), 1);
@@ -257,6 +415,7 @@ mod tests {
                 height: 2,
             },
             super::InterpolationMode::Bilinear,
+            super::BorderMode::Replicate,
         )?;
 
         let image_resized = crate::resize::resize_native(
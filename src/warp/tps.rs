@@ -0,0 +1,211 @@
+use crate::geometry::linalg::solve;
+use crate::geometry::Point2;
+use crate::image::Image;
+use crate::interpolation::{remap, BorderMode, InterpolationMode};
+use anyhow::Result;
+
+/// The radial basis function of the thin-plate spline for a squared distance.
+fn tps_kernel(r2: f64) -> f64 {
+    if r2 <= f64::EPSILON {
+        0.0
+    } else {
+        r2 * r2.ln()
+    }
+}
+
+/// A 2D thin-plate spline interpolating a set of control points.
+struct ThinPlateSpline {
+    centers: Vec<(f64, f64)>,
+    // the kernel weights followed by the affine coefficients (1, x, y), for x and y
+    coeffs_x: Vec<f64>,
+    coeffs_y: Vec<f64>,
+}
+
+impl ThinPlateSpline {
+    /// Fit the spline mapping each center to its target.
+    fn fit(centers: &[Point2], targets: &[Point2]) -> Option<Self> {
+        let n = centers.len();
+        let size = n + 3;
+        let centers = centers
+            .iter()
+            .map(|p| (p.x as f64, p.y as f64))
+            .collect::<Vec<_>>();
+
+        // the system [K P; P^T 0] [w; a] = [v; 0]
+        let mut a = vec![0.0; size * size];
+        for (i, &(xi, yi)) in centers.iter().enumerate() {
+            for (j, &(xj, yj)) in centers.iter().enumerate() {
+                a[i * size + j] = tps_kernel((xi - xj).powi(2) + (yi - yj).powi(2));
+            }
+            for (k, value) in [1.0, xi, yi].into_iter().enumerate() {
+                a[i * size + n + k] = value;
+                a[(n + k) * size + i] = value;
+            }
+        }
+
+        let rhs = |f: fn(&Point2) -> f32| {
+            let mut b = targets.iter().map(|p| f(p) as f64).collect::<Vec<_>>();
+            b.extend([0.0; 3]);
+            b
+        };
+        let coeffs_x = solve(a.clone(), rhs(|p| p.x), size)?;
+        let coeffs_y = solve(a, rhs(|p| p.y), size)?;
+
+        Some(Self {
+            centers,
+            coeffs_x,
+            coeffs_y,
+        })
+    }
+
+    /// Evaluate the spline at a point.
+    fn transform(&self, x: f64, y: f64) -> (f64, f64) {
+        let n = self.centers.len();
+        let (cx, cy) = (&self.coeffs_x, &self.coeffs_y);
+        let mut u = cx[n] + cx[n + 1] * x + cx[n + 2] * y;
+        let mut v = cy[n] + cy[n + 1] * x + cy[n + 2] * y;
+        for (i, &(xi, yi)) in self.centers.iter().enumerate() {
+            let k = tps_kernel((x - xi).powi(2) + (y - yi).powi(2));
+            u += cx[i] * k;
+            v += cy[i] * k;
+        }
+        (u, v)
+    }
+}
+
+/// Applies a thin-plate spline deformation to an image.
+///
+/// The deformation moves each source control point exactly to its destination control
+/// point and bends the rest of the image as smoothly as possible, e.g. for document
+/// dewarping or face morphing.
+///
+/// # Arguments
+///
+/// * `src` - The input image with shape (H, W, C).
+/// * `control_src` - The control points in the input image.
+/// * `control_dst` - The corresponding control points in the output image.
+/// * `interpolation` - The interpolation mode to use.
+/// * `border` - The border mode for the pixels mapped outside of the input image.
+///
+/// # Returns
+///
+/// The deformed image with shape (H, W, C).
+///
+/// # Errors
+///
+/// Returns an error if the control points have different lengths, are less than three,
+/// or are degenerate, e.g. all collinear.
+///
+/// # Example
+///
+/// ```
+/// use kornia_rs::geometry::Point2;
+/// use kornia_rs::image::{Image, ImageSize};
+/// use kornia_rs::interpolation::{BorderMode, InterpolationMode};
+/// use kornia_rs::warp::warp_tps;
+///
+/// let image = Image::<f32, 1>::new(
+///     ImageSize {
+///         width: 4,
+///         height: 4,
+///     },
+///     (0..16).map(|x| x as f32).collect(),
+/// )
+/// .unwrap();
+///
+/// // a translation by one pixel to the right
+/// let control_src = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(3.0, 0.0),
+///     Point2::new(0.0, 3.0),
+///     Point2::new(3.0, 3.0),
+/// ];
+/// let control_dst = control_src.map(|p| Point2::new(p.x + 1.0, p.y));
+///
+/// let dst = warp_tps(
+///     &image,
+///     &control_src,
+///     &control_dst,
+///     InterpolationMode::Bilinear,
+///     BorderMode::Constant(0.0),
+/// )
+/// .unwrap();
+///
+/// assert!((dst.data[[1, 2, 0]] - image.data[[1, 1, 0]]).abs() < 1e-4);
+/// ```
+pub fn warp_tps<const CHANNELS: usize>(
+    src: &Image<f32, CHANNELS>,
+    control_src: &[Point2],
+    control_dst: &[Point2],
+    interpolation: InterpolationMode,
+    border: BorderMode,
+) -> Result<Image<f32, CHANNELS>> {
+    if control_src.len() != control_dst.len() {
+        return Err(anyhow::anyhow!(
+            "The number of control points does not match: {} != {}",
+            control_src.len(),
+            control_dst.len()
+        ));
+    }
+    if control_src.len() < 3 {
+        return Err(anyhow::anyhow!(
+            "At least 3 control points are required, got {}",
+            control_src.len()
+        ));
+    }
+
+    // the inverse mapping from the output to the input image
+    let spline = ThinPlateSpline::fit(control_dst, control_src)
+        .ok_or_else(|| anyhow::anyhow!("The control points are degenerate"))?;
+
+    let mut map_x = Image::<f32, 1>::from_size_val(src.size(), 0.0)?;
+    let mut map_y = Image::<f32, 1>::from_size_val(src.size(), 0.0)?;
+    ndarray::Zip::indexed(map_x.data.rows_mut())
+        .and(map_y.data.rows_mut())
+        .par_for_each(|(y, x), mut u, mut v| {
+            let (us, vs) = spline.transform(x as f64, y as f64);
+            u[0] = us as f32;
+            v[0] = vs as f32;
+        });
+
+    remap(src, &map_x, &map_y, interpolation, border)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point2;
+    use anyhow::Result;
+
+    #[test]
+    fn thin_plate_spline() -> Result<()> {
+        let centers = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(0.0, 10.0),
+            Point2::new(10.0, 10.0),
+            Point2::new(5.0, 5.0),
+        ];
+
+        // an affine mapping is reproduced exactly
+        let affine = |p: &Point2| Point2::new(2.0 * p.x - 0.5 * p.y + 3.0, 0.3 * p.x + p.y - 1.0);
+        let targets = centers.iter().map(affine).collect::<Vec<_>>();
+        let spline = super::ThinPlateSpline::fit(&centers, &targets).unwrap();
+        let (u, v) = spline.transform(7.0, 2.0);
+        let expected = affine(&Point2::new(7.0, 2.0));
+        assert!((u - expected.x as f64).abs() < 1e-6 && (v - expected.y as f64).abs() < 1e-6);
+
+        // a non-rigid deformation interpolates the control points
+        let mut targets = centers.to_vec();
+        targets[4] = Point2::new(6.0, 4.0);
+        let spline = super::ThinPlateSpline::fit(&centers, &targets).unwrap();
+        for (c, t) in centers.iter().zip(targets.iter()) {
+            let (u, v) = spline.transform(c.x as f64, c.y as f64);
+            assert!((u - t.x as f64).abs() < 1e-6 && (v - t.y as f64).abs() < 1e-6);
+        }
+
+        // collinear control points are degenerate
+        let line = [0.0, 1.0, 2.0].map(|x| Point2::new(x, x));
+        assert!(super::ThinPlateSpline::fit(&line, &line).is_none());
+        Ok(())
+    }
+}